@@ -3,6 +3,12 @@ use colored::*;
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{self, Error, ErrorKind};
 
 /// Colours [`crate::display::Block`] fields based on loose typing of field type
 ///
@@ -178,6 +184,74 @@ pub struct ColourTheme {
         deserialize_with = "deserialize_option_color_from_string"
     )]
     pub tree_endpoint_out: Option<Color>,
+    /// Colour used in watch mode for values that changed since the previous refresh
+    #[serde(
+        default,
+        serialize_with = "color_serializer",
+        deserialize_with = "deserialize_option_color_from_string"
+    )]
+    pub changed: Option<Color>,
+    /// Colour for matches of the [`crate::display::PrintSettings`] `search` string
+    #[serde(
+        default,
+        serialize_with = "color_serializer",
+        deserialize_with = "deserialize_option_color_from_string"
+    )]
+    pub highlight: Option<Color>,
+    /// Colour for values that need attention such as a [`crate::display::DeviceBlocks::PowerBudget`] that is over budget
+    #[serde(
+        default,
+        serialize_with = "color_serializer",
+        deserialize_with = "deserialize_option_color_from_string"
+    )]
+    pub warning: Option<Color>,
+}
+
+/// Parses a colour name such as "bright blue"/"bright_blue" or hex "#rrggbb"/"#rgb" into a [`Color`]
+///
+/// ```
+/// use colored::Color;
+/// use cyme::colour::parse_colour;
+///
+/// assert_eq!(parse_colour("Bright_Blue"), Some(Color::BrightBlue));
+/// assert_eq!(parse_colour("#ff8000"), Some(Color::TrueColor { r: 255, g: 128, b: 0 }));
+/// assert_eq!(parse_colour("#f80"), Some(Color::TrueColor { r: 255, g: 136, b: 0 }));
+/// assert_eq!(parse_colour("blurple"), None);
+/// ```
+pub fn parse_colour(s: &str) -> Option<Color> {
+    match s.strip_prefix('#') {
+        Some(hex) if hex.is_ascii() => {
+            let rgb = match hex.len() {
+                6 => (0..3)
+                    .map(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok())
+                    .collect::<Option<Vec<u8>>>()?,
+                // each digit repeated: #f80 = #ff8800
+                3 => (0..3)
+                    .map(|i| {
+                        u8::from_str_radix(&hex[i..i + 1], 16)
+                            .ok()
+                            .map(|v| v * 0x11)
+                    })
+                    .collect::<Option<Vec<u8>>>()?,
+                _ => return None,
+            };
+            Some(Color::TrueColor {
+                r: rgb[0],
+                g: rgb[1],
+                b: rgb[2],
+            })
+        }
+        Some(_) => None,
+        None => Color::from_str(&s.replace('_', " ")).ok(),
+    }
+}
+
+/// [`parse_colour`] but warns and falls back to white if `s` is invalid so that a typo does not stop the config loading; [`ColourTheme::from_path`] errors instead
+fn parse_colour_or_white(s: &str) -> Color {
+    parse_colour(s).unwrap_or_else(|| {
+        log::warn!("Invalid colour {s:?}, using white");
+        Color::White
+    })
 }
 
 fn deserialize_option_color_from_string<'de, D>(deserializer: D) -> Result<Option<Color>, D::Error>
@@ -196,7 +270,7 @@ where
     match ColorOrNull::deserialize(deserializer)? {
         ColorOrNull::Str(s) => match s {
             "" => Ok(None),
-            _ => Ok(Some(Color::from(s))),
+            _ => Ok(Some(parse_colour_or_white(s))),
         },
         ColorOrNull::FromStr(i) => Ok(Some(i)),
         ColorOrNull::Null => Ok(None),
@@ -220,7 +294,7 @@ where
         where
             E: serde::de::Error,
         {
-            Ok(Color::from(value))
+            Ok(parse_colour_or_white(value))
         }
 
         fn visit_seq<M>(self, mut seq: M) -> Result<Color, M::Error>
@@ -314,6 +388,30 @@ impl Default for ColourTheme {
 }
 
 impl ColourTheme {
+    /// Attempt to read a theme from .json format file at `path`
+    ///
+    /// Colours are names such as "bright blue", hex "#rrggbb" or a `[R, G, B]` array; fields that are not in the file are not coloured. Errors with the offending key if a colour is invalid
+    pub fn from_path(path: &Path) -> error::Result<Self> {
+        let parse_error = |e: &dyn fmt::Display| {
+            Error::new(
+                ErrorKind::Parsing,
+                &format!("Failed to parse colour theme at {path:?}; Error({e})"),
+            )
+        };
+        let f = File::open(path)?;
+        let map: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_reader(BufReader::new(f)).map_err(|e| parse_error(&e))?;
+        // check each colour first so that the error includes the key, which serde_json does not
+        for (k, v) in map.iter() {
+            if let Some(s) = v.as_str() {
+                if !s.is_empty() && parse_colour(s).is_none() {
+                    return Err(parse_error(&format!("invalid colour {s:?} for '{k}'")));
+                }
+            }
+        }
+        serde_json::from_value(serde_json::Value::Object(map)).map_err(|e| parse_error(&e))
+    }
+
     /// New theme with defaults
     pub fn new() -> Self {
         ColourTheme {
@@ -341,6 +439,9 @@ impl ColourTheme {
             tree_interface_terminator: Some(Color::BrightBlack),
             tree_endpoint_in: Some(Color::Yellow),
             tree_endpoint_out: Some(Color::Magenta),
+            changed: Some(Color::BrightGreen),
+            highlight: Some(Color::BrightRed),
+            warning: Some(Color::BrightRed),
         }
     }
 }
@@ -361,6 +462,27 @@ mod tests {
         assert_eq!(ct.name, Some(Color::Blue));
     }
 
+    #[test]
+    fn test_deserialize_invalid_colour() {
+        let ct: ColourTheme = serde_json::from_str(r#"{"name": "blurple"}"#).unwrap();
+        assert_eq!(ct.name, Some(Color::White));
+        let ct: ColourTheme = serde_json::from_reader(r#"{"name": "blurple"}"#.as_bytes()).unwrap();
+        assert_eq!(ct.name, Some(Color::White));
+        let ct: ColourTheme = serde_json::from_str(r##"{"vid": "#ff0000"}"##).unwrap();
+        assert_eq!(ct.vid, Some(Color::TrueColor { r: 255, g: 0, b: 0 }));
+    }
+
+    #[test]
+    fn test_from_path_invalid_colour() {
+        let path = std::env::temp_dir().join("cyme_invalid_colour_theme.json");
+        std::fs::write(&path, r#"{"name": "blurple"}"#).unwrap();
+        let err = ColourTheme::from_path(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(err
+            .to_string()
+            .contains("invalid colour \"blurple\" for 'name'"));
+    }
+
     #[test]
     fn test_serialize_deserialize_color_theme() {
         let ct: ColourTheme = ColourTheme::new();
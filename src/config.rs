@@ -1,5 +1,6 @@
 //! Config for cyme binary
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
@@ -9,9 +10,11 @@ use crate::display;
 use crate::display::Block;
 use crate::error::{Error, ErrorKind, Result};
 use crate::icon;
+use crate::usb;
 
 const CONF_DIR: &str = "cyme";
 const CONF_NAME: &str = "cyme.json";
+const PROFILES_DIR: &str = "profiles";
 
 /// Allows user supplied icons to replace or add to `DEFAULT_ICONS` and `DEFAULT_TREE`
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
@@ -29,16 +32,24 @@ pub struct Config {
     pub bus_blocks: Option<Vec<display::BusBlocks>>,
     /// Default [`crate::display::ConfigurationBlocks`] to use for device configurations
     pub config_blocks: Option<Vec<display::ConfigurationBlocks>>,
+    /// Default [`crate::display::FunctionBlocks`] to use for device Interface Association Descriptor functions
+    pub function_blocks: Option<Vec<display::FunctionBlocks>>,
     /// Default [`crate::display::InterfaceBlocks`] to use for device interfaces
     pub interface_blocks: Option<Vec<display::InterfaceBlocks>>,
     /// Default [`crate::display::EndpointBlocks`] to use for device endpoints
     pub endpoint_blocks: Option<Vec<display::EndpointBlocks>>,
     /// Whether to hide device serial numbers by default
     pub mask_serials: Option<display::MaskSerial>,
+    /// Device fields to mask with `mask_serials` method, only serials if None
+    pub mask_fields: Option<Vec<display::MaskField>>,
+    /// Seed for random masking so masked output is reproducible
+    pub mask_seed: Option<u64>,
     /// How to group devices during display
     pub group_devices: Option<display::Group>,
     /// Encoding to use for output text
     pub encoding: Option<display::Encoding>,
+    /// Tree branch characters overriding the defaults for the encoding
+    pub tree_glyphs: Option<icon::TreeGlyphs>,
     /// When to show icons
     pub icon_when: Option<display::IconWhen>,
     /// When to use color
@@ -47,10 +58,20 @@ pub struct Config {
     pub sort_devices: Option<display::Sort>,
     /// Sort devices by bus number - irrelevant unless sort_devices is NoSort
     pub sort_buses: bool,
+    /// Reverse the order of buses and devices after sorting
+    pub sort_reverse: bool,
+    /// Sort devices by the value of a block after sort_devices
+    pub sort_block: Option<display::DeviceBlocks>,
+    /// How to sort interfaces of each configuration
+    pub sort_interfaces: Option<display::InterfaceSort>,
     /// Max variable string length to display before truncating - descriptors and classes for example
     pub max_variable_string_len: Option<usize>,
     /// Disable auto generation of max_variable_string_len based on terminal width
     pub no_auto_width: bool,
+    /// How to fit variable length values wider than max_variable_string_len
+    pub wrap_mode: Option<display::WrapMode>,
+    /// Unit to show device speeds in
+    pub speed_unit: Option<usb::SpeedUnit>,
     // non-Options copied from Args
     /// Attempt to maintain compatibility with lsusb output
     pub lsusb: bool,
@@ -60,6 +81,18 @@ pub struct Config {
     pub verbose: u8,
     /// Print more blocks by default at each verbosity
     pub more: bool,
+    /// Print all descriptors inset below devices without tree glyphs
+    pub flat_descriptors: bool,
+    /// Print a hex dump of the class-specific descriptors below each interface
+    pub hex_dump: bool,
+    /// Collapse runs of identical devices into one line with a multiplier
+    pub collapse_duplicates: bool,
+    /// Collapse identical devices even if their serial numbers differ
+    pub collapse_ignore_serial: bool,
+    /// Print a summary of the device count by speed and current drawn under each bus
+    pub bus_summary: bool,
+    /// Show the LastEvent block as the time since the event when watching
+    pub relative_time: bool,
     /// Hide empty buses when printing tree; those with no devices.
     pub hide_buses: bool,
     /// Hide empty hubs when printing tree; those with no devices. When listing will hide hubs regardless of whether empty of not
@@ -81,10 +114,40 @@ pub struct Config {
     pub no_icons: bool,
     /// Show block headings
     pub headings: bool,
+    /// Print icon blocks first regardless of their position in the block lists
+    pub pin_icons_first: bool,
+    /// Hide blocks that are "-" for every device or bus printed
+    pub auto_hide_empty: bool,
     /// Force nusb/libusb profiler on macOS rather than using/combining system_profiler output
     pub force_libusb: bool,
     /// Output in JSON format
     pub json: bool,
+    /// With `json`, include names resolved from the Linux USB IDs repository
+    pub json_resolved: bool,
+    /// Output in CSV format using blocks as columns
+    pub csv: bool,
+    /// Output as Markdown table using blocks as columns
+    pub markdown: bool,
+    /// Output as JSON Lines with an object per device event
+    pub ndjson: bool,
+    /// Output the bus tree as a GraphViz DOT digraph
+    pub dot: bool,
+    /// Block heading overrides keyed by kebab-case block name, e.g. "vendor-id": "Vendor ID"
+    pub heading_overrides: Option<HashMap<String, String>>,
+    /// Block value alignment overrides for each block type keyed by block like the block lists, e.g. "blocks": {"name": "right"}
+    pub alignment_overrides: Option<display::AlignmentOverrides>,
+    /// String to insert between columns - default single space
+    pub separator: Option<String>,
+    /// Maximum depth of devices in the tree, deeper devices are summarised
+    pub max_depth: Option<usize>,
+    /// Only print endpoints of this transfer type
+    pub endpoint_transfer_filter: Option<crate::usb::TransferType>,
+    /// Only print endpoints of this direction
+    pub endpoint_direction_filter: Option<crate::usb::Direction>,
+    /// Hide interfaces with no endpoints matching `endpoint_transfer_filter` and `endpoint_direction_filter`
+    pub hide_empty_interfaces: bool,
+    /// Only print the selected alternate setting of each interface
+    pub only_active_alt: bool,
     /// Print non-critical errors (normally due to permissions) during USB profiler to stderr
     pub print_non_critical_profiler_stderr: bool,
 }
@@ -136,9 +199,12 @@ impl Config {
             blocks: Some(display::DeviceBlocks::example_blocks()),
             bus_blocks: Some(display::BusBlocks::example_blocks()),
             config_blocks: Some(display::ConfigurationBlocks::example_blocks()),
+            function_blocks: Some(display::FunctionBlocks::example_blocks()),
             interface_blocks: Some(display::InterfaceBlocks::example_blocks()),
             endpoint_blocks: Some(display::EndpointBlocks::example_blocks()),
             mask_serials: None,
+            mask_fields: None,
+            mask_seed: None,
             group_devices: Some(display::Group::default()),
             encoding: Some(display::Encoding::default()),
             icon_when: Some(display::IconWhen::default()),
@@ -211,20 +277,37 @@ impl Config {
         self.blocks = settings.device_blocks.clone();
         self.bus_blocks = settings.bus_blocks.clone();
         self.config_blocks = settings.config_blocks.clone();
+        self.function_blocks = settings.function_blocks.clone();
         self.interface_blocks = settings.interface_blocks.clone();
         self.endpoint_blocks = settings.endpoint_blocks.clone();
         self.more = settings.more;
+        self.flat_descriptors = settings.flat_descriptors;
+        self.hex_dump = settings.hex_dump;
+        self.collapse_duplicates = settings.collapse_duplicates;
+        self.collapse_ignore_serial = settings.collapse_ignore_serial;
+        self.bus_summary = settings.bus_summary;
+        self.relative_time = settings.relative_time;
         self.decimal = settings.decimal;
         self.mask_serials = settings.mask_serials;
+        self.mask_fields = settings.mask_fields.clone();
+        self.mask_seed = settings.mask_seed;
         self.group_devices = Some(settings.group_devices);
         self.encoding = Some(settings.encoding);
+        self.tree_glyphs = settings.tree_glyphs;
         self.icon_when = Some(settings.icon_when);
+        self.wrap_mode = Some(settings.wrap_mode);
+        self.speed_unit = Some(settings.speed_unit);
         self.color_when = Some(settings.color_when);
         self.sort_devices = Some(settings.sort_devices);
         self.sort_buses = settings.sort_buses;
+        self.sort_reverse = settings.sort_reverse;
+        self.sort_block = settings.sort_block;
+        self.sort_interfaces = settings.sort_interfaces;
         self.no_color = settings.colours.is_none();
         self.no_padding = settings.no_padding;
         self.headings = settings.headings;
+        self.pin_icons_first = settings.pin_icons_first;
+        self.auto_hide_empty = settings.auto_hide_empty;
         self.tree = settings.tree;
         self.max_variable_string_len = settings.max_variable_string_len;
         self.no_auto_width = !settings.auto_width;
@@ -233,6 +316,19 @@ impl Config {
         self.ascii = matches!(settings.encoding, display::Encoding::Ascii);
         self.verbose = settings.verbosity;
         self.json = settings.json;
+        self.json_resolved = settings.json_resolved;
+        self.csv = settings.csv;
+        self.markdown = settings.markdown;
+        self.ndjson = settings.ndjson;
+        self.dot = settings.dot;
+        self.heading_overrides = settings.heading_overrides.clone();
+        self.alignment_overrides = settings.alignment_overrides.clone();
+        self.separator = settings.separator.clone();
+        self.max_depth = settings.max_depth;
+        self.endpoint_transfer_filter = settings.endpoint_transfer_filter;
+        self.endpoint_direction_filter = settings.endpoint_direction_filter;
+        self.hide_empty_interfaces = settings.hide_empty_interfaces;
+        self.only_active_alt = settings.only_active_alt;
     }
 
     /// Returns a [`display::PrintSettings`] based on the config
@@ -256,41 +352,269 @@ impl Config {
                 display::Encoding::Glyphs
             }
         });
-        let group_devices = if self.group_devices == Some(display::Group::Bus) && self.tree {
+        let group_devices = if self.tree
+            && matches!(
+                self.group_devices,
+                Some(display::Group::Bus | display::Group::Vendor)
+            ) {
             log::warn!("--group-devices with --tree is ignored; will print as tree");
             display::Group::NoGroup
         } else {
             self.group_devices.unwrap_or(display::Group::NoGroup)
         };
+        let collapse_duplicates = if self.collapse_duplicates && self.lsusb {
+            log::warn!("--collapse-duplicates with --lsusb is ignored; will list every device");
+            false
+        } else {
+            self.collapse_duplicates
+        };
         display::PrintSettings {
             device_blocks: self.blocks.clone(),
             bus_blocks: self.bus_blocks.clone(),
             config_blocks: self.config_blocks.clone(),
+            function_blocks: self.function_blocks.clone(),
             interface_blocks: self.interface_blocks.clone(),
             endpoint_blocks: self.endpoint_blocks.clone(),
             more: self.more,
+            flat_descriptors: self.flat_descriptors,
+            hex_dump: self.hex_dump,
+            collapse_duplicates,
+            collapse_ignore_serial: self.collapse_ignore_serial,
+            bus_summary: self.bus_summary,
+            relative_time: self.relative_time,
             decimal: self.decimal,
+            speed_unit: self.speed_unit.unwrap_or_default(),
             mask_serials: self.mask_serials,
+            mask_fields: self.mask_fields.clone(),
+            mask_seed: self.mask_seed,
             group_devices,
             sort_devices: self.sort_devices.unwrap_or_default(),
             sort_buses: self.sort_buses,
+            sort_reverse: self.sort_reverse,
+            sort_block: self.sort_block,
+            sort_interfaces: self.sort_interfaces,
             no_padding: self.no_padding,
             headings: self.headings,
+            pin_icons_first: self.pin_icons_first,
+            auto_hide_empty: self.auto_hide_empty,
             tree: self.tree,
             max_variable_string_len: self.max_variable_string_len,
             auto_width: !self.no_auto_width,
+            wrap_mode: self.wrap_mode.unwrap_or_default(),
             icon_when: self.icon_when.unwrap_or_default(),
             color_when: self.color_when.unwrap_or_default(),
             encoding,
             icons,
+            tree_glyphs: self.tree_glyphs,
             colours,
             verbosity: self.verbose,
             json: self.json,
+            json_resolved: self.json_resolved,
+            csv: self.csv,
+            markdown: self.markdown,
+            ndjson: self.ndjson,
+            dot: self.dot,
+            heading_overrides: self.heading_overrides.clone(),
+            alignment_overrides: self.alignment_overrides.clone(),
+            separator: self.separator.clone(),
+            max_depth: self.max_depth,
+            endpoint_transfer_filter: self.endpoint_transfer_filter,
+            endpoint_direction_filter: self.endpoint_direction_filter,
+            hide_empty_interfaces: self.hide_empty_interfaces,
+            only_active_alt: self.only_active_alt,
             ..Default::default()
         }
     }
 }
 
+/// Named selection of [`display::Block`]s saved from and loaded into [`display::PrintSettings`], such as with `cyme --save-profile audio` and `cyme --profile audio`
+///
+/// Profiles are .json files in the "profiles" folder of the [`Config::config_file_path`] using the same keys as the [`Config`] blocks. Blocks that are None are not saved so the defaults still apply when loaded
+///
+/// ```
+/// use cyme::config::BlockProfile;
+/// use cyme::display::{DeviceBlocks, PrintSettings};
+///
+/// let profile = BlockProfile::from_json(r#"{"blocks": ["vid-pid", "not-a-block", "name"]}"#).unwrap();
+/// // unknown blocks are skipped with a warning
+/// assert_eq!(profile.blocks, Some(vec![DeviceBlocks::VidPid, DeviceBlocks::Name]));
+///
+/// let mut settings = PrintSettings::default();
+/// profile.apply(&mut settings);
+/// assert_eq!(settings.device_blocks, profile.blocks);
+/// assert_eq!(settings.bus_blocks, None);
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct BlockProfile {
+    /// [`crate::display::DeviceBlocks`] of the profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocks: Option<Vec<display::DeviceBlocks>>,
+    /// [`crate::display::BusBlocks`] of the profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bus_blocks: Option<Vec<display::BusBlocks>>,
+    /// [`crate::display::ConfigurationBlocks`] of the profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_blocks: Option<Vec<display::ConfigurationBlocks>>,
+    /// [`crate::display::FunctionBlocks`] of the profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_blocks: Option<Vec<display::FunctionBlocks>>,
+    /// [`crate::display::InterfaceBlocks`] of the profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_blocks: Option<Vec<display::InterfaceBlocks>>,
+    /// [`crate::display::EndpointBlocks`] of the profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint_blocks: Option<Vec<display::EndpointBlocks>>,
+}
+
+impl BlockProfile {
+    /// Parse a profile from .json `s`
+    ///
+    /// Unknown or renamed block names and keys are logged as warnings and skipped rather than failing the whole profile, so profiles saved by other versions still load. Errors only if `s` is not a .json object
+    pub fn from_json(s: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(s).map_err(|e| {
+            Error::new(
+                ErrorKind::Parsing,
+                &format!("Failed to parse block profile; Error({e})"),
+            )
+        })?;
+        let map = value.as_object().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Parsing,
+                "Failed to parse block profile; Error(not an object)",
+            )
+        })?;
+
+        let mut profile = BlockProfile::default();
+        for (key, value) in map {
+            match key.as_str() {
+                "blocks" => profile.blocks = parse_profile_blocks(key, value),
+                "bus-blocks" => profile.bus_blocks = parse_profile_blocks(key, value),
+                "config-blocks" => profile.config_blocks = parse_profile_blocks(key, value),
+                "function-blocks" => profile.function_blocks = parse_profile_blocks(key, value),
+                "interface-blocks" => profile.interface_blocks = parse_profile_blocks(key, value),
+                "endpoint-blocks" => profile.endpoint_blocks = parse_profile_blocks(key, value),
+                _ => log::warn!("Ignoring unknown key '{key}' in block profile"),
+            }
+        }
+        Ok(profile)
+    }
+
+    /// Read the profile from .json at `file_path`; see [`BlockProfile::from_json`]
+    pub fn from_file<P: AsRef<Path>>(file_path: P) -> Result<Self> {
+        let s = std::fs::read_to_string(&file_path)?;
+        Self::from_json(&s).map_err(|e| {
+            Error::new(
+                ErrorKind::Parsing,
+                &format!("{} at {:?}", e.message, file_path.as_ref()),
+            )
+        })
+    }
+
+    /// Path of the profile `name` in the "profiles" folder of the [`Config::config_file_path`]
+    pub fn profile_path(name: &str) -> Option<PathBuf> {
+        Config::config_file_path().map(|p| p.join(PROFILES_DIR).join(format!("{name}.json")))
+    }
+
+    /// Load the profile `name` from the [`BlockProfile::profile_path`]
+    pub fn load(name: &str) -> Result<Self> {
+        let path = Self::profile_path(name)
+            .ok_or_else(|| Error::new(ErrorKind::Io, "Unable to determine profile file path"))?;
+        if !path.exists() {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                &format!("Block profile '{name}' not found at {path:?}"),
+            ));
+        }
+        log::info!("Loading block profile {:?}", path.display());
+        Self::from_file(path)
+    }
+
+    /// Save the profile to .json at `path`, creating parent folders
+    pub fn save_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        log::info!("Saving block profile to {:?}", path.as_ref().display());
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let f = File::create(&path)?;
+        serde_json::to_writer_pretty(f, self).map_err(|e| {
+            Error::new(
+                ErrorKind::Io,
+                &format!("Failed to save block profile: Error({e})"),
+            )
+        })
+    }
+
+    /// Save the profile as `name` at the [`BlockProfile::profile_path`], returning the path saved to
+    pub fn save(&self, name: &str) -> Result<PathBuf> {
+        let path = Self::profile_path(name)
+            .ok_or_else(|| Error::new(ErrorKind::Io, "Unable to determine profile file path"))?;
+        self.save_file(&path)?;
+        Ok(path)
+    }
+
+    /// Set the blocks of `settings` that are Some in the profile
+    pub fn apply(&self, settings: &mut display::PrintSettings) {
+        if self.blocks.is_some() {
+            settings.device_blocks = self.blocks.clone();
+        }
+        if self.bus_blocks.is_some() {
+            settings.bus_blocks = self.bus_blocks.clone();
+        }
+        if self.config_blocks.is_some() {
+            settings.config_blocks = self.config_blocks.clone();
+        }
+        if self.function_blocks.is_some() {
+            settings.function_blocks = self.function_blocks.clone();
+        }
+        if self.interface_blocks.is_some() {
+            settings.interface_blocks = self.interface_blocks.clone();
+        }
+        if self.endpoint_blocks.is_some() {
+            settings.endpoint_blocks = self.endpoint_blocks.clone();
+        }
+    }
+}
+
+impl From<&display::PrintSettings> for BlockProfile {
+    fn from(settings: &display::PrintSettings) -> Self {
+        BlockProfile {
+            blocks: settings.device_blocks.clone(),
+            bus_blocks: settings.bus_blocks.clone(),
+            config_blocks: settings.config_blocks.clone(),
+            function_blocks: settings.function_blocks.clone(),
+            interface_blocks: settings.interface_blocks.clone(),
+            endpoint_blocks: settings.endpoint_blocks.clone(),
+        }
+    }
+}
+
+/// Parses each block of the `key` array `value` on its own so that unknown names are skipped with a warning
+fn parse_profile_blocks<T: serde::de::DeserializeOwned>(
+    key: &str,
+    value: &serde_json::Value,
+) -> Option<Vec<T>> {
+    match value {
+        serde_json::Value::Array(values) => Some(
+            values
+                .iter()
+                .filter_map(|v| match serde_json::from_value(v.to_owned()) {
+                    Ok(b) => Some(b),
+                    Err(_) => {
+                        log::warn!("Ignoring unknown block {v} in block profile '{key}'");
+                        None
+                    }
+                })
+                .collect(),
+        ),
+        serde_json::Value::Null => None,
+        v => {
+            log::warn!("Ignoring block profile '{key}' as {v} is not an array");
+            None
+        }
+    }
+}
+
 impl From<&display::PrintSettings> for Config {
     fn from(settings: &display::PrintSettings) -> Self {
         let mut c = Config::new();
@@ -328,6 +652,50 @@ mod tests {
         assert!(Config::from_file(path).is_ok());
     }
 
+    #[test]
+    fn test_deserialize_config_invalid_colour() {
+        let config: Config =
+            serde_json::from_reader(r#"{"colours": {"name": "blurple"}}"#.as_bytes()).unwrap();
+        assert_eq!(config.colours.name, Some(colored::Color::White));
+    }
+
+    #[test]
+    fn test_block_profile_round_trip() {
+        let settings = display::PrintSettings {
+            device_blocks: Some(vec![
+                display::DeviceBlocks::VidPid,
+                display::DeviceBlocks::Name,
+            ]),
+            endpoint_blocks: Some(vec![display::EndpointBlocks::Number]),
+            ..Default::default()
+        };
+        let profile = BlockProfile::from(&settings);
+        let s = serde_json::to_string(&profile).unwrap();
+        // None blocks are not saved
+        assert!(!s.contains("bus-blocks"));
+        assert_eq!(BlockProfile::from_json(&s).unwrap(), profile);
+
+        let mut loaded = display::PrintSettings::default();
+        profile.apply(&mut loaded);
+        assert_eq!(loaded.device_blocks, settings.device_blocks);
+        assert_eq!(loaded.endpoint_blocks, settings.endpoint_blocks);
+        assert_eq!(loaded.bus_blocks, None);
+    }
+
+    #[test]
+    fn test_block_profile_unknown() {
+        let profile = BlockProfile::from_json(
+            r#"{"bus-blocks": ["bus-number", 2], "old-blocks": [], "blocks": "name"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            profile.bus_blocks,
+            Some(vec![display::BusBlocks::BusNumber])
+        );
+        assert_eq!(profile.blocks, None);
+        assert!(BlockProfile::from_json("[]").is_err());
+    }
+
     #[test]
     fn test_save_config() {
         // save to temp file
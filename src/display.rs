@@ -12,16 +12,19 @@ use std::hash::Hash;
 use std::io::{self, Write};
 use strum::{IntoEnumIterator, VariantArray};
 use strum_macros::{Display, EnumIter, VariantArray};
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use usb_ids::{self, FromId};
 
 use crate::colour;
 use crate::error::Result;
 use crate::icon;
-use crate::profiler::{Bus, Device, Filter, SystemProfile};
+use crate::profiler::diff::ProfileDiff;
+use crate::profiler::{Bus, Device, DeviceEvent, DeviceSpeed, Filter, SystemProfile};
 use crate::types::NumericalUnit;
 use crate::usb::{
     path::ConfigurationPath, path::DevicePath, path::EndpointPath, path::PortPath,
     ConfigAttributes, Configuration, DeviceExtra, Direction, Endpoint, Interface,
+    InterfaceAssociationDescriptor, Speed, SpeedUnit, TransferType,
 };
 
 const ICON_HEADING: &str = "I";
@@ -30,6 +33,8 @@ const MIN_VARIABLE_STRING_LEN: usize = 5; // minimum variable string length to s
 const LIST_INSET_SPACES: u8 = 2; // number of spaces for non-tree inset
 
 /// Colouring control for the output
+///
+/// Independent of [`IconWhen`]: the mode only decides whether ANSI escape codes are written, never which blocks are shown
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, ValueEnum, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum ColorWhen {
@@ -48,7 +53,65 @@ impl std::fmt::Display for ColorWhen {
     }
 }
 
+impl ColorWhen {
+    /// Sets the global [`colored`] override for the colouring mode
+    ///
+    /// [`ColorWhen::Auto`] unsets any override so that `NO_COLOR` and whether stdout is a terminal decide
+    pub fn set_override(&self) {
+        match self {
+            ColorWhen::Always => colored::control::set_override(true),
+            ColorWhen::Never => colored::control::set_override(false),
+            ColorWhen::Auto => colored::control::unset_override(),
+        }
+    }
+}
+
+/// How to fit [`BlockLength::Variable`] values wider than the `max_variable_string_len`
+///
+/// ```
+/// use cyme::display::{render_line, Block, DeviceBlocks, PrintSettings, WrapMode};
+/// use cyme::profiler::Device;
+///
+/// let device = Device {
+///     name: "Black Magic Probe".into(),
+///     serial_num: Some("97B6A11D".into()),
+///     ..Default::default()
+/// };
+/// let blocks = [DeviceBlocks::Name, DeviceBlocks::Serial];
+/// let mut pad = DeviceBlocks::generate_padding(&[&device]);
+/// // padding is clamped to the max length when printing
+/// pad.insert(DeviceBlocks::Name, 11);
+///
+/// let settings = PrintSettings::default();
+/// let line = render_line(&device, &blocks, &pad, &settings, Some(11), false);
+/// assert_eq!(line, "Black Ma... 97B6A11D");
+///
+/// let settings = PrintSettings {
+///     wrap_mode: WrapMode::Wrap,
+///     ..Default::default()
+/// };
+/// let line = render_line(&device, &blocks, &pad, &settings, Some(11), false);
+/// assert_eq!(line, "Black Magic 97B6A11D\nProbe");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, ValueEnum, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum WrapMode {
+    /// Truncate with '...'
+    #[default]
+    Truncate,
+    /// Wrap onto continuation lines aligned to the column start
+    Wrap,
+}
+
+impl std::fmt::Display for WrapMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
 /// Icon control for the output
+///
+/// Independent of [`ColorWhen`]: icon blocks are kept or dropped the same with and without colour
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, ValueEnum, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum IconWhen {
@@ -144,6 +207,48 @@ pub enum Encoding {
     Ascii,
 }
 
+/// Fallbacks for the [`static@icon::DEFAULT_ICONS`] glyphs as (glyph, UTF-8, ASCII)
+const GLYPH_FALLBACKS: &[(char, char, char)] = &[
+    ('\u{f287}', '•', '*'),  // usb plug default
+    ('\u{f179}', '•', '*'),  // apple
+    ('\u{f0372}', '•', '*'), // microsoft
+    ('\u{f1a0}', '•', '*'),  // google
+    ('\u{f17c}', '•', '*'),  // linux foundation
+    ('\u{e771}', '•', '*'),  // open source VID
+    ('\u{f00a3}', '•', '*'), // specialized
+    ('\u{f037d}', '•', '*'), // logitech
+    ('\u{e2a6}', '•', '*'),  // garmin
+    ('\u{f315}', '•', '*'),  // raspberry pi foundation
+    ('\u{f129e}', '▤', 's'), // sandisk
+    ('\u{ef61}', '▤', 's'),  // sd card reader
+    ('\u{e70e}', '•', '*'),  // android
+    ('\u{f0379}', '▭', 'm'), // monitor
+    ('\u{f188}', '◆', 'd'),  // debug probe
+    ('\u{f019}', '↓', 'v'),  // dfu
+    ('\u{f2db}', '▪', 'c'),  // chip
+    ('\u{f191d}', '⇄', 'n'), // can bus
+    ('\u{f001}', '♪', 'a'),  // audio
+    ('\u{f03e}', '▣', 'i'),  // image
+    ('\u{f03d}', '▶', 'v'),  // video
+    ('\u{f02f}', '⎙', 'p'),  // printer
+    ('\u{f0a0}', '▤', 's'),  // mass storage
+    ('\u{f126}', '⑂', 'h'),  // hub
+    ('\u{f084}', '⚷', 'k'),  // key
+    ('\u{f21e}', '♥', '+'),  // personal healthcare
+    ('\u{f0841}', '▶', 'v'), // audio video
+    ('\u{f05a}', 'ℹ', 'i'),  // billboard
+    ('\u{f493}', '▪', 'c'),  // i3c
+    ('\u{f489}', '▪', 'd'),  // diagnostic
+    ('\u{f1eb}', '≈', 'w'),  // wireless
+    ('\u{f074}', '⁂', 'm'),  // miscellaneous
+    ('\u{e795}', '⇄', 'c'),  // serial
+    ('\u{f030c}', '▦', 'k'), // hid
+    ('\u{f041a}', '↻', 'p'), // profiled
+    ('\u{f0c53}', '+', '+'), // connected
+    ('\u{f015b}', '×', 'x'), // disconnected
+    ('×', '×', 'x'),         // multiplier
+];
+
 impl std::fmt::Display for Encoding {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -210,6 +315,49 @@ impl Encoding {
     pub fn str_is_valid(&self, s: &str) -> bool {
         s.chars().all(|c| self.char_is_valid(c))
     }
+
+    /// Returns a substitute for `glyph` if it is not valid for the encoding and a fallback is known
+    ///
+    /// Fallbacks are single width so that icon blocks stay aligned; `None` if `glyph` is already valid or there is no known substitute
+    ///
+    /// ```
+    /// use cyme::display::Encoding;
+    ///
+    /// // hub glyph
+    /// assert_eq!(Encoding::Utf8.fallback('\u{f126}'), Some("⑂".into()));
+    /// assert_eq!(Encoding::Ascii.fallback('\u{f126}'), Some("h".into()));
+    /// assert_eq!(Encoding::Glyphs.fallback('\u{f126}'), None);
+    /// // already valid
+    /// assert_eq!(Encoding::Ascii.fallback('I'), None);
+    /// // no known substitute
+    /// assert_eq!(Encoding::Utf8.fallback('\u{e000}'), None);
+    /// ```
+    pub fn fallback(&self, glyph: char) -> Option<String> {
+        if self.char_is_valid(glyph) {
+            return None;
+        }
+        GLYPH_FALLBACKS
+            .iter()
+            .find(|(g, _, _)| *g == glyph)
+            .map(|(_, utf8, ascii)| match self {
+                Encoding::Ascii => ascii.to_string(),
+                _ => utf8.to_string(),
+            })
+            .or_else(|| match self {
+                // valid UTF-8 glyph such as the undefined classifier but not ASCII
+                Encoding::Ascii if Encoding::Utf8.char_is_valid(glyph) => Some("?".into()),
+                _ => None,
+            })
+    }
+
+    /// Replaces chars in `s` that are not valid for the encoding with their [`Encoding::fallback`]
+    ///
+    /// Chars without a known fallback are left as is so that [`IconWhen::Auto`] still removes the block
+    pub fn substitute(&self, s: &str) -> String {
+        s.chars()
+            .map(|c| self.fallback(c).unwrap_or_else(|| c.to_string()))
+            .collect()
+    }
 }
 
 /// Info that can be printed about a [`Device`]
@@ -266,6 +414,8 @@ pub enum DeviceBlocks {
     Speed,
     /// Negotiated device speed as connected
     NegotiatedSpeed,
+    /// Negotiated device speed if known else advertised speed, marked with '*' when they differ
+    CombinedSpeed,
     /// Position along all branches back to trunk device
     TreePositions,
     /// macOS system_profiler only - actually bus current in mA not power!
@@ -300,6 +450,24 @@ pub enum DeviceBlocks {
     LastEvent,
     /// Event icon
     EventIcon,
+    /// Number of devices directly attached to device (children)
+    NumDevices,
+    /// Maximum power in mA of the device configuration as a number - only available when using libusb/nusb
+    MaxPowerNumeric,
+    /// "vid:pid name" in one column; name from descriptor or usb_ids product name if empty
+    VidPidName,
+    /// Linux runtime power management status such as "active" or "suspended"
+    PowerState,
+    /// Distinct interface classes of the device such as "Audio,HID"
+    InterfaceClasses,
+    /// USB-C alternate modes advertised in Billboard capabilities such as "DisplayPort Alt Mode"
+    AltModes,
+    /// Current drawn/available in mA from [`Device::power_summary`], prefixed with '!' and the [`colour::ColourTheme`] `warning` colour if the device or a downstream port is over budget
+    PowerBudget,
+    /// Number of downstream ports of a hub class device from the hub descriptor - only available when using libusb/nusb
+    Hub,
+    /// USB specification generation such as "USB 3.2 Gen 2" from bcdUSB and the negotiated speed if known, otherwise the bcdUSB
+    UsbGeneration,
 }
 
 /// Info that can be printed about a [`Bus`]
@@ -340,8 +508,14 @@ pub enum BusBlocks {
     PciDevice,
     /// PCI Revsision ID
     PciRevision,
+    /// PCI sub-class and programming interface of the host controller from pci.ids, such as "USB controller / XHCI"; Linux only
+    HostControllerType,
     /// syspath style port path to bus, applicable to Linux only
     PortPath,
+    /// Number of devices attached to the bus including those behind hubs
+    NumDevices,
+    /// Depth of the deepest device branch on the bus
+    MaxDepth,
 }
 
 /// Info that can be printed about a [`Configuration`]
@@ -374,6 +548,52 @@ pub enum ConfigurationBlocks {
     IconAttributes,
     /// Maximum current consumption in mA
     MaxPower,
+    /// Whether bmAttributes has self powered set: "Y" or "N"
+    SelfPowered,
+    /// Whether bmAttributes has remote wake-up set: "Y" or "N"
+    RemoteWakeup,
+    /// Functions grouped by Interface Association Descriptors as interface numbers and base class
+    Functions,
+    /// Whether this is the active configuration of the device: "Y", "N" or "-" if not known
+    Active,
+}
+
+/// Info that can be printed about a function grouped by an [`InterfaceAssociationDescriptor`]
+#[non_exhaustive]
+#[derive(
+    Debug,
+    Copy,
+    EnumIter,
+    VariantArray,
+    ValueEnum,
+    Display,
+    Eq,
+    PartialEq,
+    Hash,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum FunctionBlocks {
+    /// Name from function string descriptor
+    Name,
+    /// Interface numbers grouped into the function
+    Interfaces,
+    /// First interface number, bFirstInterface
+    FirstInterface,
+    /// Number of interfaces, bInterfaceCount
+    NumInterfaces,
+    /// Base class enum of function provided by USB IF
+    BaseClass,
+    /// Base class as number value rather than enum
+    BaseValue,
+    /// Sub-class value of function provided by USB IF
+    SubClass,
+    /// Prototol value for function provided by USB IF
+    Protocol,
+    /// Fully defined USB Class Code based on BaseClass/SubClass/Protocol triplet
+    Class,
 }
 
 /// Info that can be printed about a [`Interface`]
@@ -428,6 +648,8 @@ pub enum InterfaceBlocks {
     /// Base class as number value rather than enum
     #[serde(alias = "class-value")]
     BaseValue,
+    /// Estimated periodic bandwidth of Interrupt and Isochronous endpoints as percentage of the device speed budget
+    Bandwidth,
 }
 
 /// Info that can be printed about a [`Endpoint`]
@@ -462,6 +684,74 @@ pub enum EndpointBlocks {
     MaxPacketSize,
     /// Interval for polling endpoint data transfers. Value in frame counts. Ignored for Bulk & Control Endpoints. Isochronous must equal 1 and field may range from 1 to 255 for interrupt endpoints.
     Interval,
+    /// Polling interval in milliseconds computed from bInterval, transfer type and device speed. Raw bInterval with '?' if device speed unknown
+    IntervalMs,
+    /// bMaxBurst from the SuperSpeed Endpoint Companion descriptor; '-' if the endpoint has no companion
+    MaxBurst,
+    /// wBytesPerInterval from the SuperSpeed Endpoint Companion descriptor; '-' if the endpoint has no companion
+    BytesPerInterval,
+}
+
+/// Alignment of a [`Block`] value within its padded column
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Alignment {
+    /// Pad to the right of value
+    #[default]
+    Left,
+    /// Pad to the left of value
+    Right,
+    /// Pad either side of value
+    Center,
+}
+
+impl Alignment {
+    /// Pads `s` to `width` based on the alignment
+    ///
+    /// ```
+    /// use cyme::display::Alignment;
+    ///
+    /// assert_eq!(Alignment::Left.pad("ab", 4), "ab  ");
+    /// assert_eq!(Alignment::Right.pad("ab", 4), "  ab");
+    /// assert_eq!(Alignment::Center.pad("ab", 4), " ab ");
+    /// ```
+    pub fn pad(&self, s: &str, width: usize) -> String {
+        // display width rather than chars so wide glyphs stay aligned
+        let fill = width.saturating_sub(s.width());
+        let (left, right) = match self {
+            Alignment::Left => (0, fill),
+            Alignment::Right => (fill, 0),
+            Alignment::Center => (fill / 2, fill - fill / 2),
+        };
+        format!("{}{s}{}", " ".repeat(left), " ".repeat(right))
+    }
+}
+
+/// [`Alignment`] overrides of block values for each block type, keyed by block like the block lists in the config
+///
+/// ```
+/// use cyme::display::{Alignment, AlignmentOverrides, DeviceBlocks};
+///
+/// let overrides: AlignmentOverrides =
+///     serde_json::from_str(r#"{"blocks": {"name": "right"}}"#).unwrap();
+/// assert_eq!(overrides.blocks.get(&DeviceBlocks::Name), Some(&Alignment::Right));
+/// assert!(overrides.interface_blocks.is_empty());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, default)]
+pub struct AlignmentOverrides {
+    /// [`DeviceBlocks`] alignments
+    pub blocks: HashMap<DeviceBlocks, Alignment>,
+    /// [`BusBlocks`] alignments
+    pub bus_blocks: HashMap<BusBlocks, Alignment>,
+    /// [`ConfigurationBlocks`] alignments
+    pub config_blocks: HashMap<ConfigurationBlocks, Alignment>,
+    /// [`FunctionBlocks`] alignments
+    pub function_blocks: HashMap<FunctionBlocks, Alignment>,
+    /// [`InterfaceBlocks`] alignments
+    pub interface_blocks: HashMap<InterfaceBlocks, Alignment>,
+    /// [`EndpointBlocks`] alignments
+    pub endpoint_blocks: HashMap<EndpointBlocks, Alignment>,
 }
 
 /// Length of field printed by block
@@ -512,6 +802,7 @@ pub trait BlockEnum: Eq + Hash + VariantArray + ValueEnum {}
 impl BlockEnum for DeviceBlocks {}
 impl BlockEnum for BusBlocks {}
 impl BlockEnum for ConfigurationBlocks {}
+impl BlockEnum for FunctionBlocks {}
 impl BlockEnum for InterfaceBlocks {}
 impl BlockEnum for EndpointBlocks {}
 
@@ -545,6 +836,11 @@ pub trait Block<B: BlockEnum, T> {
     /// Colour the block String
     fn colour(&self, s: &str, ct: &colour::ColourTheme) -> ColoredString;
 
+    /// Colour the block String of `d`, for blocks whose colour depends on the value; defaults to [`Block::colour`]
+    fn colour_value(&self, _d: &T, s: &str, ct: &colour::ColourTheme) -> ColoredString {
+        self.colour(s, ct)
+    }
+
     /// Creates the heading for the block value, for use with the heading flag
     fn heading(&self) -> &str;
 
@@ -559,13 +855,22 @@ pub trait Block<B: BlockEnum, T> {
         }
     }
 
+    /// Default [`Alignment`] of the value within the padded column: variable length values are left aligned and fixed values right aligned
+    fn alignment(&self) -> Alignment {
+        if self.value_is_variable_length() {
+            Alignment::Left
+        } else {
+            Alignment::Right
+        }
+    }
+
+    /// The [`Alignment`] override for the block in the [`PrintSettings`] `alignment_overrides` if any
+    fn alignment_override(&self, settings: &PrintSettings) -> Option<Alignment>;
+
     /// Formats the value associated with the block into a display String
-    fn format_value(
-        &self,
-        d: &T,
-        pad: &HashMap<B, usize>,
-        settings: &PrintSettings,
-    ) -> Option<String>;
+    ///
+    /// The value is not padded to the column width; [`render_value`] pads it with the [`Block::alignment`] or any override
+    fn format_value(&self, d: &T, settings: &PrintSettings) -> Option<String>;
 
     /// Formats u16 values like VID as base16 or base10 depending on decimal setting
     fn format_base_u16(v: u16, settings: &PrintSettings) -> String {
@@ -627,6 +932,11 @@ impl DeviceBlocks {
         blocks
     }
 
+    /// Whether the block value changes with time rather than the device, such as [`DeviceBlocks::LastEvent`] relative to now, so is not compared between profiles to highlight changes
+    pub fn is_time_dependent(&self) -> bool {
+        matches!(self, DeviceBlocks::LastEvent)
+    }
+
     /// Default `DeviceBlocks` for tree printing are different to list, get them here
     pub fn default_device_tree_blocks() -> Vec<Self> {
         #[cfg(target_os = "linux")]
@@ -658,6 +968,186 @@ impl DeviceBlocks {
     }
 }
 
+/// Typed value of a [`DeviceBlocks`] for a [`Device`] used when sorting by block
+#[derive(Debug, PartialEq, PartialOrd)]
+enum BlockSortKey {
+    Number(f64),
+    Sequence(Vec<u8>),
+    Text(String),
+}
+
+/// [`DeviceEvent`] relative to now if `relative_time` in [`PrintMode::Dynamic`], otherwise the absolute time
+///
+/// Padding is generated from the absolute time, which is never shorter than the relative one
+fn last_event_string(e: &DeviceEvent, settings: &PrintSettings) -> String {
+    if settings.relative_time && settings.print_mode == PrintMode::Dynamic {
+        e.format_relative(chrono::Local::now())
+    } else {
+        e.to_string()
+    }
+}
+
+/// [`Device::interface_classes`] short names joined with ',' or "-" if there are none
+fn interface_classes_string(d: &Device) -> String {
+    let classes = d.interface_classes();
+    if classes.is_empty() {
+        String::from("-")
+    } else {
+        classes.iter().map(|c| c.short_name()).join(",")
+    }
+}
+
+/// [`Device::alternate_modes`] descriptions joined with ", " or "-" if there are none
+fn alternate_modes_string(d: &Device) -> String {
+    let modes = d.alternate_modes();
+    if modes.is_empty() {
+        String::from("-")
+    } else {
+        modes.iter().map(|m| m.description()).join(", ")
+    }
+}
+
+/// [`Device::power_summary`] as "drawn/available" with '-' for unknown values and a '!' prefix if over budget
+fn power_budget_string(d: &Device) -> String {
+    let summary = d.power_summary();
+    let value = |v: Option<u32>| v.map_or(String::from("-"), |v| v.to_string());
+    match (summary.total(), summary.available) {
+        (None, None) => String::from("-"),
+        (total, available) => format!(
+            "{}{}/{}",
+            if summary.has_over_budget() { "!" } else { "" },
+            value(total),
+            value(available)
+        ),
+    }
+}
+
+/// Speed in Mb/s so that Gb/s and Mb/s speeds compare numerically
+fn speed_mbps(speed: &Speed) -> f64 {
+    speed.to_mbps() as f64
+}
+
+/// `speed` in the [`PrintSettings`] `speed_unit` or None for [`SpeedUnit::Auto`], where each block keeps its own format
+fn speed_in_unit(speed: &Speed, settings: &PrintSettings) -> Option<String> {
+    match settings.speed_unit {
+        SpeedUnit::Auto => None,
+        unit => Some(speed.to_string_in(unit)),
+    }
+}
+
+/// `speed` in the [`PrintSettings`] `speed_unit`; [`SpeedUnit::Auto`] and descriptions use the [`DeviceSpeed`] format
+fn device_speed_string(speed: &DeviceSpeed, settings: &PrintSettings) -> String {
+    match speed {
+        DeviceSpeed::SpeedValue(s) => {
+            speed_in_unit(s, settings).unwrap_or_else(|| speed.to_string())
+        }
+        DeviceSpeed::Description(_) => speed.to_string(),
+    }
+}
+
+impl DeviceBlocks {
+    /// Underlying typed value of the block for `d` so numeric blocks like [`DeviceBlocks::Speed`] sort by value. Textual blocks fall back to the formatted string
+    fn sort_key(&self, d: &Device, settings: &PrintSettings) -> Option<BlockSortKey> {
+        let number = |v: Option<f64>| v.map(BlockSortKey::Number);
+        match self {
+            DeviceBlocks::BusNumber => number(Some(d.location_id.bus as f64)),
+            DeviceBlocks::DeviceNumber => number(Some(d.location_id.number as f64)),
+            DeviceBlocks::BranchPosition => number(Some(d.get_branch_position() as f64)),
+            DeviceBlocks::PortPath | DeviceBlocks::TreePositions => Some(BlockSortKey::Sequence(
+                std::iter::once(d.location_id.bus)
+                    .chain(d.location_id.tree_positions.iter().copied())
+                    .collect(),
+            )),
+            DeviceBlocks::VendorId => number(d.vendor_id.map(f64::from)),
+            DeviceBlocks::ProductId => number(d.product_id.map(f64::from)),
+            DeviceBlocks::VidPid => d.vendor_id.map(|v| {
+                BlockSortKey::Number(((v as u32) << 16 | d.product_id.unwrap_or(0) as u32) as f64)
+            }),
+            DeviceBlocks::Speed => match &d.device_speed {
+                Some(DeviceSpeed::SpeedValue(v)) => number(Some(speed_mbps(v))),
+                _ => None,
+            },
+            DeviceBlocks::NegotiatedSpeed => d
+                .extra
+                .as_ref()
+                .and_then(|e| e.negotiated_speed.as_ref())
+                .map(|v| BlockSortKey::Number(speed_mbps(v))),
+            DeviceBlocks::CombinedSpeed => {
+                match d.extra.as_ref().and_then(|e| e.negotiated_speed.as_ref()) {
+                    Some(v) => number(Some(speed_mbps(v))),
+                    None => match &d.device_speed {
+                        Some(DeviceSpeed::SpeedValue(v)) => number(Some(speed_mbps(v))),
+                        _ => None,
+                    },
+                }
+            }
+            DeviceBlocks::BusPower => number(d.bus_power.map(f64::from)),
+            DeviceBlocks::BusPowerUsed => number(d.bus_power_used.map(f64::from)),
+            DeviceBlocks::ExtraCurrentUsed => number(d.extra_current_used.map(f64::from)),
+            DeviceBlocks::BcdDevice => d
+                .bcd_device
+                .map(|v| BlockSortKey::Sequence(vec![v.0, v.1, v.2])),
+            DeviceBlocks::BcdUsb | DeviceBlocks::UsbGeneration => d
+                .bcd_usb
+                .map(|v| BlockSortKey::Sequence(vec![v.0, v.1, v.2])),
+            DeviceBlocks::BaseClass | DeviceBlocks::BaseValue => {
+                number(d.base_class_code().map(f64::from))
+            }
+            DeviceBlocks::SubClass => number(d.sub_class.map(f64::from)),
+            DeviceBlocks::Protocol => number(d.protocol.map(f64::from)),
+            DeviceBlocks::LastEvent => {
+                number(d.last_event().map(|e| e.time().timestamp_millis() as f64))
+            }
+            DeviceBlocks::NumDevices => {
+                number(Some(d.devices.as_ref().map_or(0, |v| v.len()) as f64))
+            }
+            DeviceBlocks::MaxPowerNumeric => number(d.max_power().map(f64::from)),
+            DeviceBlocks::Hub => number(d.hub_ports().map(f64::from)),
+            _ => self
+                .format_value(d, settings)
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty() && s != "-")
+                .map(BlockSortKey::Text),
+        }
+    }
+
+    /// Compare [`Device`]s by the typed value of this block; devices without a value for the block are last
+    ///
+    /// ```
+    /// use cyme::display::{DeviceBlocks, PrintSettings};
+    /// use cyme::profiler::{Device, DeviceSpeed};
+    /// use cyme::usb::Speed;
+    ///
+    /// let mut high = Device::default();
+    /// high.device_speed = Some(DeviceSpeed::SpeedValue(Speed::HighSpeed));
+    /// let mut sup = Device::default();
+    /// sup.device_speed = Some(DeviceSpeed::SpeedValue(Speed::SuperSpeed));
+    /// // 480 Mb/s is slower than 5 Gb/s even though "480.0" > "5.0" as a string
+    /// assert_eq!(
+    ///     DeviceBlocks::Speed.cmp_devices(&high, &sup, &PrintSettings::default()),
+    ///     std::cmp::Ordering::Less
+    /// );
+    /// ```
+    pub fn cmp_devices(&self, a: &Device, b: &Device, settings: &PrintSettings) -> cmp::Ordering {
+        match (self.sort_key(a, settings), self.sort_key(b, settings)) {
+            (Some(ka), Some(kb)) => ka.partial_cmp(&kb).unwrap_or(cmp::Ordering::Equal),
+            (Some(_), None) => cmp::Ordering::Less,
+            (None, Some(_)) => cmp::Ordering::Greater,
+            (None, None) => cmp::Ordering::Equal,
+        }
+    }
+
+    /// Sort the [`Device`]s at each branch in place by this block; the sort is stable so [`Sort`] order is kept for equal values
+    pub fn sort_devices_recursive(&self, devices: &mut [Device], settings: &PrintSettings) {
+        devices.sort_by(|a, b| self.cmp_devices(a, b, settings));
+        for device in devices {
+            if let Some(branch_devices) = &mut device.devices {
+                self.sort_devices_recursive(branch_devices, settings);
+            }
+        }
+    }
+}
+
 impl Block<DeviceBlocks, Device> for DeviceBlocks {
     #[cfg(target_os = "linux")]
     fn default_blocks(verbose: bool) -> Vec<Self> {
@@ -755,6 +1245,12 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
     fn len(&self, d: &[&Device]) -> usize {
         match self {
             DeviceBlocks::Name => d.iter().map(|d| d.name.width()).max().unwrap_or(0),
+            // decimal is the widest id format 'vvvvv:ppppp '
+            DeviceBlocks::VidPidName => d
+                .iter()
+                .map(|d| 12 + vidpid_name_product(d).width())
+                .max()
+                .unwrap_or(0),
             DeviceBlocks::Serial => d
                 .iter()
                 .flat_map(|d| d.serial_num.as_ref().map(|s| s.width()))
@@ -794,6 +1290,35 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
                 })
                 .max()
                 .unwrap_or(0),
+            DeviceBlocks::PowerState => d
+                .iter()
+                .flat_map(|d| {
+                    d.extra
+                        .as_ref()
+                        .and_then(|e| e.power_state.as_ref().map(|s| s.len()))
+                })
+                .max()
+                .unwrap_or(0),
+            DeviceBlocks::InterfaceClasses => d
+                .iter()
+                .map(|d| interface_classes_string(d).len())
+                .max()
+                .unwrap_or(0),
+            DeviceBlocks::AltModes => d
+                .iter()
+                .map(|d| alternate_modes_string(d).width())
+                .max()
+                .unwrap_or(0),
+            DeviceBlocks::PowerBudget => d
+                .iter()
+                .map(|d| power_budget_string(d).len())
+                .max()
+                .unwrap_or(0),
+            DeviceBlocks::UsbGeneration => d
+                .iter()
+                .flat_map(|d| d.usb_generation().map(|s| s.len()))
+                .max()
+                .unwrap_or(0),
             DeviceBlocks::ProductName => d
                 .iter()
                 .flat_map(|d| {
@@ -852,70 +1377,54 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
             .collect()
     }
 
-    fn format_value(
-        &self,
-        d: &Device,
-        pad: &HashMap<Self, usize>,
-        settings: &PrintSettings,
-    ) -> Option<String> {
+    fn format_value(&self, d: &Device, settings: &PrintSettings) -> Option<String> {
         match self {
             DeviceBlocks::BusNumber => Some(format!("{:3}", d.location_id.bus)),
             DeviceBlocks::DeviceNumber => Some(format!("{:3}", d.location_id.number)),
             DeviceBlocks::BranchPosition => Some(format!("{:3}", d.get_branch_position())),
-            DeviceBlocks::PortPath => Some(format!(
-                "{:pad$}",
-                d.port_path().to_string(),
-                pad = pad.get(self).unwrap_or(&0)
-            )),
-            DeviceBlocks::SysPath => Some(match d.extra.as_ref() {
-                Some(e) => format!(
-                    "{:pad$}",
-                    e.syspath.as_ref().unwrap_or(&format!(
-                        "{:pad$}",
-                        "-",
-                        pad = pad.get(self).unwrap_or(&0)
-                    )),
-                    pad = pad.get(self).unwrap_or(&0)
-                ),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
-            }),
-            DeviceBlocks::Driver => Some(match d.extra.as_ref() {
-                Some(e) => format!(
-                    "{:pad$}",
-                    e.driver.as_ref().unwrap_or(&format!(
-                        "{:pad$}",
-                        "-",
-                        pad = pad.get(self).unwrap_or(&0)
-                    )),
-                    pad = pad.get(self).unwrap_or(&0)
-                ),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
-            }),
-            DeviceBlocks::ProductName => Some(match d.extra.as_ref() {
-                Some(e) => format!(
-                    "{:pad$}",
-                    e.product_name.as_ref().unwrap_or(&format!(
-                        "{:pad$}",
-                        "-",
-                        pad = pad.get(self).unwrap_or(&0)
-                    )),
-                    pad = pad.get(self).unwrap_or(&0)
-                ),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
-            }),
-            DeviceBlocks::VendorName => Some(match d.extra.as_ref() {
-                Some(e) => format!(
-                    "{:pad$}",
-                    e.vendor.as_ref().unwrap_or(&format!(
-                        "{:pad$}",
-                        "-",
-                        pad = pad.get(self).unwrap_or(&0)
-                    )),
-                    pad = pad.get(self).unwrap_or(&0)
-                ),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
-            }),
-            DeviceBlocks::Icon => settings.icons.as_ref().map(|i| i.get_device_icon(d)),
+            DeviceBlocks::PortPath => Some(rendered_port_path(d)),
+            DeviceBlocks::SysPath => Some(
+                d.extra
+                    .as_ref()
+                    .and_then(|e| e.syspath.as_deref())
+                    .unwrap_or("-")
+                    .to_string(),
+            ),
+            DeviceBlocks::Driver => Some(
+                d.extra
+                    .as_ref()
+                    .and_then(|e| e.driver.as_deref())
+                    .unwrap_or("-")
+                    .to_string(),
+            ),
+            DeviceBlocks::PowerState => Some(
+                d.extra
+                    .as_ref()
+                    .and_then(|e| e.power_state.as_deref())
+                    .unwrap_or("-")
+                    .to_string(),
+            ),
+            DeviceBlocks::InterfaceClasses => Some(interface_classes_string(d)),
+            DeviceBlocks::AltModes => Some(alternate_modes_string(d)),
+            DeviceBlocks::PowerBudget => Some(power_budget_string(d)),
+            DeviceBlocks::ProductName => Some(
+                d.extra
+                    .as_ref()
+                    .and_then(|e| e.product_name.as_deref())
+                    .unwrap_or("-")
+                    .to_string(),
+            ),
+            DeviceBlocks::VendorName => Some(
+                d.extra
+                    .as_ref()
+                    .and_then(|e| e.vendor.as_deref())
+                    .unwrap_or("-")
+                    .to_string(),
+            ),
+            DeviceBlocks::Icon => settings
+                .icons
+                .as_ref()
+                .map(|i| settings.encoding.substitute(&i.get_device_icon(d))),
             DeviceBlocks::VendorId => Some(match d.vendor_id {
                 Some(v) => Self::format_base_u16(v, settings),
                 None => format!("{:>6}", "-"),
@@ -925,37 +1434,57 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
                 None => format!("{:>6}", "-"),
             }),
             DeviceBlocks::VidPid => Some(Self::format_vidpid(d.vendor_id, d.product_id, settings)),
-            DeviceBlocks::Name => Some(format!(
-                "{:pad$}",
-                d.name,
-                pad = pad.get(self).unwrap_or(&0)
-            )),
+            DeviceBlocks::Name => Some(d.name.to_string()),
+            DeviceBlocks::VidPidName => {
+                let id = |v: Option<u16>| match v {
+                    Some(v) if settings.decimal => v.to_string(),
+                    Some(v) => format!("{v:04x}"),
+                    None => String::from("-"),
+                };
+                Some(format!(
+                    "{}:{} {}",
+                    id(d.vendor_id),
+                    id(d.product_id),
+                    vidpid_name_product(d)
+                ))
+            }
             DeviceBlocks::Manufacturer => Some(match d.manufacturer.as_ref() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             DeviceBlocks::Serial => Some(match d.serial_num.as_ref() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             DeviceBlocks::Speed => Some(match d.device_speed.as_ref() {
-                Some(v) => format!("{:>10}", v.to_string()),
+                Some(v) => format!("{:>10}", device_speed_string(v, settings)),
                 None => format!("{:>10}", "-"),
             }),
             DeviceBlocks::NegotiatedSpeed => Some(
                 match d.extra.as_ref().and_then(|e| e.negotiated_speed.as_ref()) {
                     Some(v) => {
-                        let nu = NumericalUnit::<f32>::from(v);
-                        format!("{:>10}", nu.to_string())
+                        let speed = speed_in_unit(v, settings)
+                            .unwrap_or_else(|| NumericalUnit::<f32>::from(v).to_string());
+                        format!("{speed:>10}")
                     }
                     None => format!("{:>10}", "-"),
                 },
             ),
-            DeviceBlocks::TreePositions => Some(format!(
-                "{:pad$}",
-                format!("{:}", d.location_id.tree_positions.iter().format("-")),
-                pad = pad.get(self).unwrap_or(&0)
-            )),
+            DeviceBlocks::CombinedSpeed => {
+                let speed = match d.extra.as_ref().and_then(|e| e.negotiated_speed.as_ref()) {
+                    Some(v) => speed_in_unit(v, settings)
+                        .unwrap_or_else(|| NumericalUnit::<f32>::from(v).to_string()),
+                    None => d
+                        .device_speed
+                        .as_ref()
+                        .map_or("-".to_string(), |v| device_speed_string(v, settings)),
+                };
+                let marker = if d.has_speed_mismatch() { "*" } else { " " };
+                Some(format!("{speed:>10}{marker}"))
+            }
+            DeviceBlocks::TreePositions => {
+                Some(d.location_id.tree_positions.iter().format("-").to_string())
+            }
             DeviceBlocks::BusPower => Some(match d.bus_power {
                 Some(v) => format!("{v:3} mA"),
                 None => format!("{:>6}", "-"),
@@ -977,8 +1506,8 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
                 None => format!("{:>5}", "-"),
             }),
             DeviceBlocks::BaseClass => Some(match d.class.as_ref() {
-                Some(v) => format!("{:pad$}", v.to_string(), pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             DeviceBlocks::SubClass => Some(match d.sub_class.as_ref() {
                 Some(v) => Self::format_base_u8(*v, settings),
@@ -989,41 +1518,69 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
                 None => format!("{:>4}", "-"),
             }),
             DeviceBlocks::UidClass => Some(match d.class_name() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             DeviceBlocks::UidSubClass => Some(match d.sub_class_name() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             DeviceBlocks::UidProtocol => Some(match d.protocol_name() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             DeviceBlocks::Class => Some(match d.fully_defined_class() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             DeviceBlocks::BaseValue => Some(match d.class.as_ref() {
                 Some(v) => Self::format_base_u8((*v).into(), settings),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                None => "-".to_string(),
             }),
             DeviceBlocks::LastEvent => Some(match d.last_event() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => last_event_string(&v, settings),
+                None => "-".to_string(),
             }),
             DeviceBlocks::EventIcon => match d.last_event() {
-                Some(e) => settings.icons.as_ref().map(|i| i.get_event_icon(&e)),
+                Some(e) => settings
+                    .icons
+                    .as_ref()
+                    .map(|i| settings.encoding.substitute(&i.get_event_icon(&e))),
                 None => None,
             },
+            DeviceBlocks::NumDevices => {
+                Some(format!("{:4}", d.devices.as_ref().map_or(0, |v| v.len())))
+            }
+            DeviceBlocks::MaxPowerNumeric => Some(match d.max_power() {
+                Some(v) => format!("{v:4}"),
+                None => format!("{:>4}", "-"),
+            }),
+            DeviceBlocks::Hub => Some(match d.hub_ports() {
+                Some(v) => format!("{v:4}"),
+                None => format!("{:>4}", "-"),
+            }),
+            DeviceBlocks::UsbGeneration => {
+                Some(d.usb_generation().unwrap_or_else(|| String::from("-")))
+            }
+        }
+    }
+
+    fn colour_value(&self, d: &Device, s: &str, ct: &colour::ColourTheme) -> ColoredString {
+        match self {
+            DeviceBlocks::PowerBudget if d.power_summary().has_over_budget() => {
+                ct.warning.map_or(s.bold(), |c| s.color(c).bold())
+            }
+            _ => self.colour(s, ct),
         }
     }
 
     fn colour(&self, s: &str, ct: &colour::ColourTheme) -> ColoredString {
         match self {
             DeviceBlocks::BcdUsb
+            | DeviceBlocks::UsbGeneration
             | DeviceBlocks::BcdDevice
             | DeviceBlocks::DeviceNumber
+            | DeviceBlocks::NumDevices
             | DeviceBlocks::LastEvent => ct.number.map_or(s.normal(), |c| s.color(c)),
             DeviceBlocks::BusNumber
             | DeviceBlocks::BranchPosition
@@ -1034,7 +1591,7 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
             DeviceBlocks::PortPath | DeviceBlocks::SysPath => {
                 ct.path.map_or(s.normal(), |c| s.color(c))
             }
-            DeviceBlocks::VendorId | DeviceBlocks::VidPid => {
+            DeviceBlocks::VendorId | DeviceBlocks::VidPid | DeviceBlocks::VidPidName => {
                 ct.vid.map_or(s.normal(), |c| s.color(c))
             }
             DeviceBlocks::ProductId => ct.pid.map_or(s.normal(), |c| s.color(c)),
@@ -1046,16 +1603,22 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
                 ct.manufacturer.map_or(s.normal(), |c| s.color(c))
             }
             DeviceBlocks::Driver => ct.driver.map_or(s.normal(), |c| s.color(c)),
-            DeviceBlocks::Speed | DeviceBlocks::NegotiatedSpeed => {
+            DeviceBlocks::Speed | DeviceBlocks::NegotiatedSpeed | DeviceBlocks::CombinedSpeed => {
                 ct.speed.map_or(s.normal(), |c| s.color(c))
             }
             DeviceBlocks::BusPower
             | DeviceBlocks::BusPowerUsed
-            | DeviceBlocks::ExtraCurrentUsed => ct.power.map_or(s.normal(), |c| s.color(c)),
+            | DeviceBlocks::ExtraCurrentUsed
+            | DeviceBlocks::MaxPowerNumeric
+            | DeviceBlocks::PowerState
+            | DeviceBlocks::PowerBudget => ct.power.map_or(s.normal(), |c| s.color(c)),
             DeviceBlocks::BaseClass
             | DeviceBlocks::UidClass
             | DeviceBlocks::Class
-            | DeviceBlocks::BaseValue => ct.class_code.map_or(s.normal(), |c| s.color(c)),
+            | DeviceBlocks::BaseValue
+            | DeviceBlocks::InterfaceClasses
+            | DeviceBlocks::AltModes
+            | DeviceBlocks::Hub => ct.class_code.map_or(s.normal(), |c| s.color(c)),
             DeviceBlocks::SubClass | DeviceBlocks::UidSubClass => {
                 ct.sub_code.map_or(s.normal(), |c| s.color(c))
             }
@@ -1077,12 +1640,14 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
             DeviceBlocks::ProductId => "PID",
             DeviceBlocks::VidPid => "VID:PID",
             DeviceBlocks::Name => "Name",
+            DeviceBlocks::VidPidName => "VID:PID Name",
             DeviceBlocks::Manufacturer => "Manfacturer",
             DeviceBlocks::ProductName => "PName",
             DeviceBlocks::VendorName => "VName",
             DeviceBlocks::Serial => "Serial",
             DeviceBlocks::Speed => "Speed",
             DeviceBlocks::NegotiatedSpeed => "NgSpd",
+            DeviceBlocks::CombinedSpeed => "Spd",
             DeviceBlocks::TreePositions => "TPos",
             // will be 000 mA = 6
             DeviceBlocks::BusPower => "PBus",
@@ -1102,9 +1667,34 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
             DeviceBlocks::Icon => ICON_HEADING,
             DeviceBlocks::EventIcon => "E",
             DeviceBlocks::LastEvent => "Event",
+            DeviceBlocks::NumDevices => "Dev#",
+            // mA
+            DeviceBlocks::MaxPowerNumeric => "PMax",
+            DeviceBlocks::PowerState => "PState",
+            DeviceBlocks::InterfaceClasses => "IfClasses",
+            DeviceBlocks::AltModes => "AltModes",
+            DeviceBlocks::PowerBudget => "PBudget",
+            DeviceBlocks::Hub => "Hub",
+            DeviceBlocks::UsbGeneration => "USB Gen",
+        }
+    }
+
+    fn alignment(&self) -> Alignment {
+        match self {
+            // right aligned like a number although variable length
+            DeviceBlocks::PowerBudget => Alignment::Right,
+            b if b.value_is_variable_length() => Alignment::Left,
+            _ => Alignment::Right,
         }
     }
 
+    fn alignment_override(&self, settings: &PrintSettings) -> Option<Alignment> {
+        settings
+            .alignment_overrides
+            .as_ref()
+            .and_then(|o| o.blocks.get(self).copied())
+    }
+
     fn heading_padded(&self, pad: &HashMap<Self, usize>) -> String {
         format!(
             "{:^pad$}",
@@ -1123,13 +1713,17 @@ impl Block<DeviceBlocks, Device> for DeviceBlocks {
             DeviceBlocks::VidPid => BlockLength::Fixed(11),
             DeviceBlocks::Speed => BlockLength::Fixed(10),
             DeviceBlocks::NegotiatedSpeed => BlockLength::Fixed(10),
+            DeviceBlocks::CombinedSpeed => BlockLength::Fixed(11),
             DeviceBlocks::BusPower
             | DeviceBlocks::BusPowerUsed
             | DeviceBlocks::ExtraCurrentUsed => BlockLength::Fixed(6),
             DeviceBlocks::BcdDevice | DeviceBlocks::BcdUsb => BlockLength::Fixed(5),
-            DeviceBlocks::SubClass | DeviceBlocks::Protocol | DeviceBlocks::BaseValue => {
-                BlockLength::Fixed(4)
-            }
+            DeviceBlocks::SubClass
+            | DeviceBlocks::Protocol
+            | DeviceBlocks::BaseValue
+            | DeviceBlocks::NumDevices
+            | DeviceBlocks::MaxPowerNumeric
+            | DeviceBlocks::Hub => BlockLength::Fixed(4),
             _ => BlockLength::Variable(self.heading().len()),
         }
     }
@@ -1181,6 +1775,11 @@ impl Block<BusBlocks, Bus> for BusBlocks {
                 .flat_map(|d| d.host_controller_device.as_ref().map(|v| v.width()))
                 .max()
                 .unwrap_or(0),
+            BusBlocks::HostControllerType => d
+                .iter()
+                .flat_map(|d| d.host_controller_type().map(|v| v.width()))
+                .max()
+                .unwrap_or(0),
             BusBlocks::PortPath => d
                 .iter()
                 .map(|d| d.path().unwrap_or_default().as_os_str().len())
@@ -1202,21 +1801,20 @@ impl Block<BusBlocks, Bus> for BusBlocks {
             BusBlocks::PciVendor => ct.vid.map_or(s.normal(), |c| s.color(c)),
             BusBlocks::PciDevice => ct.pid.map_or(s.normal(), |c| s.color(c)),
             BusBlocks::Name => ct.name.map_or(s.normal(), |c| s.color(c)),
-            BusBlocks::HostController => ct.class_code.map_or(s.normal(), |c| s.color(c)),
+            BusBlocks::HostController | BusBlocks::HostControllerType => {
+                ct.class_code.map_or(s.normal(), |c| s.color(c))
+            }
             BusBlocks::HostControllerVendor => ct.manufacturer.map_or(s.normal(), |c| s.color(c)),
             BusBlocks::HostControllerDevice => ct.name.map_or(s.normal(), |c| s.color(c)),
-            BusBlocks::PciRevision => ct.number.map_or(s.normal(), |c| s.color(c)),
+            BusBlocks::PciRevision | BusBlocks::NumDevices | BusBlocks::MaxDepth => {
+                ct.number.map_or(s.normal(), |c| s.color(c))
+            }
             BusBlocks::Icon => ct.icon.map_or(s.normal(), |c| s.color(c)),
             BusBlocks::PortPath => ct.path.map_or(s.normal(), |c| s.color(c)),
         }
     }
 
-    fn format_value(
-        &self,
-        bus: &Bus,
-        pad: &HashMap<Self, usize>,
-        settings: &PrintSettings,
-    ) -> Option<String> {
+    fn format_value(&self, bus: &Bus, settings: &PrintSettings) -> Option<String> {
         match self {
             BusBlocks::BusNumber => bus
                 .get_bus_number()
@@ -1225,7 +1823,7 @@ impl Block<BusBlocks, Bus> for BusBlocks {
             BusBlocks::Icon => settings
                 .icons
                 .as_ref()
-                .map(|i| i.get_bus_icon(bus))
+                .map(|i| settings.encoding.substitute(&i.get_bus_icon(bus)))
                 .or(Some(" ".to_string())),
             BusBlocks::PciVendor => Some(match bus.pci_vendor {
                 Some(v) => Self::format_base_u16(v, settings),
@@ -1239,28 +1837,32 @@ impl Block<BusBlocks, Bus> for BusBlocks {
                 Some(v) => Self::format_base_u16(v, settings),
                 None => format!("{:>6}", "-"),
             }),
-            BusBlocks::Name => Some(format!(
-                "{:pad$}",
-                bus.name,
-                pad = pad.get(self).unwrap_or(&0)
-            )),
-            BusBlocks::HostController => Some(format!(
-                "{:pad$}",
-                bus.host_controller,
-                pad = pad.get(self).unwrap_or(&0)
-            )),
+            BusBlocks::Name => Some(bus.name.to_string()),
+            BusBlocks::HostController => Some(bus.host_controller.to_string()),
             BusBlocks::HostControllerVendor => Some(match bus.host_controller_vendor.as_ref() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             BusBlocks::HostControllerDevice => Some(match bus.host_controller_device.as_ref() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
+            }),
+            BusBlocks::HostControllerType => Some(match bus.host_controller_type() {
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             BusBlocks::PortPath => Some(match bus.path() {
-                Some(v) => format!("{:pad$}", v.display(), pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.display().to_string(),
+                None => "-".to_string(),
             }),
+            BusBlocks::NumDevices => Some(format!(
+                "{:4}",
+                bus.devices.as_deref().map_or(0, count_visible_devices)
+            )),
+            BusBlocks::MaxDepth => Some(format!(
+                "{:4}",
+                bus.devices.as_deref().map_or(0, max_visible_depth)
+            )),
         }
     }
 
@@ -1275,10 +1877,20 @@ impl Block<BusBlocks, Bus> for BusBlocks {
             BusBlocks::HostController => "HostController",
             BusBlocks::HostControllerVendor => "HostVendor",
             BusBlocks::HostControllerDevice => "HostDevice",
+            BusBlocks::HostControllerType => "HostType",
             BusBlocks::Icon => ICON_HEADING,
+            BusBlocks::NumDevices => "NDev",
+            BusBlocks::MaxDepth => "MxDp",
         }
     }
 
+    fn alignment_override(&self, settings: &PrintSettings) -> Option<Alignment> {
+        settings
+            .alignment_overrides
+            .as_ref()
+            .and_then(|o| o.bus_blocks.get(self).copied())
+    }
+
     fn heading_padded(&self, pad: &HashMap<Self, usize>) -> String {
         format!(
             "{:^pad$}",
@@ -1294,6 +1906,7 @@ impl Block<BusBlocks, Bus> for BusBlocks {
             BusBlocks::PciDevice | BusBlocks::PciVendor | BusBlocks::PciRevision => {
                 BlockLength::Fixed(6)
             }
+            BusBlocks::NumDevices | BusBlocks::MaxDepth => BlockLength::Fixed(4),
             _ => BlockLength::Variable(self.heading().len()),
         }
     }
@@ -1303,19 +1916,81 @@ impl Block<BusBlocks, Bus> for BusBlocks {
     }
 }
 
-impl Block<ConfigurationBlocks, Configuration> for ConfigurationBlocks {
-    const INSET: u8 = 1;
+/// Number of devices in `devices` and their children that are not hidden
+fn count_visible_devices(devices: &[Device]) -> usize {
+    devices
+        .iter()
+        .filter(|d| !d.is_hidden())
+        .map(|d| 1 + d.devices.as_deref().map_or(0, count_visible_devices))
+        .sum()
+}
 
-    fn default_blocks(verbose: bool) -> Vec<Self> {
-        if verbose {
-            vec![
-                ConfigurationBlocks::Number,
-                ConfigurationBlocks::IconAttributes,
-                ConfigurationBlocks::Attributes,
-                ConfigurationBlocks::NumInterfaces,
-                ConfigurationBlocks::MaxPower,
-                ConfigurationBlocks::Name,
-            ]
+/// Deepest branch depth of `devices` and their children that are not hidden
+///
+/// Uses the device tree positions so is the same whether `devices` is flattened or not
+fn max_visible_depth(devices: &[Device]) -> usize {
+    devices
+        .iter()
+        .filter(|d| !d.is_hidden())
+        .map(|d| {
+            cmp::max(
+                d.get_depth(),
+                d.devices.as_deref().map_or(0, max_visible_depth),
+            )
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Interface numbers grouped by `iad` as "first-last", "first" if only one or "-" if none
+fn function_interfaces_string(iad: &InterfaceAssociationDescriptor) -> String {
+    match iad.interface_count {
+        0 => String::from("-"),
+        1 => iad.first_interface.to_string(),
+        n => format!(
+            "{}-{}",
+            iad.first_interface,
+            iad.first_interface.saturating_add(n - 1)
+        ),
+    }
+}
+
+/// Functions in `config` as interface numbers and base class, e.g. "0-1 video, 2-3 audio"; "-" if no Interface Association Descriptors
+fn functions_string(config: &Configuration) -> String {
+    let iads = config.interface_associations();
+    if iads.is_empty() {
+        return String::from("-");
+    }
+    iads.iter()
+        .map(|iad| {
+            format!(
+                "{} {}",
+                function_interfaces_string(iad),
+                iad.function_base_class()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// "Y" or "N" for boolean blocks
+fn yes_no(b: bool) -> String {
+    String::from(if b { "Y" } else { "N" })
+}
+
+impl Block<ConfigurationBlocks, Configuration> for ConfigurationBlocks {
+    const INSET: u8 = 1;
+
+    fn default_blocks(verbose: bool) -> Vec<Self> {
+        if verbose {
+            vec![
+                ConfigurationBlocks::Number,
+                ConfigurationBlocks::IconAttributes,
+                ConfigurationBlocks::Attributes,
+                ConfigurationBlocks::NumInterfaces,
+                ConfigurationBlocks::MaxPower,
+                ConfigurationBlocks::Name,
+            ]
         } else {
             vec![
                 ConfigurationBlocks::Number,
@@ -1334,6 +2009,13 @@ impl Block<ConfigurationBlocks, Configuration> for ConfigurationBlocks {
                 .map(|d| d.attributes_string().len())
                 .max()
                 .unwrap_or(0),
+            ConfigurationBlocks::Functions => d
+                .iter()
+                .map(|d| functions_string(d).len())
+                .max()
+                .unwrap_or(0),
+            // room for the active marker
+            ConfigurationBlocks::Number if d.iter().any(|c| c.active.is_some()) => 3,
             _ => self.block_length().len(),
         }
     }
@@ -1352,34 +2034,35 @@ impl Block<ConfigurationBlocks, Configuration> for ConfigurationBlocks {
             ConfigurationBlocks::Name => ct.name.map_or(s.normal(), |c| s.color(c)),
             ConfigurationBlocks::Attributes => ct.attributes.map_or(s.normal(), |c| s.color(c)),
             ConfigurationBlocks::IconAttributes => ct.icon.map_or(s.normal(), |c| s.color(c)),
+            ConfigurationBlocks::SelfPowered
+            | ConfigurationBlocks::RemoteWakeup
+            | ConfigurationBlocks::Active => ct.attributes.map_or(s.normal(), |c| s.color(c)),
+            ConfigurationBlocks::Functions => ct.class_code.map_or(s.normal(), |c| s.color(c)),
         }
     }
 
-    fn format_value(
-        &self,
-        config: &Configuration,
-        pad: &HashMap<Self, usize>,
-        settings: &PrintSettings,
-    ) -> Option<String> {
+    fn format_value(&self, config: &Configuration, settings: &PrintSettings) -> Option<String> {
         match self {
-            ConfigurationBlocks::Number => Some(format!("{:2}", config.number)),
+            ConfigurationBlocks::Number => Some(match config.active {
+                Some(true) => format!("{:2}*", config.number),
+                Some(false) => format!("{:2} ", config.number),
+                None => format!("{:2}", config.number),
+            }),
             ConfigurationBlocks::NumInterfaces => Some(format!("{:2}", config.interfaces.len())),
-            ConfigurationBlocks::Name => Some(format!(
-                "{:pad$}",
-                config.name,
-                pad = pad.get(self).unwrap_or(&0)
-            )),
+            ConfigurationBlocks::Name => Some(config.name.to_string()),
             ConfigurationBlocks::MaxPower => Some(format!("{:6}", config.max_power)),
-            ConfigurationBlocks::Attributes => Some(format!(
-                "{:pad$}",
-                config.attributes_string(),
-                pad = pad.get(self).unwrap_or(&0)
+            ConfigurationBlocks::Attributes => Some(config.attributes_string().to_string()),
+            ConfigurationBlocks::IconAttributes => {
+                Some(attributes_to_icons(&config.attributes, settings))
+            }
+            ConfigurationBlocks::SelfPowered => Some(yes_no(
+                config.attributes.contains(&ConfigAttributes::SelfPowered),
             )),
-            ConfigurationBlocks::IconAttributes => Some(format!(
-                "{:pad$}",
-                attributes_to_icons(&config.attributes, settings),
-                pad = pad.get(self).unwrap_or(&0)
+            ConfigurationBlocks::RemoteWakeup => Some(yes_no(
+                config.attributes.contains(&ConfigAttributes::RemoteWakeup),
             )),
+            ConfigurationBlocks::Functions => Some(functions_string(config).to_string()),
+            ConfigurationBlocks::Active => Some(config.active.map_or(String::from("-"), yes_no)),
         }
     }
 
@@ -1391,9 +2074,29 @@ impl Block<ConfigurationBlocks, Configuration> for ConfigurationBlocks {
             ConfigurationBlocks::Name => "Name",
             ConfigurationBlocks::Attributes => "Attributes",
             ConfigurationBlocks::IconAttributes => ICON_HEADING,
+            ConfigurationBlocks::SelfPowered => "SP",
+            ConfigurationBlocks::RemoteWakeup => "RW",
+            ConfigurationBlocks::Functions => "Functions",
+            ConfigurationBlocks::Active => "Act",
+        }
+    }
+
+    fn alignment(&self) -> Alignment {
+        match self {
+            // icons are left aligned like the other icon blocks
+            ConfigurationBlocks::IconAttributes => Alignment::Left,
+            b if b.value_is_variable_length() => Alignment::Left,
+            _ => Alignment::Right,
         }
     }
 
+    fn alignment_override(&self, settings: &PrintSettings) -> Option<Alignment> {
+        settings
+            .alignment_overrides
+            .as_ref()
+            .and_then(|o| o.config_blocks.get(self).copied())
+    }
+
     fn heading_padded(&self, pad: &HashMap<Self, usize>) -> String {
         format!(
             "{:^pad$}",
@@ -1409,6 +2112,9 @@ impl Block<ConfigurationBlocks, Configuration> for ConfigurationBlocks {
             ConfigurationBlocks::MaxPower => BlockLength::Fixed(6),
             // two possible icons and a space between
             ConfigurationBlocks::IconAttributes => BlockLength::Fixed(3),
+            ConfigurationBlocks::SelfPowered
+            | ConfigurationBlocks::RemoteWakeup
+            | ConfigurationBlocks::Active => BlockLength::Fixed(1),
             _ => BlockLength::Variable(self.heading().len()),
         }
     }
@@ -1418,6 +2124,138 @@ impl Block<ConfigurationBlocks, Configuration> for ConfigurationBlocks {
     }
 }
 
+impl Block<FunctionBlocks, InterfaceAssociationDescriptor> for FunctionBlocks {
+    const INSET: u8 = 2;
+
+    fn default_blocks(verbose: bool) -> Vec<Self> {
+        if verbose {
+            vec![
+                FunctionBlocks::Interfaces,
+                FunctionBlocks::NumInterfaces,
+                FunctionBlocks::BaseValue,
+                FunctionBlocks::BaseClass,
+                FunctionBlocks::SubClass,
+                FunctionBlocks::Protocol,
+                FunctionBlocks::Class,
+                FunctionBlocks::Name,
+            ]
+        } else {
+            vec![
+                FunctionBlocks::Interfaces,
+                FunctionBlocks::BaseClass,
+                FunctionBlocks::SubClass,
+                FunctionBlocks::Protocol,
+                FunctionBlocks::Name,
+            ]
+        }
+    }
+
+    fn len(&self, d: &[&InterfaceAssociationDescriptor]) -> usize {
+        match self {
+            FunctionBlocks::Name => d
+                .iter()
+                .flat_map(|d| d.function_string.as_ref().map(|s| s.width()))
+                .max()
+                .unwrap_or(0),
+            FunctionBlocks::Interfaces => d
+                .iter()
+                .map(|d| function_interfaces_string(d).len())
+                .max()
+                .unwrap_or(0),
+            FunctionBlocks::BaseClass => d
+                .iter()
+                .map(|d| d.function_base_class().to_string().len())
+                .max()
+                .unwrap_or(0),
+            FunctionBlocks::Class => d
+                .iter()
+                .map(|d| d.function_class_code().to_string().len())
+                .max()
+                .unwrap_or(0),
+            _ => self.block_length().len(),
+        }
+    }
+
+    fn generate_padding(d: &[&InterfaceAssociationDescriptor]) -> HashMap<Self, usize> {
+        FunctionBlocks::iter()
+            .map(|b| (b, cmp::max(b.heading().len(), b.len(d))))
+            .collect()
+    }
+
+    fn colour(&self, s: &str, ct: &colour::ColourTheme) -> ColoredString {
+        match self {
+            FunctionBlocks::Name => ct.name.map_or(s.normal(), |c| s.color(c)),
+            FunctionBlocks::Interfaces
+            | FunctionBlocks::FirstInterface
+            | FunctionBlocks::NumInterfaces => ct.number.map_or(s.normal(), |c| s.color(c)),
+            FunctionBlocks::BaseClass | FunctionBlocks::BaseValue | FunctionBlocks::Class => {
+                ct.class_code.map_or(s.normal(), |c| s.color(c))
+            }
+            FunctionBlocks::SubClass => ct.sub_code.map_or(s.normal(), |c| s.color(c)),
+            FunctionBlocks::Protocol => ct.protocol.map_or(s.normal(), |c| s.color(c)),
+        }
+    }
+
+    fn format_value(
+        &self,
+        iad: &InterfaceAssociationDescriptor,
+        settings: &PrintSettings,
+    ) -> Option<String> {
+        match self {
+            FunctionBlocks::Name => Some(iad.function_string.as_deref().unwrap_or("-").to_string()),
+            FunctionBlocks::Interfaces => Some(function_interfaces_string(iad).to_string()),
+            FunctionBlocks::FirstInterface => Some(format!("{:2}", iad.first_interface)),
+            FunctionBlocks::NumInterfaces => Some(format!("{:2}", iad.interface_count)),
+            FunctionBlocks::BaseClass => Some(iad.function_base_class().to_string()),
+            FunctionBlocks::BaseValue => Some(Self::format_base_u8(iad.function_class, settings)),
+            FunctionBlocks::SubClass => {
+                Some(Self::format_base_u8(iad.function_sub_class, settings))
+            }
+            FunctionBlocks::Protocol => Some(Self::format_base_u8(iad.function_protocol, settings)),
+            FunctionBlocks::Class => Some(iad.function_class_code().to_string()),
+        }
+    }
+
+    fn heading(&self) -> &str {
+        match self {
+            FunctionBlocks::Name => "Name",
+            FunctionBlocks::Interfaces => "Ifs",
+            FunctionBlocks::FirstInterface => "#",
+            FunctionBlocks::NumInterfaces => "I#",
+            FunctionBlocks::BaseClass => "BaseC",
+            FunctionBlocks::BaseValue => "CVal",
+            FunctionBlocks::SubClass => "SubC",
+            FunctionBlocks::Protocol => "Pcol",
+            FunctionBlocks::Class => "Class",
+        }
+    }
+
+    fn alignment_override(&self, settings: &PrintSettings) -> Option<Alignment> {
+        settings
+            .alignment_overrides
+            .as_ref()
+            .and_then(|o| o.function_blocks.get(self).copied())
+    }
+
+    fn heading_padded(&self, pad: &HashMap<Self, usize>) -> String {
+        format!(
+            "{:^pad$}",
+            self.heading(),
+            pad = pad.get(self).unwrap_or(&0)
+        )
+    }
+
+    fn block_length(&self) -> BlockLength {
+        match self {
+            FunctionBlocks::FirstInterface | FunctionBlocks::NumInterfaces => BlockLength::Fixed(2),
+            FunctionBlocks::BaseValue | FunctionBlocks::SubClass | FunctionBlocks::Protocol => {
+                BlockLength::Fixed(4)
+            }
+            _ => BlockLength::Variable(self.heading().len()),
+        }
+    }
+}
+
 impl Block<InterfaceBlocks, Interface> for InterfaceBlocks {
     const INSET: u8 = 2;
 
@@ -1570,68 +2408,62 @@ impl Block<InterfaceBlocks, Interface> for InterfaceBlocks {
             InterfaceBlocks::AltSetting | InterfaceBlocks::NumEndpoints => {
                 ct.number.map_or(s.normal(), |c| s.color(c))
             }
+            InterfaceBlocks::Bandwidth => ct.speed.map_or(s.normal(), |c| s.color(c)),
         }
     }
 
-    fn format_value(
-        &self,
-        interface: &Interface,
-        pad: &HashMap<Self, usize>,
-        settings: &PrintSettings,
-    ) -> Option<String> {
+    fn format_value(&self, interface: &Interface, settings: &PrintSettings) -> Option<String> {
         match self {
             InterfaceBlocks::Number => Some(format!("{:2}", interface.number)),
             InterfaceBlocks::Name => Some(match interface.name.as_ref() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             InterfaceBlocks::NumEndpoints => Some(format!("{:2}", interface.endpoints.len())),
-            InterfaceBlocks::PortPath => Some(format!(
-                "{:pad$}",
-                interface.path,
-                pad = pad.get(self).unwrap_or(&0)
-            )),
+            InterfaceBlocks::PortPath => Some(interface.path.to_string()),
             InterfaceBlocks::SysPath => Some(match interface.syspath.as_ref() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             InterfaceBlocks::Driver => Some(match interface.driver.as_ref() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
-            InterfaceBlocks::BaseClass => Some(format!(
-                "{:pad$}",
-                interface.class.to_string(),
-                pad = pad.get(self).unwrap_or(&0)
-            )),
+            InterfaceBlocks::BaseClass => Some(interface.class.to_string()),
             InterfaceBlocks::SubClass => Some(Self::format_base_u8(interface.sub_class, settings)),
             InterfaceBlocks::Protocol => Some(Self::format_base_u8(interface.protocol, settings)),
             InterfaceBlocks::AltSetting => {
                 Some(Self::format_base_u8(interface.alt_setting, settings))
             }
             InterfaceBlocks::Icon => settings.icons.as_ref().map(|i| {
-                i.get_classifier_icon(&interface.class, interface.sub_class, interface.protocol)
+                settings.encoding.substitute(&i.get_classifier_icon(
+                    &interface.class,
+                    interface.sub_class,
+                    interface.protocol,
+                ))
             }),
             InterfaceBlocks::UidClass => Some(match interface.class_name() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             InterfaceBlocks::UidSubClass => Some(match interface.sub_class_name() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
             InterfaceBlocks::UidProtocol => Some(match interface.protocol_name() {
-                Some(v) => format!("{:pad$}", v, pad = pad.get(self).unwrap_or(&0)),
-                None => format!("{:pad$}", "-", pad = pad.get(self).unwrap_or(&0)),
+                Some(v) => v.to_string(),
+                None => "-".to_string(),
             }),
-            InterfaceBlocks::Class => Some(format!(
-                "{:pad$}",
-                interface.fully_defined_class(),
-                pad = pad.get(self).unwrap_or(&0)
-            )),
+            InterfaceBlocks::Class => Some(interface.fully_defined_class().to_string()),
             InterfaceBlocks::BaseValue => {
                 Some(Self::format_base_u8(interface.class.into(), settings))
             }
+            InterfaceBlocks::Bandwidth => Some(match interface.periodic_bandwidth() {
+                // clamp to the fixed width; anything over 100% is already over budget
+                Some(v) if v >= 999.95 => format!("{:>6}", ">999%"),
+                Some(v) => format!("{v:>5.1}%"),
+                None => format!("{:>6}", "-"),
+            }),
         }
     }
 
@@ -1653,9 +2485,17 @@ impl Block<InterfaceBlocks, Interface> for InterfaceBlocks {
             InterfaceBlocks::Class => "Class",
             InterfaceBlocks::BaseValue => "CVal",
             InterfaceBlocks::Icon => ICON_HEADING,
+            InterfaceBlocks::Bandwidth => "PBw%",
         }
     }
 
+    fn alignment_override(&self, settings: &PrintSettings) -> Option<Alignment> {
+        settings
+            .alignment_overrides
+            .as_ref()
+            .and_then(|o| o.interface_blocks.get(self).copied())
+    }
+
     fn heading_padded(&self, pad: &HashMap<Self, usize>) -> String {
         format!(
             "{:^pad$}",
@@ -1673,6 +2513,7 @@ impl Block<InterfaceBlocks, Interface> for InterfaceBlocks {
             | InterfaceBlocks::Protocol
             | InterfaceBlocks::AltSetting
             | InterfaceBlocks::BaseValue => BlockLength::Fixed(4),
+            InterfaceBlocks::Bandwidth => BlockLength::Fixed(6),
             _ => BlockLength::Variable(self.heading().len()),
         }
     }
@@ -1745,11 +2586,24 @@ impl Block<EndpointBlocks, Endpoint> for EndpointBlocks {
             .collect()
     }
 
-    fn colour(&self, s: &str, ct: &colour::ColourTheme) -> ColoredString {
+    fn colour_value(&self, end: &Endpoint, s: &str, ct: &colour::ColourTheme) -> ColoredString {
         match self {
-            EndpointBlocks::Number | EndpointBlocks::Interval | EndpointBlocks::MaxPacketSize => {
-                ct.number.map_or(s.normal(), |c| s.color(c))
+            // high-bandwidth endpoints with more than one transaction per microframe stand out with the speed colour
+            EndpointBlocks::MaxPacketSize if end.is_high_bandwidth() => {
+                ct.speed.map_or(s.normal(), |c| s.color(c))
             }
+            _ => self.colour(s, ct),
+        }
+    }
+
+    fn colour(&self, s: &str, ct: &colour::ColourTheme) -> ColoredString {
+        match self {
+            EndpointBlocks::Number
+            | EndpointBlocks::Interval
+            | EndpointBlocks::IntervalMs
+            | EndpointBlocks::MaxBurst
+            | EndpointBlocks::BytesPerInterval
+            | EndpointBlocks::MaxPacketSize => ct.number.map_or(s.normal(), |c| s.color(c)),
             EndpointBlocks::Direction
             | EndpointBlocks::UsageType
             | EndpointBlocks::TransferType
@@ -1757,40 +2611,32 @@ impl Block<EndpointBlocks, Endpoint> for EndpointBlocks {
         }
     }
 
-    fn format_value(
-        &self,
-        end: &Endpoint,
-        pad: &HashMap<Self, usize>,
-        _settings: &PrintSettings,
-    ) -> Option<String> {
+    fn format_value(&self, end: &Endpoint, _settings: &PrintSettings) -> Option<String> {
         match self {
             EndpointBlocks::Number => Some(format!("{:2}", end.address.number)),
             EndpointBlocks::Interval => Some(format!("{:2}", end.interval)),
-            EndpointBlocks::MaxPacketSize => Some(format!(
-                "{:pad$}",
-                end.max_packet_string(),
-                pad = pad.get(self).unwrap_or(&0)
-            )),
-            EndpointBlocks::Direction => Some(format!(
-                "{:pad$}",
-                end.address.direction.to_string(),
-                pad = pad.get(self).unwrap_or(&0)
-            )),
-            EndpointBlocks::TransferType => Some(format!(
-                "{:pad$}",
-                end.transfer_type.to_string(),
-                pad = pad.get(self).unwrap_or(&0)
-            )),
-            EndpointBlocks::SyncType => Some(format!(
-                "{:pad$}",
-                end.sync_type.to_string(),
-                pad = pad.get(self).unwrap_or(&0)
-            )),
-            EndpointBlocks::UsageType => Some(format!(
-                "{:pad$}",
-                end.usage_type.to_string(),
-                pad = pad.get(self).unwrap_or(&0)
-            )),
+            EndpointBlocks::IntervalMs => Some(match end.device_speed() {
+                Some(speed) => match end.interval_ms(speed) {
+                    Some(ms) => format!("{ms:>6}"),
+                    None => format!("{:>6}", "-"),
+                },
+                None => format!("{:>6}", format!("{}?", end.interval)),
+            }),
+            EndpointBlocks::MaxBurst => Some(match end.ss_companion() {
+                Some(ss) => format!("{:3}", ss.max_burst),
+                None => format!("{:>3}", "-"),
+            }),
+            EndpointBlocks::BytesPerInterval => Some(
+                match end.ss_companion().and_then(|ss| ss.bytes_per_interval) {
+                    Some(bpi) => format!("{bpi:5}"),
+                    None => format!("{:>5}", "-"),
+                },
+            ),
+            EndpointBlocks::MaxPacketSize => Some(end.max_packet_string().to_string()),
+            EndpointBlocks::Direction => Some(end.address.direction.to_string()),
+            EndpointBlocks::TransferType => Some(end.transfer_type.to_string()),
+            EndpointBlocks::SyncType => Some(end.sync_type.to_string()),
+            EndpointBlocks::UsageType => Some(end.usage_type.to_string()),
         }
     }
 
@@ -1798,6 +2644,9 @@ impl Block<EndpointBlocks, Endpoint> for EndpointBlocks {
         match self {
             EndpointBlocks::Number => "#",
             EndpointBlocks::Interval => "Iv",
+            EndpointBlocks::IntervalMs => "IvMs",
+            EndpointBlocks::MaxBurst => "Bst",
+            EndpointBlocks::BytesPerInterval => "BytIv",
             EndpointBlocks::MaxPacketSize => "MaxPkb",
             EndpointBlocks::Direction => "Dir",
             EndpointBlocks::TransferType => "TranT",
@@ -1806,6 +2655,13 @@ impl Block<EndpointBlocks, Endpoint> for EndpointBlocks {
         }
     }
 
+    fn alignment_override(&self, settings: &PrintSettings) -> Option<Alignment> {
+        settings
+            .alignment_overrides
+            .as_ref()
+            .and_then(|o| o.endpoint_blocks.get(self).copied())
+    }
+
     fn heading_padded(&self, pad: &HashMap<Self, usize>) -> String {
         format!(
             "{:^pad$}",
@@ -1818,6 +2674,9 @@ impl Block<EndpointBlocks, Endpoint> for EndpointBlocks {
         match self {
             EndpointBlocks::Number => BlockLength::Fixed(2),
             EndpointBlocks::Interval => BlockLength::Fixed(2),
+            EndpointBlocks::IntervalMs => BlockLength::Fixed(6),
+            EndpointBlocks::MaxBurst => BlockLength::Fixed(3),
+            EndpointBlocks::BytesPerInterval => BlockLength::Fixed(5),
             _ => BlockLength::Variable(self.heading().len()),
         }
     }
@@ -1834,9 +2693,34 @@ pub enum Sort {
     BranchPosition,
     /// No sorting; whatever order it was parsed
     NoSort,
+    /// Sort by vendor ID, devices without a vendor ID last
+    VendorId,
+    /// Sort by product ID, devices without a product ID last
+    ProductId,
+    /// Sort by device name ignoring case
+    Name,
 }
 
 impl Sort {
+    /// Compare [`Device`]s for the field based sorts with bus and device number as a stable tiebreaker
+    fn cmp_devices(&self, a: &Device, b: &Device) -> cmp::Ordering {
+        let ordering = match self {
+            Sort::VendorId => {
+                (a.vendor_id.is_none(), a.vendor_id).cmp(&(b.vendor_id.is_none(), b.vendor_id))
+            }
+            Sort::ProductId => {
+                (a.product_id.is_none(), a.product_id).cmp(&(b.product_id.is_none(), b.product_id))
+            }
+            Sort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            _ => cmp::Ordering::Equal,
+        };
+
+        ordering.then_with(|| {
+            (a.location_id.bus, a.location_id.number)
+                .cmp(&(b.location_id.bus, b.location_id.number))
+        })
+    }
+
     /// Sort the [`Device`]s in place
     pub fn sort_devices(&self, devices: &mut [Device]) {
         // add bus number to maintain bus order when sorting
@@ -1845,7 +2729,10 @@ impl Sort {
                 devices.sort_by_key(|d| d.get_branch_position() + d.location_id.bus)
             }
             Sort::DeviceNumber => devices.sort_by_key(|d| d.location_id.number + d.location_id.bus),
-            _ => (),
+            Sort::VendorId | Sort::ProductId | Sort::Name => {
+                devices.sort_by(|a, b| self.cmp_devices(a, b))
+            }
+            Sort::NoSort => (),
         }
     }
 
@@ -1856,7 +2743,10 @@ impl Sort {
                 devices.sort_by_key(|d| d.get_branch_position() + d.location_id.bus)
             }
             Sort::DeviceNumber => devices.sort_by_key(|d| d.location_id.number + d.location_id.bus),
-            _ => (),
+            Sort::VendorId | Sort::ProductId | Sort::Name => {
+                devices.sort_by(|a, b| self.cmp_devices(a, b))
+            }
+            Sort::NoSort => (),
         }
     }
 
@@ -1890,6 +2780,92 @@ impl Sort {
             self.sort_bus(bus);
         }
     }
+
+    /// Reverse the order of the [`Device`]s at each branch in place
+    pub fn reverse_devices_recursive(devices: &mut [Device]) {
+        devices.reverse();
+        for device in devices {
+            if let Some(branch_devices) = &mut device.devices {
+                Self::reverse_devices_recursive(branch_devices);
+            }
+        }
+    }
+
+    /// Reverse the order of buses and the [`Device`]s at each branch of each bus in place
+    pub fn reverse_buses(buses: &mut [Bus]) {
+        buses.reverse();
+        for bus in buses {
+            if let Some(devices) = &mut bus.devices {
+                Self::reverse_devices_recursive(devices);
+            }
+        }
+    }
+}
+
+/// Value to sort the [`Interface`]s of a configuration when printing; parse order if not set
+///
+/// ```
+/// use cyme::display::InterfaceSort;
+/// use cyme::profiler::read_json_dump;
+///
+/// let spusb = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let device = spusb.get_node_by_str("2-2.1").unwrap();
+/// let mut interfaces = device.extra.as_ref().unwrap().configurations[0]
+///     .interfaces
+///     .iter()
+///     .collect::<Vec<_>>();
+/// // CDC Communications, CDC Data then Vendor Specific
+/// InterfaceSort::Class.sort_interfaces(&mut interfaces);
+/// assert_eq!(
+///     interfaces.iter().map(|i| i.number).collect::<Vec<_>>(),
+///     vec![0, 2, 1, 3, 4]
+/// );
+/// InterfaceSort::Number.sort_interfaces(&mut interfaces);
+/// assert_eq!(
+///     interfaces.iter().map(|i| i.number).collect::<Vec<_>>(),
+///     vec![0, 1, 2, 3, 4]
+/// );
+/// ```
+#[derive(Debug, ValueEnum, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InterfaceSort {
+    /// Sort by interface number then alternate setting
+    Number,
+    /// Sort by class, sub-class and protocol then interface number and alternate setting
+    Class,
+}
+
+impl InterfaceSort {
+    /// Compare [`Interface`]s with the interface number and alternate setting as the tiebreaker
+    pub fn cmp_interfaces(&self, a: &Interface, b: &Interface) -> cmp::Ordering {
+        let ordering = match self {
+            InterfaceSort::Number => cmp::Ordering::Equal,
+            InterfaceSort::Class => (u8::from(a.class), a.sub_class, a.protocol).cmp(&(
+                u8::from(b.class),
+                b.sub_class,
+                b.protocol,
+            )),
+        };
+
+        ordering.then_with(|| (a.number, a.alt_setting).cmp(&(b.number, b.alt_setting)))
+    }
+
+    /// Sort the references to [`Interface`]s in place
+    pub fn sort_interfaces(&self, interfaces: &mut [&Interface]) {
+        interfaces.sort_by(|a, b| self.cmp_interfaces(a, b));
+    }
+}
+
+/// Product name for [`DeviceBlocks::VidPidName`]: descriptor name, usb_ids product name if empty or "-"
+fn vidpid_name_product(d: &Device) -> &str {
+    if !d.name.is_empty() {
+        &d.name
+    } else {
+        d.extra
+            .as_ref()
+            .and_then(|e| e.product_name.as_deref())
+            .unwrap_or("-")
+    }
 }
 
 /// Value to group [`Device`]
@@ -1901,6 +2877,8 @@ pub enum Group {
     NoGroup,
     /// Group into buses with bus info as heading - like a flat tree
     Bus,
+    /// Group by vendor id with vendor id and name as heading
+    Vendor,
 }
 
 /// Options for [`PrintSettings`] mask_serials
@@ -1918,6 +2896,35 @@ pub enum MaskSerial {
     Replace,
 }
 
+/// Device fields that can be masked with the [`PrintSettings`] mask_serials [`MaskSerial`] method
+#[derive(Debug, ValueEnum, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MaskField {
+    /// Device serial string
+    Serial,
+    /// Device manufacturer string
+    Manufacturer,
+    /// Device product string; the device name
+    Product,
+    /// Port path and syspath of the device and syspath of its interfaces; JSON keeps the numeric `location_id` and interface paths used for lookups
+    PortPath,
+}
+
+/// Named selection of blocks for every block type, applied with [`PrintSettings::apply_preset`]
+#[derive(Debug, Default, ValueEnum, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlockPreset {
+    /// Just enough blocks to identify each item
+    Minimal,
+    /// The default blocks
+    #[default]
+    Normal,
+    /// The default blocks with `more`
+    Full,
+    /// Every block available
+    Debug,
+}
+
 /// Mode being used for printing
 #[derive(Default, Debug, ValueEnum, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PrintMode {
@@ -1935,12 +2942,20 @@ pub struct PrintSettings {
     pub no_padding: bool,
     /// Print in decimal not base16
     pub decimal: bool,
+    /// [`SpeedUnit`] for the speed blocks
+    pub speed_unit: SpeedUnit,
     /// No tree printing
     pub tree: bool,
     /// Sort devices
     pub sort_devices: Sort,
     /// Sort buses by bus number
     pub sort_buses: bool,
+    /// Reverse the order of buses and devices at each tree level after sorting
+    pub sort_reverse: bool,
+    /// Sort devices by the value of a [`DeviceBlocks`] after [`Sort`], such as [`DeviceBlocks::Speed`]
+    pub sort_block: Option<DeviceBlocks>,
+    /// Sort the [`Interface`]s of each configuration by [`InterfaceSort`] - None for parse order
+    pub sort_interfaces: Option<InterfaceSort>,
     /// Group devices
     pub group_devices: Group,
     /// Print headings for blocks
@@ -1949,58 +2964,335 @@ pub struct PrintSettings {
     pub verbosity: u8,
     /// Print more blocks by default
     pub more: bool,
+    /// Print every configuration, interface and endpoint inset below its device without tree glyphs, regardless of `verbosity` and `tree`
+    pub flat_descriptors: bool,
+    /// Print the raw class-specific descriptor bytes of each printed [`Interface`] as an `xxd` style hex dump below it
+    pub hex_dump: bool,
     /// Print as json
     pub json: bool,
+    /// With `json`, include names resolved from the Linux USB IDs repository in device and interface objects
+    pub json_resolved: bool,
+    /// Print as CSV using blocks as columns
+    pub csv: bool,
+    /// Print as Markdown table using blocks as columns
+    pub markdown: bool,
+    /// Print as JSON Lines with an object per device event
+    pub ndjson: bool,
+    /// Print the bus tree as a GraphViz DOT digraph labeled with blocks
+    pub dot: bool,
+    /// Block heading overrides keyed by kebab-case block name, e.g. "vendor-id": "Vendor ID"
+    pub heading_overrides: Option<HashMap<String, String>>,
+    /// Block value [`Alignment`] overrides for each block type
+    pub alignment_overrides: Option<AlignmentOverrides>,
+    /// String to insert between columns - None for a single space
+    pub separator: Option<String>,
+    /// Maximum depth of devices to print in the tree, deeper devices are summarised - None for no limit
+    pub max_depth: Option<usize>,
+    /// Only print endpoints of this [`TransferType`] - None for all
+    pub endpoint_transfer_filter: Option<TransferType>,
+    /// Only print endpoints of this [`Direction`] - None for both
+    pub endpoint_direction_filter: Option<Direction>,
+    /// Hide interfaces with no endpoints matching `endpoint_transfer_filter` and `endpoint_direction_filter`
+    pub hide_empty_interfaces: bool,
+    /// Only print the selected alternate setting of each interface number with [`active_alt_settings`]
+    pub only_active_alt: bool,
+    /// Only show devices with a connected or disconnected event within this time in [`PrintMode::Dynamic`] - None to show all
+    pub since: Option<std::time::Duration>,
+    /// Show [`DeviceBlocks::LastEvent`] as the time since the event, such as "C: 3s ago", in [`PrintMode::Dynamic`]
+    pub relative_time: bool,
+    /// Collapse runs of identical [`Device`]s at each tree level into one line with a multiplier such as "x4"; see [`collapse_duplicate_devices`]
+    ///
+    /// Not applied to outputs that list every device without a multiplier; see [`PrintSettings::shows_duplicates`]
+    pub collapse_duplicates: bool,
+    /// Collapse devices with `collapse_duplicates` even if their serial numbers differ
+    pub collapse_ignore_serial: bool,
+    /// Print a [`bus_summary`] line of the device count by speed and current drawn under each bus
+    pub bus_summary: bool,
+    /// Highlight case-insensitive matches of this string in every block value with the [`colour::ColourTheme`] `highlight` colour - None for no search
+    pub search: Option<String>,
     /// Character encoding to use
     pub encoding: Encoding,
     /// Scramble serial numbers, useful if sharing sensitive device dumps
     pub mask_serials: Option<MaskSerial>,
+    /// [`MaskField`]s to mask using `mask_serials` method, [`MaskSerial::Hide`] if that is None - only serials if None
+    pub mask_fields: Option<Vec<MaskField>>,
+    /// Seed for the [`MaskSerial::Scramble`] and [`MaskSerial::Replace`] random chars so the same input gives the same masked output with [`mask_string_seeded`] - random if None
+    pub mask_seed: Option<u64>,
     /// [`DeviceBlocks`] to use for printing
     pub device_blocks: Option<Vec<DeviceBlocks>>,
     /// [`BusBlocks`] to use for printing
     pub bus_blocks: Option<Vec<BusBlocks>>,
     /// [`ConfigurationBlocks`] to use for printing
     pub config_blocks: Option<Vec<ConfigurationBlocks>>,
+    /// [`FunctionBlocks`] to use for printing Interface Association Descriptor functions
+    pub function_blocks: Option<Vec<FunctionBlocks>>,
     /// [`InterfaceBlocks`] to use for printing
     pub interface_blocks: Option<Vec<InterfaceBlocks>>,
     /// [`EndpointBlocks`] to use for printing
     pub endpoint_blocks: Option<Vec<EndpointBlocks>>,
     /// [`crate::icon::IconTheme`] to apply - None to not print any icons
     pub icons: Option<icon::IconTheme>,
+    /// [`crate::icon::TreeGlyphs`] to draw tree branches with in place of the `icons` tree or defaults - None to use those
+    pub tree_glyphs: Option<icon::TreeGlyphs>,
     /// [`crate::colour::ColourTheme`] to apply - None to not colour
     pub colours: Option<colour::ColourTheme>,
     /// Max variable string length to display before truncating - descriptors and classes for example
     pub max_variable_string_len: Option<usize>,
     /// Enable auto generation of max_variable_string_len based on terminal width
     pub auto_width: bool,
+    /// How to fit variable values wider than max_variable_string_len
+    pub wrap_mode: WrapMode,
     /// Terminal width and height data
     pub terminal_size: Option<(u16, u16)>,
     /// When to print icon blocks
     pub icon_when: IconWhen,
+    /// Print icon blocks before the other blocks regardless of their position in the block lists
+    pub pin_icons_first: bool,
+    /// Drop blocks whose value is the "-" placeholder for every device or bus being printed
+    pub auto_hide_empty: bool,
     /// When to print colour
     pub color_when: ColorWhen,
     /// Printing in watch mode
     pub print_mode: PrintMode,
 }
 
-/// Converts a HashSet of [`ConfigAttributes`] a String of nerd icons
-fn attributes_to_icons(attributes: &Vec<ConfigAttributes>, settings: &PrintSettings) -> String {
-    let mut icon_strs = Vec::new();
-    if settings.icons.is_some() {
-        for a in attributes {
-            match a {
-                ConfigAttributes::SelfPowered => icon_strs.push("\u{f06a5}"), // 󰚥
-                ConfigAttributes::RemoteWakeup => icon_strs.push("\u{f0155}"), // 󰅕
-                ConfigAttributes::BatteryPowered => icon_strs.push("\u{f244}"), // 
-                ConfigAttributes::BusPowered => icon_strs.push("\u{f11f0}"),  // 󱇰
+impl PrintSettings {
+    /// Tree drawing string for `icon` from `tree_glyphs` if valid for the `encoding`, otherwise the `icons` tree or default
+    pub fn tree_icon(&self, icon: &icon::Icon) -> String {
+        self.tree_glyphs
+            .and_then(|g| g.tree_icon(icon, &self.encoding))
+            .unwrap_or_else(|| {
+                self.icons
+                    .as_ref()
+                    .map_or(icon::get_default_tree_icon(icon, &self.encoding), |i| {
+                        i.get_tree_icon(icon, &self.encoding)
+                    })
+            })
+    }
+
+    /// Whether descriptor rows are drawn as tree branches; not if `flat_descriptors`
+    fn descriptor_tree(&self) -> bool {
+        self.tree && !self.flat_descriptors
+    }
+
+    /// Verbosity used for descriptor rows; `flat_descriptors` prints all of them
+    fn descriptor_verbosity(&self) -> u8 {
+        if self.flat_descriptors {
+            self.verbosity.max(3)
+        } else {
+            self.verbosity
+        }
+    }
+
+    /// Sets all the block vectors from the [`BlockPreset`] - `Normal` device blocks follow the `tree` setting
+    ///
+    /// ```
+    /// use cyme::display::{BlockPreset, EndpointBlocks, PrintSettings};
+    /// use strum::VariantArray;
+    ///
+    /// let mut settings = PrintSettings::default();
+    /// settings.apply_preset(BlockPreset::Debug);
+    /// assert_eq!(settings.endpoint_blocks.as_deref(), Some(EndpointBlocks::VARIANTS));
+    /// ```
+    pub fn apply_preset(&mut self, preset: BlockPreset) {
+        match preset {
+            BlockPreset::Minimal => {
+                self.device_blocks = Some(vec![
+                    DeviceBlocks::BusNumber,
+                    DeviceBlocks::DeviceNumber,
+                    DeviceBlocks::VendorId,
+                    DeviceBlocks::ProductId,
+                    DeviceBlocks::Name,
+                ]);
+                self.bus_blocks = Some(vec![BusBlocks::PortPath, BusBlocks::Name]);
+                self.config_blocks =
+                    Some(vec![ConfigurationBlocks::Number, ConfigurationBlocks::Name]);
+                self.function_blocks = Some(vec![FunctionBlocks::Interfaces, FunctionBlocks::Name]);
+                self.interface_blocks = Some(vec![
+                    InterfaceBlocks::PortPath,
+                    InterfaceBlocks::BaseClass,
+                    InterfaceBlocks::Name,
+                ]);
+                self.endpoint_blocks = Some(vec![
+                    EndpointBlocks::Number,
+                    EndpointBlocks::Direction,
+                    EndpointBlocks::TransferType,
+                ]);
+            }
+            BlockPreset::Normal | BlockPreset::Full => {
+                let verbose = preset == BlockPreset::Full;
+                self.device_blocks = Some(if !verbose && self.tree {
+                    DeviceBlocks::default_device_tree_blocks()
+                } else {
+                    DeviceBlocks::default_blocks(verbose)
+                });
+                self.bus_blocks = Some(Block::<BusBlocks, Bus>::default_blocks(verbose));
+                self.config_blocks = Some(
+                    Block::<ConfigurationBlocks, Configuration>::default_blocks(verbose),
+                );
+                self.function_blocks = Some(
+                    Block::<FunctionBlocks, InterfaceAssociationDescriptor>::default_blocks(
+                        verbose,
+                    ),
+                );
+                self.interface_blocks =
+                    Some(Block::<InterfaceBlocks, Interface>::default_blocks(verbose));
+                self.endpoint_blocks =
+                    Some(Block::<EndpointBlocks, Endpoint>::default_blocks(verbose));
+            }
+            BlockPreset::Debug => {
+                self.device_blocks = Some(DeviceBlocks::VARIANTS.to_vec());
+                self.bus_blocks = Some(BusBlocks::VARIANTS.to_vec());
+                self.config_blocks = Some(ConfigurationBlocks::VARIANTS.to_vec());
+                self.function_blocks = Some(FunctionBlocks::VARIANTS.to_vec());
+                self.interface_blocks = Some(InterfaceBlocks::VARIANTS.to_vec());
+                self.endpoint_blocks = Some(EndpointBlocks::VARIANTS.to_vec());
             }
         }
     }
-    icon_strs.join(" ")
+
+    /// The [`MaskSerial`] method to apply to `field` if it should be masked
+    ///
+    /// ```
+    /// use cyme::display::{MaskField, MaskSerial, PrintSettings};
+    ///
+    /// let mut settings = PrintSettings {
+    ///     mask_serials: Some(MaskSerial::Scramble),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(settings.field_mask(MaskField::Serial), Some(MaskSerial::Scramble));
+    /// assert_eq!(settings.field_mask(MaskField::Product), None);
+    ///
+    /// settings.mask_serials = None;
+    /// settings.mask_fields = Some(vec![MaskField::Product]);
+    /// assert_eq!(settings.field_mask(MaskField::Product), Some(MaskSerial::Hide));
+    /// assert_eq!(settings.field_mask(MaskField::Serial), None);
+    /// ```
+    pub fn field_mask(&self, field: MaskField) -> Option<MaskSerial> {
+        match (&self.mask_fields, self.mask_serials) {
+            (Some(fields), m) => fields.contains(&field).then(|| m.unwrap_or_default()),
+            (None, Some(m)) => (field == MaskField::Serial).then_some(m),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether the output shows the [`Device::duplicates`] multiplier of devices collapsed with `collapse_duplicates`
+    ///
+    /// JSON, CSV, Markdown, JSON Lines and DOT list every device so are not collapsed:
+    ///
+    /// ```
+    /// use cyme::display::PrintSettings;
+    ///
+    /// assert!(PrintSettings::default().shows_duplicates());
+    /// let settings = PrintSettings {
+    ///     csv: true,
+    ///     ..Default::default()
+    /// };
+    /// assert!(!settings.shows_duplicates());
+    /// ```
+    pub fn shows_duplicates(&self) -> bool {
+        !(self.json || self.csv || self.markdown || self.ndjson || self.dot)
+    }
 }
 
-/// Truncates and appends '...' to show string has been truncated
-///
+/// The `interface` endpoints matching the [`PrintSettings`] `endpoint_transfer_filter` and `endpoint_direction_filter`
+fn visible_endpoints<'a>(interface: &'a Interface, settings: &PrintSettings) -> Vec<&'a Endpoint> {
+    interface
+        .endpoints
+        .iter()
+        .filter(|e| {
+            settings
+                .endpoint_transfer_filter
+                .is_none_or(|t| e.transfer_type == t)
+                && settings
+                    .endpoint_direction_filter
+                    .is_none_or(|d| e.address.direction == d)
+        })
+        .collect()
+}
+
+/// The `interfaces` to print, dropping those without [`visible_endpoints`] if `hide_empty_interfaces` and inactive alternate settings if `only_active_alt`
+fn visible_interfaces<'a>(
+    interfaces: &'a [Interface],
+    settings: &PrintSettings,
+) -> Vec<&'a Interface> {
+    let ret = interfaces
+        .iter()
+        .filter(|i| {
+            !(settings.hide_empty_interfaces
+                && (settings.endpoint_transfer_filter.is_some()
+                    || settings.endpoint_direction_filter.is_some())
+                && visible_endpoints(i, settings).is_empty())
+        })
+        .collect();
+
+    if settings.only_active_alt {
+        active_alt_settings(ret)
+    } else {
+        ret
+    }
+}
+
+/// Keep only the selected alternate setting of each interface number in `interfaces`, or alternate setting 0 if none are marked [`Interface::active`]
+///
+/// Interface numbers where it is not known which alternate setting is selected, such as from a dump without `active`, are kept as is
+///
+/// ```
+/// use cyme::display::active_alt_settings;
+/// use cyme::profiler::read_json_dump;
+///
+/// let mut spusb = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let device = spusb.get_node_str_mut("2-2.1").unwrap();
+/// let config = &mut device.extra.as_mut().unwrap().configurations[0];
+/// // not known so all kept
+/// assert_eq!(active_alt_settings(config.interfaces.iter().collect()).len(), 5);
+///
+/// let mut alt = config.interfaces[0].clone();
+/// alt.alt_setting = 1;
+/// alt.active = Some(true);
+/// config.interfaces[0].active = Some(false);
+/// config.interfaces.insert(1, alt);
+/// let interfaces = active_alt_settings(config.interfaces.iter().collect());
+/// assert_eq!(interfaces.len(), 5);
+/// assert_eq!((interfaces[0].number, interfaces[0].alt_setting), (0, 1));
+/// ```
+pub fn active_alt_settings(interfaces: Vec<&Interface>) -> Vec<&Interface> {
+    let mut selected: HashMap<u8, u8> = HashMap::new();
+    for i in interfaces.iter().filter(|i| i.active.is_some()) {
+        let alt = selected.entry(i.number).or_insert(0);
+        if i.active == Some(true) {
+            *alt = i.alt_setting;
+        }
+    }
+
+    interfaces
+        .into_iter()
+        .filter(|i| {
+            selected
+                .get(&i.number)
+                .is_none_or(|alt| *alt == i.alt_setting)
+        })
+        .collect()
+}
+
+/// Converts a HashSet of [`ConfigAttributes`] a String of nerd icons
+fn attributes_to_icons(attributes: &Vec<ConfigAttributes>, settings: &PrintSettings) -> String {
+    let mut icon_strs = Vec::new();
+    if settings.icons.is_some() {
+        for a in attributes {
+            match a {
+                ConfigAttributes::SelfPowered => icon_strs.push("\u{f06a5}"), // 󰚥
+                ConfigAttributes::RemoteWakeup => icon_strs.push("\u{f0155}"), // 󰅕
+                ConfigAttributes::BatteryPowered => icon_strs.push("\u{f244}"), // 
+                ConfigAttributes::BusPowered => icon_strs.push("\u{f11f0}"),  // 󱇰
+            }
+        }
+    }
+    icon_strs.join(" ")
+}
+
+/// Truncates and appends '...' to show string has been truncated
+///
 /// `len` is length of resulting String, with '...' so original `s` content will be len - 3
 ///
 /// If `len` is less than 3, `s` truncated to this length
@@ -2039,6 +3331,79 @@ pub fn truncate_string(s: &mut String, len: usize) {
     }
 }
 
+/// Wraps `s` into lines of at most `len` width, breaking at spaces where possible
+///
+/// Words wider than `len` are split on char boundaries
+///
+/// ```
+/// use cyme::display::wrap_string;
+/// assert_eq!(
+///     wrap_string("Black Magic Probe v1.8", 11),
+///     vec!["Black Magic", "Probe v1.8"]
+/// );
+/// assert_eq!(wrap_string("ABCDEFGHIJ", 4), vec!["ABCD", "EFGH", "IJ"]);
+/// assert_eq!(wrap_string("short", 8), vec!["short"]);
+/// ```
+pub fn wrap_string(s: &str, len: usize) -> Vec<String> {
+    let len = cmp::max(len, 1);
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in s.split(' ') {
+        let sep = if line.is_empty() { 0 } else { 1 };
+        if line.width() + sep + word.width() <= len {
+            if sep == 1 {
+                line.push(' ');
+            }
+            line.push_str(word);
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        for c in word.chars() {
+            if line.width() + c.width().unwrap_or(0) > len && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push(c);
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Joins the `cells` from [`render_value`] with the column separator
+///
+/// Cells wrapped with [`WrapMode::Wrap`] have the same number of '\n' separated lines so each line is joined in turn, with trailing padding trimmed from the continuation lines
+fn join_cells(cells: &[String], settings: &PrintSettings) -> String {
+    let sep = column_separator(settings);
+    if !cells.iter().any(|c| c.contains('\n')) {
+        return cells.join(sep);
+    }
+    let mut cell_lines: Vec<std::str::Split<'_, char>> =
+        cells.iter().map(|c| c.split('\n')).collect();
+    let mut lines: Vec<String> = Vec::new();
+    loop {
+        let line: Vec<&str> = cell_lines.iter_mut().flat_map(|c| c.next()).collect();
+        if line.is_empty() {
+            break;
+        }
+        let line = line.join(sep);
+        if lines.is_empty() {
+            lines.push(line);
+        } else {
+            lines.push(line.trim_end().to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+/// Returns the string to join columns with; [`PrintSettings`] separator or a single space
+fn column_separator(settings: &PrintSettings) -> &str {
+    settings.separator.as_deref().unwrap_or(" ")
+}
+
 /// Finds the maximum string size to truncate variable fields
 ///
 /// Calculates based on the [`PrintSettings`] terminal_size width, the total length of the [`BlockLength::Fixed`] fields and thus the remaining space to divide between [`BlockLength::Variable`] fields as the maximum string size
@@ -2054,12 +3419,12 @@ pub fn auto_max_string_len<B: BlockEnum, T>(
         return None;
     }
 
-    // total fixed includes length of blocks to account for spaces between fields, plus tree offset
+    // total fixed includes length of blocks to account for separators between fields, plus tree offset
     let total_fixed: usize = blocks
         .iter()
         .filter_map(|b| b.block_length().fixed_len())
         .sum::<usize>()
-        + blocks.len()
+        + blocks.len() * column_separator(settings).width()
         + offset;
     let total_variable: usize = variable_lens.iter().sum();
     let total_len: usize = total_fixed + total_variable + (blocks.len() * 2);
@@ -2095,9 +3460,9 @@ pub fn auto_max_string_len<B: BlockEnum, T>(
             .iter()
             .filter(|v| **v > auto_max_string)
             .count();
-        if variable_longer != 0 {
-            remaining_chars /= variable_longer;
-        }
+        remaining_chars = remaining_chars
+            .checked_div(variable_longer)
+            .unwrap_or(remaining_chars);
         auto_max_string += remaining_chars;
 
         if auto_max_string < MIN_VARIABLE_STRING_LEN {
@@ -2122,7 +3487,7 @@ pub fn has_valid_icons<B: BlockEnum, T>(
 ) -> bool {
     blocks.iter().filter(|b| b.is_icon()).all(|b| {
         if log::log_enabled!(log::Level::Trace) {
-            let val = b.format_value(d, &HashMap::new(), settings);
+            let val = b.format_value(d, settings);
             let ret = match &val {
                 Some(v) => settings.encoding.str_is_valid(v),
                 None => false,
@@ -2135,7 +3500,7 @@ pub fn has_valid_icons<B: BlockEnum, T>(
             );
             ret
         } else {
-            match b.format_value(d, &HashMap::new(), settings) {
+            match b.format_value(d, settings) {
                 Some(v) => settings.encoding.str_is_valid(&v),
                 None => false,
             }
@@ -2144,49 +3509,469 @@ pub fn has_valid_icons<B: BlockEnum, T>(
 }
 
 /// Formats each [`Block`] value shown from a device `d`
-pub fn render_value<B: BlockEnum, T>(
+///
+/// Values are padded to the `pad` width of their block with the [`Block::alignment`] unless overridden in the [`PrintSettings`] `alignment_overrides`
+///
+/// ```
+/// use std::collections::HashMap;
+/// use cyme::display::{render_value, Alignment, AlignmentOverrides, DeviceBlocks, PrintSettings};
+/// use cyme::profiler::Device;
+///
+/// let device = Device {
+///     name: "J-Link".into(),
+///     bus_power: Some(100),
+///     ..Default::default()
+/// };
+/// let blocks = [DeviceBlocks::Name, DeviceBlocks::BusPower];
+/// let pad = HashMap::from([(DeviceBlocks::Name, 8), (DeviceBlocks::BusPower, 8)]);
+///
+/// let settings = PrintSettings::default();
+/// let values = render_value(&device, &blocks, &pad, &settings, None, false);
+/// assert_eq!(values, vec!["J-Link  ", "  100 mA"]);
+///
+/// let settings = PrintSettings {
+///     alignment_overrides: Some(AlignmentOverrides {
+///         blocks: HashMap::from([
+///             (DeviceBlocks::Name, Alignment::Right),
+///             (DeviceBlocks::BusPower, Alignment::Left),
+///         ]),
+///         ..Default::default()
+///     }),
+///     ..Default::default()
+/// };
+/// let values = render_value(&device, &blocks, &pad, &settings, None, false);
+/// assert_eq!(values, vec!["  J-Link", "100 mA  "]);
+/// ```
+pub fn render_value<B: BlockEnum + Block<B, T>, T>(
     d: &T,
-    blocks: &[impl Block<B, T>],
+    blocks: &[B],
     pad: &HashMap<B, usize>,
     settings: &PrintSettings,
     max_string_length: Option<usize>,
     dimmed: bool,
 ) -> Vec<String> {
-    let mut ret = Vec::new();
-    for b in blocks {
-        if let Some(mut string) = b.format_value(d, pad, settings) {
-            // truncate if max_string_length present and before colour applied as this will _add_ chars
-            if b.value_is_variable_length() {
-                if let Some(ml) = max_string_length {
-                    truncate_string(&mut string, ml)
-                }
+    render_value_changed(d, blocks, pad, settings, max_string_length, dimmed, None)
+}
+
+/// Formats each [`Block`] value shown from a device `d` and joins them with the column separator into a line; lines separated by '\n' if [`WrapMode::Wrap`] wraps a value
+pub fn render_line<B: BlockEnum + Block<B, T>, T>(
+    d: &T,
+    blocks: &[B],
+    pad: &HashMap<B, usize>,
+    settings: &PrintSettings,
+    max_string_length: Option<usize>,
+    dimmed: bool,
+) -> String {
+    join_cells(
+        &render_value(d, blocks, pad, settings, max_string_length, dimmed),
+        settings,
+    )
+}
+
+/// Renders the [`PrintSettings`] device blocks of a single `device` to a line without tree prefix, padded to its own values
+///
+/// This is the line [`DisplayWriter::print_flattened_devices`] prints for `device` alone, without the duplicates suffix; the print functions pad each column across all devices printed together
+///
+/// ```
+/// use cyme::display::{device_to_string, DeviceBlocks, PrintSettings};
+///
+/// # let spusb = cyme::profiler::read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let device = spusb.get_node_by_str("2-2.1").unwrap();
+/// let settings = PrintSettings {
+///     device_blocks: Some(vec![DeviceBlocks::VidPid, DeviceBlocks::Name]),
+///     ..Default::default()
+/// };
+/// assert_eq!(device_to_string(device, &settings), " 1366:1050  J-Link");
+/// ```
+pub fn device_to_string(device: &Device, settings: &PrintSettings) -> String {
+    flattened_layout(&[device], settings).render(device, settings)
+}
+
+/// Renders the [`PrintSettings`] bus blocks of a single `bus` to a line, padded to its own values like [`device_to_string`]
+///
+/// ```
+/// use cyme::display::{bus_to_string, BusBlocks, PrintSettings};
+///
+/// # let spusb = cyme::profiler::read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let settings = PrintSettings {
+///     bus_blocks: Some(vec![BusBlocks::BusNumber, BusBlocks::Name]),
+///     ..Default::default()
+/// };
+/// assert_eq!(bus_to_string(&spusb.buses[0], &settings), "  1 EHCI Host Controller");
+/// ```
+pub fn bus_to_string(bus: &Bus, settings: &PrintSettings) -> String {
+    let blocks = settings_bus_blocks(settings);
+    let pad = if !settings.no_padding {
+        generate_block_padding::<BusBlocks, _>(&[bus], settings)
+    } else {
+        HashMap::new()
+    };
+    render_line(
+        bus,
+        &blocks,
+        &pad,
+        settings,
+        settings.max_variable_string_len,
+        false,
+    )
+}
+
+/// One line summary of the devices on `bus` that are not hidden: the total, a count by negotiated speed fastest first and the total current drawn if known
+///
+/// The current is the sum of the [`Device::power_summary`] drawn by each device so is not counted twice for bus powered hubs
+///
+/// ```
+/// use cyme::display::bus_summary;
+/// use cyme::profiler::read_json_dump;
+///
+/// let spusb = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let bus = spusb.buses.iter().find(|b| b.usb_bus_number == Some(2)).unwrap();
+/// assert_eq!(bus_summary(bus), "4 devices: 4×FullSpeed; 200 mA");
+/// ```
+pub fn bus_summary(bus: &Bus) -> String {
+    let devices: Vec<&Device> = bus
+        .flattened_devices()
+        .into_iter()
+        .filter(|d| !d.is_hidden())
+        .collect();
+    let mut speeds: Vec<(usize, String, usize)> = Vec::new();
+    let mut current: Option<u32> = None;
+
+    for d in devices.iter() {
+        if let Some(speed) = d.device_speed.as_ref() {
+            let (rank, name) = match speed {
+                DeviceSpeed::SpeedValue(s) => (
+                    speed_rank(s),
+                    heck::AsUpperCamelCase(s.to_string()).to_string(),
+                ),
+                DeviceSpeed::Description(s) => (usize::MAX, s.to_owned()),
+            };
+            match speeds.iter_mut().find(|(_, n, _)| *n == name) {
+                Some((_, _, count)) => *count += 1,
+                None => speeds.push((rank, name, 1)),
             }
-            match &settings.colours {
-                Some(c) => {
-                    if dimmed {
-                        ret.push(format!("{}", string.dimmed().white()))
+        }
+        if let Some(drawn) = d.power_summary().drawn {
+            current = Some(current.unwrap_or(0) + drawn);
+        }
+    }
+    speeds.sort_by_key(|(rank, _, _)| *rank);
+
+    let mut ret = format!(
+        "{} {}",
+        devices.len(),
+        if devices.len() == 1 {
+            "device"
+        } else {
+            "devices"
+        }
+    );
+    if !speeds.is_empty() {
+        ret.push_str(": ");
+        ret.push_str(
+            &speeds
+                .iter()
+                .map(|(_, name, count)| format!("{count}×{name}"))
+                .collect::<Vec<String>>()
+                .join(", "),
+        );
+    }
+    if let Some(c) = current {
+        ret.push_str(&format!("; {c} mA"));
+    }
+    ret
+}
+
+/// Order of [`Speed`] in [`bus_summary`], fastest first
+fn speed_rank(speed: &Speed) -> usize {
+    match speed {
+        Speed::SuperSpeedPlusX2 => 0,
+        Speed::SuperSpeedPlus => 1,
+        Speed::SuperSpeed => 2,
+        Speed::HighSpeed | Speed::HighBandwidth => 3,
+        Speed::FullSpeed => 4,
+        Speed::LowSpeed => 5,
+        Speed::Unknown => 6,
+    }
+}
+
+/// Formats `bytes` as `xxd` style rows of 16 bytes: offset, hex pairs and printable ASCII
+///
+/// ```
+/// use cyme::display::hex_dump;
+///
+/// let rows = hex_dump(&[0x09, 0x24, 0x01, 0x00, 0x01, 0x09, 0x00, 0x01, 0x01]);
+/// assert_eq!(rows, vec!["00000000: 0924 0100 0109 0001 01                   .$......."]);
+/// ```
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex = chunk
+                .chunks(2)
+                .map(|pair| pair.iter().map(|b| format!("{b:02x}")).collect::<String>())
+                .join(" ");
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
                     } else {
-                        ret.push(format!("{}", b.colour(&string, c)))
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:08x}: {hex:<39}  {ascii}", i * 16)
+        })
+        .collect()
+}
+
+/// The [`PrintSettings`] bus blocks or the defaults for `more`
+fn settings_bus_blocks(settings: &PrintSettings) -> Vec<BusBlocks> {
+    settings
+        .bus_blocks
+        .to_owned()
+        .unwrap_or(Block::<BusBlocks, Bus>::default_blocks(settings.more))
+}
+
+/// The [`PrintSettings`] device blocks or the defaults for `more` and `tree`
+fn settings_device_blocks(settings: &PrintSettings) -> Vec<DeviceBlocks> {
+    settings
+        .device_blocks
+        .to_owned()
+        .unwrap_or(if settings.more {
+            DeviceBlocks::default_blocks(true)
+        } else if settings.tree {
+            DeviceBlocks::default_device_tree_blocks()
+        } else {
+            DeviceBlocks::default_blocks(false)
+        })
+}
+
+/// Like [`render_value`] but compares each value with the one in `previous` for the same block, styling those that differ with the [`colour::ColourTheme`] `changed` colour
+///
+/// Values are compared without padding and blocks missing from `previous` are never changed
+///
+/// ```
+/// use std::collections::HashMap;
+/// use cyme::colour::ColourTheme;
+/// use cyme::display::{render_value, render_value_changed, DeviceBlocks, PrintSettings};
+/// use cyme::profiler::Device;
+///
+/// colored::control::set_override(true);
+/// let settings = PrintSettings {
+///     colours: Some(ColourTheme::new()),
+///     ..Default::default()
+/// };
+/// let device = Device { name: "Probe".into(), serial_num: Some("AB12".into()), ..Default::default() };
+/// let blocks = [DeviceBlocks::Name, DeviceBlocks::Serial];
+/// let pad = HashMap::new();
+/// let unchanged = render_value(&device, &blocks, &pad, &settings, None, false);
+///
+/// let previous = HashMap::from([(DeviceBlocks::Name, "Probe".to_string()), (DeviceBlocks::Serial, "AB11".to_string())]);
+/// let values = render_value_changed(&device, &blocks, &pad, &settings, None, false, Some(&previous));
+/// assert_eq!(values[0], unchanged[0]);
+/// assert_ne!(values[1], unchanged[1]);
+///
+/// let previous = HashMap::from([(DeviceBlocks::Name, "Probe".to_string())]);
+/// let values = render_value_changed(&device, &blocks, &pad, &settings, None, false, Some(&previous));
+/// assert_eq!(values, unchanged);
+/// ```
+pub fn render_value_changed<B: BlockEnum + Block<B, T>, T>(
+    d: &T,
+    blocks: &[B],
+    pad: &HashMap<B, usize>,
+    settings: &PrintSettings,
+    max_string_length: Option<usize>,
+    dimmed: bool,
+    previous: Option<&HashMap<B, String>>,
+) -> Vec<String> {
+    // lines of each value and the width to blank continuation lines of those not wrapped
+    let mut cells: Vec<(Vec<String>, usize)> = Vec::new();
+    for b in ordered_blocks::<B, T>(blocks, settings) {
+        if let Some(mut string) = b.format_value(d, settings) {
+            // compare without padding as column widths can change between profiles
+            let changed = previous
+                .and_then(|p| p.get(b))
+                .is_some_and(|v| v != string.trim());
+            let alignment = b.alignment_override(settings).unwrap_or(b.alignment());
+            // variable length values are limited to max_string_length, like the heading
+            let ml = max_string_length.filter(|_| b.value_is_variable_length());
+            let width = pad.get(b).copied().unwrap_or(string.width());
+            let width = ml.map_or(width, |ml| cmp::min(width, ml));
+            let lines = match ml {
+                Some(ml) if settings.wrap_mode == WrapMode::Wrap && string.width() > ml => {
+                    wrap_string(&string, ml)
+                        .iter()
+                        .map(|l| alignment.pad(l, width))
+                        .collect()
+                }
+                _ => {
+                    // truncate before colour applied as this will _add_ chars
+                    if let Some(ml) = ml {
+                        truncate_string(&mut string, ml)
                     }
+                    vec![alignment.pad(&string, width)]
                 }
-                None => ret.push(string.to_string()),
             };
+            let line_width = lines.first().map_or(0, |l| l.width());
+            let lines = lines
+                .into_iter()
+                .map(|string| match &settings.colours {
+                    Some(c) => {
+                        if dimmed {
+                            string.dimmed().white().to_string()
+                        } else if changed {
+                            format!(
+                                "{}",
+                                c.changed
+                                    .map_or(string.reversed(), |col| string.color(col).bold())
+                            )
+                        } else if let Some(search) = settings.search.as_deref() {
+                            highlight_matches(&string, search, |s| b.colour_value(d, s, c), c)
+                        } else {
+                            b.colour_value(d, &string, c).to_string()
+                        }
+                    }
+                    None => string,
+                })
+                .collect();
+            cells.push((lines, line_width));
+        }
+    }
+
+    // blank the continuation lines of values not wrapped so columns stay aligned in [`join_cells`]
+    let rows = cells.iter().map(|(l, _)| l.len()).max().unwrap_or(1);
+    cells
+        .into_iter()
+        .map(|(mut lines, width)| {
+            lines.resize(rows, " ".repeat(width));
+            lines.join("\n")
+        })
+        .collect()
+}
+
+/// Byte ranges of case-insensitive, non-overlapping matches of `needle` in `haystack`
+///
+/// ```
+/// use cyme::display::search_matches;
+///
+/// assert_eq!(search_matches("J-Link j-link", "J-LINK"), vec![0..6, 7..13]);
+/// assert!(search_matches("Hub", "").is_empty());
+/// ```
+pub fn search_matches(haystack: &str, needle: &str) -> Vec<std::ops::Range<usize>> {
+    let mut ret = Vec::new();
+    if needle.is_empty() {
+        return ret;
+    }
+    let mut start = 0;
+    while start < haystack.len() {
+        let mut chars = haystack[start..].char_indices();
+        let mut end = None;
+        let mut needle_chars = needle.chars();
+        loop {
+            match (needle_chars.next(), chars.next()) {
+                (None, Some((i, _))) => {
+                    end = Some(start + i);
+                    break;
+                }
+                (None, None) => {
+                    end = Some(haystack.len());
+                    break;
+                }
+                (Some(n), Some((_, h))) if n.to_lowercase().eq(h.to_lowercase()) => (),
+                _ => break,
+            }
+        }
+        match end {
+            Some(e) => {
+                ret.push(start..e);
+                start = e;
+            }
+            None => {
+                start += haystack[start..].chars().next().map_or(1, |c| c.len_utf8());
+            }
         }
     }
+    ret
+}
 
+/// Colours matches of `search` in `string` with the [`colour::ColourTheme`] `highlight` colour and the rest with `colour`
+///
+/// Each part is coloured separately since the reset at the end of a highlighted match would end an outer colour
+fn highlight_matches<F: Fn(&str) -> ColoredString>(
+    string: &str,
+    search: &str,
+    colour: F,
+    ct: &colour::ColourTheme,
+) -> String {
+    let mut ret = String::new();
+    let mut last = 0;
+    for m in search_matches(string, search) {
+        if m.start > last {
+            ret.push_str(&colour(&string[last..m.start]).to_string());
+        }
+        let matched = &string[m.clone()];
+        ret.push_str(
+            &ct.highlight
+                .map_or(matched.reversed(), |col| matched.color(col).bold())
+                .to_string(),
+        );
+        last = m.end;
+    }
+    if last < string.len() || last == 0 {
+        ret.push_str(&colour(&string[last..]).to_string());
+    }
     ret
 }
 
-/// Renders the headings for each [`Block`] being shown
-pub fn render_heading<B: BlockEnum, T>(
-    blocks: &[impl Block<B, T>],
+/// Gets the heading override for `block` from [`PrintSettings`] `heading_overrides` keyed by kebab-case block name
+fn heading_override<'a, B: BlockEnum>(block: &B, settings: &'a PrintSettings) -> Option<&'a str> {
+    let overrides = settings.heading_overrides.as_ref()?;
+    let name = block.to_possible_value()?;
+    overrides.get(name.get_name()).map(|h| h.as_str())
+}
+
+/// Gets the heading for `block` using any override in `settings` before falling back to [`Block::heading`]
+fn block_heading<'a, B: BlockEnum + Block<B, T>, T>(
+    block: &'a B,
+    settings: &'a PrintSettings,
+) -> &'a str {
+    heading_override(block, settings).unwrap_or(block.heading())
+}
+
+/// Like [`Block::generate_padding`] but uses heading overrides in `settings` for the heading length
+fn generate_block_padding<B: BlockEnum + Block<B, T>, T>(
+    d: &[&T],
+    settings: &PrintSettings,
+) -> HashMap<B, usize> {
+    B::VARIANTS
+        .iter()
+        .map(|b| {
+            (
+                b.to_owned(),
+                cmp::max(block_heading(b, settings).width(), b.len(d)),
+            )
+        })
+        .collect()
+}
+
+/// Renders the headings for each [`Block`] being shown, using any heading overrides in `settings`
+pub fn render_heading<B: BlockEnum + Block<B, T>, T>(
+    blocks: &[B],
     pad: &HashMap<B, usize>,
     max_string_length: Option<usize>,
+    settings: &PrintSettings,
 ) -> Vec<String> {
     let mut ret = Vec::new();
 
-    for b in blocks {
-        let mut string = b.heading_padded(pad);
+    for b in ordered_blocks::<B, T>(blocks, settings) {
+        let mut string = format!(
+            "{:^pad$}",
+            block_heading(b, settings),
+            pad = pad.get(b).unwrap_or(&0)
+        );
         if b.value_is_variable_length() {
             if let Some(ml) = max_string_length {
                 truncate_string(&mut string, ml)
@@ -2198,6 +3983,65 @@ pub fn render_heading<B: BlockEnum, T>(
     ret
 }
 
+/// The `blocks` in the order to render; icon blocks stably moved to the front if `pin_icons_first`
+fn ordered_blocks<'a, B: BlockEnum + Block<B, T>, T>(
+    blocks: &'a [B],
+    settings: &PrintSettings,
+) -> Vec<&'a B> {
+    if settings.pin_icons_first {
+        let (icons, others): (Vec<&B>, Vec<&B>) = blocks.iter().partition(|b| b.is_icon());
+        icons.into_iter().chain(others).collect()
+    } else {
+        blocks.iter().collect()
+    }
+}
+
+/// Removes from `blocks` those whose value is empty or the "-" placeholder for every item in `d`
+///
+/// Must be called before padding is generated so that the widths and auto width only consider the blocks shown. Nothing is removed if `d` is empty.
+///
+/// ```
+/// use cyme::display::{retain_non_empty_blocks, DeviceBlocks, PrintSettings};
+/// use cyme::profiler::Device;
+///
+/// let devices = [
+///     Device {
+///         name: "Black Magic Probe".into(),
+///         ..Default::default()
+///     },
+///     Device {
+///         name: "Hub".into(),
+///         ..Default::default()
+///     },
+/// ];
+/// let mut blocks = vec![DeviceBlocks::Name, DeviceBlocks::Serial, DeviceBlocks::Driver];
+/// retain_non_empty_blocks(
+///     &mut blocks,
+///     &devices.iter().collect::<Vec<_>>(),
+///     &PrintSettings::default(),
+/// );
+/// assert_eq!(blocks, vec![DeviceBlocks::Name]);
+/// ```
+pub fn retain_non_empty_blocks<B: BlockEnum + Block<B, T>, T>(
+    blocks: &mut Vec<B>,
+    d: &[&T],
+    settings: &PrintSettings,
+) {
+    if d.is_empty() {
+        return;
+    }
+    blocks.retain(|b| {
+        let keep = d.iter().any(|t| {
+            b.format_value(t, settings)
+                .is_some_and(|v| !matches!(v.trim(), "" | "-"))
+        });
+        if let Some(name) = b.to_possible_value().filter(|_| !keep) {
+            log::debug!("Hiding empty block {}", name.get_name());
+        }
+        keep
+    });
+}
+
 /// Generates tree formatting and values given `current_tree`, current `branch_length` and item `index` in branch
 fn generate_tree_data(
     current_tree: &TreeData,
@@ -2216,14 +4060,7 @@ fn generate_tree_data(
                 icon::Icon::TreeBlank
             };
 
-            format!(
-                "{}{}",
-                pass_tree.prefix,
-                settings.icons.as_ref().map_or(
-                    icon::get_default_tree_icon(&edge_icon, &settings.encoding),
-                    |i| i.get_tree_icon(&edge_icon, &settings.encoding)
-                )
-            )
+            format!("{}{}", pass_tree.prefix, settings.tree_icon(&edge_icon))
         } else {
             pass_tree.prefix.to_string()
         };
@@ -2236,6 +4073,44 @@ fn generate_tree_data(
     pass_tree
 }
 
+/// Tree prefix for the continuation lines of a [`WrapMode::Wrap`] value so they align after the `terminator`
+///
+/// The branch is continued if `more` items follow in `tree` and below the `terminator` if the item `has_children`
+fn tree_continuation(
+    tree: &TreeData,
+    more: bool,
+    terminator: &str,
+    has_children: bool,
+    settings: &PrintSettings,
+) -> String {
+    let mut prefix = tree.prefix.to_string();
+    if tree.depth > 0 {
+        let edge_icon = if more {
+            icon::Icon::TreeLine
+        } else {
+            icon::Icon::TreeBlank
+        };
+        prefix.push_str(&settings.tree_icon(&edge_icon));
+    }
+    let below = if has_children {
+        settings
+            .tree_icon(&icon::Icon::TreeLine)
+            .chars()
+            .next()
+            .unwrap_or(' ')
+    } else {
+        ' '
+    };
+    let prefix = format!("{prefix}{below:width$} ", width = terminator.width());
+    match settings.colours.as_ref() {
+        Some(ct) => ct
+            .tree
+            .map_or(prefix.normal(), |c| prefix.color(c))
+            .to_string(),
+        None => prefix,
+    }
+}
+
 /// Generates the [`DeviceExtra`] blocks based on the [`PrintSettings`] or defaults. Will also retain based on `is_icon` and [`IconWhen`] setting
 ///
 /// If [`IconWhen::Auto`] will render icon block values to check if supported by [`Encoding`] and remove if not
@@ -2400,9 +4275,30 @@ pub enum LineItem {
 pub struct DisplayWriter<W: Write> {
     raw_mode: bool,
     line_context: Vec<LineItem>,
+    previous_values: ValueCache,
+    value_cache: ValueCache,
     inner: W,
 }
 
+/// Serializes `device` as a single line JSON object of `port_path`, last `event` and `device` for JSON Lines output
+///
+/// Child devices are not included in `device` since each has its own line
+pub fn ndjson_event(device: &Device) -> Result<String> {
+    let mut value = serde_json::to_value(device)?;
+    if let Some(o) = value.as_object_mut() {
+        o.remove("devices");
+    }
+    let event = serde_json::json!({
+        "port_path": rendered_port_path(device),
+        "event": device.last_event,
+        "device": value,
+    });
+    Ok(serde_json::to_string(&event)?)
+}
+
+/// Previously rendered [`DeviceBlocks`] values keyed by device port path, used to highlight changes in [`PrintMode::Dynamic`]
+pub type ValueCache = HashMap<String, HashMap<DeviceBlocks, String>>;
+
 impl<W: Write> Write for DisplayWriter<W> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
@@ -2425,6 +4321,8 @@ impl<W: Write> DisplayWriter<W> {
         Self {
             raw_mode: false,
             line_context: Vec::new(),
+            previous_values: HashMap::new(),
+            value_cache: HashMap::new(),
             inner,
         }
     }
@@ -2455,6 +4353,21 @@ impl<W: Write> DisplayWriter<W> {
         Ok(())
     }
 
+    /// Print `text` from [`render_line`] with a newline, printing any continuation lines from [`WrapMode::Wrap`] after `continuation` so they align with the first
+    fn println_wrapped(
+        &mut self,
+        text: &str,
+        continuation: &str,
+        item: LineItem,
+    ) -> io::Result<()> {
+        let mut lines = text.split('\n');
+        self.println(lines.next().unwrap_or_default(), item.clone())?;
+        for line in lines {
+            self.println(format!("{continuation}{line}"), item.clone())?;
+        }
+        Ok(())
+    }
+
     /// Get the inner writer
     pub fn into_inner(self) -> W {
         self.inner
@@ -2465,27 +4378,124 @@ impl<W: Write> DisplayWriter<W> {
         &self.line_context
     }
 
-    /// All device [`Endpoint`]
-    pub fn print_endpoints(
+    /// Print `device` as a single line [`ndjson_event`] object and flush so that each event can be consumed as it occurs
+    pub fn print_ndjson_event(&mut self, device: &Device) -> Result<()> {
+        let line = ndjson_event(device)?;
+        self.println(line, LineItem::Device(device.port_path()))?;
+        Ok(())
+    }
+
+    /// Set the [`ValueCache`] of values printed before the profile last changed; device values that differ are highlighted when using [`PrintMode::Dynamic`]
+    pub fn set_previous_values(&mut self, cache: ValueCache) {
+        self.previous_values = cache;
+    }
+
+    /// Take the [`ValueCache`] of the device values printed when using [`PrintMode::Dynamic`], excluding [`DeviceBlocks::is_time_dependent`] blocks
+    ///
+    /// Only devices printed are included so passing it to [`Self::set_previous_values`] once the profile changes drops removed devices
+    ///
+    /// ```
+    /// use cyme::display::{DeviceBlocks, DisplayWriter, PrintMode, PrintSettings};
+    /// use cyme::profiler::{Bus, Device, DeviceEvent, DeviceLocation, SystemProfile};
+    ///
+    /// let settings = PrintSettings {
+    ///     print_mode: PrintMode::Dynamic,
+    ///     device_blocks: Some(vec![DeviceBlocks::Name, DeviceBlocks::LastEvent]),
+    ///     ..Default::default()
+    /// };
+    /// let device = |name: &str, port: u8| Device {
+    ///     name: name.into(),
+    ///     location_id: DeviceLocation { bus: 1, tree_positions: vec![port], number: port },
+    ///     last_event: Some(DeviceEvent::Connected(chrono::Local::now())),
+    ///     ..Default::default()
+    /// };
+    /// let profile = |devices: Vec<Device>| {
+    ///     let mut bus = Bus::default();
+    ///     bus.usb_bus_number = Some(1);
+    ///     bus.devices = Some(devices);
+    ///     SystemProfile { buses: vec![bus] }
+    /// };
+    ///
+    /// let mut dw = DisplayWriter::new(Vec::new());
+    /// dw.print_sp_usb(&profile(vec![device("Probe", 1), device("Dongle", 2)]), &settings);
+    /// let values = dw.take_value_cache();
+    /// assert_eq!(values["1-1"][&DeviceBlocks::Name], "Probe");
+    /// assert!(!values["1-1"].contains_key(&DeviceBlocks::LastEvent));
+    ///
+    /// // the profile changed: Dongle removed
+    /// dw.set_previous_values(values);
+    /// dw.print_sp_usb(&profile(vec![device("Probe", 1)]), &settings);
+    /// let values = dw.take_value_cache();
+    /// assert!(values.contains_key("1-1"));
+    /// assert!(!values.contains_key("1-2"));
+    /// ```
+    pub fn take_value_cache(&mut self) -> ValueCache {
+        std::mem::take(&mut self.value_cache)
+    }
+
+    /// Renders the device [`DeviceBlocks`] values, highlighting those changed since the previous refresh if using [`PrintMode::Dynamic`]
+    fn render_device_value(
         &mut self,
-        interface: &Interface,
-        blocks: &[EndpointBlocks],
+        device: &Device,
+        blocks: &[DeviceBlocks],
+        pad: &HashMap<DeviceBlocks, usize>,
         settings: &PrintSettings,
-        tree: &TreeData,
-        dimmed: bool,
-    ) {
-        let endpoints = &interface.endpoints;
-        let device_path = interface.device_path();
-        let mut pad = if !settings.no_padding {
-            let endpoints: Vec<&Endpoint> = endpoints.iter().collect();
-            EndpointBlocks::generate_padding(&endpoints)
-        } else {
-            HashMap::new()
-        };
+        max_string_length: Option<usize>,
+    ) -> Vec<String> {
+        if settings.print_mode != PrintMode::Dynamic {
+            return render_value(
+                device,
+                blocks,
+                pad,
+                settings,
+                max_string_length,
+                device.is_disconnected(),
+            );
+        }
+
+        let port_path = device.port_path().to_string();
+        let values = render_value_changed(
+            device,
+            blocks,
+            pad,
+            settings,
+            max_string_length,
+            device.is_disconnected(),
+            self.previous_values.get(&port_path),
+        );
+        // compare without padding as column widths can change between profiles
+        let current = blocks
+            .iter()
+            .filter(|b| !b.is_time_dependent())
+            .filter_map(|b| {
+                b.format_value(device, settings)
+                    .map(|v| (*b, v.trim().to_string()))
+            })
+            .collect();
+        self.value_cache.insert(port_path, current);
+        values
+    }
+
+    /// All device [`Endpoint`] matching the `endpoint_transfer_filter` and `endpoint_direction_filter`
+    pub fn print_endpoints(
+        &mut self,
+        interface: &Interface,
+        blocks: &[EndpointBlocks],
+        settings: &PrintSettings,
+        tree: &TreeData,
+        dimmed: bool,
+    ) {
+        let endpoints = visible_endpoints(interface, settings);
+        let device_path = interface.device_path();
+        let mut pad = if !settings.no_padding {
+            generate_block_padding::<EndpointBlocks, _>(&endpoints, settings)
+        } else {
+            HashMap::new()
+        };
         pad.retain(|k, _| blocks.contains(k));
 
         let max_variable_string_len: Option<usize> = if settings.auto_width {
-            let offset = if settings.tree {
+            let offset = if settings.descriptor_tree() {
                 tree.depth * 3 + 1
             } else {
                 (EndpointBlocks::INSET * LIST_INSET_SPACES) as usize
@@ -2512,7 +4522,7 @@ impl<W: Write> DisplayWriter<W> {
             }
         }
 
-        for (i, endpoint) in endpoints.iter().enumerate() {
+        for (i, endpoint) in endpoints.into_iter().enumerate() {
             let line_item = if let Some(dp) = device_path.as_ref() {
                 LineItem::Endpoint(EndpointPath::new_with_device_path(
                     dp.to_owned(),
@@ -2522,34 +4532,28 @@ impl<W: Write> DisplayWriter<W> {
                 LineItem::None
             };
             // get current prefix based on if last in tree and whether we are within the tree
-            if settings.tree {
+            if settings.descriptor_tree() {
                 let mut prefix = if tree.depth > 0 {
                     let edge_icon = if i + 1 != tree.branch_length {
                         icon::Icon::TreeEdge
                     } else {
                         icon::Icon::TreeCorner
                     };
-                    let edge = settings.icons.as_ref().map_or(
-                        icon::get_default_tree_icon(&edge_icon, &settings.encoding),
-                        |i| i.get_tree_icon(&edge_icon, &settings.encoding),
-                    );
+                    let edge = settings.tree_icon(&edge_icon);
                     format!("{}{}", tree.prefix, edge)
                 // zero depth
                 } else {
                     tree.prefix.to_string()
                 };
 
-                let mut terminator = settings.icons.as_ref().map_or(
-                    icon::get_default_tree_icon(
-                        &icon::Icon::Endpoint(endpoint.address.direction),
-                        &settings.encoding,
-                    ),
-                    |i| {
-                        i.get_tree_icon(
-                            &icon::Icon::Endpoint(endpoint.address.direction),
-                            &settings.encoding,
-                        )
-                    },
+                let mut terminator =
+                    settings.tree_icon(&icon::Icon::Endpoint(endpoint.address.direction));
+                let continuation = tree_continuation(
+                    tree,
+                    i + 1 != tree.branch_length,
+                    &terminator,
+                    false,
+                    settings,
                 );
 
                 // colour tree
@@ -2571,7 +4575,8 @@ impl<W: Write> DisplayWriter<W> {
 
                 // maybe should just do once at start of bus
                 if settings.headings && i == 0 {
-                    let heading = render_heading(blocks, &pad, max_variable_string_len).join(" ");
+                    let heading = render_heading(blocks, &pad, max_variable_string_len, settings)
+                        .join(column_separator(settings));
                     self.println(
                         format!("{}  {}", prefix, heading.bold().underline()),
                         LineItem::None,
@@ -2581,22 +4586,23 @@ impl<W: Write> DisplayWriter<W> {
 
                 // render and print tree if doing it
                 self.print(format!("{prefix}{terminator} ")).unwrap();
-                self.println(
-                    render_value(
+                self.println_wrapped(
+                    &render_line(
                         endpoint,
                         blocks,
                         &pad,
                         settings,
                         max_variable_string_len,
                         dimmed,
-                    )
-                    .join(" "),
+                    ),
+                    &continuation,
                     line_item,
                 )
                 .unwrap();
             } else {
                 if settings.headings && i == 0 {
-                    let heading = render_heading(blocks, &pad, max_variable_string_len).join(" ");
+                    let heading = render_heading(blocks, &pad, max_variable_string_len, settings)
+                        .join(column_separator(settings));
                     self.println(
                         format!("{:spaces$}{}", "", heading.bold().underline(), spaces = 6),
                         LineItem::None,
@@ -2604,21 +4610,21 @@ impl<W: Write> DisplayWriter<W> {
                     .unwrap();
                 }
 
-                self.println(
-                    format!(
+                let spaces = (EndpointBlocks::INSET * LIST_INSET_SPACES) as usize;
+                self.println_wrapped(
+                    &format!(
                         "{:spaces$}{}",
                         "",
-                        render_value(
+                        render_line(
                             endpoint,
                             blocks,
                             &pad,
                             settings,
                             max_variable_string_len,
                             dimmed
-                        )
-                        .join(" "),
-                        spaces = (EndpointBlocks::INSET * LIST_INSET_SPACES) as usize
+                        ),
                     ),
+                    &" ".repeat(spaces),
                     line_item,
                 )
                 .unwrap();
@@ -2635,16 +4641,19 @@ impl<W: Write> DisplayWriter<W> {
         tree: &TreeData,
         dimmed: bool,
     ) {
+        let mut interfaces = visible_interfaces(interfaces, settings);
+        if let Some(sort) = settings.sort_interfaces {
+            sort.sort_interfaces(&mut interfaces);
+        }
         let mut pad = if !settings.no_padding {
-            let interfaces: Vec<&Interface> = interfaces.iter().collect();
-            InterfaceBlocks::generate_padding(&interfaces)
+            generate_block_padding::<InterfaceBlocks, _>(&interfaces, settings)
         } else {
             HashMap::new()
         };
         pad.retain(|k, _| blocks.0.contains(k));
 
         let max_variable_string_len: Option<usize> = if settings.auto_width {
-            let offset = if settings.tree {
+            let offset = if settings.descriptor_tree() {
                 tree.depth * 3 + 1
             } else {
                 (InterfaceBlocks::INSET * LIST_INSET_SPACES) as usize
@@ -2671,36 +4680,35 @@ impl<W: Write> DisplayWriter<W> {
 
         log::trace!("Print interfaces padding {pad:?}, tree {tree:?}");
 
-        for (i, interface) in interfaces.iter().enumerate() {
+        for (i, interface) in interfaces.into_iter().enumerate() {
             let line_item = if let Some(dp) = interface.device_path() {
                 LineItem::Interface(dp)
             } else {
                 LineItem::None
             };
             // get current prefix based on if last in tree and whether we are within the tree
-            if settings.tree {
+            if settings.descriptor_tree() {
                 let mut prefix = if tree.depth > 0 {
                     let edge_icon = if i + 1 != tree.branch_length {
                         icon::Icon::TreeEdge
                     } else {
                         icon::Icon::TreeCorner
                     };
-                    let edge = settings.icons.as_ref().map_or(
-                        icon::get_default_tree_icon(&edge_icon, &settings.encoding),
-                        |i| i.get_tree_icon(&edge_icon, &settings.encoding),
-                    );
+                    let edge = settings.tree_icon(&edge_icon);
                     format!("{}{}", tree.prefix, edge)
                 // zero depth
                 } else {
                     tree.prefix.to_string()
                 };
 
-                let mut terminator = settings.icons.as_ref().map_or(
-                    icon::get_default_tree_icon(
-                        &icon::Icon::TreeInterfaceTerminator,
-                        &settings.encoding,
-                    ),
-                    |i| i.get_tree_icon(&icon::Icon::TreeInterfaceTerminator, &settings.encoding),
+                let mut terminator = settings.tree_icon(&icon::Icon::TreeInterfaceTerminator);
+                let continuation = tree_continuation(
+                    tree,
+                    i + 1 != tree.branch_length,
+                    &terminator,
+                    (settings.descriptor_verbosity() >= 3 || interface.is_expanded())
+                        && !visible_endpoints(interface, settings).is_empty(),
+                    settings,
                 );
 
                 // colour tree
@@ -2717,7 +4725,8 @@ impl<W: Write> DisplayWriter<W> {
 
                 // maybe should just do once at start of bus
                 if settings.headings && i == 0 {
-                    let heading = render_heading(blocks.0, &pad, max_variable_string_len).join(" ");
+                    let heading = render_heading(blocks.0, &pad, max_variable_string_len, settings)
+                        .join(column_separator(settings));
                     self.println(
                         format!("{}  {}", prefix, heading.bold().underline()),
                         LineItem::None,
@@ -2728,22 +4737,23 @@ impl<W: Write> DisplayWriter<W> {
                 // render and print tree if doing it
                 self.print(format!("{prefix}{terminator} ")).unwrap();
 
-                self.println(
-                    render_value(
+                self.println_wrapped(
+                    &render_line(
                         interface,
                         blocks.0,
                         &pad,
                         settings,
                         max_variable_string_len,
                         dimmed,
-                    )
-                    .join(" "),
+                    ),
+                    &continuation,
                     line_item,
                 )
                 .unwrap();
             } else {
                 if settings.headings && i == 0 {
-                    let heading = render_heading(blocks.0, &pad, max_variable_string_len).join(" ");
+                    let heading = render_heading(blocks.0, &pad, max_variable_string_len, settings)
+                        .join(column_separator(settings));
                     self.println(
                         format!("{:spaces$}{}", "", heading.bold().underline(), spaces = 4),
                         LineItem::None,
@@ -2751,39 +4761,208 @@ impl<W: Write> DisplayWriter<W> {
                     .unwrap();
                 }
 
-                self.println(
-                    format!(
+                let spaces = (InterfaceBlocks::INSET * LIST_INSET_SPACES) as usize;
+                self.println_wrapped(
+                    &format!(
                         "{:spaces$}{}",
                         "",
-                        render_value(
+                        render_line(
                             interface,
                             blocks.0,
                             &pad,
                             settings,
                             max_variable_string_len,
                             dimmed
-                        )
-                        .join(" "),
-                        spaces = (InterfaceBlocks::INSET * LIST_INSET_SPACES) as usize
+                        ),
                     ),
+                    &" ".repeat(spaces),
                     line_item,
                 )
                 .unwrap();
             }
 
+            if settings.hex_dump {
+                let inset = if settings.descriptor_tree() {
+                    // continue the branch line if endpoints follow
+                    let edge = if (settings.descriptor_verbosity() >= 3 || interface.is_expanded())
+                        && !visible_endpoints(interface, settings).is_empty()
+                    {
+                        settings.tree_icon(&icon::Icon::TreeLine)
+                    } else {
+                        "   ".to_string()
+                    };
+                    let inset = format!(
+                        "{}{}",
+                        generate_tree_data(tree, 0, i, settings).prefix,
+                        edge
+                    );
+                    match settings.colours.as_ref() {
+                        Some(ct) => ct
+                            .tree
+                            .map_or(inset.normal(), |c| inset.color(c))
+                            .to_string(),
+                        None => inset,
+                    }
+                } else {
+                    " ".repeat(((InterfaceBlocks::INSET + 1) * LIST_INSET_SPACES) as usize)
+                };
+                let bytes: Vec<u8> = interface
+                    .extra
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .flat_map(Vec::<u8>::from)
+                    .collect();
+                for row in hex_dump(&bytes) {
+                    self.println(format!("{inset}{row}"), LineItem::None)
+                        .unwrap();
+                }
+            }
+
             // print the endpoints
-            if settings.verbosity >= 3 || interface.is_expanded() {
+            if settings.descriptor_verbosity() >= 3 || interface.is_expanded() {
                 self.print_endpoints(
                     interface,
                     blocks.1,
                     settings,
-                    &generate_tree_data(tree, interface.endpoints.len(), i, settings),
+                    &generate_tree_data(
+                        tree,
+                        visible_endpoints(interface, settings).len(),
+                        i,
+                        settings,
+                    ),
                     dimmed,
                 );
             }
         }
     }
 
+    /// Functions grouped by [`InterfaceAssociationDescriptor`]s in a configuration, printed before its interfaces
+    ///
+    /// `tree` is the same as the interfaces that follow so the last function only ends the branch if there are none
+    pub fn print_functions(
+        &mut self,
+        functions: &[&InterfaceAssociationDescriptor],
+        blocks: &[FunctionBlocks],
+        settings: &PrintSettings,
+        tree: &TreeData,
+        dimmed: bool,
+    ) {
+        let mut pad = if !settings.no_padding {
+            generate_block_padding::<FunctionBlocks, _>(functions, settings)
+        } else {
+            HashMap::new()
+        };
+        pad.retain(|k, _| blocks.contains(k));
+
+        let max_variable_string_len: Option<usize> = if settings.auto_width {
+            let offset = if settings.descriptor_tree() {
+                tree.depth * 3 + 1
+            } else {
+                (FunctionBlocks::INSET * LIST_INSET_SPACES) as usize
+            };
+            let variable_lens: Vec<usize> = pad
+                .iter()
+                .filter(|(k, _)| k.value_is_variable_length())
+                .map(|(_, v)| *v)
+                .collect();
+            auto_max_string_len(blocks, offset, &variable_lens, settings)
+                .or(settings.max_variable_string_len)
+        } else {
+            settings.max_variable_string_len
+        };
+
+        // if there is a max variable length, adjust padding to this if current > it
+        if let Some(ml) = max_variable_string_len.as_ref() {
+            for (k, v) in pad.iter_mut() {
+                if k.value_is_variable_length() {
+                    *v = cmp::min(*v, *ml);
+                }
+            }
+        }
+
+        log::trace!("Print functions padding {pad:?}, tree {tree:?}");
+
+        for (i, iad) in functions.iter().enumerate() {
+            let value = render_line(
+                *iad,
+                blocks,
+                &pad,
+                settings,
+                max_variable_string_len,
+                dimmed,
+            );
+
+            if settings.descriptor_tree() {
+                let mut prefix = if tree.depth > 0 {
+                    let edge_icon = if i + 1 != functions.len() || tree.branch_length != 0 {
+                        icon::Icon::TreeEdge
+                    } else {
+                        icon::Icon::TreeCorner
+                    };
+                    let edge = settings.tree_icon(&edge_icon);
+                    format!("{}{}", tree.prefix, edge)
+                // zero depth
+                } else {
+                    tree.prefix.to_string()
+                };
+
+                let mut terminator = settings.tree_icon(&icon::Icon::TreeInterfaceTerminator);
+                let continuation = tree_continuation(
+                    tree,
+                    i + 1 != functions.len() || tree.branch_length != 0,
+                    &terminator,
+                    false,
+                    settings,
+                );
+
+                // colour tree
+                if let Some(ct) = settings.colours.as_ref() {
+                    prefix = ct
+                        .tree
+                        .map_or(prefix.normal(), |c| prefix.color(c))
+                        .to_string();
+                    terminator = ct
+                        .tree_interface_terminator
+                        .map_or(terminator.normal(), |c| terminator.color(c))
+                        .to_string();
+                }
+
+                if settings.headings && i == 0 {
+                    let heading = render_heading(blocks, &pad, max_variable_string_len, settings)
+                        .join(column_separator(settings));
+                    self.println(
+                        format!("{}  {}", prefix, heading.bold().underline()),
+                        LineItem::None,
+                    )
+                    .unwrap();
+                }
+
+                self.print(format!("{prefix}{terminator} ")).unwrap();
+                self.println_wrapped(&value, &continuation, LineItem::None)
+                    .unwrap();
+            } else {
+                if settings.headings && i == 0 {
+                    let heading = render_heading(blocks, &pad, max_variable_string_len, settings)
+                        .join(column_separator(settings));
+                    self.println(
+                        format!("{:spaces$}{}", "", heading.bold().underline(), spaces = 4),
+                        LineItem::None,
+                    )
+                    .unwrap();
+                }
+
+                let spaces = (FunctionBlocks::INSET * LIST_INSET_SPACES) as usize;
+                self.println_wrapped(
+                    &format!("{:spaces$}{}", "", value),
+                    &" ".repeat(spaces),
+                    LineItem::None,
+                )
+                .unwrap();
+            }
+        }
+    }
+
     /// All device [`Configuration`]
     pub fn print_configurations(
         &mut self,
@@ -2799,14 +4978,14 @@ impl<W: Write> DisplayWriter<W> {
     ) {
         let mut pad = if !settings.no_padding {
             let configs: Vec<&Configuration> = configs.iter().collect();
-            ConfigurationBlocks::generate_padding(&configs)
+            generate_block_padding::<ConfigurationBlocks, _>(&configs, settings)
         } else {
             HashMap::new()
         };
         pad.retain(|k, _| blocks.0.contains(k));
 
         let max_variable_string_len: Option<usize> = if settings.auto_width {
-            let offset = if settings.tree {
+            let offset = if settings.descriptor_tree() {
                 tree.depth * 3 + 1
             } else {
                 (ConfigurationBlocks::INSET * LIST_INSET_SPACES) as usize
@@ -2836,34 +5015,28 @@ impl<W: Write> DisplayWriter<W> {
         for (i, config) in configs.iter().enumerate() {
             let line_item = LineItem::Config((device.port_path(), config.number));
             // get current prefix based on if last in tree and whether we are within the tree
-            if settings.tree {
+            if settings.descriptor_tree() {
                 let mut prefix = if tree.depth > 0 {
                     let edge_icon = if i + 1 != tree.branch_length {
                         icon::Icon::TreeEdge
                     } else {
                         icon::Icon::TreeCorner
                     };
-                    let edge = settings.icons.as_ref().map_or(
-                        icon::get_default_tree_icon(&edge_icon, &settings.encoding),
-                        |i| i.get_tree_icon(&edge_icon, &settings.encoding),
-                    );
+                    let edge = settings.tree_icon(&edge_icon);
                     format!("{}{}", tree.prefix, edge)
                 // zero depth
                 } else {
                     tree.prefix.to_string()
                 };
 
-                let mut terminator = settings.icons.as_ref().map_or(
-                    icon::get_default_tree_icon(
-                        &icon::Icon::TreeConfigurationTerminator,
-                        &settings.encoding,
-                    ),
-                    |i| {
-                        i.get_tree_icon(
-                            &icon::Icon::TreeConfigurationTerminator,
-                            &settings.encoding,
-                        )
-                    },
+                let mut terminator = settings.tree_icon(&icon::Icon::TreeConfigurationTerminator);
+                let continuation = tree_continuation(
+                    tree,
+                    i + 1 != tree.branch_length,
+                    &terminator,
+                    (settings.descriptor_verbosity() >= 2 || config.is_expanded())
+                        && !visible_interfaces(&config.interfaces, settings).is_empty(),
+                    settings,
                 );
 
                 // colour tree
@@ -2880,7 +5053,8 @@ impl<W: Write> DisplayWriter<W> {
 
                 // maybe should just do once at start of bus
                 if settings.headings && i == 0 {
-                    let heading = render_heading(blocks.0, &pad, max_variable_string_len).join(" ");
+                    let heading = render_heading(blocks.0, &pad, max_variable_string_len, settings)
+                        .join(column_separator(settings));
                     self.println(
                         format!("{}  {}", prefix, heading.bold().underline()),
                         LineItem::None,
@@ -2891,22 +5065,23 @@ impl<W: Write> DisplayWriter<W> {
                 // render and print tree if doing it
                 self.print(format!("{prefix}{terminator} ")).unwrap();
 
-                self.println(
-                    render_value(
+                self.println_wrapped(
+                    &render_line(
                         config,
                         blocks.0,
                         &pad,
                         settings,
                         max_variable_string_len,
                         device.is_disconnected(),
-                    )
-                    .join(" "),
+                    ),
+                    &continuation,
                     line_item,
                 )
                 .unwrap();
             } else {
                 if settings.headings && i == 0 {
-                    let heading = render_heading(blocks.0, &pad, max_variable_string_len).join(" ");
+                    let heading = render_heading(blocks.0, &pad, max_variable_string_len, settings)
+                        .join(column_separator(settings));
                     self.println(
                         format!("{:spaces$}{}", "", heading.bold().underline(), spaces = 2),
                         LineItem::None,
@@ -2914,33 +5089,56 @@ impl<W: Write> DisplayWriter<W> {
                     .unwrap();
                 }
 
-                self.println(
-                    format!(
+                let spaces = (ConfigurationBlocks::INSET * LIST_INSET_SPACES) as usize;
+                self.println_wrapped(
+                    &format!(
                         "{:spaces$}{}",
                         "",
-                        render_value(
+                        render_line(
                             config,
                             blocks.0,
                             &pad,
                             settings,
                             max_variable_string_len,
                             device.is_disconnected()
-                        )
-                        .join(" "),
-                        spaces = (ConfigurationBlocks::INSET * LIST_INSET_SPACES) as usize
+                        ),
                     ),
+                    &" ".repeat(spaces),
                     line_item,
                 )
                 .unwrap();
             }
 
-            // print the interfaces
-            if settings.verbosity >= 2 || config.is_expanded() {
+            // print the functions and interfaces
+            if settings.descriptor_verbosity() >= 2 || config.is_expanded() {
+                let interface_tree = generate_tree_data(
+                    tree,
+                    visible_interfaces(&config.interfaces, settings).len(),
+                    i,
+                    settings,
+                );
+                let functions = config.interface_associations();
+                if !functions.is_empty() {
+                    let function_blocks =
+                        settings.function_blocks.to_owned().unwrap_or(Block::<
+                            FunctionBlocks,
+                            InterfaceAssociationDescriptor,
+                        >::default_blocks(
+                            settings.more
+                        ));
+                    self.print_functions(
+                        &functions,
+                        &function_blocks,
+                        settings,
+                        &interface_tree,
+                        device.is_disconnected(),
+                    );
+                }
                 self.print_interfaces(
                     &config.interfaces,
                     ((blocks.1), (blocks.2)),
                     settings,
-                    &generate_tree_data(tree, config.interfaces.len(), i, settings),
+                    &interface_tree,
                     device.is_disconnected(),
                 );
             }
@@ -2958,6 +5156,15 @@ impl<W: Write> DisplayWriter<W> {
         tree: &TreeData,
         padding: &HashMap<DeviceBlocks, usize>,
     ) {
+        // summarise rather than print devices beyond max depth
+        if settings.max_depth.is_some_and(|md| tree.depth > md) {
+            let num = count_visible_devices(devices);
+            if num > 0 {
+                self.print_depth_summary(num, settings, tree);
+            }
+            return;
+        }
+
         let mut padding = padding.clone();
         let max_variable_string_len: Option<usize> = if settings.auto_width {
             let offset = if settings.tree { tree.depth * 3 + 1 } else { 0 };
@@ -2984,6 +5191,14 @@ impl<W: Write> DisplayWriter<W> {
         log::trace!("Print devices padding {padding:?}, tree {tree:?}");
 
         for (i, device) in devices.iter().filter(|d| !d.is_hidden()).enumerate() {
+            // configurations or child devices are printed below
+            let has_children = (device.extra.is_some()
+                && (settings.descriptor_verbosity() >= 1 || device.is_expanded()))
+                || device
+                    .devices
+                    .as_ref()
+                    .is_some_and(|d| d.iter().any(|d| !d.is_hidden()));
+            let mut continuation = String::new();
             // get current prefix based on if last in tree and whether we are within the tree
             if settings.tree {
                 let mut prefix = if tree.depth > 0 {
@@ -2992,10 +5207,7 @@ impl<W: Write> DisplayWriter<W> {
                     } else {
                         icon::Icon::TreeCorner
                     };
-                    let edge = settings.icons.as_ref().map_or(
-                        icon::get_default_tree_icon(&edge_icon, &settings.encoding),
-                        |i| i.get_tree_icon(&edge_icon, &settings.encoding),
-                    );
+                    let edge = settings.tree_icon(&edge_icon);
                     format!("{}{}", tree.prefix, edge)
                 // zero depth
                 } else {
@@ -3007,9 +5219,13 @@ impl<W: Write> DisplayWriter<W> {
                 } else {
                     icon::Icon::TreeDeviceTerminator
                 };
-                let mut terminator = settings.icons.as_ref().map_or(
-                    icon::get_default_tree_icon(&icon_terminator, &settings.encoding),
-                    |i| i.get_tree_icon(&icon_terminator, &settings.encoding),
+                let mut terminator = settings.tree_icon(&icon_terminator);
+                continuation = tree_continuation(
+                    tree,
+                    i + 1 != tree.branch_length,
+                    &terminator,
+                    has_children,
+                    settings,
                 );
 
                 // colour tree
@@ -3026,7 +5242,8 @@ impl<W: Write> DisplayWriter<W> {
 
                 // maybe should just do once at start of bus
                 if settings.headings && i == 0 {
-                    let heading = render_heading(db, &padding, max_variable_string_len).join(" ");
+                    let heading = render_heading(db, &padding, max_variable_string_len, settings)
+                        .join(column_separator(settings));
                     self.println(
                         format!("{}  {}", prefix, heading.bold().underline()),
                         LineItem::None,
@@ -3037,33 +5254,39 @@ impl<W: Write> DisplayWriter<W> {
                 // render and print tree if doing it
                 self.print(format!("{prefix}{terminator} ")).unwrap();
             } else if settings.headings && i == 0 {
-                let heading = render_heading(db, &padding, max_variable_string_len).join(" ");
+                let heading = render_heading(db, &padding, max_variable_string_len, settings)
+                    .join(column_separator(settings));
                 self.println(format!("{}", heading.bold().underline()), LineItem::None)
                     .unwrap();
             }
 
             // print the device
-            let device_string = render_value(
-                device,
-                db,
-                &padding,
+            let device_string = join_cells(
+                &self.render_device_value(device, db, &padding, settings, max_variable_string_len),
                 settings,
-                max_variable_string_len,
-                device.is_disconnected(),
+            );
+            self.println_wrapped(
+                &format!("{device_string}{}", duplicates_suffix(device, settings)),
+                &continuation,
+                LineItem::Device(device.port_path()),
             )
-            .join(" ");
-            self.println(&device_string, LineItem::Device(device.port_path()))
-                .unwrap();
+            .unwrap();
 
             // print the configurations
             if let Some(extra) = device.extra.as_ref() {
-                if settings.verbosity >= 1 || device.is_expanded() {
+                if settings.descriptor_verbosity() >= 1 || device.is_expanded() {
                     // generate extra blocks if not passed and drop icons if not supported by encoding
                     let blocks = generate_extra_blocks(extra, settings);
                     let num = device
                         .devices
                         .as_ref()
                         .map_or(0, |d| d.iter().filter(|d| !d.is_hidden()).count());
+                    // devices beyond max depth are a single summary line
+                    let num = if settings.max_depth.is_some_and(|md| tree.depth >= md) {
+                        num.min(1)
+                    } else {
+                        num
+                    };
 
                     // pass branch length as number of configurations for this device plus devices still to print
                     self.print_configurations(
@@ -3074,7 +5297,7 @@ impl<W: Write> DisplayWriter<W> {
                         &generate_tree_data(tree, extra.configurations.len() + num, i, settings),
                     );
                 }
-            } else if settings.verbosity >= 1 {
+            } else if settings.descriptor_verbosity() >= 1 {
                 log::warn!(
                     "Unable to print verbose information for {device} because libusb extra data is missing"
                 )
@@ -3098,22 +5321,34 @@ impl<W: Write> DisplayWriter<W> {
         }
     }
 
+    /// Print a line indicating `num` devices were not printed because they are beyond [`PrintSettings`] `max_depth`
+    fn print_depth_summary(&mut self, num: usize, settings: &PrintSettings, tree: &TreeData) {
+        let summary = format!("(+{} device{})", num, if num == 1 { "" } else { "s" });
+        if settings.tree {
+            let mut prefix = if tree.depth > 0 {
+                let edge = settings.tree_icon(&icon::Icon::TreeCorner);
+                format!("{}{}", tree.prefix, edge)
+            } else {
+                tree.prefix.to_string()
+            };
+            if let Some(ct) = settings.colours.as_ref() {
+                prefix = ct
+                    .tree
+                    .map_or(prefix.normal(), |c| prefix.color(c))
+                    .to_string();
+            }
+            self.println(format!("{prefix} {}", summary.dimmed()), LineItem::None)
+                .unwrap();
+        } else {
+            self.println(format!("{}", summary.dimmed()), LineItem::None)
+                .unwrap();
+        }
+    }
+
     /// Print [`SystemProfile`] [`Bus`] and [`Device`] information
     pub fn print_sp_usb(&mut self, sp_usb: &SystemProfile, settings: &PrintSettings) {
-        let mut bb = settings
-            .bus_blocks
-            .to_owned()
-            .unwrap_or(Block::<BusBlocks, Bus>::default_blocks(settings.more));
-        let mut db = settings
-            .device_blocks
-            .to_owned()
-            .unwrap_or(if settings.more {
-                DeviceBlocks::default_blocks(true)
-            } else if settings.tree {
-                DeviceBlocks::default_device_tree_blocks()
-            } else {
-                DeviceBlocks::default_blocks(false)
-            });
+        let mut bb = settings_bus_blocks(settings);
+        let mut db = settings_device_blocks(settings);
 
         // remove icon blocks if not supported by encoding
         match settings.icon_when {
@@ -3140,12 +5375,27 @@ impl<W: Write> DisplayWriter<W> {
             }
         }
 
+        // hide blocks empty for all buses and all devices, not just those in a branch, so columns are consistent
+        if settings.auto_hide_empty {
+            let buses: Vec<&Bus> = sp_usb.buses.iter().filter(|b| !b.is_hidden()).collect();
+            retain_non_empty_blocks(&mut bb, &buses, settings);
+            let devices: Vec<&Device> = buses
+                .iter()
+                .flat_map(|b| b.flattened_devices())
+                .filter(|d| !d.is_hidden())
+                .collect();
+            retain_non_empty_blocks(&mut db, &devices, settings);
+        }
+
         let base_tree = TreeData {
             ..Default::default()
         };
 
         let mut pad: HashMap<BusBlocks, usize> = if !settings.no_padding {
-            BusBlocks::generate_padding(&sp_usb.buses.iter().collect::<Vec<&Bus>>())
+            generate_block_padding::<BusBlocks, _>(
+                &sp_usb.buses.iter().collect::<Vec<&Bus>>(),
+                settings,
+            )
         } else {
             HashMap::new()
         };
@@ -3178,11 +5428,18 @@ impl<W: Write> DisplayWriter<W> {
 
         let len = sp_usb.buses.iter().filter(|b| !b.is_hidden()).count();
         for (i, bus) in sp_usb.buses.iter().filter(|b| !b.is_hidden()).enumerate() {
+            let mut continuation = String::new();
             if settings.tree {
                 let mut prefix = base_tree.prefix.to_owned();
-                let mut start = settings.icons.as_ref().map_or(
-                    icon::get_default_tree_icon(&icon::Icon::TreeBusStart, &settings.encoding),
-                    |i| i.get_tree_icon(&icon::Icon::TreeBusStart, &settings.encoding),
+                let mut start = settings.tree_icon(&icon::Icon::TreeBusStart);
+                continuation = tree_continuation(
+                    &base_tree,
+                    i + 1 != len,
+                    &start,
+                    bus.devices
+                        .as_ref()
+                        .is_some_and(|d| d.iter().any(|d| !d.is_hidden())),
+                    settings,
                 );
 
                 // colour tree
@@ -3198,7 +5455,8 @@ impl<W: Write> DisplayWriter<W> {
                 }
 
                 if settings.headings {
-                    let heading = render_heading(&bb, &pad, max_variable_string_len).join(" ");
+                    let heading = render_heading(&bb, &pad, max_variable_string_len, settings)
+                        .join(column_separator(settings));
                     // 2 spaces for bus start icon and space to info
                     self.println(
                         format!("{:>spaces$}{}", "", heading.bold().underline(), spaces = 2),
@@ -3209,17 +5467,30 @@ impl<W: Write> DisplayWriter<W> {
 
                 self.print(format!("{prefix}{start} ")).unwrap()
             } else if settings.headings {
-                let heading = render_heading(&bb, &pad, max_variable_string_len).join(" ");
+                let heading = render_heading(&bb, &pad, max_variable_string_len, settings)
+                    .join(column_separator(settings));
                 // 2 spaces for bus start icon and space to info
                 self.println(format!("{}", heading.bold().underline()), LineItem::Bus(i))
                     .unwrap();
             }
-            self.println(
-                render_value(bus, &bb, &pad, settings, max_variable_string_len, false).join(" "),
+            self.println_wrapped(
+                &render_line(bus, &bb, &pad, settings, max_variable_string_len, false),
+                &continuation,
                 LineItem::Bus(i),
             )
             .unwrap();
 
+            if settings.bus_summary {
+                let summary = settings.encoding.substitute(&bus_summary(bus));
+                // inset under bus start icon like the heading
+                let spaces = if settings.tree { 2 } else { 0 };
+                self.println(
+                    format!("{:>spaces$}{}", "", summary.italic(), spaces = spaces),
+                    LineItem::Bus(i),
+                )
+                .unwrap();
+            }
+
             if let Some(d) = bus.devices.as_ref() {
                 let num = d.iter().filter(|d| !d.is_hidden()).count();
                 let tree = generate_tree_data(&base_tree, num, i, settings);
@@ -3231,14 +5502,14 @@ impl<W: Write> DisplayWriter<W> {
                             .iter()
                             .filter(|d| !d.is_hidden())
                             .collect::<Vec<&Device>>();
-                        DeviceBlocks::generate_padding(&devices)
+                        generate_block_padding::<DeviceBlocks, _>(&devices, settings)
                     } else {
                         let devices = bus
                             .flattened_devices()
                             .into_iter()
                             .filter(|d| !d.is_hidden())
                             .collect::<Vec<&Device>>();
-                        DeviceBlocks::generate_padding(&devices)
+                        generate_block_padding::<DeviceBlocks, _>(&devices, settings)
                     }
                 } else {
                     HashMap::new()
@@ -3258,78 +5529,39 @@ impl<W: Write> DisplayWriter<W> {
 
     /// Print `devices` [`Device`] references without looking down each device's devices!
     pub fn print_flattened_devices(&mut self, devices: &[&Device], settings: &PrintSettings) {
-        let mut db = settings
-            .device_blocks
-            .to_owned()
-            .unwrap_or(DeviceBlocks::default_blocks(settings.more));
-
-        // remove icon blocks if not supported
-        match settings.icon_when {
-            IconWhen::Never | IconWhen::Auto if settings.icons.is_none() => {
-                db.retain(|b| !b.is_icon());
-            }
-            IconWhen::Auto if settings.encoding == Encoding::Glyphs => (),
-            IconWhen::Always => {
-                if settings.icons.is_none() {
-                    log::warn!(
-                        "{:?} blocks requested but no icons provided",
-                        settings.icon_when
-                    );
-                }
-            }
-            _ => settings.icon_when.retain_ref(devices, &mut db, settings),
-        }
-
-        let mut pad = if !settings.no_padding {
-            DeviceBlocks::generate_padding(devices)
-        } else {
-            HashMap::new()
-        };
-        pad.retain(|k, _| db.contains(k));
-        log::trace!("Flattened devices padding {pad:?}");
-
-        let max_variable_string_len: Option<usize> = if settings.auto_width {
-            let variable_lens: Vec<usize> = pad
-                .iter()
-                .filter(|(k, _)| k.value_is_variable_length())
-                .map(|(_, v)| *v)
-                .collect();
-            auto_max_string_len(&db, 0, &variable_lens, settings)
-                .or(settings.max_variable_string_len)
-        } else {
-            settings.max_variable_string_len
-        };
+        let layout = flattened_layout(devices, settings);
+        self.print_flattened_layout(devices, &layout, settings);
+    }
 
-        // if there is a max variable length, adjust padding to this if current > it
-        if let Some(ml) = max_variable_string_len.as_ref() {
-            for (k, v) in pad.iter_mut() {
-                if k.value_is_variable_length() {
-                    *v = cmp::min(*v, *ml);
-                }
-            }
-        }
+    /// Print `devices` flat with blocks and padding from a [`FlattenedLayout`], which may have been generated from a larger set of devices so that groups line up
+    fn print_flattened_layout(
+        &mut self,
+        devices: &[&Device],
+        layout: &FlattenedLayout,
+        settings: &PrintSettings,
+    ) {
+        let FlattenedLayout {
+            blocks: db,
+            pad,
+            max_variable_string_len,
+        } = layout;
+        let max_variable_string_len = *max_variable_string_len;
 
         if settings.headings {
-            let heading = render_heading(&db, &pad, max_variable_string_len).join(" ");
+            let heading = render_heading(db, pad, max_variable_string_len, settings)
+                .join(column_separator(settings));
             println!("{}", heading.bold().underline());
         }
 
         for (i, device) in devices.iter().enumerate() {
             println!(
-                "{}",
-                render_value(
-                    *device,
-                    &db,
-                    &pad,
-                    settings,
-                    max_variable_string_len,
-                    device.is_disconnected()
-                )
-                .join(" ")
+                "{}{}",
+                layout.render(device, settings),
+                duplicates_suffix(device, settings)
             );
             // print the configurations
             if let Some(extra) = device.extra.as_ref() {
-                if settings.verbosity >= 1 || device.is_expanded() {
+                if settings.descriptor_verbosity() >= 1 || device.is_expanded() {
                     let blocks = generate_extra_blocks(extra, settings);
 
                     // pass branch length as number of configurations for this device plus devices still to print
@@ -3350,7 +5582,7 @@ impl<W: Write> DisplayWriter<W> {
                         ),
                     );
                 }
-            } else if settings.verbosity >= 1 {
+            } else if settings.descriptor_verbosity() >= 1 {
                 log::warn!(
                     "Unable to print verbose information for {device} because libusb extra data is missing"
                 )
@@ -3358,21 +5590,71 @@ impl<W: Write> DisplayWriter<W> {
         }
     }
 
-    /// A way of printing a reference flattened [`SystemProfile`] rather than hard flatten
+    /// Print `devices` [`Device`] references grouped under a heading for each vendor id
     ///
-    /// Prints each `&Bus` and tuple pair `Vec<&Device>`
-    pub fn print_bus_grouped(
-        &mut self,
+    /// Each group is printed flat with [`Self::print_flattened_devices`]; devices without a vendor id are grouped last
+    pub fn print_vendor_grouped_devices(&mut self, devices: &[&Device], settings: &PrintSettings) {
+        let groups = devices
+            .iter()
+            .sorted_by_key(|d| (d.vendor_id.is_none(), d.vendor_id))
+            .group_by(|d| d.vendor_id);
+        let groups: Vec<(Option<u16>, Vec<&Device>)> = groups
+            .into_iter()
+            .map(|(vid, g)| (vid, g.copied().collect()))
+            .collect();
+
+        // padding from all devices so vendor groups line up
+        let layout = flattened_layout(devices, settings);
+        let len = groups.len();
+        for (i, (vid, group)) in groups.iter().enumerate() {
+            let id = match vid {
+                Some(v) => DeviceBlocks::format_base_u16(*v, settings)
+                    .trim_start()
+                    .to_owned(),
+                None => String::from("-"),
+            };
+            let name = group
+                .iter()
+                .find_map(|d| d.extra.as_ref().and_then(|e| e.vendor.to_owned()))
+                .or_else(|| {
+                    vid.and_then(|v| usb_ids::Vendor::from_id(v).map(|v| v.name().to_owned()))
+                })
+                .unwrap_or_else(|| String::from("[unknown]"));
+
+            if let Some(ct) = settings.colours.as_ref() {
+                println!(
+                    "{} {}",
+                    DeviceBlocks::VendorId.colour(&id, ct).bold(),
+                    DeviceBlocks::VendorName.colour(&name, ct).bold()
+                );
+            } else {
+                println!("{} {}", id.bold(), name.bold());
+            }
+
+            self.print_flattened_layout(group, &layout, settings);
+
+            // separate vendor groups with line
+            if i + 1 != len {
+                println!();
+            }
+        }
+    }
+
+    /// A way of printing a reference flattened [`SystemProfile`] rather than hard flatten
+    ///
+    /// Prints each `&Bus` and tuple pair `Vec<&Device>`
+    pub fn print_bus_grouped(
+        &mut self,
         bus_devices: Vec<(&Bus, Vec<&Device>)>,
         settings: &PrintSettings,
     ) {
-        let bb = settings
-            .bus_blocks
-            .to_owned()
-            .unwrap_or(Block::<BusBlocks, Bus>::default_blocks(settings.more));
+        let mut bb = settings_bus_blocks(settings);
+        let buses: Vec<&Bus> = bus_devices.iter().map(|bd| bd.0).collect();
+        if settings.auto_hide_empty {
+            retain_non_empty_blocks(&mut bb, &buses, settings);
+        }
         let mut pad: HashMap<BusBlocks, usize> = if !settings.no_padding {
-            let buses: Vec<&Bus> = bus_devices.iter().map(|bd| bd.0).collect();
-            BusBlocks::generate_padding(&buses)
+            generate_block_padding::<BusBlocks, _>(&buses, settings)
         } else {
             HashMap::new()
         };
@@ -3396,19 +5678,27 @@ impl<W: Write> DisplayWriter<W> {
             }
         }
 
+        // device blocks and padding from all buses so hidden empty blocks and columns are the same for each group
+        let all_devices: Vec<&Device> = bus_devices
+            .iter()
+            .flat_map(|(_, d)| d.iter().copied())
+            .collect();
+        let layout = flattened_layout(&all_devices, settings);
+
         let len = bus_devices.len();
         for (i, (bus, devices)) in bus_devices.into_iter().enumerate() {
             if settings.headings {
-                let heading = render_heading(&bb, &pad, max_variable_string_len).join(" ");
+                let heading = render_heading(&bb, &pad, max_variable_string_len, settings)
+                    .join(column_separator(settings));
                 self.println(format!("{}", heading.bold().underline()), LineItem::Bus(i))
                     .unwrap();
             }
             self.println(
-                render_value(bus, &bb, &pad, settings, max_variable_string_len, false).join(" "),
+                render_line(bus, &bb, &pad, settings, max_variable_string_len, false),
                 LineItem::Bus(i),
             )
             .unwrap();
-            self.print_flattened_devices(&devices, settings);
+            self.print_flattened_layout(&devices, &layout, settings);
             // new line for each group
             if i + 1 != len {
                 self.println("", LineItem::None).unwrap();
@@ -3417,27 +5707,170 @@ impl<W: Write> DisplayWriter<W> {
     }
 }
 
+/// Blocks, padding and max variable string length for printing flattened [`Device`]s
+struct FlattenedLayout {
+    blocks: Vec<DeviceBlocks>,
+    pad: HashMap<DeviceBlocks, usize>,
+    max_variable_string_len: Option<usize>,
+}
+
+impl FlattenedLayout {
+    /// Render the line for `device` with this layout
+    fn render(&self, device: &Device, settings: &PrintSettings) -> String {
+        render_line(
+            device,
+            &self.blocks,
+            &self.pad,
+            settings,
+            self.max_variable_string_len,
+            device.is_disconnected(),
+        )
+    }
+}
+
+/// Generate the [`FlattenedLayout`] for `devices`
+///
+/// When devices are printed in groups, generate this from all devices so that the columns of each group line up
+fn flattened_layout(devices: &[&Device], settings: &PrintSettings) -> FlattenedLayout {
+    let mut db = settings_device_blocks(settings);
+
+    // remove icon blocks if not supported
+    match settings.icon_when {
+        IconWhen::Never | IconWhen::Auto if settings.icons.is_none() => {
+            db.retain(|b| !b.is_icon());
+        }
+        IconWhen::Auto if settings.encoding == Encoding::Glyphs => (),
+        IconWhen::Always => {
+            if settings.icons.is_none() {
+                log::warn!(
+                    "{:?} blocks requested but no icons provided",
+                    settings.icon_when
+                );
+            }
+        }
+        _ => settings.icon_when.retain_ref(devices, &mut db, settings),
+    }
+
+    if settings.auto_hide_empty {
+        retain_non_empty_blocks(&mut db, devices, settings);
+    }
+
+    let mut pad = if !settings.no_padding {
+        generate_block_padding::<DeviceBlocks, _>(devices, settings)
+    } else {
+        HashMap::new()
+    };
+    pad.retain(|k, _| db.contains(k));
+    log::trace!("Flattened devices padding {pad:?}");
+
+    let max_variable_string_len: Option<usize> = if settings.auto_width {
+        let variable_lens: Vec<usize> = pad
+            .iter()
+            .filter(|(k, _)| k.value_is_variable_length())
+            .map(|(_, v)| *v)
+            .collect();
+        auto_max_string_len(&db, 0, &variable_lens, settings).or(settings.max_variable_string_len)
+    } else {
+        settings.max_variable_string_len
+    };
+
+    // if there is a max variable length, adjust padding to this if current > it
+    if let Some(ml) = max_variable_string_len.as_ref() {
+        for (k, v) in pad.iter_mut() {
+            if k.value_is_variable_length() {
+                *v = cmp::min(*v, *ml);
+            }
+        }
+    }
+
+    FlattenedLayout {
+        blocks: db,
+        pad,
+        max_variable_string_len,
+    }
+}
+
+/// Mask `s` using the [`MaskSerial`] method, keeping the display width
+///
+/// Each char is masked with chars of the same width, so wide chars are replaced by two narrow ones and columns padded to the masked values line up as they would with the originals
+///
+/// ```
+/// use cyme::display::{mask_string, MaskSerial};
+/// use unicode_width::UnicodeWidthStr;
+///
+/// assert_eq!(mask_string("AB12", &MaskSerial::Hide), "****");
+/// assert_eq!(mask_string("序列1", &MaskSerial::Hide), "*****");
+/// for hide in [MaskSerial::Scramble, MaskSerial::Replace] {
+///     assert_eq!(mask_string("序列1", &hide).width(), 5);
+/// }
+/// ```
+pub fn mask_string(s: &str, hide: &MaskSerial) -> String {
+    mask_string_rng(s, hide, &mut fastrand::Rng::new())
+}
+
+/// Like [`mask_string`] but the random chars are derived from the `seed` and `s`, so the same value is always masked the same regardless of what else is masked
+///
+/// ```
+/// use cyme::display::{mask_string_seeded, MaskSerial};
+///
+/// let masked = mask_string_seeded("AB12CD34", &MaskSerial::Replace, 42);
+/// assert_eq!(masked.len(), 8);
+/// assert_eq!(mask_string_seeded("AB12CD34", &MaskSerial::Replace, 42), masked);
+/// assert_ne!(mask_string_seeded("AB12CD34", &MaskSerial::Replace, 43), masked);
+/// assert_ne!(mask_string_seeded("AB12CD35", &MaskSerial::Replace, 42), masked);
+/// assert_eq!(mask_string_seeded("AB12", &MaskSerial::Hide, 42), "****");
+/// ```
+pub fn mask_string_seeded(s: &str, hide: &MaskSerial, seed: u64) -> String {
+    // FNV-1a of the seed and value so the masking is stable between runs and builds
+    let hash = seed
+        .to_le_bytes()
+        .iter()
+        .chain(s.as_bytes())
+        .fold(0xcbf29ce484222325_u64, |h, b| {
+            (h ^ *b as u64).wrapping_mul(0x100000001b3)
+        });
+    mask_string_rng(s, hide, &mut fastrand::Rng::with_seed(hash))
+}
+
+fn mask_string_rng(s: &str, hide: &MaskSerial, rng: &mut fastrand::Rng) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut ret = String::new();
+    for c in chars.iter() {
+        let width = c.width().unwrap_or(0);
+        match hide {
+            MaskSerial::Hide => ret.extend(std::iter::repeat_n('*', width)),
+            MaskSerial::Scramble => {
+                let same_width: Vec<&char> = chars
+                    .iter()
+                    .filter(|o| o.width().unwrap_or(0) == width)
+                    .collect();
+                match same_width.get(rng.usize(0..same_width.len())) {
+                    Some(o) if width > 0 => ret.push(**o),
+                    _ => ret.extend(std::iter::repeat_n('*', width)),
+                }
+            }
+            MaskSerial::Replace => ret.extend(
+                std::iter::repeat_with(|| rng.alphanumeric().to_ascii_uppercase()).take(width),
+            ),
+            _ => ret.push(*c),
+        }
+    }
+    ret
+}
+
+/// Port path of the `device` for output, the one masked by [`mask_device_fields`] if [`MaskField::PortPath`] is set
+fn rendered_port_path(device: &Device) -> String {
+    device
+        .internal
+        .masked_port_path
+        .clone()
+        .unwrap_or_else(|| device.port_path().to_string())
+}
+
 /// Mask the `device` serial if it has one using the [`MaskSerial`] method and recursively if `recursive`
 pub fn mask_serial(device: &mut Device, hide: &MaskSerial, recursive: bool) {
     if let Some(serial) = device.serial_num.as_mut() {
-        *serial = match hide {
-            MaskSerial::Hide => serial.chars().map(|_| '*').collect::<String>(),
-            MaskSerial::Scramble => serial
-                .chars()
-                .map(|_| {
-                    serial
-                        .chars()
-                        .nth(fastrand::usize(0..serial.len()))
-                        .unwrap_or('*')
-                })
-                .collect::<String>(),
-            MaskSerial::Replace => serial
-                .chars()
-                .map(|_| fastrand::alphanumeric())
-                .collect::<String>()
-                .to_uppercase(),
-            _ => serial.to_string(),
-        };
+        *serial = mask_string(serial, hide);
     }
 
     if recursive {
@@ -3448,12 +5881,168 @@ pub fn mask_serial(device: &mut Device, hide: &MaskSerial, recursive: bool) {
     }
 }
 
+/// Mask the `device` string descriptor fields selected by [`PrintSettings::field_mask`] and recursively if `recursive`
+///
+/// A device is only masked once; masking again, such as each watch refresh, keeps the first masked values.
+///
+/// [`MaskField::PortPath`] masks the device and interface syspaths in the `device` but the [`Device::location_id`] is not changed since it is used for lookups, rather the port path is masked and stored for [`DeviceBlocks`], CSV and JSON Lines output.
+///
+/// Masked fields keep their display width so rendered columns stay aligned:
+///
+/// ```
+/// use cyme::display::{mask_device_fields, render_line, Block, DeviceBlocks, MaskSerial, PrintSettings};
+/// use cyme::profiler::Device;
+/// use unicode_width::UnicodeWidthStr;
+///
+/// let settings = PrintSettings {
+///     mask_serials: Some(MaskSerial::Scramble),
+///     ..Default::default()
+/// };
+/// let mut devices = vec![
+///     Device { name: "Dongle".into(), serial_num: Some("序列号-1".into()), ..Default::default() },
+///     Device { name: "Probe".into(), serial_num: Some("AB".into()), ..Default::default() },
+/// ];
+/// devices.iter_mut().for_each(|d| mask_device_fields(d, &settings, false));
+///
+/// let blocks = [DeviceBlocks::Serial, DeviceBlocks::Name];
+/// let refs: Vec<&Device> = devices.iter().collect();
+/// let pad = DeviceBlocks::generate_padding(&refs);
+/// let lines: Vec<String> = devices
+///     .iter()
+///     .map(|d| render_line(d, &blocks, &pad, &settings, None, false))
+///     .collect();
+/// let name_column = |line: &str, name: &str| line[..line.find(name).unwrap()].width();
+/// assert_eq!(lines[0].width(), lines[1].width());
+/// assert_eq!(name_column(&lines[0], "Dongle"), name_column(&lines[1], "Probe"));
+/// ```
+///
+/// The masked port path is the same in every output:
+///
+/// ```
+/// use cyme::display::{mask_device_fields, ndjson_event, Block, DeviceBlocks, MaskField, MaskSerial, PrintSettings};
+/// use cyme::profiler::{Device, DeviceLocation};
+///
+/// let settings = PrintSettings {
+///     mask_serials: Some(MaskSerial::Scramble),
+///     mask_fields: Some(vec![MaskField::PortPath]),
+///     ..Default::default()
+/// };
+/// let mut device = Device {
+///     location_id: DeviceLocation { bus: 1, tree_positions: vec![4, 2], number: 7 },
+///     extra: Some(serde_json::from_str(r#"{"max_packet_size": 64, "syspath": "/sys/devices/usb1/1-4.2", "configurations": []}"#).unwrap()),
+///     ..Default::default()
+/// };
+/// mask_device_fields(&mut device, &settings, false);
+///
+/// let port_path = DeviceBlocks::PortPath.format_value(&device, &settings).unwrap();
+/// assert_ne!(port_path, "1-4.2");
+/// assert_eq!(DeviceBlocks::PortPath.format_value(&device, &settings).unwrap(), port_path);
+/// let syspath = DeviceBlocks::SysPath.format_value(&device, &settings).unwrap();
+/// assert!(!syspath.contains("1-4.2"));
+///
+/// let line: serde_json::Value = serde_json::from_str(&ndjson_event(&device).unwrap()).unwrap();
+/// assert_eq!(line["port_path"], port_path);
+/// assert_eq!(line["device"]["extra"]["syspath"], syspath);
+///
+/// // masking again keeps the first masked values
+/// mask_device_fields(&mut device, &settings, false);
+/// assert_eq!(DeviceBlocks::PortPath.format_value(&device, &settings).unwrap(), port_path);
+/// assert_eq!(DeviceBlocks::SysPath.format_value(&device, &settings).unwrap(), syspath);
+/// ```
+///
+/// With a `mask_seed` each value is masked the same whatever other fields or devices are masked, and however many times:
+///
+/// ```
+/// use cyme::display::{mask_device_fields, MaskField, MaskSerial, PrintSettings};
+/// use cyme::profiler::Device;
+///
+/// let device = Device {
+///     name: "Probe".into(),
+///     serial_num: Some("AB12CD34".into()),
+///     ..Default::default()
+/// };
+/// let masked_serial = |mask_fields: Vec<MaskField>| {
+///     let settings = PrintSettings {
+///         mask_serials: Some(MaskSerial::Replace),
+///         mask_fields: Some(mask_fields),
+///         mask_seed: Some(7),
+///         ..Default::default()
+///     };
+///     let mut device = device.clone();
+///     mask_device_fields(&mut device, &settings, false);
+///     device.serial_num.unwrap()
+/// };
+/// assert_eq!(
+///     masked_serial(vec![MaskField::Serial]),
+///     masked_serial(vec![MaskField::Product, MaskField::Manufacturer, MaskField::Serial])
+/// );
+///
+/// let settings = PrintSettings {
+///     mask_serials: Some(MaskSerial::Scramble),
+///     mask_fields: Some(vec![MaskField::Serial, MaskField::Product]),
+///     mask_seed: Some(7),
+///     ..Default::default()
+/// };
+/// let mut once = device.clone();
+/// mask_device_fields(&mut once, &settings, false);
+/// let mut twice = device.clone();
+/// mask_device_fields(&mut twice, &settings, false);
+/// mask_device_fields(&mut twice, &settings, false);
+/// assert_eq!(twice.serial_num, once.serial_num);
+/// assert_eq!(twice.name, once.name);
+/// ```
+pub fn mask_device_fields(device: &mut Device, settings: &PrintSettings, recursive: bool) {
+    let mask_string = |s: &str, hide: &MaskSerial| match settings.mask_seed {
+        Some(seed) => mask_string_seeded(s, hide, seed),
+        None => mask_string(s, hide),
+    };
+    // masking the masked values again, such as each watch refresh, would change them
+    if !device.internal.masked {
+        if let Some(hide) = settings.field_mask(MaskField::Serial) {
+            if let Some(serial) = device.serial_num.as_mut() {
+                *serial = mask_string(serial, &hide);
+            }
+        }
+        if let Some(hide) = settings.field_mask(MaskField::Manufacturer) {
+            if let Some(manufacturer) = device.manufacturer.as_mut() {
+                *manufacturer = mask_string(manufacturer, &hide);
+            }
+        }
+        if let Some(hide) = settings.field_mask(MaskField::Product) {
+            device.name = mask_string(&device.name, &hide);
+        }
+        if let Some(hide) = settings.field_mask(MaskField::PortPath) {
+            device.internal.masked_port_path =
+                Some(mask_string(&device.port_path().to_string(), &hide));
+            if let Some(extra) = device.extra.as_mut() {
+                let interface_syspaths = extra
+                    .configurations
+                    .iter_mut()
+                    .flat_map(|c| c.interfaces.iter_mut())
+                    .filter_map(|i| i.syspath.as_mut());
+                for syspath in extra.syspath.iter_mut().chain(interface_syspaths) {
+                    *syspath = mask_string(syspath, &hide);
+                }
+            }
+        }
+        device.internal.masked = true;
+    }
+
+    if recursive {
+        device.devices.iter_mut().for_each(|dd| {
+            dd.iter_mut()
+                .for_each(|d| mask_device_fields(d, settings, recursive))
+        });
+    }
+}
+
 /// Main cyme bin prepare for printing function - changes mutable `sp_usb` with requested `filter` and sort in `settings`
 pub fn prepare(sp_usb: &mut SystemProfile, filter: Option<&Filter>, settings: &PrintSettings) {
     // if not printing tree, hard flatten now before filtering as filter will retain non-matching parents with matching devices in tree
     // flattening now will also mean hubs will be removed when listing if `hide_hubs` because they will appear empty and sorting will be in bus -> device order rather than tree position
+    // DOT output is always the tree
     log::debug!("Running prepare pre-printing");
-    if !settings.tree && !matches!(settings.print_mode, PrintMode::Dynamic) {
+    if !settings.tree && !settings.dot && !matches!(settings.print_mode, PrintMode::Dynamic) {
         log::debug!("Flattening SPUSBDataType");
         sp_usb.into_flattened();
     }
@@ -3468,23 +6057,83 @@ pub fn prepare(sp_usb: &mut SystemProfile, filter: Option<&Filter>, settings: &P
         }
     }
 
+    // watch mode can also hide devices without a recent event
+    if let (PrintMode::Dynamic, Some(since)) = (settings.print_mode, settings.since) {
+        log::debug!("Hiding devices without an event in the last {since:?}");
+        // a span before the earliest representable time has no cutoff so hides nothing
+        let cutoff = chrono::Duration::from_std(since)
+            .ok()
+            .and_then(|since| chrono::Local::now().checked_sub_signed(since));
+        if let Some(cutoff) = cutoff {
+            for bus in sp_usb.buses.iter_mut() {
+                if let Some(devices) = bus.devices.as_mut() {
+                    // without a filter nothing resets hidden devices
+                    hide_stale_devices(devices, cutoff, filter.is_none());
+                }
+            }
+        }
+    }
+
     // sort device tree based on sort option
     log::debug!("Sorting with {:?}", settings.sort_devices);
     settings.sort_devices.sort_buses(&mut sp_usb.buses);
 
+    if let Some(block) = settings.sort_block {
+        log::debug!("Sorting devices by block {:?}", block);
+        for bus in sp_usb.buses.iter_mut() {
+            if let Some(devices) = bus.devices.as_mut() {
+                block.sort_devices_recursive(devices, settings);
+            }
+        }
+    }
+
     // sort the buses if asked and not already sorted
     if settings.sort_buses && matches!(settings.sort_devices, Sort::NoSort) {
         log::debug!("Sorting buses by bus number");
         sp_usb.buses.sort_by_key(|d| d.get_bus_number());
     }
 
-    // hide serials Recursively
-    if let Some(hide) = settings.mask_serials.as_ref() {
-        log::debug!("Masking serials with {hide:?}");
+    // reverse after sorting, including NoSort so parse order is reversed
+    if settings.sort_reverse {
+        log::debug!("Reversing sort order");
+        // buses are always sorted again but NoSort devices are not, so watch mode must reverse those only once
+        let devices_sorted =
+            !matches!(settings.sort_devices, Sort::NoSort) || settings.sort_block.is_some();
+        sp_usb.buses.reverse();
+        for bus in sp_usb.buses.iter_mut() {
+            if devices_sorted || !bus.internal.reversed {
+                if let Some(devices) = bus.devices.as_mut() {
+                    Sort::reverse_devices_recursive(devices);
+                }
+            }
+            bus.internal.reversed = !devices_sorted;
+        }
+    }
+
+    // collapse after sorting so that identical devices are adjacent; watch mode needs every device for events
+    if settings.collapse_duplicates
+        && settings.shows_duplicates()
+        && !matches!(settings.print_mode, PrintMode::Dynamic)
+    {
+        log::debug!("Collapsing duplicate devices");
+        for bus in sp_usb.buses.iter_mut() {
+            if let Some(devices) = bus.devices.as_mut() {
+                collapse_duplicate_devices(devices, settings.collapse_ignore_serial);
+            }
+        }
+    }
+
+    // hide serials and other masked fields Recursively
+    if settings.mask_serials.is_some() || settings.mask_fields.is_some() {
+        log::debug!(
+            "Masking {:?} with {:?}",
+            settings.mask_fields,
+            settings.mask_serials
+        );
         for bus in &mut sp_usb.buses {
             bus.devices.iter_mut().for_each(|devices| {
                 for device in devices {
-                    mask_serial(device, hide, true);
+                    mask_device_fields(device, settings, true);
                 }
             });
         }
@@ -3493,19 +6142,500 @@ pub fn prepare(sp_usb: &mut SystemProfile, filter: Option<&Filter>, settings: &P
     log::trace!("sp_usb data post filter and bus sort\n\r{sp_usb:#}");
 }
 
+/// Collapse runs of adjacent identical `devices` at each tree level into the first, which counts them in [`Device::duplicates`]
+///
+/// Devices are identical if the vendor id, product id, name and class match and, unless `ignore_serial`, the serial number. Devices with child devices are never collapsed so the tree below them is kept.
+///
+/// ```
+/// use cyme::display::collapse_duplicate_devices;
+/// use cyme::profiler::Device;
+///
+/// let dongle = |serial: &str| Device {
+///     name: "Dongle".into(),
+///     vendor_id: Some(0x1d50),
+///     product_id: Some(0x6018),
+///     serial_num: Some(serial.into()),
+///     ..Default::default()
+/// };
+/// let mut devices = vec![dongle("A"), dongle("A"), dongle("B")];
+/// collapse_duplicate_devices(&mut devices, false);
+/// assert_eq!(devices.len(), 2);
+/// assert_eq!(devices[0].duplicates(), 1);
+///
+/// collapse_duplicate_devices(&mut devices, true);
+/// assert_eq!(devices.len(), 1);
+/// assert_eq!(devices[0].duplicates(), 2);
+/// ```
+pub fn collapse_duplicate_devices(devices: &mut Vec<Device>, ignore_serial: bool) {
+    for device in devices.iter_mut() {
+        if let Some(d) = device.devices.as_mut() {
+            collapse_duplicate_devices(d, ignore_serial);
+        }
+    }
+
+    devices.dedup_by(|next, first| {
+        let duplicate = !next.has_devices()
+            && !first.has_devices()
+            && next.vendor_id == first.vendor_id
+            && next.product_id == first.product_id
+            && next.name == first.name
+            && next.class == first.class
+            && (ignore_serial || next.serial_num == first.serial_num);
+        if duplicate {
+            first.internal.duplicates += next.internal.duplicates + 1;
+        }
+        duplicate
+    });
+}
+
+/// The " xN" multiplier appended to a [`Device`] line that has [`Device::duplicates`] collapsed into it
+fn duplicates_suffix(device: &Device, settings: &PrintSettings) -> String {
+    match device.duplicates() {
+        0 => String::new(),
+        n => {
+            let suffix = format!("x{}", n + 1);
+            match settings.colours {
+                Some(_) => format!(" {}", suffix.bold()),
+                None => format!(" {suffix}"),
+            }
+        }
+    }
+}
+
+/// Hide `devices` without a [`DeviceEvent::Connected`] or [`DeviceEvent::Disconnected`] event since `cutoff`, unhiding first if `reset`
+///
+/// Parents of devices with a recent event are kept visible. Returns whether any device in `devices` is visible.
+fn hide_stale_devices(
+    devices: &mut [Device],
+    cutoff: chrono::DateTime<chrono::Local>,
+    reset: bool,
+) -> bool {
+    let mut visible = false;
+    for device in devices.iter_mut() {
+        let child_visible = device
+            .devices
+            .as_deref_mut()
+            .is_some_and(|d| hide_stale_devices(d, cutoff, reset));
+        let recent = matches!(
+            device.last_event(),
+            Some(DeviceEvent::Connected(t) | DeviceEvent::Disconnected(t)) if t >= cutoff
+        );
+        if reset {
+            device.internal.hidden = false;
+        }
+        device.internal.hidden |= !(recent || child_visible);
+        visible |= !device.internal.hidden;
+    }
+
+    visible
+}
+
+/// Renders plain heading and value rows for `devices` with [`DeviceBlocks`] as columns for delimited/table output
+///
+/// If `settings.verbosity` >= 2 the [`InterfaceBlocks`] are appended as columns with one row per interface. Values are not padded, coloured or truncated and icon blocks are dropped.
+fn render_plain_rows(
+    devices: &[&Device],
+    settings: &PrintSettings,
+) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut db = settings
+        .device_blocks
+        .to_owned()
+        .unwrap_or(DeviceBlocks::default_blocks(settings.more));
+    db.retain(|b| !b.is_icon());
+    let mut ib = if settings.verbosity >= 2 {
+        settings.interface_blocks.to_owned().unwrap_or(
+            Block::<InterfaceBlocks, Interface>::default_blocks(settings.more),
+        )
+    } else {
+        Vec::new()
+    };
+    ib.retain(|b| !b.is_icon());
+
+    let headings = db
+        .iter()
+        .map(|b| block_heading(b, settings).to_string())
+        .chain(ib.iter().map(|b| block_heading(b, settings).to_string()))
+        .collect();
+
+    let mut rows = Vec::new();
+    for device in devices {
+        let device_values: Vec<String> = db
+            .iter()
+            .map(|b| {
+                b.format_value(device, settings)
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            })
+            .collect();
+
+        let interfaces: Vec<&Interface> = device
+            .extra
+            .as_ref()
+            .map(|e| {
+                e.configurations
+                    .iter()
+                    .flat_map(|c| c.interfaces.iter())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if ib.is_empty() || interfaces.is_empty() {
+            let empty = std::iter::repeat_n(String::new(), ib.len());
+            rows.push(device_values.into_iter().chain(empty).collect());
+        } else {
+            for interface in interfaces {
+                let interface_values = ib.iter().map(|b| {
+                    b.format_value(interface, settings)
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string()
+                });
+                rows.push(
+                    device_values
+                        .iter()
+                        .cloned()
+                        .chain(interface_values)
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    (headings, rows)
+}
+
+/// Quotes CSV `field` if it contains the separator, quotes or newlines
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Writes `devices` as CSV with [`DeviceBlocks`] as columns and a heading row
+///
+/// If `settings.verbosity` >= 2 the [`InterfaceBlocks`] are appended as columns with one row per interface. Values are not padded, coloured or truncated and icon blocks are dropped.
+pub fn write_csv<W: Write>(
+    writer: &mut W,
+    devices: &[&Device],
+    settings: &PrintSettings,
+) -> io::Result<()> {
+    let (headings, rows) = render_plain_rows(devices, settings);
+    writeln!(
+        writer,
+        "{}",
+        headings.iter().map(|h| csv_field(h)).join(",")
+    )?;
+    for row in rows {
+        writeln!(writer, "{}", row.iter().map(|v| csv_field(v)).join(","))?;
+    }
+
+    Ok(())
+}
+
+/// Prints `devices` as CSV to stdout; see [`write_csv`]
+pub fn print_csv(devices: &[&Device], settings: &PrintSettings) {
+    let mut stdout = io::stdout().lock();
+    if let Err(e) = write_csv(&mut stdout, devices, settings) {
+        log::error!("Failed to write CSV: {e}");
+    }
+}
+
+/// Escapes Markdown table `field` pipes and replaces line breaks with a space
+fn markdown_field(field: &str) -> String {
+    field
+        .replace('|', "\\|")
+        .replace("\r\n", " ")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Writes `devices` as a GitHub flavoured Markdown table with [`DeviceBlocks`] as columns
+///
+/// Uses the same columns and rows as [`write_csv`]
+///
+/// ```
+/// use cyme::display::{write_markdown, DeviceBlocks, PrintSettings};
+///
+/// # let spusb = cyme::profiler::read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let mut device = spusb.get_node_by_str("2-2.1").unwrap().to_owned();
+/// device.name = "J-Link | Probe".to_string();
+/// let settings = PrintSettings {
+///     device_blocks: Some(vec![DeviceBlocks::VidPid, DeviceBlocks::Name]),
+///     ..Default::default()
+/// };
+/// let mut out = Vec::new();
+/// write_markdown(&mut out, &[&device], &settings).unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     concat!(
+///         "| VID:PID | Name |\n",
+///         "| --- | --- |\n",
+///         "| 1366:1050 | J-Link \\| Probe |\n"
+///     )
+/// );
+/// ```
+pub fn write_markdown<W: Write>(
+    writer: &mut W,
+    devices: &[&Device],
+    settings: &PrintSettings,
+) -> io::Result<()> {
+    let (headings, rows) = render_plain_rows(devices, settings);
+    writeln!(
+        writer,
+        "| {} |",
+        headings.iter().map(|h| markdown_field(h)).join(" | ")
+    )?;
+    writeln!(writer, "| {} |", headings.iter().map(|_| "---").join(" | "))?;
+    for row in rows {
+        writeln!(
+            writer,
+            "| {} |",
+            row.iter().map(|v| markdown_field(v)).join(" | ")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Prints `devices` as a Markdown table to stdout; see [`write_markdown`]
+pub fn print_markdown(devices: &[&Device], settings: &PrintSettings) {
+    let mut stdout = io::stdout().lock();
+    if let Err(e) = write_markdown(&mut stdout, devices, settings) {
+        log::error!("Failed to write Markdown: {e}");
+    }
+}
+
+/// Escapes GraphViz DOT quoted string `field` quotes, backslashes and newlines
+fn dot_field(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Plain block values of `d` joined with a space for a DOT node label; empty values are skipped
+fn dot_label<B: BlockEnum + Block<B, T>, T>(
+    d: &T,
+    blocks: &[B],
+    settings: &PrintSettings,
+) -> String {
+    blocks
+        .iter()
+        .filter_map(|b| b.format_value(d, settings))
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .join(" ")
+}
+
+fn write_dot_devices<W: Write>(
+    writer: &mut W,
+    parent: &str,
+    devices: &[Device],
+    blocks: &[DeviceBlocks],
+    settings: &PrintSettings,
+) -> io::Result<()> {
+    for device in devices.iter().filter(|d| !d.is_hidden()) {
+        let id = device.port_path().to_string();
+        writeln!(
+            writer,
+            "    \"{}\" [label=\"{}\"];",
+            dot_field(&id),
+            dot_field(&dot_label(device, blocks, settings))
+        )?;
+        writeln!(
+            writer,
+            "    \"{}\" -> \"{}\";",
+            dot_field(parent),
+            dot_field(&id)
+        )?;
+        if let Some(d) = device.devices.as_ref() {
+            write_dot_devices(writer, &id, d, blocks, settings)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the [`Bus`] and [`Device`] tree of `sp_usb` as a GraphViz DOT digraph
+///
+/// Each bus and device is a node with its port path as id, labeled with the [`BusBlocks`] and [`DeviceBlocks`] values joined by spaces; edges go from parent to child. Values are not padded or coloured and icon blocks are dropped.
+///
+/// ```
+/// use cyme::display::{write_dot, PrintSettings};
+///
+/// let spusb = cyme::profiler::read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let mut out = Vec::new();
+/// write_dot(&mut out, &spusb, &PrintSettings::default()).unwrap();
+/// let dot = String::from_utf8(out).unwrap();
+/// assert!(dot.starts_with("digraph cyme {"));
+/// // J-Link is on a hub on bus 2
+/// assert!(dot.contains("\"2-0\" -> \"2-2\";"));
+/// assert!(dot.contains("\"2-2\" -> \"2-2.1\";"));
+/// ```
+pub fn write_dot<W: Write>(
+    writer: &mut W,
+    sp_usb: &SystemProfile,
+    settings: &PrintSettings,
+) -> io::Result<()> {
+    let mut bb = settings_bus_blocks(settings);
+    bb.retain(|b| !b.is_icon());
+    let mut db = settings_device_blocks(settings);
+    db.retain(|b| !b.is_icon());
+
+    writeln!(writer, "digraph cyme {{")?;
+    writeln!(writer, "    node [shape=box];")?;
+    for (i, bus) in sp_usb.buses.iter().filter(|b| !b.is_hidden()).enumerate() {
+        let id = bus
+            .path()
+            .map_or_else(|| format!("bus{i}"), |p| p.to_string_lossy().to_string());
+        writeln!(
+            writer,
+            "    \"{}\" [label=\"{}\", shape=folder];",
+            dot_field(&id),
+            dot_field(&dot_label(bus, &bb, settings))
+        )?;
+        if let Some(d) = bus.devices.as_ref() {
+            write_dot_devices(writer, &id, d, &db, settings)?;
+        }
+    }
+    writeln!(writer, "}}")?;
+
+    Ok(())
+}
+
+/// Prints `profile` as a GraphViz DOT digraph to stdout; see [`write_dot`]
+pub fn print_dot(profile: &SystemProfile, settings: &PrintSettings) {
+    let mut stdout = io::stdout().lock();
+    if let Err(e) = write_dot(&mut stdout, profile, settings) {
+        log::error!("Failed to write DOT: {e}");
+    }
+}
+
+/// A [`crate::profiler::diff::FieldChange`] value without JSON quotes for strings; "-" if not present
+fn diff_value(value: &Option<serde_json::Value>) -> String {
+    match value {
+        Some(serde_json::Value::String(s)) => s.to_owned(),
+        Some(v) => v.to_string(),
+        None => String::from("-"),
+    }
+}
+
+/// Writes `diff` as a unified view: a line of [`PrintSettings`] device blocks per device prefixed '+' if added, '-' if removed or '~' if changed with a line per changed field below
+///
+/// ```
+/// use cyme::display::{write_diff, DeviceBlocks, PrintSettings};
+///
+/// let before = cyme::profiler::read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let mut after = cyme::profiler::read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// after.get_node_str_mut("2-2.1").unwrap().name = "J-Link Pro".into();
+/// let settings = PrintSettings {
+///     device_blocks: Some(vec![DeviceBlocks::Name]),
+///     no_padding: true,
+///     ..Default::default()
+/// };
+/// let mut out = Vec::new();
+/// write_diff(&mut out, &before.diff(&after), &settings).unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     "~ 2-2.1 J-Link Pro\n    name: J-Link -> J-Link Pro\n"
+/// );
+/// ```
+pub fn write_diff<W: Write>(
+    writer: &mut W,
+    diff: &ProfileDiff,
+    settings: &PrintSettings,
+) -> io::Result<()> {
+    let marker = |m: &str, colour: Color| -> String {
+        match settings.colours {
+            Some(_) => m.color(colour).bold().to_string(),
+            None => m.to_string(),
+        }
+    };
+
+    for device in diff.added.iter() {
+        writeln!(
+            writer,
+            "{} {} {}",
+            marker("+", Color::Green),
+            device.port_path(),
+            device_to_string(device, settings).trim_start()
+        )?;
+    }
+    for device in diff.removed.iter() {
+        writeln!(
+            writer,
+            "{} {} {}",
+            marker("-", Color::Red),
+            device.port_path(),
+            device_to_string(device, settings).trim_start()
+        )?;
+    }
+    for changed in diff.changed.iter() {
+        writeln!(
+            writer,
+            "{} {} {}",
+            marker("~", Color::Yellow),
+            changed.port_path,
+            device_to_string(&changed.device, settings).trim_start()
+        )?;
+        for field in changed.fields.iter() {
+            writeln!(
+                writer,
+                "    {}: {} -> {}",
+                field.field,
+                diff_value(&field.old),
+                diff_value(&field.new)
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints `diff` to stdout; see [`write_diff`]
+pub fn print_diff(diff: &ProfileDiff, settings: &PrintSettings) {
+    let mut stdout = io::stdout().lock();
+    if let Err(e) = write_diff(&mut stdout, diff, settings) {
+        log::error!("Failed to write diff: {e}");
+    }
+}
+
 /// Main cyme bin print function
 pub fn print(sp_usb: &SystemProfile, settings: &PrintSettings) {
     log::trace!("Printing with {settings:?}");
     let mut dw = DisplayWriter::default();
 
-    match settings.color_when {
-        ColorWhen::Always => colored::control::set_override(true),
-        ColorWhen::Never => colored::control::set_override(false),
-        ColorWhen::Auto => colored::control::unset_override(),
+    // CSV, Markdown and JSON Lines are always a flat list of devices
+    if settings.csv {
+        print_csv(&sp_usb.flattened_devices(), settings);
+        return;
+    } else if settings.markdown {
+        print_markdown(&sp_usb.flattened_devices(), settings);
+        return;
+    } else if settings.dot {
+        print_dot(sp_usb, settings);
+        return;
+    } else if settings.ndjson {
+        for device in sp_usb.flattened_devices().iter().filter(|d| !d.is_hidden()) {
+            if let Err(e) = dw.print_ndjson_event(device) {
+                log::error!("Failed to print JSON line for {device}: {e}");
+            }
+        }
+        return;
     }
 
+    settings.color_when.set_override();
+
     if settings.tree || settings.group_devices == Group::Bus {
-        if settings.json {
+        if settings.json && settings.json_resolved {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&sp_usb.resolved_json()).unwrap()
+            );
+        } else if settings.json {
             println!("{}", serde_json::to_string_pretty(&sp_usb).unwrap());
         } else {
             dw.print_sp_usb(sp_usb, settings);
@@ -3514,10 +6644,253 @@ pub fn print(sp_usb: &SystemProfile, settings: &PrintSettings) {
         // get a list of all devices
         let devs = sp_usb.flattened_devices();
 
-        if settings.json {
+        if settings.json && settings.json_resolved {
+            let resolved: Vec<_> = devs.iter().map(|d| d.resolved_json()).collect();
+            println!("{}", serde_json::to_string_pretty(&resolved).unwrap());
+        } else if settings.json {
             println!("{}", serde_json::to_string_pretty(&devs).unwrap());
+        } else if settings.group_devices == Group::Vendor {
+            dw.print_vendor_grouped_devices(&devs, settings);
         } else {
             dw.print_flattened_devices(&devs, settings);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiler::DeviceLocation;
+
+    fn device(port: &[u8], last_event: Option<DeviceEvent>) -> Device {
+        Device {
+            location_id: DeviceLocation {
+                bus: 1,
+                tree_positions: port.to_vec(),
+                number: port.iter().sum(),
+            },
+            last_event,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hide_stale_devices() {
+        let now = chrono::Local::now();
+        let old = DeviceEvent::Connected(now - chrono::Duration::hours(1));
+        let recent = DeviceEvent::Disconnected(now);
+        let cutoff = now - chrono::Duration::minutes(1);
+
+        let mut hub = device(&[1], Some(old));
+        hub.devices = Some(vec![
+            device(&[1, 1], Some(recent)),
+            device(&[1, 2], Some(old)),
+        ]);
+        let mut devices = vec![hub, device(&[2], Some(old)), device(&[3], None)];
+        assert!(hide_stale_devices(&mut devices, cutoff, true));
+
+        // parent of a recent device is kept visible
+        assert!(!devices[0].is_hidden());
+        let children = devices[0].devices.as_ref().unwrap();
+        assert!(!children[0].is_hidden());
+        assert!(children[1].is_hidden());
+        assert!(devices[1].is_hidden());
+        assert!(devices[2].is_hidden());
+
+        // without reset hidden devices stay hidden even with an earlier cutoff
+        let earlier = now - chrono::Duration::days(1);
+        hide_stale_devices(&mut devices, earlier, false);
+        assert!(devices[1].is_hidden());
+        // reset unhides devices with an event since the cutoff
+        assert!(hide_stale_devices(&mut devices, earlier, true));
+        assert!(!devices[1].is_hidden());
+        assert!(devices[0]
+            .devices
+            .as_ref()
+            .unwrap()
+            .iter()
+            .all(|d| !d.is_hidden()));
+        assert!(devices[2].is_hidden());
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let spusb =
+            crate::profiler::read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        let mut device = spusb.get_node_by_str("1-2").unwrap().to_owned();
+        device.name = "Mouse, \"Virtual\"\nUSB".to_string();
+        let mut settings = PrintSettings {
+            device_blocks: Some(vec![DeviceBlocks::VidPid, DeviceBlocks::Name]),
+            interface_blocks: Some(vec![InterfaceBlocks::Number]),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        write_csv(&mut out, &[&device], &settings).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "VID:PID,Name\n203a:fffc,\"Mouse, \"\"Virtual\"\"\nUSB\"\n"
+        );
+
+        // one row per interface with -vv
+        settings.verbosity = 2;
+        let mut out = Vec::new();
+        write_csv(&mut out, &[&device], &settings).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            concat!(
+                "VID:PID,Name,#\n",
+                "203a:fffc,\"Mouse, \"\"Virtual\"\"\nUSB\",0\n",
+                "203a:fffc,\"Mouse, \"\"Virtual\"\"\nUSB\",1\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_markdown_field() {
+        assert_eq!(markdown_field("a|b"), "a\\|b");
+        assert_eq!(markdown_field("a\r\nb\nc\rd"), "a b c d");
+        assert_eq!(markdown_field("plain"), "plain");
+    }
+
+    #[test]
+    fn test_sort_devices_fields() {
+        let mut devices: Vec<Device> = [
+            (4, Some(0x2), Some(0x10), "beta"),
+            (1, None, None, "Alpha"),
+            (3, Some(0x1), Some(0x20), "alpha"),
+            (2, Some(0x2), Some(0x10), "Beta"),
+        ]
+        .into_iter()
+        .map(|(port, vendor_id, product_id, name)| Device {
+            vendor_id,
+            product_id,
+            name: name.to_string(),
+            ..device(&[port], None)
+        })
+        .collect();
+        let numbers = |devices: &[&Device]| -> Vec<u8> {
+            devices.iter().map(|d| d.location_id.number).collect()
+        };
+
+        // no ID sorts last and equal values by device number
+        Sort::VendorId.sort_devices(&mut devices);
+        assert_eq!(numbers(&devices.iter().collect::<Vec<_>>()), [3, 2, 4, 1]);
+        Sort::ProductId.sort_devices(&mut devices);
+        assert_eq!(numbers(&devices.iter().collect::<Vec<_>>()), [2, 4, 3, 1]);
+        // name ignores case
+        Sort::Name.sort_devices(&mut devices);
+        assert_eq!(numbers(&devices.iter().collect::<Vec<_>>()), [1, 3, 2, 4]);
+
+        let mut refs: Vec<&Device> = devices.iter().rev().collect();
+        Sort::VendorId.sort_devices_ref(&mut refs);
+        assert_eq!(numbers(&refs), [3, 2, 4, 1]);
+        Sort::ProductId.sort_devices_ref(&mut refs);
+        assert_eq!(numbers(&refs), [2, 4, 3, 1]);
+        Sort::Name.sort_devices_ref(&mut refs);
+        assert_eq!(numbers(&refs), [1, 3, 2, 4]);
+    }
+
+    /// Bus 1 with devices 1-1 (with 1-1.1 and 1-1.2 attached) and 1-2, and bus 2 with 2-1
+    fn tree() -> SystemProfile {
+        let mut hub = device(&[1], None);
+        hub.devices = Some(vec![device(&[1, 1], None), device(&[1, 2], None)]);
+        SystemProfile {
+            buses: vec![
+                Bus {
+                    usb_bus_number: Some(1),
+                    devices: Some(vec![hub, device(&[2], None)]),
+                    ..Default::default()
+                },
+                Bus {
+                    usb_bus_number: Some(2),
+                    devices: Some(vec![Device {
+                        location_id: DeviceLocation {
+                            bus: 2,
+                            tree_positions: vec![1],
+                            number: 1,
+                        },
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    /// Port paths of the buses and devices at each level of the tree in order
+    fn tree_order(sp_usb: &SystemProfile) -> Vec<String> {
+        fn walk(devices: &[Device], order: &mut Vec<String>) {
+            for d in devices {
+                order.push(d.port_path().to_string());
+                walk(d.devices.as_deref().unwrap_or_default(), order);
+            }
+        }
+        let mut order = Vec::new();
+        for bus in sp_usb.buses.iter() {
+            order.push(format!("bus {}", bus.usb_bus_number.unwrap_or_default()));
+            walk(bus.devices.as_deref().unwrap_or_default(), &mut order);
+        }
+        order
+    }
+
+    #[test]
+    fn test_reverse_buses() {
+        let mut sp_usb = tree();
+        Sort::reverse_buses(&mut sp_usb.buses);
+        assert_eq!(
+            tree_order(&sp_usb),
+            vec!["bus 2", "2-1", "bus 1", "1-2", "1-1", "1-1.2", "1-1.1"]
+        );
+    }
+
+    #[test]
+    fn test_prepare_reverse_no_sort_once() {
+        let mut sp_usb = tree();
+        // swap so parse order differs from sorted order
+        sp_usb.buses[0].devices.as_mut().unwrap().swap(0, 1);
+        let settings = PrintSettings {
+            print_mode: PrintMode::Dynamic,
+            tree: true,
+            sort_devices: Sort::NoSort,
+            sort_reverse: true,
+            ..Default::default()
+        };
+        let expected = vec!["bus 2", "2-1", "bus 1", "1-1", "1-1.2", "1-1.1", "1-2"];
+        // watch mode prepares the same profile on every redraw
+        for _ in 0..3 {
+            prepare(&mut sp_usb, None, &settings);
+            assert_eq!(tree_order(&sp_usb), expected);
+        }
+
+        // sorted devices are reversed after sorting each time
+        let settings = PrintSettings {
+            sort_devices: Sort::BranchPosition,
+            ..settings
+        };
+        for _ in 0..2 {
+            prepare(&mut sp_usb, None, &settings);
+            assert_eq!(
+                tree_order(&sp_usb),
+                vec!["bus 2", "2-1", "bus 1", "1-2", "1-1", "1-1.2", "1-1.1"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_prepare_since_overflow() {
+        let mut sp_usb = SystemProfile {
+            buses: vec![Bus {
+                devices: Some(vec![device(&[1], None)]),
+                ..Default::default()
+            }],
+        };
+        let settings = PrintSettings {
+            print_mode: PrintMode::Dynamic,
+            since: Some(std::time::Duration::from_secs(u64::MAX)),
+            ..Default::default()
+        };
+        prepare(&mut sp_usb, None, &settings);
+        assert!(!sp_usb.flattened_devices()[0].is_hidden());
+    }
+}
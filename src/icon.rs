@@ -23,6 +23,8 @@ fn sort_alphabetically<T: Serialize, S: serde::Serializer>(
 }
 
 /// Icon type enum is used as key in `HashMaps`
+///
+/// As a [`IconTheme`] key the enum is kebab-case with base16 values after '#' separated by ':', for example "vid-pid#1d50:6018". Class icons can be matched with increasing specificity: "classifier#03" for all HID, "classifier-sub#03:01" for HID boot interface and "classifier-sub-protocol#03:01:01" for HID boot keyboard. Trailing values of a class triplet can be '*' wildcards, so "classifier-sub-protocol#03:01:*" is the same as "classifier-sub#03:01". The most specific match wins
 #[derive(Debug, Clone, Hash, PartialEq, Eq, SerializeDisplay, DeserializeFromStr)]
 pub enum Icon {
     /// Vendor ID lookup
@@ -33,6 +35,8 @@ pub enum Icon {
     VidPidMsb((u16, u8)),
     /// Class classifier icon
     Classifier(BaseClass),
+    /// Class classifier lookup with SubClass, matches any Protocol
+    ClassifierSub((BaseClass, u8)),
     /// Class classifier lookup with SubClass and Protocol
     ClassifierSubProtocol((BaseClass, u8, u8)),
     /// Pattern match device name icon
@@ -120,6 +124,35 @@ impl FromStr for Icon {
             ))
         // enum contains value
         } else {
+            // trailing wildcard values of a class triplet are the less specific classifier
+            if matches!(enum_name, "classifier-sub-protocol" | "classifier-sub") {
+                let values: Vec<&str> = value_split[1].split(':').collect();
+                let specific = values.iter().take_while(|v| **v != "*").count();
+                if values[specific..].iter().any(|v| *v != "*") {
+                    return Err(Error::new(
+                        ErrorKind::Parsing,
+                        &format!("Only trailing values can be '*' wildcards in {s}"),
+                    ));
+                }
+                if specific < values.len() {
+                    let less_specific = match specific {
+                        1 => "classifier",
+                        2 => "classifier-sub",
+                        _ => {
+                            return Err(Error::new(
+                                ErrorKind::Parsing,
+                                &format!("Class cannot be a '*' wildcard in {s}"),
+                            ))
+                        }
+                    };
+                    return Icon::from_str(&format!(
+                        "{}#{}",
+                        less_specific,
+                        values[..specific].join(":")
+                    ));
+                }
+            }
+
             let (parse_ints, errors): (Vec<Result<u32, _>>, Vec<_>) = value_split[1]
                 .split(':')
                 .map(|vs| u32::from_str_radix(vs.trim_start_matches("0x"), 16))
@@ -150,6 +183,13 @@ impl FromStr for Icon {
                     Some(i) => Ok(Icon::Classifier(BaseClass::from(*i as u8))),
                     None => Err(Error::new(ErrorKind::Parsing, "No value for enum after $")),
                 },
+                "classifier-sub" => match numbers.get(0..2) {
+                    Some(slice) => Ok(Icon::ClassifierSub((
+                        BaseClass::from(slice[0] as u8),
+                        slice[1] as u8,
+                    ))),
+                    None => Err(Error::new(ErrorKind::Parsing, "No value for enum after $")),
+                },
                 "classifier-sub-protocol" => match numbers.get(0..3) {
                     Some(slice) => Ok(Icon::ClassifierSubProtocol((
                         BaseClass::from(slice[0] as u8),
@@ -186,6 +226,12 @@ impl fmt::Display for Icon {
             Icon::VidPid((v, p)) => write!(f, "vid-pid#{v:04x}:{p:04x}"),
             Icon::VidPidMsb((v, p)) => write!(f, "vid-pid-msb#{v:04x}:{p:02x}"),
             Icon::Classifier(c) => write!(f, "classifier#{:02x}", u8::from(c.to_owned())),
+            Icon::ClassifierSub(c) => write!(
+                f,
+                "classifier-sub#{:02x}:{:02x}",
+                u8::from(c.0.to_owned()),
+                c.1
+            ),
             Icon::ClassifierSubProtocol(c) => write!(
                 f,
                 "classifier-sub-protocol#{:02x}:{:02x}:{:02x}",
@@ -204,6 +250,23 @@ impl fmt::Display for Icon {
     }
 }
 
+/// Most specific class icon in `icons` for the class triplet: `ClassifierSubProtocol` -> `ClassifierSub` -> `Classifier`
+fn get_classifier<'a, V>(
+    icons: &'a HashMap<Icon, V>,
+    class: &BaseClass,
+    sub: u8,
+    protocol: u8,
+) -> Option<&'a V> {
+    icons
+        .get(&Icon::ClassifierSubProtocol((
+            class.to_owned(),
+            sub,
+            protocol,
+        )))
+        .or_else(|| icons.get(&Icon::ClassifierSub((class.to_owned(), sub))))
+        .or_else(|| icons.get(&Icon::Classifier(class.to_owned())))
+}
+
 /// Allows user supplied icons to replace or add to [`static@DEFAULT_ICONS`] and [`static@DEFAULT_UTF8_TREE`]
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "kebab-case")]
@@ -262,6 +325,95 @@ pub static DEFAULT_ASCII_TREE: LazyLock<HashMap<Icon, &'static str>> = LazyLock:
     ])
 });
 
+/// Characters used to draw the tree branches: [`Icon::TreeEdge`], [`Icon::TreeCorner`], [`Icon::TreeLine`] and [`Icon::TreeBlank`]
+///
+/// Set with [`crate::display::PrintSettings`] `tree_glyphs` to override the defaults for the [`Encoding`] and user [`IconTheme`] `tree`. Overrides not valid for the encoding are ignored.
+///
+/// ```
+/// use cyme::display::Encoding;
+/// use cyme::icon::{Icon, TreeGlyphs};
+///
+/// let glyphs = TreeGlyphs {
+///     branch: '+',
+///     ..TreeGlyphs::new(&Encoding::Ascii)
+/// };
+/// assert_eq!(glyphs.tree_icon(&Icon::TreeEdge, &Encoding::Ascii), Some("+__".into()));
+/// assert_eq!(glyphs.tree_icon(&Icon::TreeBusStart, &Encoding::Ascii), None);
+/// // not valid for ascii
+/// let glyphs = TreeGlyphs::new(&Encoding::Utf8);
+/// assert_eq!(glyphs.tree_icon(&Icon::TreeCorner, &Encoding::Ascii), None);
+/// ```
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub struct TreeGlyphs {
+    /// Branch to an item with more items below it, '├' in utf-8
+    pub branch: char,
+    /// Branch to the last item, '└' in utf-8
+    pub last_branch: char,
+    /// Line continuing past items of a parent, '│' in utf-8
+    pub vertical: char,
+    /// Line from a branch to the item, '─' in utf-8
+    pub horizontal: char,
+}
+
+impl TreeGlyphs {
+    /// The default glyphs for `encoding`, matching [`static@DEFAULT_UTF8_TREE`] and [`static@DEFAULT_ASCII_TREE`]
+    pub fn new(encoding: &Encoding) -> Self {
+        match encoding {
+            Encoding::Utf8 | Encoding::Glyphs => TreeGlyphs {
+                branch: '\u{251c}',
+                last_branch: '\u{2514}',
+                vertical: '\u{2502}',
+                horizontal: '\u{2500}',
+            },
+            Encoding::Ascii => TreeGlyphs {
+                branch: '|',
+                last_branch: '|',
+                vertical: '|',
+                horizontal: '_',
+            },
+        }
+    }
+
+    /// Whether all the glyphs are valid for `encoding` with [`Encoding::char_is_valid`]
+    pub fn is_valid(&self, encoding: &Encoding) -> bool {
+        [
+            self.branch,
+            self.last_branch,
+            self.vertical,
+            self.horizontal,
+        ]
+        .iter()
+        .all(|c| encoding.char_is_valid(*c))
+    }
+
+    /// Tree drawing string for `icon` if it is a branch [`Icon`] and the glyphs are valid for `encoding`
+    pub fn tree_icon(&self, icon: &Icon, encoding: &Encoding) -> Option<String> {
+        if !self.is_valid(encoding) {
+            return None;
+        }
+        match icon {
+            Icon::TreeEdge => Some(format!(
+                "{}{}{}",
+                self.branch, self.horizontal, self.horizontal
+            )),
+            Icon::TreeCorner => Some(format!(
+                "{}{}{}",
+                self.last_branch, self.horizontal, self.horizontal
+            )),
+            Icon::TreeLine => Some(format!("{}  ", self.vertical)),
+            Icon::TreeBlank => Some("   ".into()),
+            _ => None,
+        }
+    }
+}
+
+impl Default for TreeGlyphs {
+    fn default() -> Self {
+        TreeGlyphs::new(&Encoding::default())
+    }
+}
+
 /// Default icon lookup can be overridden by user icons with IconTheme `icons`
 ///
 /// Should probably keep fairly short but I've added things I use like debuggers, mcus as examples
@@ -449,43 +601,22 @@ impl IconTheme {
         }
     }
 
-    /// Drill through `DEFAULT_ICONS` first looking for `ClassifierSubProtocol` -> `Classifier` -> `UndefinedClassifier` -> ""
+    /// Drill through `DEFAULT_ICONS` first looking for `ClassifierSubProtocol` -> `ClassifierSub` -> `Classifier` -> `UndefinedClassifier` -> ""
     pub fn get_default_classifier_icon(class: &BaseClass, sub: u8, protocol: u8) -> String {
-        // try vid pid first
-        DEFAULT_ICONS
-            .get(&Icon::ClassifierSubProtocol((
-                class.to_owned(),
-                sub,
-                protocol,
-            )))
-            .unwrap_or(
-                DEFAULT_ICONS
-                    .get(&Icon::Classifier(class.to_owned()))
-                    .unwrap_or(DEFAULT_ICONS.get(&Icon::UndefinedClassifier).unwrap_or(&"")),
-            )
+        get_classifier(&DEFAULT_ICONS, class, sub, protocol)
+            .unwrap_or(DEFAULT_ICONS.get(&Icon::UndefinedClassifier).unwrap_or(&""))
             .to_string()
     }
 
-    /// Drill through `Self` icons first looking for `ClassifierSubProtocol` -> `Classifier` -> `UndefinedClassifier` -> get_default_classifier_icon
+    /// Drill through `Self` icons first looking for `ClassifierSubProtocol` -> `ClassifierSub` -> `Classifier` -> get_default_classifier_icon
     pub fn get_classifier_icon(&self, class: &BaseClass, sub: u8, protocol: u8) -> String {
-        if let Some(user_icons) = self.user.as_ref() {
-            user_icons
-                .get(&Icon::ClassifierSubProtocol((
-                    class.to_owned(),
-                    sub,
-                    protocol,
-                )))
-                .unwrap_or(
-                    user_icons
-                        .get(&Icon::Classifier(class.to_owned()))
-                        .unwrap_or(&IconTheme::get_default_classifier_icon(
-                            class, sub, protocol,
-                        )),
-                )
-                .to_owned()
-        } else {
-            IconTheme::get_default_classifier_icon(class, sub, protocol)
-        }
+        self.user
+            .as_ref()
+            .and_then(|user_icons| get_classifier(user_icons, class, sub, protocol))
+            .map_or_else(
+                || IconTheme::get_default_classifier_icon(class, sub, protocol),
+                |s| s.to_owned(),
+            )
     }
 
     /// Get default icon for device based on descriptor name pattern `[Icon::Name]` pattern match
@@ -682,6 +813,28 @@ mod tests {
         assert_eq!(item_ser, r#"["classifier-sub-protocol#03:01:0a","K"]"#);
     }
 
+    #[test]
+    fn test_classifier_icon_precedence() {
+        let theme = IconTheme {
+            user: Some(HashMap::from([
+                (Icon::Classifier(BaseClass::Hid), "H".into()),
+                (Icon::ClassifierSub((BaseClass::Hid, 0x01)), "B".into()),
+                (
+                    Icon::ClassifierSubProtocol((BaseClass::Hid, 0x01, 0x01)),
+                    "K".into(),
+                ),
+            ])),
+            ..Default::default()
+        };
+        assert_eq!(theme.get_classifier_icon(&BaseClass::Hid, 0x01, 0x01), "K");
+        assert_eq!(theme.get_classifier_icon(&BaseClass::Hid, 0x01, 0x02), "B");
+        assert_eq!(theme.get_classifier_icon(&BaseClass::Hid, 0x00, 0x00), "H");
+        assert_eq!(
+            theme.get_classifier_icon(&BaseClass::Hub, 0x00, 0x00),
+            IconTheme::get_default_classifier_icon(&BaseClass::Hub, 0x00, 0x00)
+        );
+    }
+
     #[test]
     fn icon_from_str() {
         let str = "vid#1d50";
@@ -703,6 +856,21 @@ mod tests {
             Icon::ClassifierSubProtocol((BaseClass::Hid, 1, 10))
         );
 
+        let str = "classifier-sub#03:01";
+        let icon = Icon::from_str(str);
+        assert_eq!(icon.unwrap(), Icon::ClassifierSub((BaseClass::Hid, 1)));
+
+        let str = "classifier-sub-protocol#03:01:*";
+        let icon = Icon::from_str(str);
+        assert_eq!(icon.unwrap(), Icon::ClassifierSub((BaseClass::Hid, 1)));
+
+        let str = "classifier-sub-protocol#03:*:*";
+        let icon = Icon::from_str(str);
+        assert_eq!(icon.unwrap(), Icon::Classifier(BaseClass::Hid));
+
+        assert!(Icon::from_str("classifier-sub-protocol#03:*:01").is_err());
+        assert!(Icon::from_str("classifier-sub-protocol#*:*:*").is_err());
+
         let str = "endpoint_in";
         let icon = Icon::from_str(str);
         assert_eq!(icon.unwrap(), Icon::Endpoint(Direction::In));
@@ -4,6 +4,7 @@
 use crate::display::PrintSettings;
 use crate::error::{Error, ErrorKind};
 use crate::profiler::{Device, SystemProfile};
+use std::collections::HashMap;
 use std::path::Path;
 use uuid::Uuid;
 
@@ -16,6 +17,7 @@ use crate::usb::*;
 mod audio_dumps;
 mod bos_dumps;
 pub mod names;
+pub(crate) mod parse;
 mod video_dumps;
 
 use audio_dumps::*;
@@ -620,6 +622,44 @@ fn dump_config(config: &Configuration, indent: usize) {
     }
 }
 
+/// The AudioControl interface descriptors in the interface `extra` descriptors, parsing any generic ones
+fn audio_control_descriptors(extra: &[Descriptor]) -> Vec<audio::UacInterfaceDescriptor> {
+    extra
+        .iter()
+        .filter_map(|dt| match dt {
+            Descriptor::Device(cd) | Descriptor::Interface(cd) => match cd {
+                ClassDescriptor::Audio(uacd, _) => Some(uacd.to_owned()),
+                ClassDescriptor::Generic(Some((BaseClass::Audio, s, p)), gd) if *s != 3 => {
+                    audio::UacDescriptor::try_from((gd.to_owned(), *s, *p)).ok()
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|uacd| matches!(uacd.descriptor_subtype, audio::UacType::Control(_)))
+        .map(|uacd| uacd.interface)
+        .collect()
+}
+
+/// The MIDIStreaming interface descriptors in the interface `extra` descriptors, parsing any generic ones
+fn midi_streaming_descriptors(extra: &[Descriptor]) -> Vec<audio::MidiInterfaceDescriptor> {
+    extra
+        .iter()
+        .filter_map(|dt| match dt {
+            Descriptor::Device(cd) | Descriptor::Interface(cd) => match cd {
+                ClassDescriptor::Midi(md, _) => Some(md.interface.to_owned()),
+                ClassDescriptor::Generic(Some((BaseClass::Audio, 3, _)), gd) => {
+                    audio::MidiDescriptor::try_from(gd.to_owned())
+                        .ok()
+                        .map(|md| md.interface)
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
 /// Dump a [`InterfaceAssociation`] in style of lsusb --verbose
 fn dump_interface(interface: &Interface, indent: usize) {
     let interface_name = names::class(interface.class.into());
@@ -682,6 +722,8 @@ fn dump_interface(interface: &Interface, indent: usize) {
 
     // dump extra descriptors
     if let Some(dt_vec) = &interface.extra {
+        let entities = audio::resolve_entities(&audio_control_descriptors(dt_vec));
+        let jacks = audio::resolve_midi_jacks(&midi_streaming_descriptors(dt_vec));
         for dt in dt_vec {
             match dt {
                 // Should only be Device or Interface as we mask out the rest
@@ -691,10 +733,12 @@ fn dump_interface(interface: &Interface, indent: usize) {
                     ClassDescriptor::Printer(pd) => dump_printer_desc(pd, indent + 4),
                     ClassDescriptor::Communication(cd) => dump_comm_descriptor(cd, indent + 4),
                     ClassDescriptor::Dfu(dfud) => dump_dfu_interface(dfud, indent + 4),
-                    ClassDescriptor::Midi(md, _) => dump_midistreaming_interface(md, indent + 4),
+                    ClassDescriptor::Midi(md, _) => {
+                        dump_midistreaming_interface(md, &jacks, indent + 4)
+                    }
                     ClassDescriptor::Audio(uacd, uacp) => match &uacd.descriptor_subtype {
                         audio::UacType::Control(cs) => {
-                            dump_audiocontrol_interface(uacd, cs, uacp, indent + 2)
+                            dump_audiocontrol_interface(uacd, cs, uacp, &entities, indent + 2)
                         }
                         audio::UacType::Streaming(ss) => {
                             dump_audiostreaming_interface(uacd, ss, uacp, indent + 2)
@@ -712,7 +756,7 @@ fn dump_interface(interface: &Interface, indent: usize) {
                     ClassDescriptor::Generic(cc, gd) => match cc {
                         Some((BaseClass::Audio, 3, _)) => {
                             if let Ok(md) = audio::MidiDescriptor::try_from(gd.to_owned()) {
-                                dump_midistreaming_interface(&md, indent + 4);
+                                dump_midistreaming_interface(&md, &jacks, indent + 4);
                             }
                         }
                         Some((BaseClass::Audio, s, p)) => {
@@ -721,9 +765,13 @@ fn dump_interface(interface: &Interface, indent: usize) {
                             {
                                 let uacp = audio::UacProtocol::from(*p);
                                 match &uacd.descriptor_subtype {
-                                    audio::UacType::Control(cs) => {
-                                        dump_audiocontrol_interface(&uacd, cs, &uacp, indent + 2)
-                                    }
+                                    audio::UacType::Control(cs) => dump_audiocontrol_interface(
+                                        &uacd,
+                                        cs,
+                                        &uacp,
+                                        &entities,
+                                        indent + 2,
+                                    ),
                                     audio::UacType::Streaming(ss) => {
                                         dump_audiostreaming_interface(&uacd, ss, &uacp, indent + 2)
                                     }
@@ -914,15 +962,13 @@ fn dump_endpoint(endpoint: &Endpoint, indent: usize) {
                         indent = indent + 2
                     );
                     match endpoint.transfer_type {
-                        TransferType::Bulk => {
-                            if ss.attributes & 0x1f != 0 {
-                                println!(
-                                    "{:indent$}MaxStreams {:>13}",
-                                    "",
-                                    1 << ss.attributes,
-                                    indent = indent + 2
-                                );
-                            }
+                        TransferType::Bulk if ss.attributes & 0x1f != 0 => {
+                            println!(
+                                "{:indent$}MaxStreams {:>13}",
+                                "",
+                                1 << ss.attributes,
+                                indent = indent + 2
+                            );
                         }
                         TransferType::Isochronous | TransferType::Interrupt => {
                             if ss.attributes & 0x03 != 0 {
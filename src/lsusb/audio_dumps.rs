@@ -63,6 +63,39 @@ const UAC3_PROCESSING_UNIT_STEREO_EXTENDER_BMCONTROLS: [&str; 3] =
     ["Width", "Underflow", "Overflow"];
 const UAC3_PROCESSING_UNIT_MULTI_FUNC_BMCONTROLS: [&str; 2] = ["Underflow", "Overflow"];
 
+/// Dump an Entity id like lsusb followed by the name of the Entity it references in `entities` if known
+fn dump_entity_id(
+    id: u8,
+    field_name: &str,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
+    match entities.get(&id) {
+        Some(name) => dump_value_string(id, field_name, name, indent, width),
+        None => dump_value(id, field_name, indent, width),
+    }
+}
+
+/// Dump an array of Entity ids like [`dump_array`] with [`dump_entity_id`]
+fn dump_entity_ids(
+    ids: &[u8],
+    field_name: &str,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
+    for (i, id) in ids.iter().enumerate() {
+        dump_entity_id(
+            *id,
+            &format!("{field_name}({i:2})"),
+            entities,
+            indent,
+            width,
+        );
+    }
+}
+
 fn dump_bitmap_controls<T: Into<u32>>(
     controls: T,
     control_descriptions: &[&'static str],
@@ -109,10 +142,21 @@ fn dump_bitmap_controls_array<T: Into<u32> + std::fmt::Display + Copy>(
     }
 }
 
-fn dump_audio_mixer_unit1(mixer_unit: &audio::MixerUnit1, indent: usize, width: usize) {
+fn dump_audio_mixer_unit1(
+    mixer_unit: &audio::MixerUnit1,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(mixer_unit.unit_id, "bUnitID", indent, width);
     dump_value(mixer_unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&mixer_unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(
+        &mixer_unit.source_ids,
+        "baSourceID",
+        entities,
+        indent,
+        width,
+    );
     dump_value(mixer_unit.nr_channels, "bNrChannels", indent, width);
     dump_hex(mixer_unit.channel_config, "wChannelConfig", indent, width);
     let channel_names = audio::UacInterfaceDescriptor::get_channel_name_strings(
@@ -127,10 +171,21 @@ fn dump_audio_mixer_unit1(mixer_unit: &audio::MixerUnit1, indent: usize, width:
     dump_value(mixer_unit.mixer, "iMixer", indent, width);
 }
 
-fn dump_audio_mixer_unit2(mixer_unit: &audio::MixerUnit2, indent: usize, width: usize) {
+fn dump_audio_mixer_unit2(
+    mixer_unit: &audio::MixerUnit2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(mixer_unit.unit_id, "bUnitID", indent, width);
     dump_value(mixer_unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&mixer_unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(
+        &mixer_unit.source_ids,
+        "baSourceID",
+        entities,
+        indent,
+        width,
+    );
     dump_value(mixer_unit.nr_channels, "bNrChannels", indent, width);
     dump_hex(mixer_unit.channel_config, "bmChannelConfig", indent, width);
     let channel_names = audio::UacInterfaceDescriptor::get_channel_name_strings(
@@ -152,10 +207,21 @@ fn dump_audio_mixer_unit2(mixer_unit: &audio::MixerUnit2, indent: usize, width:
     dump_value(mixer_unit.mixer, "iMixer", indent, width);
 }
 
-fn dump_audio_mixer_unit3(mixer_unit: &audio::MixerUnit3, indent: usize, width: usize) {
+fn dump_audio_mixer_unit3(
+    mixer_unit: &audio::MixerUnit3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(mixer_unit.unit_id, "bUnitID", indent, width);
     dump_value(mixer_unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&mixer_unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(
+        &mixer_unit.source_ids,
+        "baSourceID",
+        entities,
+        indent,
+        width,
+    );
     dump_value(
         mixer_unit.cluster_descr_id,
         "wClusterDescrID",
@@ -173,7 +239,12 @@ fn dump_audio_mixer_unit3(mixer_unit: &audio::MixerUnit3, indent: usize, width:
     dump_value(mixer_unit.mixer_descr_str, "wMixerDescrStr", indent, width);
 }
 
-fn dump_audio_power_domain(power_domain: &audio::PowerDomain, indent: usize, width: usize) {
+fn dump_audio_power_domain(
+    power_domain: &audio::PowerDomain,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(
         power_domain.power_domain_id,
         "bPowerDomainID",
@@ -193,7 +264,13 @@ fn dump_audio_power_domain(power_domain: &audio::PowerDomain, indent: usize, wid
         width,
     );
     dump_value(power_domain.nr_entities, "bNrEntities", indent, width);
-    dump_array(&power_domain.entity_ids, "baEntityID", indent, width);
+    dump_entity_ids(
+        &power_domain.entity_ids,
+        "baEntityID",
+        entities,
+        indent,
+        width,
+    );
     dump_value(
         power_domain.domain_descr_str,
         "wPDomainDescrStr",
@@ -204,12 +281,19 @@ fn dump_audio_power_domain(power_domain: &audio::PowerDomain, indent: usize, wid
 
 pub(crate) fn dump_audio_selector_unit1(
     selector_unit: &audio::SelectorUnit1,
+    entities: &HashMap<u8, String>,
     indent: usize,
     width: usize,
 ) {
     dump_value(selector_unit.unit_id, "bUnitID", indent, width);
     dump_value(selector_unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&selector_unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(
+        &selector_unit.source_ids,
+        "baSourceID",
+        entities,
+        indent,
+        width,
+    );
     dump_value_string(
         selector_unit.selector_index,
         "iSelector",
@@ -219,10 +303,21 @@ pub(crate) fn dump_audio_selector_unit1(
     );
 }
 
-fn dump_audio_selector_unit2(selector_unit: &audio::SelectorUnit2, indent: usize, width: usize) {
+fn dump_audio_selector_unit2(
+    selector_unit: &audio::SelectorUnit2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(selector_unit.unit_id, "bUnitID", indent, width);
     dump_value(selector_unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&selector_unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(
+        &selector_unit.source_ids,
+        "baSourceID",
+        entities,
+        indent,
+        width,
+    );
     dump_hex(selector_unit.controls, "bmControls", indent, width);
     dump_bitmap_controls(
         selector_unit.controls,
@@ -239,10 +334,21 @@ fn dump_audio_selector_unit2(selector_unit: &audio::SelectorUnit2, indent: usize
     );
 }
 
-fn dump_audio_selector_unit3(selector_unit: &audio::SelectorUnit3, indent: usize, width: usize) {
+fn dump_audio_selector_unit3(
+    selector_unit: &audio::SelectorUnit3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(selector_unit.unit_id, "bUnitID", indent, width);
     dump_value(selector_unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&selector_unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(
+        &selector_unit.source_ids,
+        "baSourceID",
+        entities,
+        indent,
+        width,
+    );
     dump_hex(selector_unit.controls, "bmControls", indent, width);
     dump_bitmap_controls(
         selector_unit.controls,
@@ -259,7 +365,12 @@ fn dump_audio_selector_unit3(selector_unit: &audio::SelectorUnit3, indent: usize
 }
 
 /// Dumps the contents of a UAC1 Processing Unit Descriptor
-fn dump_audio_processing_unit1(unit: &audio::ProcessingUnit1, indent: usize, width: usize) {
+fn dump_audio_processing_unit1(
+    unit: &audio::ProcessingUnit1,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
     dump_value_string(
         unit.process_type,
@@ -269,7 +380,7 @@ fn dump_audio_processing_unit1(unit: &audio::ProcessingUnit1, indent: usize, wid
         width,
     );
     dump_value(unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(&unit.source_ids, "baSourceID", entities, indent, width);
     dump_value(unit.nr_channels, "bNrChannels", indent, width);
     dump_hex(unit.channel_config, "wChannelConfig", indent, width);
     let channel_names = audio::UacInterfaceDescriptor::get_channel_name_strings(
@@ -295,14 +406,23 @@ fn dump_audio_processing_unit1(unit: &audio::ProcessingUnit1, indent: usize, wid
         indent,
         width,
     );
-    if let Some(ref specific) = unit.specific {
-        dump_value(specific.nr_modes, "bNrModes", indent, width);
-        dump_bitmap_array(&specific.modes, "waModes", indent, width);
+    match unit.specific {
+        Some(audio::AudioProcessingUnit1Specific::UpDownMix(ref specific))
+        | Some(audio::AudioProcessingUnit1Specific::DolbyPrologic(ref specific)) => {
+            dump_value(specific.nr_modes, "bNrModes", indent, width);
+            dump_bitmap_array(&specific.modes, "waModes", indent, width);
+        }
+        _ => (),
     }
 }
 
 /// Dumps the contents of a UAC2 Processing Unit Descriptor
-fn dump_audio_processing_unit2(unit: &audio::ProcessingUnit2, indent: usize, width: usize) {
+fn dump_audio_processing_unit2(
+    unit: &audio::ProcessingUnit2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
     dump_value_string(
         unit.process_type,
@@ -312,7 +432,7 @@ fn dump_audio_processing_unit2(unit: &audio::ProcessingUnit2, indent: usize, wid
         width,
     );
     dump_value(unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(&unit.source_ids, "baSourceID", entities, indent, width);
     dump_value(unit.nr_channels, "bNrChannels", indent, width);
     dump_hex(unit.channel_config, "bmChannelConfig", indent, width);
     let channel_names = audio::UacInterfaceDescriptor::get_channel_name_strings(
@@ -352,7 +472,12 @@ fn dump_audio_processing_unit2(unit: &audio::ProcessingUnit2, indent: usize, wid
 }
 
 /// Dumps the contents of a UAC3 Processing Unit Descriptor
-fn dump_audio_processing_unit3(unit: &audio::ProcessingUnit3, indent: usize, width: usize) {
+fn dump_audio_processing_unit3(
+    unit: &audio::ProcessingUnit3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
     dump_value_string(
         unit.process_type,
@@ -362,7 +487,7 @@ fn dump_audio_processing_unit3(unit: &audio::ProcessingUnit3, indent: usize, wid
         width,
     );
     dump_value(unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(&unit.source_ids, "baSourceID", entities, indent, width);
     dump_value(
         unit.processing_descr_str,
         "wProcessingDescrStr",
@@ -422,10 +547,15 @@ fn dump_audio_processing_unit3(unit: &audio::ProcessingUnit3, indent: usize, wid
 }
 
 /// Dumps the contents of a UAC2 Effect Unit Descriptor
-fn dump_audio_effect_unit2(unit: &audio::EffectUnit2, indent: usize, width: usize) {
+fn dump_audio_effect_unit2(
+    unit: &audio::EffectUnit2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
     dump_value(unit.effect_type, "wEffectType", indent, width);
-    dump_value(unit.source_id, "bSourceID", indent, width);
+    dump_entity_id(unit.source_id, "bSourceID", entities, indent, width);
     dump_bitmap_array(&unit.controls, "bmaControls", indent, width);
     dump_value(unit.effect_index, "iEffects", indent, width);
     dump_value_string(
@@ -438,18 +568,28 @@ fn dump_audio_effect_unit2(unit: &audio::EffectUnit2, indent: usize, width: usiz
 }
 
 /// Dumps the contents of a UAC3 Effect Unit Descriptor
-fn dump_audio_effect_unit3(unit: &audio::EffectUnit3, indent: usize, width: usize) {
+fn dump_audio_effect_unit3(
+    unit: &audio::EffectUnit3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
     dump_value(unit.effect_type, "wEffectType", indent, width);
-    dump_value(unit.source_id, "bSourceID", indent, width);
+    dump_entity_id(unit.source_id, "bSourceID", entities, indent, width);
     dump_bitmap_array(&unit.controls, "bmaControls", indent, width);
     dump_value(unit.effect_descr_str, "wEffectsDescrStr", indent, width);
 }
 
 /// Dumps the contents of a UAC1 Feature Unit Descriptor
-fn dump_audio_feature_unit1(unit: &audio::FeatureUnit1, indent: usize, width: usize) {
+fn dump_audio_feature_unit1(
+    unit: &audio::FeatureUnit1,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
-    dump_value(unit.source_id, "bSourceID", indent, width);
+    dump_entity_id(unit.source_id, "bSourceID", entities, indent, width);
     dump_value(unit.control_size, "bControlSize", indent, width);
     dump_bitmap_controls_array(
         "bmaControls",
@@ -469,12 +609,17 @@ fn dump_audio_feature_unit1(unit: &audio::FeatureUnit1, indent: usize, width: us
 }
 
 /// Dumps the contents of a UAC2 Feature Unit Descriptor
-fn dump_audio_feature_unit2(unit: &audio::FeatureUnit2, indent: usize, width: usize) {
+fn dump_audio_feature_unit2(
+    unit: &audio::FeatureUnit2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
-    dump_value(unit.source_id, "bSourceID", indent, width);
+    dump_entity_id(unit.source_id, "bSourceID", entities, indent, width);
     dump_bitmap_controls_array(
         "bmaControls",
-        &unit.controls,
+        &unit.channel_controls(),
         &UAC1_FEATURE_UNIT_BMCONTROLS,
         &audio::ControlType::BmControl1,
         indent,
@@ -490,12 +635,17 @@ fn dump_audio_feature_unit2(unit: &audio::FeatureUnit2, indent: usize, width: us
 }
 
 /// Dumps the contents of a UAC3 Feature Unit Descriptor
-fn dump_audio_feature_unit3(unit: &audio::FeatureUnit3, indent: usize, width: usize) {
+fn dump_audio_feature_unit3(
+    unit: &audio::FeatureUnit3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
-    dump_value(unit.source_id, "bSourceID", indent, width);
+    dump_entity_id(unit.source_id, "bSourceID", entities, indent, width);
     dump_bitmap_controls_array(
         "bmaControls",
-        &unit.controls,
+        &unit.channel_controls(),
         &UAC1_FEATURE_UNIT_BMCONTROLS,
         &audio::ControlType::BmControl1,
         indent,
@@ -505,11 +655,16 @@ fn dump_audio_feature_unit3(unit: &audio::FeatureUnit3, indent: usize, width: us
 }
 
 /// Dumps the contents of a UAC1 Extension Unit Descriptor
-fn dump_audio_extension_unit1(unit: &audio::ExtensionUnit1, indent: usize, width: usize) {
+fn dump_audio_extension_unit1(
+    unit: &audio::ExtensionUnit1,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
     dump_value(unit.extension_code, "wExtensionCode", indent, width);
     dump_value(unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(&unit.source_ids, "baSourceID", entities, indent, width);
     dump_value(unit.nr_channels, "bNrChannels", indent, width);
     dump_hex(unit.channel_config, "wChannelConfig", indent, width);
     let channel_names = audio::UacInterfaceDescriptor::get_channel_name_strings(
@@ -539,11 +694,16 @@ fn dump_audio_extension_unit1(unit: &audio::ExtensionUnit1, indent: usize, width
 }
 
 /// Dumps the contents of a UAC2 Extension Unit Descriptor
-fn dump_audio_extension_unit2(unit: &audio::ExtensionUnit2, indent: usize, width: usize) {
+fn dump_audio_extension_unit2(
+    unit: &audio::ExtensionUnit2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
     dump_value(unit.extension_code, "wExtensionCode", indent, width);
     dump_value(unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(&unit.source_ids, "baSourceID", entities, indent, width);
     dump_value(unit.nr_channels, "bNrChannels", indent, width);
     dump_hex(unit.channel_config, "bmChannelConfig", indent, width);
     let channel_names = audio::UacInterfaceDescriptor::get_channel_name_strings(
@@ -577,11 +737,16 @@ fn dump_audio_extension_unit2(unit: &audio::ExtensionUnit2, indent: usize, width
 }
 
 /// Dumps the contents of a UAC3 Extension Unit Descriptor
-fn dump_audio_extension_unit3(unit: &audio::ExtensionUnit3, indent: usize, width: usize) {
+fn dump_audio_extension_unit3(
+    unit: &audio::ExtensionUnit3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(unit.unit_id, "bUnitID", indent, width);
     dump_value(unit.extension_code, "wExtensionCode", indent, width);
     dump_value(unit.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&unit.source_ids, "baSourceID", indent, width);
+    dump_entity_ids(&unit.source_ids, "baSourceID", entities, indent, width);
     dump_value(
         unit.extension_descr_str,
         "wExtensionDescrStr",
@@ -662,10 +827,21 @@ fn dump_audio_clock_source3(source: &audio::ClockSource3, indent: usize, width:
 }
 
 /// Dumps the contents of a UAC2 Clock Selector Descriptor
-fn dump_audio_clock_selector2(selector: &audio::ClockSelector2, indent: usize, width: usize) {
+fn dump_audio_clock_selector2(
+    selector: &audio::ClockSelector2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(selector.clock_id, "bClockID", indent, width);
     dump_value(selector.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&selector.csource_ids, "baCSourceID", indent, width);
+    dump_entity_ids(
+        &selector.csource_ids,
+        "baCSourceID",
+        entities,
+        indent,
+        width,
+    );
     dump_hex(selector.controls, "bmControls", indent, width);
     dump_bitmap_controls(
         selector.controls,
@@ -683,10 +859,21 @@ fn dump_audio_clock_selector2(selector: &audio::ClockSelector2, indent: usize, w
 }
 
 /// Dumps the contents of a UAC3 Clock Selector Descriptor
-fn dump_audio_clock_selector3(selector: &audio::ClockSelector3, indent: usize, width: usize) {
+fn dump_audio_clock_selector3(
+    selector: &audio::ClockSelector3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(selector.clock_id, "bClockID", indent, width);
     dump_value(selector.nr_in_pins, "bNrInPins", indent, width);
-    dump_array(&selector.csource_ids, "baCSourceID", indent, width);
+    dump_entity_ids(
+        &selector.csource_ids,
+        "baCSourceID",
+        entities,
+        indent,
+        width,
+    );
     dump_hex(selector.controls, "bmControls", indent, width);
     dump_bitmap_controls(
         selector.controls,
@@ -703,9 +890,14 @@ fn dump_audio_clock_selector3(selector: &audio::ClockSelector3, indent: usize, w
 }
 
 /// Dumps the contents of a UAC2 Clock Multiplier Descriptor
-fn dump_audio_clock_multiplier2(multiplier: &audio::ClockMultiplier2, indent: usize, width: usize) {
+fn dump_audio_clock_multiplier2(
+    multiplier: &audio::ClockMultiplier2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(multiplier.clock_id, "bClockID", indent, width);
-    dump_value(multiplier.csource_id, "bCSourceID", indent, width);
+    dump_entity_id(multiplier.csource_id, "bCSourceID", entities, indent, width);
     dump_hex(multiplier.controls, "bmControls", indent, width);
     dump_bitmap_controls(
         multiplier.controls,
@@ -723,9 +915,14 @@ fn dump_audio_clock_multiplier2(multiplier: &audio::ClockMultiplier2, indent: us
 }
 
 /// Dumps the contents of a UAC3 Clock Multiplier Descriptor
-fn dump_audio_clock_multiplier3(multiplier: &audio::ClockMultiplier3, indent: usize, width: usize) {
+fn dump_audio_clock_multiplier3(
+    multiplier: &audio::ClockMultiplier3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(multiplier.clock_id, "bClockID", indent, width);
-    dump_value(multiplier.csource_id, "bCSourceID", indent, width);
+    dump_entity_id(multiplier.csource_id, "bCSourceID", entities, indent, width);
     dump_hex(multiplier.controls, "bmControls", indent, width);
     dump_bitmap_controls(
         multiplier.controls,
@@ -743,13 +940,26 @@ fn dump_audio_clock_multiplier3(multiplier: &audio::ClockMultiplier3, indent: us
 
 fn dump_audio_sample_rate_converter2(
     converter: &audio::SampleRateConverter2,
+    entities: &HashMap<u8, String>,
     indent: usize,
     width: usize,
 ) {
     dump_value(converter.unit_id, "bUnitID", indent, width);
-    dump_value(converter.source_id, "bSourceID", indent, width);
-    dump_value(converter.csource_in_id, "bCSourceInID", indent, width);
-    dump_value(converter.csource_out_id, "bCSourceOutID", indent, width);
+    dump_entity_id(converter.source_id, "bSourceID", entities, indent, width);
+    dump_entity_id(
+        converter.csource_in_id,
+        "bCSourceInID",
+        entities,
+        indent,
+        width,
+    );
+    dump_entity_id(
+        converter.csource_out_id,
+        "bCSourceOutID",
+        entities,
+        indent,
+        width,
+    );
     dump_value_string(
         converter.src_index,
         "iSRC",
@@ -761,13 +971,26 @@ fn dump_audio_sample_rate_converter2(
 
 fn dump_audio_sample_rate_converter3(
     converter: &audio::SampleRateConverter3,
+    entities: &HashMap<u8, String>,
     indent: usize,
     width: usize,
 ) {
     dump_value(converter.unit_id, "bUnitID", indent, width);
-    dump_value(converter.source_id, "bSourceID", indent, width);
-    dump_value(converter.csource_in_id, "bCSourceInID", indent, width);
-    dump_value(converter.csource_out_id, "bCSourceOutID", indent, width);
+    dump_entity_id(converter.source_id, "bSourceID", entities, indent, width);
+    dump_entity_id(
+        converter.csource_in_id,
+        "bCSourceInID",
+        entities,
+        indent,
+        width,
+    );
+    dump_entity_id(
+        converter.csource_out_id,
+        "bCSourceOutID",
+        entities,
+        indent,
+        width,
+    );
     dump_value(converter.src_descr_str, "wSRCDescrStr", indent, width);
 }
 
@@ -837,7 +1060,12 @@ fn dump_audio_input_terminal1(ait: &audio::InputTerminal1, indent: usize, width:
     );
 }
 
-fn dump_audio_input_terminal2(ait: &audio::InputTerminal2, indent: usize, width: usize) {
+fn dump_audio_input_terminal2(
+    ait: &audio::InputTerminal2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(ait.terminal_id, "bTerminalID", indent, width);
     dump_name_hex(
         ait.terminal_type,
@@ -847,7 +1075,7 @@ fn dump_audio_input_terminal2(ait: &audio::InputTerminal2, indent: usize, width:
         width,
     );
     dump_value(ait.assoc_terminal, "bAssocTerminal", indent, width);
-    dump_value(ait.csource_id, "bCSourceID", indent, width);
+    dump_entity_id(ait.csource_id, "bCSourceID", entities, indent, width);
     dump_value(ait.nr_channels, "bNrChannels", indent, width);
     dump_hex(ait.channel_config, "bmChannelConfig", indent, width);
     let channel_names = audio::UacInterfaceDescriptor::get_channel_name_strings(
@@ -880,7 +1108,12 @@ fn dump_audio_input_terminal2(ait: &audio::InputTerminal2, indent: usize, width:
     );
 }
 
-fn dump_audio_input_terminal3(ait: &audio::InputTerminal3, indent: usize, width: usize) {
+fn dump_audio_input_terminal3(
+    ait: &audio::InputTerminal3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(ait.terminal_id, "bTerminalID", indent, width);
     dump_name_hex(
         ait.terminal_type,
@@ -890,7 +1123,7 @@ fn dump_audio_input_terminal3(ait: &audio::InputTerminal3, indent: usize, width:
         width,
     );
     dump_value(ait.assoc_terminal, "bAssocTerminal", indent, width);
-    dump_value(ait.csource_id, "bCSourceID", indent, width);
+    dump_entity_id(ait.csource_id, "bCSourceID", entities, indent, width);
     dump_hex(ait.controls, "bmControls", indent, width);
     dump_bitmap_controls(
         ait.controls,
@@ -909,7 +1142,12 @@ fn dump_audio_input_terminal3(ait: &audio::InputTerminal3, indent: usize, width:
     dump_value(ait.terminal_descr_str, "wTerminalDescrStr", indent, width);
 }
 
-pub(crate) fn dump_audio_output_terminal1(a: &audio::OutputTerminal1, indent: usize, width: usize) {
+pub(crate) fn dump_audio_output_terminal1(
+    a: &audio::OutputTerminal1,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(a.terminal_id, "bTerminalID", indent, width);
     dump_name_hex(
         a.terminal_type,
@@ -919,7 +1157,7 @@ pub(crate) fn dump_audio_output_terminal1(a: &audio::OutputTerminal1, indent: us
         width,
     );
     dump_value(a.assoc_terminal, "bAssocTerminal", indent, width);
-    dump_value(a.source_id, "bSourceID", indent, width);
+    dump_entity_id(a.source_id, "bSourceID", entities, indent, width);
     dump_value_string(
         a.terminal_index,
         "iTerminal",
@@ -929,7 +1167,12 @@ pub(crate) fn dump_audio_output_terminal1(a: &audio::OutputTerminal1, indent: us
     );
 }
 
-fn dump_audio_output_terminal2(a: &audio::OutputTerminal2, indent: usize, width: usize) {
+fn dump_audio_output_terminal2(
+    a: &audio::OutputTerminal2,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(a.terminal_id, "bTerminalID", indent, width);
     dump_name_hex(
         a.terminal_type,
@@ -939,8 +1182,8 @@ fn dump_audio_output_terminal2(a: &audio::OutputTerminal2, indent: usize, width:
         width,
     );
     dump_value(a.assoc_terminal, "bAssocTerminal", indent, width);
-    dump_value(a.source_id, "bSourceID", indent, width);
-    dump_value(a.c_source_id, "bCSourceID", indent, width);
+    dump_entity_id(a.source_id, "bSourceID", entities, indent, width);
+    dump_entity_id(a.c_source_id, "bCSourceID", entities, indent, width);
     dump_hex(a.controls, "bmControls", indent, width);
     dump_bitmap_controls(
         a.controls,
@@ -957,7 +1200,12 @@ fn dump_audio_output_terminal2(a: &audio::OutputTerminal2, indent: usize, width:
     );
 }
 
-fn dump_audio_output_terminal3(a: &audio::OutputTerminal3, indent: usize, width: usize) {
+fn dump_audio_output_terminal3(
+    a: &audio::OutputTerminal3,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+    width: usize,
+) {
     dump_value(a.terminal_id, "bTerminalID", indent, width);
     dump_name_hex(
         a.terminal_type,
@@ -967,7 +1215,7 @@ fn dump_audio_output_terminal3(a: &audio::OutputTerminal3, indent: usize, width:
         width,
     );
     dump_value(a.assoc_terminal, "bAssocTerminal", indent, width);
-    dump_value(a.c_source_id, "bCSourceID", indent, width);
+    dump_entity_id(a.c_source_id, "bCSourceID", entities, indent, width);
     dump_hex(a.controls, "bmControls", indent, width);
     dump_bitmap_controls(
         a.controls,
@@ -983,6 +1231,12 @@ fn dump_audio_output_terminal3(a: &audio::OutputTerminal3, indent: usize, width:
 fn dump_extended_terminal_header(d: &audio::ExtendedTerminalHeader, indent: usize, width: usize) {
     dump_value(d.descriptor_id, "wDescriptorID", indent, width);
     dump_value(d.nr_channels, "bNrChannels", indent, width);
+    for (i, channel) in d.channels.iter().enumerate() {
+        println!("{:indent$}Channel {}:", "", i, indent = indent);
+        dump_value(channel.purpose, "bChPurpose", indent + 2, width);
+        dump_value(channel.relationship, "bChRelationship", indent + 2, width);
+        dump_value(channel.label_str, "wChLabelStr", indent + 2, width);
+    }
 }
 
 fn dump_audio_streaming_interface1(asi: &audio::StreamingInterface1, indent: usize, width: usize) {
@@ -1001,6 +1255,10 @@ fn dump_audio_streaming_interface2(asi: &audio::StreamingInterface2, indent: usi
         indent + 2,
     );
     dump_value(asi.format_type, "bFormatType", indent, width);
+    dump_hex(asi.formats, "bmFormats", indent, width);
+    for name in asi.format_names() {
+        println!("{:indent$}{}", "", name, indent = indent + 2);
+    }
     dump_value(asi.nr_channels, "bNrChannels", indent, width);
     dump_hex(asi.channel_config, "bmChannelConfig", indent, width);
     let channel_names = audio::UacInterfaceDescriptor::get_channel_name_strings(
@@ -1030,6 +1288,9 @@ fn dump_audio_streaming_interface3(asi: &audio::StreamingInterface3, indent: usi
     );
     dump_value(asi.cluster_descr_id, "wClusterDescrID", indent, width);
     dump_hex(asi.formats, "bmFormats", indent, width);
+    for name in asi.format_names() {
+        println!("{:indent$}{}", "", name, indent = indent + 2);
+    }
     dump_value(asi.sub_slot_size, "bSubslotSize", indent, width);
     dump_value(asi.bit_resolution, "bBitResolution", indent, width);
     dump_hex(asi.aux_protocols, "bmAuxProtocols", indent, width);
@@ -1120,6 +1381,8 @@ fn dump_audio_streaming_format(af: &audio::StreamingFormat, indent: usize, width
         audio::StreamingFormatInterface::FormatTypeIII2(asi) => {
             dump_format_type_iii_uac2(asi, indent, width);
         }
+        // no fields after bFormatType
+        audio::StreamingFormatInterface::FormatTypeIV2(_) => (),
         i => {
             let data: Vec<u8> = i.to_owned().into();
             println!(
@@ -1165,7 +1428,11 @@ fn dump_audio_streaming_format_specific(
     }
 }
 
-fn dump_audio_subtype(uacid: &audio::UacInterfaceDescriptor, indent: usize) {
+fn dump_audio_subtype(
+    uacid: &audio::UacInterfaceDescriptor,
+    entities: &HashMap<u8, String>,
+    indent: usize,
+) {
     match uacid {
         audio::UacInterfaceDescriptor::Header1(a) => {
             dump_audio_header1(a, indent, LSUSB_DUMP_WIDTH);
@@ -1180,76 +1447,76 @@ fn dump_audio_subtype(uacid: &audio::UacInterfaceDescriptor, indent: usize) {
             dump_audio_input_terminal1(ait, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::InputTerminal2(ait) => {
-            dump_audio_input_terminal2(ait, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_input_terminal2(ait, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::InputTerminal3(ait) => {
-            dump_audio_input_terminal3(ait, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_input_terminal3(ait, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::OutputTerminal1(a) => {
-            dump_audio_output_terminal1(a, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_output_terminal1(a, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::OutputTerminal2(a) => {
-            dump_audio_output_terminal2(a, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_output_terminal2(a, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::OutputTerminal3(a) => {
-            dump_audio_output_terminal3(a, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_output_terminal3(a, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ExtendedTerminalHeader(d) => {
             dump_extended_terminal_header(d, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::PowerDomain(power_domain) => {
-            dump_audio_power_domain(power_domain, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_power_domain(power_domain, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::MixerUnit1(mixer_unit) => {
-            dump_audio_mixer_unit1(mixer_unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_mixer_unit1(mixer_unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::MixerUnit2(mixer_unit) => {
-            dump_audio_mixer_unit2(mixer_unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_mixer_unit2(mixer_unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::MixerUnit3(mixer_unit) => {
-            dump_audio_mixer_unit3(mixer_unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_mixer_unit3(mixer_unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::SelectorUnit1(selector_unit) => {
-            dump_audio_selector_unit1(selector_unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_selector_unit1(selector_unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::SelectorUnit2(selector_unit) => {
-            dump_audio_selector_unit2(selector_unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_selector_unit2(selector_unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::SelectorUnit3(selector_unit) => {
-            dump_audio_selector_unit3(selector_unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_selector_unit3(selector_unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ProcessingUnit1(unit) => {
-            dump_audio_processing_unit1(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_processing_unit1(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ProcessingUnit2(unit) => {
-            dump_audio_processing_unit2(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_processing_unit2(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ProcessingUnit3(unit) => {
-            dump_audio_processing_unit3(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_processing_unit3(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::EffectUnit2(unit) => {
-            dump_audio_effect_unit2(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_effect_unit2(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::EffectUnit3(unit) => {
-            dump_audio_effect_unit3(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_effect_unit3(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::FeatureUnit1(unit) => {
-            dump_audio_feature_unit1(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_feature_unit1(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::FeatureUnit2(unit) => {
-            dump_audio_feature_unit2(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_feature_unit2(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::FeatureUnit3(unit) => {
-            dump_audio_feature_unit3(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_feature_unit3(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ExtensionUnit1(unit) => {
-            dump_audio_extension_unit1(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_extension_unit1(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ExtensionUnit2(unit) => {
-            dump_audio_extension_unit2(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_extension_unit2(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ExtensionUnit3(unit) => {
-            dump_audio_extension_unit3(unit, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_extension_unit3(unit, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ClockSource2(source) => {
             dump_audio_clock_source2(source, indent, LSUSB_DUMP_WIDTH);
@@ -1258,22 +1525,22 @@ fn dump_audio_subtype(uacid: &audio::UacInterfaceDescriptor, indent: usize) {
             dump_audio_clock_source3(source, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ClockSelector2(selector) => {
-            dump_audio_clock_selector2(selector, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_clock_selector2(selector, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ClockSelector3(selector) => {
-            dump_audio_clock_selector3(selector, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_clock_selector3(selector, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ClockMultiplier2(multiplier) => {
-            dump_audio_clock_multiplier2(multiplier, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_clock_multiplier2(multiplier, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::ClockMultiplier3(multiplier) => {
-            dump_audio_clock_multiplier3(multiplier, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_clock_multiplier3(multiplier, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::SampleRateConverter2(converter) => {
-            dump_audio_sample_rate_converter2(converter, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_sample_rate_converter2(converter, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::SampleRateConverter3(converter) => {
-            dump_audio_sample_rate_converter3(converter, indent, LSUSB_DUMP_WIDTH);
+            dump_audio_sample_rate_converter3(converter, entities, indent, LSUSB_DUMP_WIDTH);
         }
         audio::UacInterfaceDescriptor::StreamingInterface1(asi) => {
             dump_audio_streaming_interface1(asi, indent, LSUSB_DUMP_WIDTH);
@@ -1316,10 +1583,14 @@ fn dump_audio_subtype(uacid: &audio::UacInterfaceDescriptor, indent: usize) {
     }
 }
 
+/// Dumps an AudioControl interface descriptor like lsusb with the source, clock source and Power Domain Entity ids followed by the name of the Entity in `entities`
+///
+/// `entities` is usually from [`audio::resolve_entities`] of the interface this descriptor is part of
 pub(crate) fn dump_audiocontrol_interface(
     uacd: &audio::UacDescriptor,
     uaci: &audio::ControlSubtype,
     protocol: &audio::UacProtocol,
+    entities: &HashMap<u8, String>,
     indent: usize,
 ) {
     dump_string("AudioControl Interface Descriptor", indent);
@@ -1348,7 +1619,7 @@ pub(crate) fn dump_audiocontrol_interface(
                 indent = indent
             );
         }
-        uacid => dump_audio_subtype(uacid, indent + 2),
+        uacid => dump_audio_subtype(uacid, entities, indent + 2),
     }
 }
 
@@ -1513,42 +1784,20 @@ fn dump_format_type_iii_uac2(ft: &audio::FormatTypeIII2, indent: usize, width: u
 
 fn dump_format_specific_mpeg(fs: &audio::FormatSpecificMpeg, indent: usize, width: usize) {
     dump_hex(fs.mpeg_capabilities, "bmMPEGCapabilities", indent, width);
-    dump_bitmap_strings(
-        fs.mpeg_capabilities as u8,
-        |b| match b {
-            0 => Some("Layer I"),
-            1 => Some("Layer II"),
-            2 => Some("Layer III"),
-            3 => Some("MPEG-1 only"),
-            4 => Some("MPEG-1 dual-channel"),
-            5 => Some("MPEG-2 second stereo"),
-            6 => Some("MPEG-2 7.1 channel augmentation"),
-            7 => Some("Adaptive multi-channel prediction"),
-            _ => None,
-        },
-        indent + 2,
-    );
+    for capability in fs.capabilities() {
+        println!("{:indent$}{}", "", capability, indent = indent + 2);
+    }
     println!(
         "{:indent$}MPEG-2 multilingual support: {}",
         "",
-        match (fs.mpeg_capabilities >> 8) & 3 {
-            0 => "Not supported",
-            1 => "Supported at Fs",
-            2 => "Reserved",
-            _ => "Supported at Fs and 1/2Fs",
-        },
+        fs.multilingual_support(),
         indent = indent + 2
     );
     dump_hex(fs.mpeg_features, "bmMPEGFeatures", indent, width);
     println!(
         "{:indent$}Internal Dynamic Range Control: {}",
         "",
-        match (fs.mpeg_features >> 4) & 3 {
-            0 => "not supported",
-            1 => "supported but not scalable",
-            2 => "scalable, common boost and cut scaling value",
-            _ => "scalable, separate boost and cut scaling value",
-        },
+        fs.dynamic_range_control(),
         indent = indent + 2
     );
 }
@@ -1556,26 +1805,13 @@ fn dump_format_specific_mpeg(fs: &audio::FormatSpecificMpeg, indent: usize, widt
 fn dump_format_specific_ac3(fs: &audio::FormatSpecificAc3, indent: usize, width: usize) {
     dump_hex(fs.bsid, "bmBSID", indent, width);
     dump_hex(fs.ac3_features, "bmAC3Features", indent, width);
-    dump_bitmap_strings(
-        fs.ac3_features,
-        |b| match b {
-            0 => Some("RF mode"),
-            1 => Some("Line mode"),
-            2 => Some("Custom0 mode"),
-            3 => Some("Custom1 mode"),
-            _ => None,
-        },
-        indent + 2,
-    );
+    for feature in fs.features() {
+        println!("{:indent$}{}", "", feature, indent = indent + 2);
+    }
     println!(
         "{:indent$}Internal Dynamic Range Control: {}",
         "",
-        match (fs.ac3_features >> 4) & 3 {
-            0 => "not supported",
-            1 => "supported but not scalable",
-            2 => "scalable, common boost and cut scaling value",
-            _ => "scalable, separate boost and cut scaling value",
-        },
+        fs.dynamic_range_control(),
         indent = indent + 2
     );
 }
@@ -1612,7 +1848,7 @@ pub(crate) fn dump_audiostreaming_interface(
                 indent = indent + 2
             );
         }
-        uacid => dump_audio_subtype(uacid, indent + 2),
+        uacid => dump_audio_subtype(uacid, &HashMap::new(), indent + 2),
     }
 }
 
@@ -1643,11 +1879,18 @@ pub(crate) fn dump_audiostreaming_endpoint(ad: &audio::UacDescriptor, indent: us
         ad.descriptor_subtype,
         audio::UacType::Streaming(audio::StreamingSubtype::General)
     ) {
-        dump_audio_subtype(&ad.interface, indent + 2);
+        dump_audio_subtype(&ad.interface, &HashMap::new(), indent + 2);
     }
 }
 
-pub(crate) fn dump_midistreaming_interface(md: &audio::MidiDescriptor, indent: usize) {
+/// Dumps a MIDIStreaming interface descriptor like lsusb with the baSourceID of each source followed by the name of the Jack or Element in `jacks`
+///
+/// `jacks` is usually from [`audio::resolve_midi_jacks`] of the interface this descriptor is part of
+pub(crate) fn dump_midistreaming_interface(
+    md: &audio::MidiDescriptor,
+    jacks: &HashMap<u8, String>,
+    indent: usize,
+) {
     let jack_types = |t: u8| match t {
         0x00 => "Undefined",
         0x01 => "Embedded",
@@ -1663,10 +1906,17 @@ pub(crate) fn dump_midistreaming_interface(md: &audio::MidiDescriptor, indent: u
         indent + 2,
         LSUSB_DUMP_WIDTH,
     );
+    let subtype_string = match &md.interface {
+        audio::MidiInterfaceDescriptor::GroupTerminalBlockHeader(_) => {
+            "GR_TRM_BLOCK_HEADER".to_string()
+        }
+        audio::MidiInterfaceDescriptor::GroupTerminalBlock(_) => "GR_TRM_BLOCK".to_string(),
+        _ => format!("{:#}", md.descriptor_subtype),
+    };
     dump_value_string(
         md.descriptor_subtype.to_owned() as u8,
         "bDescriptorSubtype",
-        format!("({:#})", md.descriptor_subtype),
+        format!("({subtype_string})"),
         indent + 2,
         LSUSB_DUMP_WIDTH,
     );
@@ -1710,9 +1960,10 @@ pub(crate) fn dump_midistreaming_interface(md: &audio::MidiDescriptor, indent: u
             );
 
             for (i, p) in d.source_ids.iter() {
-                dump_value(
-                    i,
+                dump_entity_id(
+                    *i,
                     &format!("baSourceID({i:2})"),
+                    jacks,
                     indent + 2,
                     LSUSB_DUMP_WIDTH,
                 );
@@ -1741,9 +1992,10 @@ pub(crate) fn dump_midistreaming_interface(md: &audio::MidiDescriptor, indent: u
             );
 
             for (i, p) in d.source_ids.iter() {
-                dump_value(
-                    i,
+                dump_entity_id(
+                    *i,
                     &format!("baSourceID({i:2})"),
+                    jacks,
                     indent + 2,
                     LSUSB_DUMP_WIDTH,
                 );
@@ -1808,6 +2060,47 @@ pub(crate) fn dump_midistreaming_interface(md: &audio::MidiDescriptor, indent: u
                 LSUSB_DUMP_WIDTH,
             );
         }
+        audio::MidiInterfaceDescriptor::GroupTerminalBlockHeader(d) => {
+            dump_hex(d.total_length, "wTotalLength", indent + 2, LSUSB_DUMP_WIDTH);
+        }
+        audio::MidiInterfaceDescriptor::GroupTerminalBlock(d) => {
+            dump_value(d.block_id, "bGrpTrmBlkID", indent + 2, LSUSB_DUMP_WIDTH);
+            dump_value_string(
+                d.block_type,
+                "bGrpTrmBlkType",
+                d.block_type_name(),
+                indent + 2,
+                LSUSB_DUMP_WIDTH,
+            );
+            dump_value(d.first_group, "nGroupTrm", indent + 2, LSUSB_DUMP_WIDTH);
+            dump_value(d.num_groups, "nNumGroupTrm", indent + 2, LSUSB_DUMP_WIDTH);
+            dump_value_string(
+                d.block_string_index,
+                "iBlockItem",
+                d.block_string.as_ref().unwrap_or(&String::new()),
+                indent + 2,
+                LSUSB_DUMP_WIDTH,
+            );
+            dump_value_string(
+                d.midi_protocol,
+                "bMIDIProtocol",
+                d.midi_protocol_name(),
+                indent + 2,
+                LSUSB_DUMP_WIDTH,
+            );
+            dump_value(
+                d.max_input_bandwidth,
+                "wMaxInputBandwidth",
+                indent + 2,
+                LSUSB_DUMP_WIDTH,
+            );
+            dump_value(
+                d.max_output_bandwidth,
+                "wMaxOutputBandwidth",
+                indent + 2,
+                LSUSB_DUMP_WIDTH,
+            );
+        }
         audio::MidiInterfaceDescriptor::Undefined(_) => (),
         invalid => {
             let data: Vec<u8> = invalid.to_owned().into();
@@ -0,0 +1,497 @@
+//! Parse `lsusb -v` text output into a partial [`SystemProfile`]
+//!
+//! Only the device, configuration, interface and endpoint descriptor fields that lsusb prints are populated; class specific and other descriptors are skipped. lsusb does not print the port a device is attached to so all devices are placed directly on their [`Bus`], using the device number as the port. Root hubs (device 1 with hub class) are kept as the bus root like the other profilers.
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::profiler::{Bus, Device, DeviceLocation, DeviceSpeed, SystemProfile};
+use crate::types::NumericalUnit;
+use crate::usb::*;
+
+/// Descriptor the lines at the next indent belong to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Device,
+    Configuration,
+    Interface,
+    Endpoint,
+    /// Any descriptor not parsed, such as class specific ones
+    Other,
+}
+
+/// Parse the text output of `lsusb -v` into a [`SystemProfile`]
+pub(crate) fn parse_verbose(text: &str) -> Result<SystemProfile> {
+    let mut devices: Vec<Device> = Vec::new();
+    let mut section = (Section::Other, 0);
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        // 'Bus 001 Device 002: ID 203a:fffc PARALLELS Virtual Mouse' starts each device
+        if indent == 0 {
+            if let Some(device) = trimmed.strip_prefix("Bus ").and_then(parse_bus_line) {
+                devices.push(device);
+                section = (Section::Other, 0);
+                continue;
+            }
+        }
+
+        let Some(device) = devices.last_mut() else {
+            continue;
+        };
+
+        if let Some(speed) = trimmed.strip_prefix("Negotiated speed:") {
+            if let Some(speed) = parse_negotiated_speed(speed) {
+                device.device_speed = Some(DeviceSpeed::SpeedValue(speed.clone()));
+                if let Some(extra) = device.extra.as_mut() {
+                    extra.negotiated_speed = Some(speed);
+                }
+            }
+            continue;
+        }
+
+        if let Some(status) = trimmed.strip_prefix("Device Status:") {
+            if let Some(extra) = device.extra.as_mut() {
+                extra.status = parse_number(status).and_then(|v| u16::try_from(v).ok());
+            }
+            continue;
+        }
+
+        if let Some(header) = trimmed.strip_suffix(':') {
+            let kind = match header {
+                "Device Descriptor" => Section::Device,
+                "Configuration Descriptor" => device
+                    .extra
+                    .as_mut()
+                    .map(|e| {
+                        e.configurations.push(new_configuration());
+                        Section::Configuration
+                    })
+                    .unwrap_or(Section::Other),
+                "Interface Descriptor" => last_configuration(device)
+                    .map(|c| {
+                        c.interfaces.push(new_interface());
+                        Section::Interface
+                    })
+                    .unwrap_or(Section::Other),
+                "Endpoint Descriptor" => last_interface(device)
+                    .map(|i| {
+                        i.endpoints.push(new_endpoint());
+                        Section::Endpoint
+                    })
+                    .unwrap_or(Section::Other),
+                _ => Section::Other,
+            };
+            section = (kind, indent);
+            continue;
+        }
+
+        // only direct fields of the descriptor, deeper lines are decoded values
+        if indent != section.1 + 2 {
+            continue;
+        }
+        let (key, value) = match trimmed.split_once(char::is_whitespace) {
+            Some((k, v)) => (k, v.trim()),
+            None => (trimmed, ""),
+        };
+
+        match section.0 {
+            Section::Device => apply_device_field(device, key, value),
+            Section::Configuration => {
+                if let Some(config) = last_configuration(device) {
+                    apply_configuration_field(config, key, value);
+                }
+            }
+            Section::Interface => {
+                if let Some(interface) = last_interface(device) {
+                    apply_interface_field(interface, key, value);
+                }
+            }
+            Section::Endpoint => {
+                if let Some(endpoint) = last_interface(device).and_then(|i| i.endpoints.last_mut())
+                {
+                    apply_endpoint_field(endpoint, key, value);
+                }
+            }
+            Section::Other => (),
+        }
+    }
+
+    if devices.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "No devices found in lsusb verbose text",
+        ));
+    }
+
+    let mut buses: BTreeMap<u8, Vec<Device>> = BTreeMap::new();
+    for mut device in devices {
+        if !(device.location_id.number == 1 && device.class == Some(BaseClass::Hub)) {
+            device.location_id.tree_positions = vec![device.location_id.number];
+        }
+        update_paths(&mut device);
+        buses
+            .entry(device.location_id.bus)
+            .or_default()
+            .push(device);
+    }
+
+    let buses = buses
+        .into_iter()
+        .map(|(number, mut devices)| {
+            devices.sort_by_key(|d| d.location_id.number);
+            let mut bus = Bus::from(number);
+            if let Some(root_hub) = devices.iter().find(|d| d.is_root_hub()) {
+                bus.name = root_hub.name.to_owned();
+                bus.host_controller = root_hub.manufacturer.to_owned().unwrap_or_default();
+            }
+            bus.devices = Some(devices);
+            bus
+        })
+        .collect();
+
+    Ok(SystemProfile { buses })
+}
+
+/// Parse the line after 'Bus ': '001 Device 002: ID 203a:fffc PARALLELS Virtual Mouse'
+fn parse_bus_line(line: &str) -> Option<Device> {
+    let (bus, rest) = line.split_once(" Device ")?;
+    let (number, rest) = rest.split_once(':')?;
+    let rest = rest.trim().strip_prefix("ID ")?;
+    let (id, name) = rest.split_once(' ').unwrap_or((rest, ""));
+    let (vid, pid) = id.split_once(':')?;
+
+    Some(Device {
+        name: name.trim().to_string(),
+        vendor_id: u16::from_str_radix(vid, 16).ok(),
+        product_id: u16::from_str_radix(pid, 16).ok(),
+        location_id: DeviceLocation {
+            bus: bus.trim().parse().ok()?,
+            tree_positions: Vec::new(),
+            number: number.trim().parse().ok()?,
+        },
+        extra: Some(DeviceExtra {
+            max_packet_size: 0,
+            driver: None,
+            syspath: None,
+            vendor: None,
+            product_name: None,
+            string_indexes: (0, 0, 0),
+            configurations: Vec::new(),
+            status: None,
+            debug: None,
+            binary_object_store: None,
+            qualifier: None,
+            hub: None,
+            negotiated_speed: None,
+            power_state: None,
+        }),
+        ..Default::default()
+    })
+}
+
+/// Parse 'High Speed (480Mbps)' into the [`Speed`] within the brackets
+fn parse_negotiated_speed(value: &str) -> Option<Speed> {
+    let rate = value.split_once('(')?.1.trim_end_matches(')');
+    let speed = match rate.strip_suffix("Gbps") {
+        Some(gbps) => Speed::from_str(&(gbps.parse::<f32>().ok()? * 1000.0).to_string()),
+        None => Speed::from_str(rate.strip_suffix("Mbps")?),
+    }
+    .ok()?;
+
+    (speed != Speed::Unknown).then_some(speed)
+}
+
+/// Parse the first word of `value` as a decimal or '0x' prefixed hex number
+fn parse_number(value: &str) -> Option<u32> {
+    let number = value.split_whitespace().next()?;
+    match number.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => number.parse().ok(),
+    }
+}
+
+fn parse_u8(value: &str) -> Option<u8> {
+    parse_number(value).and_then(|v| u8::try_from(v).ok())
+}
+
+fn parse_u16(value: &str) -> Option<u16> {
+    parse_number(value).and_then(|v| u16::try_from(v).ok())
+}
+
+/// The string following the number in `value` such as the string descriptor after its index, None if there is none
+fn trailing_string(value: &str) -> Option<String> {
+    value
+        .split_once(char::is_whitespace)
+        .map(|(_, s)| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn apply_device_field(device: &mut Device, key: &str, value: &str) {
+    match key {
+        "bcdUSB" => device.bcd_usb = Version::from_str(value).ok(),
+        "bcdDevice" => device.bcd_device = Version::from_str(value).ok(),
+        "bDeviceClass" => device.class = parse_u8(value).map(BaseClass::from),
+        "bDeviceSubClass" => device.sub_class = parse_u8(value),
+        "bDeviceProtocol" => device.protocol = parse_u8(value),
+        "idVendor" => {
+            device.vendor_id = parse_u16(value);
+            if let Some(extra) = device.extra.as_mut() {
+                extra.vendor = trailing_string(value);
+            }
+        }
+        "idProduct" => {
+            device.product_id = parse_u16(value);
+            if let Some(extra) = device.extra.as_mut() {
+                extra.product_name = trailing_string(value);
+            }
+        }
+        "iManufacturer" => device.manufacturer = trailing_string(value),
+        "iProduct" => {
+            if let Some(name) = trailing_string(value) {
+                device.name = name;
+            }
+        }
+        "iSerial" => device.serial_num = trailing_string(value),
+        _ => (),
+    }
+
+    if let Some(extra) = device.extra.as_mut() {
+        match key {
+            "bMaxPacketSize0" => extra.max_packet_size = parse_u8(value).unwrap_or(0),
+            "iProduct" => extra.string_indexes.0 = parse_u8(value).unwrap_or(0),
+            "iManufacturer" => extra.string_indexes.1 = parse_u8(value).unwrap_or(0),
+            "iSerial" => extra.string_indexes.2 = parse_u8(value).unwrap_or(0),
+            _ => (),
+        }
+    }
+}
+
+fn apply_configuration_field(config: &mut Configuration, key: &str, value: &str) {
+    match key {
+        "bLength" => config.length = parse_u8(value).unwrap_or(config.length),
+        "wTotalLength" => config.total_length = parse_u16(value).unwrap_or(0),
+        "bConfigurationValue" => config.number = parse_u8(value).unwrap_or(0),
+        "iConfiguration" => {
+            config.string_index = parse_u8(value).unwrap_or(0);
+            config.name = trailing_string(value).unwrap_or_default();
+        }
+        "bmAttributes" => {
+            let attributes = parse_u8(value).unwrap_or(0);
+            config.attributes.clear();
+            if attributes & 0x20 != 0 {
+                config.attributes.push(ConfigAttributes::RemoteWakeup);
+            }
+            if attributes & 0x40 != 0 {
+                config.attributes.push(ConfigAttributes::SelfPowered);
+            } else {
+                config.attributes.push(ConfigAttributes::BusPowered);
+            }
+        }
+        // already scaled for the device speed: '100mA'
+        "MaxPower" => {
+            config.max_power.value = value.trim_end_matches("mA").parse().unwrap_or(0);
+        }
+        _ => (),
+    }
+}
+
+fn apply_interface_field(interface: &mut Interface, key: &str, value: &str) {
+    match key {
+        "bLength" => interface.length = parse_u8(value).unwrap_or(interface.length),
+        "bInterfaceNumber" => interface.number = parse_u8(value).unwrap_or(0),
+        "bAlternateSetting" => interface.alt_setting = parse_u8(value).unwrap_or(0),
+        "bInterfaceClass" => interface.class = BaseClass::from(parse_u8(value).unwrap_or(0)),
+        "bInterfaceSubClass" => interface.sub_class = parse_u8(value).unwrap_or(0),
+        "bInterfaceProtocol" => interface.protocol = parse_u8(value).unwrap_or(0),
+        "iInterface" => {
+            interface.string_index = parse_u8(value).unwrap_or(0);
+            interface.name = trailing_string(value);
+        }
+        _ => (),
+    }
+}
+
+fn apply_endpoint_field(endpoint: &mut Endpoint, key: &str, value: &str) {
+    match key {
+        "bLength" => endpoint.length = parse_u8(value).unwrap_or(endpoint.length),
+        "bEndpointAddress" => {
+            endpoint.address = EndpointAddress::from(parse_u8(value).unwrap_or(0))
+        }
+        "bmAttributes" => {
+            let attributes = parse_u8(value).unwrap_or(0);
+            endpoint.transfer_type = TransferType::from(attributes);
+            endpoint.sync_type = SyncType::from(attributes);
+            endpoint.usage_type = UsageType::from(attributes);
+        }
+        "wMaxPacketSize" => endpoint.max_packet_size = parse_u16(value).unwrap_or(0),
+        "bInterval" => endpoint.interval = parse_u8(value).unwrap_or(0),
+        _ => (),
+    }
+}
+
+fn last_configuration(device: &mut Device) -> Option<&mut Configuration> {
+    device.extra.as_mut()?.configurations.last_mut()
+}
+
+fn last_interface(device: &mut Device) -> Option<&mut Interface> {
+    last_configuration(device)?.interfaces.last_mut()
+}
+
+fn new_configuration() -> Configuration {
+    Configuration {
+        name: String::new(),
+        string_index: 0,
+        number: 0,
+        interfaces: Vec::new(),
+        attributes: Vec::new(),
+        max_power: NumericalUnit {
+            value: 0,
+            unit: String::from("mA"),
+            description: None,
+        },
+        length: 9,
+        total_length: 0,
+        extra: None,
+        active: None,
+        internal: Default::default(),
+    }
+}
+
+fn new_interface() -> Interface {
+    Interface {
+        name: None,
+        string_index: 0,
+        number: 0,
+        path: String::new(),
+        class: BaseClass::default(),
+        sub_class: 0,
+        protocol: 0,
+        alt_setting: 0,
+        active: None,
+        driver: None,
+        syspath: None,
+        endpoints: Vec::new(),
+        length: 9,
+        extra: None,
+        internal: Default::default(),
+        device_path: None,
+    }
+}
+
+fn new_endpoint() -> Endpoint {
+    Endpoint {
+        length: 7,
+        address: EndpointAddress::from(0),
+        transfer_type: TransferType::Control,
+        sync_type: SyncType::None,
+        usage_type: UsageType::Data,
+        max_packet_size: 0,
+        interval: 0,
+        extra: None,
+        internal: Default::default(),
+        endpoint_path: None,
+        device_speed: None,
+    }
+}
+
+/// Set the interface and endpoint paths once the `device` location is known
+fn update_paths(device: &mut Device) {
+    let port_path = device.port_path();
+    let Some(extra) = device.extra.as_mut() else {
+        return;
+    };
+
+    for config in extra.configurations.iter_mut() {
+        for interface in config.interfaces.iter_mut() {
+            let device_path = DevicePath::new_with_port_path(
+                port_path.clone(),
+                Some(config.number),
+                Some(interface.number),
+                Some(interface.alt_setting),
+            );
+            interface.path = device_path.to_string();
+            for endpoint in interface.endpoints.iter_mut() {
+                endpoint.endpoint_path = Some(EndpointPath::new_with_device_path(
+                    device_path.clone(),
+                    endpoint.address.number,
+                ));
+            }
+            interface.device_path = Some(device_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verbose() {
+        let text = std::fs::read_to_string("./tests/data/lsusb_verbose.txt").unwrap();
+        let spusb = parse_verbose(&text).unwrap();
+        assert_eq!(spusb.buses.len(), 4);
+        assert_eq!(spusb.len(), 9);
+
+        let bus = &spusb.buses[1];
+        assert_eq!(bus.usb_bus_number, Some(2));
+        assert_eq!(bus.name, "UHCI Host Controller");
+
+        let jlink = bus
+            .devices
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|d| d.product_id == Some(0x1050))
+            .unwrap();
+        assert_eq!(jlink.port_path().to_string(), "2-4");
+        assert_eq!(jlink.name, "J-Link");
+        assert_eq!(jlink.manufacturer.as_deref(), Some("SEGGER"));
+        assert_eq!(jlink.serial_num.as_deref(), Some("001050027328"));
+        assert_eq!(jlink.class, Some(BaseClass::Miscellaneous));
+        assert_eq!(jlink.bcd_usb, Some(Version(2, 0, 0)));
+
+        let extra = jlink.extra.as_ref().unwrap();
+        assert_eq!(extra.vendor.as_deref(), Some("SEGGER"));
+        assert_eq!(extra.string_indexes, (2, 1, 3));
+        let config = &extra.configurations[0];
+        assert_eq!(config.total_length, 0xa4);
+        assert_eq!(config.name, "Configuration");
+        assert_eq!(config.attributes, vec![ConfigAttributes::BusPowered]);
+        assert_eq!(config.max_power.value, 100);
+        assert_eq!(config.interfaces.len(), 5);
+
+        let interface = &config.interfaces[0];
+        assert_eq!(interface.path, "2-4:1.0");
+        let endpoint = &interface.endpoints[0];
+        assert_eq!(endpoint.address.address, 0x82);
+        assert!(matches!(endpoint.transfer_type, TransferType::Interrupt));
+    }
+
+    #[test]
+    fn test_parse_negotiated_speed() {
+        assert_eq!(
+            parse_negotiated_speed(" High Speed (480Mbps)"),
+            Some(Speed::HighSpeed)
+        );
+        assert_eq!(
+            parse_negotiated_speed(" SuperSpeed (5Gbps)"),
+            Some(Speed::SuperSpeed)
+        );
+        assert_eq!(
+            parse_negotiated_speed(" Low Speed (1.5Mbps)"),
+            Some(Speed::LowSpeed)
+        );
+        assert_eq!(parse_negotiated_speed(" Unknown"), None);
+    }
+
+    #[test]
+    fn test_parse_verbose_empty() {
+        assert!(parse_verbose("").is_err());
+    }
+}
@@ -3,31 +3,6 @@ use crate::usb::descriptors::video;
 use super::*;
 
 // TODO - convert these to Rust enum like [`Uac1ChannelNames`] etc.
-const CAM_CTRL_NAMES: [&str; 22] = [
-    "Scanning Mode",
-    "Auto-Exposure Mode",
-    "Auto-Exposure Priority",
-    "Exposure Time (Absolute)",
-    "Exposure Time (Relative)",
-    "Focus (Absolute)",
-    "Focus (Relative)",
-    "Iris (Absolute)",
-    "Iris (Relative)",
-    "Zoom (Absolute)",
-    "Zoom (Relative)",
-    "PanTilt (Absolute)",
-    "PanTilt (Relative)",
-    "Roll (Absolute)",
-    "Roll (Relative)",
-    "Reserved",
-    "Reserved",
-    "Focus, Auto",
-    "Privacy",
-    "Focus, Simple",
-    "Window",
-    "Region of Interest",
-];
-
 const CTRL_NAMES: [&str; 19] = [
     "Brightness",
     "Contrast",
@@ -695,26 +670,16 @@ fn dump_video_subtype(
                 dump_value(extra.control_size, "bControlSize", indent + 2, width);
                 dump_hex(extra.controls, "bmControls", indent + 2, width);
 
-                if protocol == 0x01 {
-                    for (i, n) in CAM_CTRL_NAMES.iter().enumerate().take(22) {
-                        if (extra.controls >> i) & 1 != 0 {
-                            dump_string(n, indent + 4);
-                        }
-                    }
-                } else {
-                    for (i, n) in CAM_CTRL_NAMES.iter().enumerate().take(19) {
-                        if (extra.controls >> i) & 1 != 0 {
-                            dump_string(n, indent + 4);
-                        }
-                    }
+                for control in extra.camera_controls(protocol) {
+                    dump_string(&control.to_string(), indent + 4);
                 }
             }
         }
         video::UvcInterfaceDescriptor::OutputTerminal(ot) => {
-            dump_audio_output_terminal1(ot, indent + 2, width);
+            dump_audio_output_terminal1(ot, &HashMap::new(), indent + 2, width);
         }
         video::UvcInterfaceDescriptor::SelectorUnit(su) => {
-            dump_audio_selector_unit1(su, indent, width);
+            dump_audio_selector_unit1(su, &HashMap::new(), indent, width);
         }
         video::UvcInterfaceDescriptor::ProcessingUnit(pu) => {
             dump_processing_unit(pu, protocol, indent, width);
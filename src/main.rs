@@ -12,12 +12,13 @@ use std::env;
 use std::path::{Path, PathBuf};
 use terminal_size::terminal_size;
 
-use cyme::config::Config;
+use cyme::colour;
+use cyme::config::{BlockProfile, Config};
 use cyme::display::{self, Block, DeviceBlocks};
 use cyme::error::{Error, ErrorKind, Result};
 use cyme::lsusb;
 use cyme::profiler;
-use cyme::usb::BaseClass;
+use cyme::usb::{self, BaseClass};
 
 #[cfg(feature = "watch")]
 mod watch;
@@ -48,6 +49,10 @@ struct Args {
     #[arg(short = 'D', long)]
     device: Option<String>,
 
+    /// Show only the device at the port path and devices below it, in format BUS-PORT[.PORT...] such as 1-4.2
+    #[arg(long)]
+    filter_port_path: Option<String>,
+
     /// Filter on string contained in name
     #[arg(long)]
     filter_name: Option<String>,
@@ -56,10 +61,22 @@ struct Args {
     #[arg(long)]
     filter_serial: Option<String>,
 
-    /// Filter on USB class code
-    #[arg(long)]
+    /// Filter on USB class code; devices match if the device class or any of its interface classes match. Parent hubs of matching devices are kept in the tree
+    #[arg(long, visible_alias = "class")]
     filter_class: Option<BaseClass>,
 
+    /// Filter on string contained in the device or any interface driver, such as 'snd-usb-audio' or 'usbhid' - Linux only. Parent hubs of matching devices are kept in the tree
+    #[arg(long, visible_alias = "driver")]
+    filter_driver: Option<String>,
+
+    /// Filter on devices where the advertised device speed and negotiated speed are both known and differ, such as a USB 3 device connected at USB 2 speed. Parent hubs of matching devices are kept in the tree
+    #[arg(long, default_value_t = false)]
+    only_speed_mismatch: bool,
+
+    /// Highlight case-insensitive matches of SEARCH in every printed block, like grep --color, while still showing the full tree
+    #[arg(long)]
+    search: Option<String>,
+
     /// Verbosity level (repeat provides count): 1 prints device configurations; 2 prints interfaces; 3 prints interface endpoints; 4 prints everything and more blocks
     #[arg(short = 'v', long, default_value_t = 0, action = clap::ArgAction::Count)]
     verbose: u8,
@@ -82,6 +99,12 @@ struct Args {
     #[arg(long, value_enum, value_delimiter = ',', num_args = 1..)]
     config_blocks: Option<Vec<display::ConfigurationBlocks>>,
 
+    /// Specify the blocks which will be displayed for each function grouped by an Interface Association Descriptor and in what order. Supply arg multiple times or csv to specify multiple blocks.
+    ///
+    /// [default: interfaces,base-class,sub-class,protocol,name]
+    #[arg(long, value_enum, value_delimiter = ',', num_args = 1..)]
+    function_blocks: Option<Vec<display::FunctionBlocks>>,
+
     /// Specify the blocks which will be displayed for each interface and in what order. Supply arg multiple times or csv to specify multiple blocks.
     ///
     /// [default: port-path,icon,alt-setting,base-class,sub-class]
@@ -106,6 +129,38 @@ struct Args {
     #[arg(short, long, default_value_t = false)]
     more: bool,
 
+    /// Print every configuration, interface and endpoint inset below its device without tree glyphs; grep friendly but complete
+    #[arg(long, default_value_t = false)]
+    flat_descriptors: bool,
+
+    /// Print the raw class-specific descriptor bytes below each interface as an xxd style hex dump; interfaces are shown with -vv or --flat-descriptors
+    #[arg(long = "hex", default_value_t = false)]
+    hex_dump: bool,
+
+    /// Collapse runs of identical devices (vendor id, product id, name, class and serial) at each tree level into one line with a multiplier such as 'x4'. Devices with child devices are not collapsed. Ignored by --json, --csv, --markdown, --ndjson, --dot and --lsusb which list every device
+    #[arg(long, default_value_t = false)]
+    collapse_duplicates: bool,
+
+    /// Collapse identical devices with --collapse-duplicates even if their serial numbers differ
+    #[arg(long, default_value_t = false, requires = "collapse_duplicates")]
+    collapse_ignore_serial: bool,
+
+    /// Print a summary line under each bus with the number of devices, a count by negotiated speed and the total current drawn if known; buses are printed with --tree or --group-devices bus
+    #[arg(long, default_value_t = false)]
+    bus_summary: bool,
+
+    /// Use a preset selection of blocks for every block type; --blocks,--x--blocks args then operate on these
+    #[arg(long, value_enum)]
+    preset: Option<display::BlockPreset>,
+
+    /// Load the blocks saved with --save-profile NAME; --blocks,--x--blocks args then operate on these
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Save the blocks in use after applying config, --preset, --profile and --blocks,--x--blocks args as profile NAME to load with --profile NAME
+    #[arg(long, value_name = "NAME")]
+    save_profile: Option<String>,
+
     /// Sort devices operation
     ///
     /// [default: device-number]
@@ -116,6 +171,18 @@ struct Args {
     #[arg(long, default_value_t = false)]
     sort_buses: bool,
 
+    /// Reverse the order of buses and devices after sorting. Applies to no-sort too, reversing parse order
+    #[arg(long, default_value_t = false)]
+    sort_reverse: bool,
+
+    /// Sort devices by the value of a device block after --sort-devices, such as speed; numeric blocks sort by value rather than text. Combine with --sort-reverse for descending
+    #[arg(long, value_enum)]
+    sort_block: Option<DeviceBlocks>,
+
+    /// Sort the interfaces of each configuration by interface number and alternate setting or by class; parse order if not set
+    #[arg(long, value_enum)]
+    sort_interfaces: Option<display::InterfaceSort>,
+
     /// Group devices by value when listing
     ///
     /// [default: no-group]
@@ -139,6 +206,12 @@ struct Args {
     #[arg(long, default_value_t = false)]
     decimal: bool,
 
+    /// Unit to show device speeds in: Mb/s or Gb/s as in the USB specification, always Mb/s or binary Mib/s or Gib/s
+    ///
+    /// [default: auto]
+    #[arg(long, value_enum)]
+    speed_unit: Option<usb::SpeedUnit>,
+
     /// Disable padding to align blocks - will cause --headings to become maligned
     #[arg(long, default_value_t = false)]
     no_padding: bool,
@@ -173,6 +246,20 @@ struct Args {
     #[arg(long, value_enum, aliases = &["icon_when"])]
     icon: Option<display::IconWhen>,
 
+    /// How to fit variable length values wider than the terminal: truncate with '...' or wrap onto continuation lines
+    ///
+    /// [default: truncate]
+    #[arg(long, value_enum)]
+    wrap: Option<display::WrapMode>,
+
+    /// Print icon blocks in the first columns regardless of their position in the block lists, such as to align icons in --tree
+    #[arg(long, default_value_t = false)]
+    pin_icons_first: bool,
+
+    /// Hide blocks that are '-' for every device or bus printed, such as Driver or Serial where not available
+    #[arg(long, default_value_t = false)]
+    auto_hide_empty: bool,
+
     /// Show block headings
     #[arg(long, default_value_t = false)]
     headings: bool,
@@ -181,10 +268,62 @@ struct Args {
     #[arg(long, default_value_t = false, overrides_with = "lsusb")]
     json: bool,
 
+    /// Output as --json with vendor_name, product_name, class_name, sub_class_name and protocol_name resolved from the USB IDs repository in each device and interface object
+    #[arg(long, default_value_t = false, overrides_with = "lsusb")]
+    json_resolved: bool,
+
+    /// Output as CSV with blocks as columns after sorting and filters are applied; always a flattened list of devices. With -vv adds interface block columns and a row per interface
+    #[arg(long, default_value_t = false, overrides_with_all = ["lsusb", "json"])]
+    csv: bool,
+
+    /// Print the JSON Schema for the --json output and exit
+    #[arg(long, default_value_t = false, exclusive = true)]
+    json_schema: bool,
+
+    /// Output as GitHub flavoured Markdown table with blocks as columns; like --csv
+    #[arg(long, default_value_t = false, overrides_with_all = ["lsusb", "json", "csv"])]
+    markdown: bool,
+
+    /// Output as JSON Lines, an object per device with port_path, event and device; with watch a line is written per device event
+    #[arg(long, default_value_t = false, overrides_with_all = ["lsusb", "json", "csv", "markdown"])]
+    ndjson: bool,
+
+    /// Output the bus tree as a GraphViz DOT digraph with port paths as node ids, labeled with the bus and device blocks
+    #[arg(long, default_value_t = false, overrides_with_all = ["lsusb", "json", "csv", "markdown", "ndjson"])]
+    dot: bool,
+
+    /// String to insert between columns, e.g. " | "; default is a single space
+    #[arg(long)]
+    separator: Option<String>,
+
+    /// Maximum depth of devices to print with --tree; devices below are summarised as a count. 1 is devices on the bus only
+    #[arg(long)]
+    max_depth: Option<usize>,
+
+    /// Only print endpoints of this transfer type
+    #[arg(long, value_enum)]
+    endpoint_transfer_type: Option<usb::TransferType>,
+
+    /// Only print endpoints of this direction, such as 'in' when auditing the streaming endpoints of a capture device
+    #[arg(long, value_enum)]
+    endpoint_direction: Option<usb::Direction>,
+
+    /// Hide interfaces with no endpoints matching --endpoint-transfer-type and --endpoint-direction
+    #[arg(long, default_value_t = false)]
+    hide_empty_interfaces: bool,
+
+    /// Only print the selected alternate setting of each interface, or alternate setting 0 if not selected; all are printed if it cannot be determined, such as from a --json dump of an older cyme
+    #[arg(long = "only-active", default_value_t = false)]
+    only_active_alt: bool,
+
     /// Read from json output rather than profiling system
     #[arg(long)]
     from_json: Option<PathBuf>,
 
+    /// Compare with a previous --json dump, printing devices added, removed or changed since it keyed by port path. Use with --from-json to compare two dumps; device filters apply to both
+    #[arg(long, value_name = "PATH")]
+    diff: Option<PathBuf>,
+
     /// Force pure libusb profiler on macOS rather than combining system_profiler output
     ///
     /// Has no effect on other platforms or when using nusb
@@ -195,6 +334,10 @@ struct Args {
     #[arg(short = 'c', long)]
     config: Option<PathBuf>,
 
+    /// Path to .json colour theme file to use in place of config colours
+    #[arg(long)]
+    colour_theme: Option<PathBuf>,
+
     /// Turn debugging information on. Alternatively can use RUST_LOG env: INFO, DEBUG, TRACE
     #[arg(short = 'z', long, action = clap::ArgAction::Count)]
     // short -d taken by lsusb compat vid:pid
@@ -204,6 +347,16 @@ struct Args {
     #[arg(long)]
     mask_serials: Option<display::MaskSerial>,
 
+    /// Device fields to mask using the --mask-serials method; hidden with '*' if that is not set
+    ///
+    /// [default: serial]
+    #[arg(long, value_enum, value_delimiter = ',', num_args = 1..)]
+    mask_fields: Option<Vec<display::MaskField>>,
+
+    /// Seed for scramble and replace masking so the same input gives the same masked output
+    #[arg(long)]
+    mask_seed: Option<u64>,
+
     /// Generate cli completions and man page
     #[cfg(feature = "cli_generate")]
     #[arg(long, hide = true, exclusive = true)]
@@ -215,6 +368,16 @@ struct Args {
     #[arg(long, default_value_t = false)]
     system_profiler: bool,
 
+    /// Only show devices with a connect or disconnect event within the last SINCE seconds when watching
+    #[cfg(feature = "watch")]
+    #[arg(long)]
+    since: Option<u64>,
+
+    /// Show the LastEvent block as the time since the event, such as '3s ago', when watching
+    #[cfg(feature = "watch")]
+    #[arg(long, default_value_t = false)]
+    relative_time: bool,
+
     /// Watch sub-command
     #[cfg(feature = "watch")]
     #[command(subcommand)]
@@ -258,17 +421,36 @@ fn merge_config(c: &mut Config, a: &Args) {
     c.lsusb |= a.lsusb;
     c.tree |= a.tree;
     c.more |= a.more;
+    c.flat_descriptors |= a.flat_descriptors;
+    c.hex_dump |= a.hex_dump;
+    c.collapse_duplicates |= a.collapse_duplicates;
+    c.collapse_ignore_serial |= a.collapse_ignore_serial;
+    c.bus_summary |= a.bus_summary;
+    #[cfg(feature = "watch")]
+    {
+        c.relative_time |= a.relative_time;
+    }
     c.hide_buses |= a.hide_buses;
     c.hide_hubs |= a.hide_hubs;
     c.list_root_hubs |= a.list_root_hubs;
     c.decimal |= a.decimal;
+    if a.speed_unit.is_some() {
+        c.speed_unit = a.speed_unit;
+    }
     c.no_padding |= a.no_padding;
     c.ascii |= a.ascii;
     c.headings |= a.headings;
+    c.pin_icons_first |= a.pin_icons_first;
+    c.auto_hide_empty |= a.auto_hide_empty;
     c.force_libusb |= a.force_libusb;
     c.no_icons |= a.no_icons;
     c.no_color |= a.no_color;
-    c.json |= a.json;
+    c.json |= a.json || a.json_resolved;
+    c.json_resolved |= a.json_resolved;
+    c.csv |= a.csv;
+    c.markdown |= a.markdown;
+    c.ndjson |= a.ndjson;
+    c.dot |= a.dot;
     // override group devices if passed
     if a.group_devices.is_some() {
         c.group_devices = a.group_devices;
@@ -282,13 +464,43 @@ fn merge_config(c: &mut Config, a: &Args) {
     if a.icon.is_some() {
         c.icon_when = a.icon;
     }
+    if a.wrap.is_some() {
+        c.wrap_mode = a.wrap;
+    }
     if a.color.is_some() {
         c.color_when = a.color;
     }
     if a.mask_serials.is_some() {
         c.mask_serials = a.mask_serials;
     }
+    if a.mask_fields.is_some() {
+        c.mask_fields = a.mask_fields.clone();
+    }
+    if a.mask_seed.is_some() {
+        c.mask_seed = a.mask_seed;
+    }
+    if a.separator.is_some() {
+        c.separator = a.separator.clone();
+    }
+    if a.max_depth.is_some() {
+        c.max_depth = a.max_depth;
+    }
+    if a.endpoint_transfer_type.is_some() {
+        c.endpoint_transfer_filter = a.endpoint_transfer_type;
+    }
+    if a.endpoint_direction.is_some() {
+        c.endpoint_direction_filter = a.endpoint_direction;
+    }
+    c.hide_empty_interfaces |= a.hide_empty_interfaces;
+    c.only_active_alt |= a.only_active_alt;
     c.sort_buses |= a.sort_buses;
+    c.sort_reverse |= a.sort_reverse;
+    if a.sort_block.is_some() {
+        c.sort_block = a.sort_block;
+    }
+    if a.sort_interfaces.is_some() {
+        c.sort_interfaces = a.sort_interfaces;
+    }
     // take larger debug level
     c.verbose = c.verbose.max(a.verbose);
 }
@@ -395,7 +607,13 @@ fn get_system_profile_macos(config: &Config, args: &Args) -> Result<profiler::Sy
         if !config.force_libusb
             && args.device.is_none() // device path requires extra
                 && args.filter_class.is_none() // class filter requires extra
-                && !((config.tree && config.lsusb) || config.verbose > 0 || config.more)
+                && args.filter_driver.is_none() // driver filter requires extra
+                && !args.only_speed_mismatch // negotiated speed requires extra
+                && !((config.tree && config.lsusb)
+                    || config.verbose > 0
+                    || config.more
+                    || config.flat_descriptors
+                    || config.hex_dump)
         {
             profiler::macos::get_spusb()
                 .map_or_else(|e| {
@@ -431,12 +649,15 @@ fn get_system_profile_macos(config: &Config, args: &Args) -> Result<profiler::Sy
 /// Detects and switches between verbose profiler (extra) and normal profiler
 fn get_system_profile(config: &Config, args: &Args) -> Result<profiler::SystemProfile> {
     if config.verbose > 0
+        || config.flat_descriptors
         || config.tree
         || args.device.is_some()
         || config.lsusb
         || config.more
         || args.filter_class.is_none()
-    // class filter requires extra
+        || args.filter_driver.is_some()
+        || args.only_speed_mismatch
+    // class filter, driver and negotiated speed require extra
     {
         profiler::get_spusb_with_extra()
     } else {
@@ -599,22 +820,25 @@ pub fn set_log_level(debug: u8) -> Result<()> {
 }
 
 /// Merge with arg blocks with config blocks (or default if None) depending on BlockOperation
-fn merge_blocks(config: &Config, args: &Args, settings: &mut display::PrintSettings) -> Result<()> {
+fn merge_blocks(args: &Args, settings: &mut display::PrintSettings) -> Result<()> {
     if let Some(blocks) = &args.blocks {
-        let mut device_blocks = config.blocks.to_owned().unwrap_or(if settings.more {
-            DeviceBlocks::default_blocks(true)
-        } else if settings.tree {
-            DeviceBlocks::default_device_tree_blocks()
-        } else {
-            DeviceBlocks::default_blocks(false)
-        });
+        let mut device_blocks = settings
+            .device_blocks
+            .to_owned()
+            .unwrap_or(if settings.more {
+                DeviceBlocks::default_blocks(true)
+            } else if settings.tree {
+                DeviceBlocks::default_device_tree_blocks()
+            } else {
+                DeviceBlocks::default_blocks(false)
+            });
         args.block_operation.run(&mut device_blocks, blocks)?;
         settings.device_blocks = Some(device_blocks);
     }
 
     if let Some(blocks) = &args.bus_blocks {
         settings.bus_blocks = Some(args.block_operation.new_or_op(
-            config.bus_blocks.to_owned(),
+            settings.bus_blocks.to_owned(),
             blocks,
             settings.more,
         )?);
@@ -628,6 +852,14 @@ fn merge_blocks(config: &Config, args: &Args, settings: &mut display::PrintSetti
         )?);
     }
 
+    if let Some(blocks) = &args.function_blocks {
+        settings.function_blocks = Some(args.block_operation.new_or_op(
+            settings.function_blocks.to_owned(),
+            blocks,
+            settings.more,
+        )?);
+    }
+
     if let Some(blocks) = &args.interface_blocks {
         settings.interface_blocks = Some(args.block_operation.new_or_op(
             settings.interface_blocks.to_owned(),
@@ -656,11 +888,23 @@ fn cyme() -> Result<()> {
         std::process::exit(0);
     }
 
+    if args.json_schema {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&profiler::schema::json_schema())?
+        );
+        std::process::exit(0);
+    }
+
     // set the module debug level, will also check env if args.debug == 0
     set_log_level(args.debug)?;
 
     let mut config = load_config(args.config.as_deref())?;
 
+    if let Some(p) = args.colour_theme.as_ref() {
+        config.colours = colour::ColourTheme::from_path(p)?;
+    }
+
     // add any config ENV override
     if config.print_non_critical_profiler_stderr {
         std::env::set_var("CYME_PRINT_NON_CRITICAL_PROFILER_STDERR", "1");
@@ -687,17 +931,18 @@ fn cyme() -> Result<()> {
     match config.color_when {
         Some(display::ColorWhen::Always) => {
             env::set_var("NO_COLOR", "0");
-            colored::control::set_override(true);
             config.no_color = false;
         }
         Some(display::ColorWhen::Never) => {
             // set env to be sure too
             env::set_var("NO_COLOR", "1");
-            colored::control::set_override(false);
             config.no_color = true;
         }
         _ => (),
     };
+    if let Some(cw) = config.color_when {
+        cw.set_override();
+    }
 
     let mut spusb = if let Some(file_path) = args.from_json.clone() {
         match profiler::read_json_dump(&file_path) {
@@ -726,9 +971,12 @@ fn cyme() -> Result<()> {
         || args.vidpid.is_some()
         || args.show.is_some()
         || args.device.is_some()
+        || args.filter_port_path.is_some()
         || args.filter_name.is_some()
         || args.filter_serial.is_some()
         || args.filter_class.is_some()
+        || args.filter_driver.is_some()
+        || args.only_speed_mismatch
     {
         let mut f = profiler::Filter::new();
 
@@ -766,10 +1014,21 @@ fn cyme() -> Result<()> {
             f.number = number;
         }
 
+        if let Some(path) = &args.filter_port_path {
+            f.port_path = Some(path.parse().map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidArg,
+                    &format!("Failed to parse port path '{path}'; Error({e})"),
+                )
+            })?);
+        }
+
         // no need to unwrap as these are Option
         f.name = args.filter_name.clone();
         f.serial = args.filter_serial.clone();
         f.class = args.filter_class;
+        f.driver = args.filter_driver.clone();
+        f.speed_mismatch = args.only_speed_mismatch;
         f.exclude_empty_hub = config.hide_hubs;
         f.exclude_empty_bus = config.hide_buses;
         // exclude root hubs unless:
@@ -797,13 +1056,59 @@ fn cyme() -> Result<()> {
     // create print settings from config - merged with arg flags above
     let mut settings = config.print_settings();
     settings.terminal_size = terminal_size().map(|(w, h)| (w.0, h.0));
-    merge_blocks(&config, &args, &mut settings)?;
+    settings.search = args.search.clone();
+    if settings
+        .tree_glyphs
+        .is_some_and(|g| !g.is_valid(&settings.encoding))
+    {
+        log::warn!(
+            "Tree glyphs {:?} are not valid for {:?} encoding, using defaults",
+            settings.tree_glyphs,
+            settings.encoding
+        );
+    }
+    #[cfg(feature = "watch")]
+    {
+        settings.since = args.since.map(std::time::Duration::from_secs);
+    }
+    if let Some(preset) = args.preset {
+        settings.apply_preset(preset);
+    }
+    if let Some(name) = args.profile.as_ref() {
+        BlockProfile::load(name)?.apply(&mut settings);
+    }
+    merge_blocks(&args, &mut settings)?;
+    if let Some(name) = args.save_profile.as_ref() {
+        let path = BlockProfile::from(&settings).save(name)?;
+        log::info!("Saved block profile '{name}' to {path:?}");
+    }
 
     log::trace!("Returned system_profiler data\n\r{spusb:#?}");
 
+    if let Some(file_path) = args.diff.as_ref() {
+        let mut previous = profiler::read_json_dump(file_path)?;
+        // flatten before filtering so non-matching parents of matching devices are not compared
+        if let Some(f) = filter.as_ref() {
+            for profile in [&mut previous, &mut spusb] {
+                profile.into_flattened();
+                f.retain_buses(&mut profile.buses);
+            }
+        }
+        let diff = previous.diff(&spusb);
+        if settings.json {
+            println!("{}", serde_json::to_string_pretty(&diff)?);
+        } else {
+            settings.color_when.set_override();
+            display::print_diff(&diff, &settings);
+        }
+        return Ok(());
+    }
+
     #[cfg(feature = "watch")]
     if matches!(args.command, Some(SubCommand::Watch)) {
-        if settings.json {
+        if settings.ndjson {
+            watch::watch_usb_devices_ndjson(spusb, filter, settings)?;
+        } else if settings.json {
             watch::watch_usb_devices_json(spusb, filter, settings)?;
         } else {
             watch::watch_usb_devices(spusb, filter, settings, config)?;
@@ -817,11 +1122,13 @@ fn cyme() -> Result<()> {
         print_lsusb(&spusb, &args.device, &settings)?;
     } else {
         // check and report if was looking for args.device
-        if args.device.is_some() && !spusb.buses.iter().any(|b| b.is_empty()) {
-            return Err(Error::new(
-                ErrorKind::NotFound,
-                &format!("Unable to find device at {:?}", args.device.unwrap()),
-            ));
+        if let Some(device) = &args.device {
+            if !spusb.buses.iter().any(|b| b.is_empty()) {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    &format!("Unable to find device at {device:?}"),
+                ));
+            }
         }
         display::print(&spusb, &settings);
     }
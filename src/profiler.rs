@@ -28,6 +28,10 @@ pub(crate) const SYSFS_USB_PREFIX: &str = "/sys/bus/usb/devices/";
 pub(crate) const SYSFS_PCI_PREFIX: &str = "/sys/bus/pci/devices/";
 
 // separate module but import all
+pub mod diff;
+pub mod iter;
+pub mod resolved;
+pub mod schema;
 pub mod types;
 pub use types::*;
 
@@ -409,86 +413,17 @@ where
                     usb::descriptors::audio::MidiInterfaceDescriptor::Element(ref mut mh) => {
                         mh.element_string = device.get_descriptor_string(mh.element_string_index);
                     }
-                    _ => (),
-                },
-                usb::ClassDescriptor::Audio(ref mut ad, _) => match ad.interface {
-                    usb::descriptors::audio::UacInterfaceDescriptor::InputTerminal1(ref mut ah) => {
-                        ah.channel_names = device.get_descriptor_string(ah.channel_names_index);
-                        ah.terminal = device.get_descriptor_string(ah.terminal_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::InputTerminal2(ref mut ah) => {
-                        ah.channel_names = device.get_descriptor_string(ah.channel_names_index);
-                        ah.terminal = device.get_descriptor_string(ah.terminal_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::OutputTerminal1(
-                        ref mut ah,
-                    ) => {
-                        ah.terminal = device.get_descriptor_string(ah.terminal_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::OutputTerminal2(
-                        ref mut ah,
-                    ) => {
-                        ah.terminal = device.get_descriptor_string(ah.terminal_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::StreamingInterface2(
-                        ref mut ah,
+                    usb::descriptors::audio::MidiInterfaceDescriptor::GroupTerminalBlock(
+                        ref mut gb,
                     ) => {
-                        ah.channel_names = device.get_descriptor_string(ah.channel_names_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::SelectorUnit1(ref mut ah) => {
-                        ah.selector = device.get_descriptor_string(ah.selector_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::SelectorUnit2(ref mut ah) => {
-                        ah.selector = device.get_descriptor_string(ah.selector_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::ProcessingUnit1(
-                        ref mut ah,
-                    ) => {
-                        ah.channel_names = device.get_descriptor_string(ah.channel_names_index);
-                        ah.processing = device.get_descriptor_string(ah.processing_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::ProcessingUnit2(
-                        ref mut ah,
-                    ) => {
-                        ah.channel_names = device.get_descriptor_string(ah.channel_names_index);
-                        ah.processing = device.get_descriptor_string(ah.processing_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::EffectUnit2(ref mut ah) => {
-                        ah.effect = device.get_descriptor_string(ah.effect_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::FeatureUnit1(ref mut ah) => {
-                        ah.feature = device.get_descriptor_string(ah.feature_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::FeatureUnit2(ref mut ah) => {
-                        ah.feature = device.get_descriptor_string(ah.feature_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::ExtensionUnit1(ref mut ah) => {
-                        ah.channel_names = device.get_descriptor_string(ah.channel_names_index);
-                        ah.extension = device.get_descriptor_string(ah.extension_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::ExtensionUnit2(ref mut ah) => {
-                        ah.channel_names = device.get_descriptor_string(ah.channel_names_index);
-                        ah.extension = device.get_descriptor_string(ah.extension_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::ClockSource2(ref mut ah) => {
-                        ah.clock_source = device.get_descriptor_string(ah.clock_source_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::ClockSelector2(ref mut ah) => {
-                        ah.clock_selector = device.get_descriptor_string(ah.clock_selector_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::ClockMultiplier2(
-                        ref mut ah,
-                    ) => {
-                        ah.clock_multiplier =
-                            device.get_descriptor_string(ah.clock_multiplier_index);
-                    }
-                    usb::descriptors::audio::UacInterfaceDescriptor::SampleRateConverter2(
-                        ref mut ah,
-                    ) => {
-                        ah.src = device.get_descriptor_string(ah.src_index);
+                        gb.block_string = device.get_descriptor_string(gb.block_string_index);
                     }
                     _ => (),
                 },
+                usb::ClassDescriptor::Audio(ref mut ad, _) => {
+                    ad.interface
+                        .resolve_strings(&|i| device.get_descriptor_string(i));
+                }
                 usb::ClassDescriptor::Video(ref mut vd, _) => match vd.interface {
                     usb::descriptors::video::UvcInterfaceDescriptor::InputTerminal(ref mut vh) => {
                         vh.terminal = device.get_descriptor_string(vh.terminal_index);
@@ -572,6 +507,9 @@ where
             taken += dt_len;
         }
 
+        // UAC3 terminals reference the channel cluster by id so can only be resolved once all are parsed
+        usb::descriptors::resolve_audio_channel_names(&mut ret);
+
         Ok(ret)
     }
 
@@ -872,6 +810,7 @@ mod platform {
             vendor_id: pci_id.0,
             product_id: pci_id.1,
             revision: pci_id.2 as u16,
+            class: None,
         })
     }
 
@@ -908,6 +847,7 @@ mod platform {
                 pci_vendor: Some(pci_info.vendor_id),
                 pci_device: Some(pci_info.product_id),
                 pci_revision: Some(pci_info.revision),
+                pci_class: pci_info.class,
                 id: bus.bus_id().to_string(),
                 ..Default::default()
             }
@@ -1010,6 +950,12 @@ mod platform {
         }
     }
 
+    impl FromHexStr for u32 {
+        fn from_hex_str(s: &str) -> Result<Self> {
+            u32::from_str_radix(s, 16).map_err(|_| Error::new(ErrorKind::Parsing, s))
+        }
+    }
+
     impl std::fmt::Display for SysfsPath {
         fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
             self.0.display().fmt(f)
@@ -1053,6 +999,7 @@ mod platform {
             vendor_id: pci_path.read_attr_hex("vendor").ok()?,
             product_id: pci_path.read_attr_hex("device").ok()?,
             revision: pci_path.read_attr_hex("revision").ok()?,
+            class: pci_path.read_attr_hex("class").ok(),
         })
     }
 
@@ -1103,6 +1050,7 @@ mod platform {
                 pci_vendor: Some(pci_info.vendor_id),
                 pci_device: Some(pci_info.product_id),
                 pci_revision: Some(pci_info.revision),
+                pci_class: pci_info.class,
                 ..Default::default()
             }
         } else {
@@ -1133,6 +1081,7 @@ mod platform {
                 vendor_id: pci_info.vendor_id,
                 product_id: pci_info.device_id,
                 revision: pci_info.revision_id,
+                class: None,
             }
         }
     }
@@ -1170,6 +1119,7 @@ mod platform {
                 pci_vendor: Some(pci_info.vendor_id),
                 pci_device: Some(pci_info.product_id),
                 pci_revision: Some(pci_info.revision),
+                pci_class: pci_info.class,
                 ..Default::default()
             }
         } else {
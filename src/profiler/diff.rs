@@ -0,0 +1,229 @@
+//! Compare two [`SystemProfile`]s, such as `cyme --json` captures before and after plugging a device or a firmware update
+//!
+//! Devices are matched by port path and compared field by field using their serde representation so the comparison follows the `--json` output.
+//!
+//! ```
+//! use cyme::profiler::read_json_dump;
+//!
+//! let before = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+//! let mut after = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+//! assert!(before.diff(&after).is_empty());
+//!
+//! after.get_node_str_mut("2-2.1").unwrap().name = "J-Link Pro".into();
+//! let diff = before.diff(&after);
+//! assert_eq!(diff.changed.len(), 1);
+//! assert_eq!(diff.changed[0].port_path, "2-2.1");
+//! assert_eq!(diff.changed[0].fields[0].field, "name");
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::{Device, SystemProfile};
+
+/// Fields that differ between every capture so are not compared
+const IGNORED_FIELDS: [&str; 2] = ["devices", "last_event"];
+
+/// A field of a [`Device`] that differs between two profiles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Path of the field in the `--json` device object, such as `extra.configurations[0].max_power`
+    pub field: String,
+    /// Value in the previous profile - None if not present
+    pub old: Option<Value>,
+    /// Value in the new profile - None if not present
+    pub new: Option<Value>,
+}
+
+/// A [`Device`] at the same port path in both profiles with [`FieldChange`]s
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceDiff {
+    /// Port path of the device in both profiles
+    pub port_path: String,
+    /// The device in the new profile
+    pub device: Device,
+    /// Fields that differ, in field path order
+    pub fields: Vec<FieldChange>,
+}
+
+/// Devices added, removed or changed between two [`SystemProfile`]s keyed by port path; returned by [`SystemProfile::diff`]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProfileDiff {
+    /// Devices only in the new profile
+    pub added: Vec<Device>,
+    /// Devices only in the previous profile
+    pub removed: Vec<Device>,
+    /// Devices in both profiles with differing fields
+    pub changed: Vec<DeviceDiff>,
+}
+
+impl ProfileDiff {
+    /// Whether there are no added, removed or changed devices
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl SystemProfile {
+    /// Compare with the `other` [`SystemProfile`], taken to be the newer, returning the [`ProfileDiff`] of devices keyed by port path
+    ///
+    /// Child devices and the last event are not compared as fields; children are compared at their own port path
+    pub fn diff(&self, other: &SystemProfile) -> ProfileDiff {
+        let old = devices_by_port_path(self);
+        let new = devices_by_port_path(other);
+        let mut ret = ProfileDiff::default();
+
+        for (port_path, device) in new.iter() {
+            match old.get(port_path) {
+                Some(previous) => {
+                    let fields = previous.diff(device);
+                    if !fields.is_empty() {
+                        ret.changed.push(DeviceDiff {
+                            port_path: port_path.to_owned(),
+                            device: without_devices(device),
+                            fields,
+                        });
+                    }
+                }
+                None => ret.added.push(without_devices(device)),
+            }
+        }
+
+        ret.removed = old
+            .iter()
+            .filter(|(k, _)| !new.contains_key(*k))
+            .map(|(_, d)| without_devices(d))
+            .collect();
+
+        ret
+    }
+}
+
+fn devices_by_port_path(profile: &SystemProfile) -> BTreeMap<String, &Device> {
+    profile
+        .flattened_devices()
+        .into_iter()
+        .map(|d| (d.port_path().to_string(), d))
+        .collect()
+}
+
+/// Clone of `device` without its child devices, which are reported at their own port path
+fn without_devices(device: &Device) -> Device {
+    let mut ret = device.clone();
+    ret.devices = None;
+    ret
+}
+
+/// Flattened serde fields of `device` keyed by field path, without [`IGNORED_FIELDS`]
+fn device_fields(device: &Device) -> BTreeMap<String, Value> {
+    let mut ret = BTreeMap::new();
+    if let Ok(Value::Object(map)) = serde_json::to_value(device) {
+        for (k, v) in map {
+            if !IGNORED_FIELDS.contains(&k.as_str()) {
+                flatten_value(k, v, &mut ret);
+            }
+        }
+    }
+    ret
+}
+
+fn flatten_value(path: String, value: Value, fields: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (k, v) in map {
+                flatten_value(format!("{path}.{k}"), v, fields);
+            }
+        }
+        Value::Array(array) if !array.is_empty() => {
+            for (i, v) in array.into_iter().enumerate() {
+                flatten_value(format!("{path}[{i}]"), v, fields);
+            }
+        }
+        v => {
+            fields.insert(path, v);
+        }
+    }
+}
+
+impl Device {
+    /// [`FieldChange`]s from this device to the `other`, taken to be the newer; empty if they are equal
+    ///
+    /// Child devices and the last event are not compared
+    pub fn diff(&self, other: &Device) -> Vec<FieldChange> {
+        let old = device_fields(self);
+        let mut new = device_fields(other);
+        let mut ret = Vec::new();
+        for (field, value) in old {
+            match new.remove(&field) {
+                Some(v) if v == value => (),
+                v => ret.push(FieldChange {
+                    field,
+                    old: Some(value),
+                    new: v,
+                }),
+            }
+        }
+        ret.extend(new.into_iter().map(|(field, v)| FieldChange {
+            field,
+            old: None,
+            new: Some(v),
+        }));
+        ret.sort_by(|a, b| a.field.cmp(&b.field));
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiler::read_json_dump;
+
+    #[test]
+    fn test_diff_added_removed() {
+        let mut before = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        let mut after = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        before
+            .get_node_str_mut("2-2")
+            .and_then(|d| d.devices.as_mut())
+            .unwrap()
+            .retain(|d| d.port_path().to_string() != "2-2.1");
+        after
+            .get_node_str_mut("2-2")
+            .and_then(|d| d.devices.as_mut())
+            .unwrap()
+            .retain(|d| d.port_path().to_string() != "2-2.8");
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].port_path().to_string(), "2-2.1");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].port_path().to_string(), "2-2.8");
+        // the hub children are compared at their own port path
+        assert!(diff.changed.is_empty());
+    }
+
+    #[test]
+    fn test_diff_array_index_field() {
+        let before = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        let mut after = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        let config = &mut after
+            .get_node_str_mut("2-2.1")
+            .and_then(|d| d.extra.as_mut())
+            .unwrap()
+            .configurations[0];
+        let old = config.max_power.value;
+        config.max_power.value = old + 100;
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].port_path, "2-2.1");
+        assert_eq!(
+            diff.changed[0].fields,
+            vec![FieldChange {
+                field: "extra.configurations[0].max_power.value".into(),
+                old: Some(old.into()),
+                new: Some((old + 100).into()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Depth-first traversal of the [`Device`] tree in a [`SystemProfile`] or [`Bus`]
+//!
+//! Devices are visited parent first then children in order, the same order as [`SystemProfile::flattened_devices`] and the `--tree` output. Each device is paired with its depth in the tree: devices directly on a bus are depth 0, devices on a hub at depth 0 are depth 1 and so on.
+//!
+//! ```
+//! use cyme::profiler::read_json_dump;
+//!
+//! let mut spusb = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+//! let jlink = spusb
+//!     .iter_devices()
+//!     .find(|(_, d)| d.name == "J-Link")
+//!     .unwrap();
+//! assert_eq!(jlink.0, 1);
+//! assert_eq!(spusb.iter_devices().count(), spusb.flattened_devices().len());
+//!
+//! let bus = spusb.get_bus(2).unwrap();
+//! let depths: Vec<usize> = bus.iter_devices().map(|(depth, _)| depth).collect();
+//! // root hub, hub then J-Link and Black Magic Probe on the hub
+//! assert_eq!(depths, vec![0, 0, 1, 1]);
+//!
+//! spusb.iter_devices_mut(|depth, d| {
+//!     if depth > 0 {
+//!         d.name = d.name.to_uppercase();
+//!     }
+//! });
+//! assert_eq!(spusb.get_node_by_str("2-2.1").unwrap().name, "J-LINK");
+//! ```
+use super::{Bus, Device, SystemProfile};
+
+/// Depth-first iterator over references to [`Device`]s and their depth in the tree; returned by [`SystemProfile::iter_devices`], [`Bus::iter_devices`] and [`Device::iter_devices`]
+#[derive(Debug, Clone, Default)]
+pub struct DeviceIter<'a> {
+    /// Devices still to visit, next on top
+    stack: Vec<(usize, &'a Device)>,
+}
+
+impl<'a> DeviceIter<'a> {
+    fn new(devices: impl DoubleEndedIterator<Item = &'a Device>, depth: usize) -> Self {
+        DeviceIter {
+            stack: devices.rev().map(|d| (depth, d)).collect(),
+        }
+    }
+}
+
+impl<'a> Iterator for DeviceIter<'a> {
+    type Item = (usize, &'a Device);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, device) = self.stack.pop()?;
+        if let Some(devices) = device.devices.as_ref() {
+            self.stack
+                .extend(devices.iter().rev().map(|d| (depth + 1, d)));
+        }
+        Some((depth, device))
+    }
+}
+
+/// Visit `device` then its children depth-first with `f`; children are walked after `f` returns so it may modify them
+fn visit_device_mut<F: FnMut(usize, &mut Device)>(device: &mut Device, depth: usize, f: &mut F) {
+    f(depth, device);
+    if let Some(devices) = device.devices.as_mut() {
+        for child in devices {
+            visit_device_mut(child, depth + 1, f);
+        }
+    }
+}
+
+impl SystemProfile {
+    /// Depth-first iterator over all [`Device`]s on all `buses` with their depth in the tree
+    pub fn iter_devices(&self) -> impl Iterator<Item = (usize, &Device)> {
+        self.buses.iter().flat_map(|b| b.iter_devices())
+    }
+
+    /// Call `f` with each [`Device`] on all `buses` and its depth in the tree, depth-first
+    ///
+    /// A visitor rather than an [`Iterator`] since a mutable reference to a device would alias those to its children
+    pub fn iter_devices_mut<F: FnMut(usize, &mut Device)>(&mut self, mut f: F) {
+        for bus in self.buses.iter_mut() {
+            bus.iter_devices_mut(&mut f);
+        }
+    }
+}
+
+impl Bus {
+    /// Depth-first [`DeviceIter`] over all [`Device`]s on the bus with their depth in the tree
+    pub fn iter_devices(&self) -> DeviceIter<'_> {
+        DeviceIter::new(self.devices.iter().flatten(), 0)
+    }
+
+    /// Call `f` with each [`Device`] on the bus and its depth in the tree, depth-first
+    pub fn iter_devices_mut<F: FnMut(usize, &mut Device)>(&mut self, mut f: F) {
+        for device in self.devices.iter_mut().flatten() {
+            visit_device_mut(device, 0, &mut f);
+        }
+    }
+}
+
+impl Device {
+    /// Depth-first [`DeviceIter`] over the device, at depth 0, and all devices below it
+    pub fn iter_devices(&self) -> DeviceIter<'_> {
+        DeviceIter::new(std::iter::once(self), 0)
+    }
+
+    /// Call `f` with the device, at depth 0, and each device below it, depth-first
+    pub fn iter_devices_mut<F: FnMut(usize, &mut Device)>(&mut self, mut f: F) {
+        visit_device_mut(self, 0, &mut f);
+    }
+}
@@ -264,6 +264,7 @@ impl LibUsbProfiler {
                 extra: extra_desc,
                 internal: InternalData::default(),
                 endpoint_path: Some(endpoint_path),
+                device_speed: None,
             });
         }
 
@@ -299,6 +300,9 @@ impl LibUsbProfiler {
                     sub_class: interface_desc.sub_class_code(),
                     protocol: interface_desc.protocol_code(),
                     alt_setting: interface_desc.setting_number(),
+                    active: get_sysfs_string(&path, "bAlternateSetting")
+                        .and_then(|s| s.parse::<u8>().ok())
+                        .map(|a| a == interface_desc.setting_number()),
                     driver: get_sysfs_readlink(&path, "driver")
                         .or_else(|| get_udev_driver_name(&path).ok().flatten()),
                     syspath: get_syspath(&path).or_else(|| get_udev_syspath(&path).ok().flatten()),
@@ -337,6 +341,7 @@ impl LibUsbProfiler {
     ) -> Result<Vec<usb::Configuration>> {
         // Retrieve the current configuration (if available)
         let cur_config = get_sysfs_configuration_string(&sp_device.sysfs_name());
+        let active_config = handle.handle.active_configuration().ok();
         let mut ret: Vec<usb::Configuration> = Vec::new();
 
         for n in 0..device_desc.num_configurations() {
@@ -345,15 +350,11 @@ impl LibUsbProfiler {
                 Err(_) => continue,
             };
 
-            let mut attributes = Vec::new();
-            if config_desc.remote_wakeup() {
-                attributes.push(usb::ConfigAttributes::RemoteWakeup);
-            }
-            if config_desc.self_powered() {
-                attributes.push(usb::ConfigAttributes::SelfPowered);
-            } else {
-                attributes.push(usb::ConfigAttributes::BusPowered);
-            }
+            // rusb only exposes the self powered and remote wakeup bits of bmAttributes
+            let attributes = usb::ConfigAttributes::attributes_from_value(
+                ((config_desc.self_powered() as u8) << 6)
+                    | ((config_desc.remote_wakeup() as u8) << 5),
+            );
 
             // Check if we have a cached iConfiguration string
             let config_name = if let Some((config_num, ref config_name)) = cur_config {
@@ -395,6 +396,7 @@ impl LibUsbProfiler {
                 extra: self
                     .build_config_descriptor_extra(handle, config_desc.extra().to_vec())
                     .ok(),
+                active: active_config.map(|n| n == config_desc.number()),
                 internal: Default::default(),
             });
         }
@@ -454,6 +456,7 @@ impl LibUsbProfiler {
             qualifier: None,
             hub: None,
             negotiated_speed: Some(usb::Speed::from(device.speed())),
+            power_state: get_sysfs_string(&sysfs_name, "power/runtime_status"),
         };
 
         // Get device specific stuff: bos, hub, dualspeed, debug and status
@@ -634,10 +637,14 @@ impl LibUsbProfiler {
                     qualifier: None,
                     hub: None,
                     negotiated_speed: Some(usb::Speed::from(device.speed())),
+                    power_state: get_sysfs_string(&sysfs_name, "power/runtime_status"),
                 });
             }
         }
 
+        // not serialized so set here rather than when printing
+        sp_device.update_endpoint_speeds();
+
         Ok(sp_device)
     }
 }
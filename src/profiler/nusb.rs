@@ -387,6 +387,7 @@ impl NusbProfiler {
                     .flatten(),
                 internal: InternalData::default(),
                 endpoint_path: Some(endpoint_path),
+                device_speed: None,
             });
         }
 
@@ -430,6 +431,9 @@ impl NusbProfiler {
                     sub_class: interface_alt.subclass(),
                     protocol: interface_alt.protocol(),
                     alt_setting: interface_alt.alternate_setting(),
+                    active: get_sysfs_string(&path, "bAlternateSetting")
+                        .and_then(|s| s.parse::<u8>().ok())
+                        .map(|a| a == interface_alt.alternate_setting()),
                     driver: get_sysfs_readlink(&path, "driver")
                         .or_else(|| get_udev_driver_name(&path).ok().flatten()),
                     syspath: get_syspath(&path).or_else(|| get_udev_syspath(&path).ok().flatten()),
@@ -465,20 +469,14 @@ impl NusbProfiler {
         device_desc: &usb::DeviceDescriptor,
     ) -> Result<Vec<usb::Configuration>> {
         let mut ret: Vec<usb::Configuration> = Vec::new();
+        let active_config = device
+            .handle
+            .active_configuration()
+            .ok()
+            .map(|c| c.configuration_value());
 
         for c in device.handle.configurations() {
-            let mut attributes = Vec::new();
-            if c.attributes() & 0x10 != 0 {
-                attributes.push(usb::ConfigAttributes::BatteryPowered);
-            }
-            if c.attributes() & 0x20 != 0 {
-                attributes.push(usb::ConfigAttributes::RemoteWakeup);
-            }
-            if c.attributes() & 0x40 != 0 {
-                attributes.push(usb::ConfigAttributes::SelfPowered);
-            } else {
-                attributes.push(usb::ConfigAttributes::BusPowered);
-            }
+            let attributes = usb::ConfigAttributes::attributes_from_value(c.attributes());
 
             let config_desc = c.as_bytes();
             let config_extra = c
@@ -516,6 +514,7 @@ impl NusbProfiler {
                 extra: self
                     .build_config_descriptor_extra(device, config_extra)
                     .ok(),
+                active: active_config.map(|n| n == c.configuration_value()),
                 internal: InternalData::default(),
             });
         }
@@ -586,6 +585,7 @@ impl NusbProfiler {
             qualifier: None,
             hub: None,
             negotiated_speed: device.handle.speed().map(usb::Speed::from),
+            power_state: get_sysfs_string(&sysfs_name, "power/runtime_status"),
         };
 
         // Get device specific stuff: bos, hub, dualspeed, debug and status
@@ -646,6 +646,7 @@ impl NusbProfiler {
                 qualifier: None,
                 hub: None,
                 negotiated_speed: None,
+                power_state: get_sysfs_string(sysfs_name, "power/runtime_status"),
             }
         };
 
@@ -692,6 +693,9 @@ impl NusbProfiler {
             }
         }
 
+        // not serialized so set here rather than when printing
+        sp_device.update_endpoint_speeds();
+
         Ok(sp_device)
     }
 }
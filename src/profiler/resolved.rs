@@ -0,0 +1,122 @@
+//! JSON of a [`SystemProfile`], [`Bus`] or [`Device`] with names resolved from the Linux USB IDs repository inline, for `--json-resolved`
+//!
+//! Each device object gains `vendor_name`, `product_name`, `class_name`, `sub_class_name` and `protocol_name` and each interface object the class names, so consumers don't need their own copy of usb.ids. Like the other fields, names that are not found are not included.
+//!
+//! ```
+//! use cyme::profiler::read_json_dump;
+//!
+//! let spusb = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+//! let json = spusb.get_node_by_str("2-2.1").unwrap().resolved_json();
+//! assert_eq!(json["vendor_name"], "SEGGER");
+//! assert_eq!(json["class_name"], "Miscellaneous Device");
+//! assert_eq!(
+//!     json["extra"]["configurations"][0]["interfaces"][0]["class_name"],
+//!     "Communications"
+//! );
+//! ```
+use serde_json::{Map, Value};
+use usb_ids::{self, FromId};
+
+use super::{Bus, Device, SystemProfile};
+use crate::usb::Interface;
+
+/// Insert the `names` that are present into `map`
+fn insert_names(map: &mut Map<String, Value>, names: &[(&str, Option<&str>)]) {
+    for (key, name) in names {
+        if let Some(name) = name {
+            map.insert(key.to_string(), Value::from(*name));
+        }
+    }
+}
+
+fn resolve_interface(interface: &Interface, value: &mut Value) {
+    if let Value::Object(map) = value {
+        insert_names(
+            map,
+            &[
+                ("class_name", interface.class_name()),
+                ("sub_class_name", interface.sub_class_name()),
+                ("protocol_name", interface.protocol_name()),
+            ],
+        );
+    }
+}
+
+/// Replace each of `devices` serialized in `value` with its [`Device::resolved_json`]
+fn resolve_devices(devices: Option<&Vec<Device>>, value: Option<&mut Value>) {
+    if let (Some(devices), Some(Value::Array(values))) = (devices, value) {
+        for (device, value) in devices.iter().zip(values.iter_mut()) {
+            *value = device.resolved_json();
+        }
+    }
+}
+
+impl Device {
+    /// Vendor name of `vendor_id` from the Linux USB IDs repository
+    pub fn usb_ids_vendor_name(&self) -> Option<&str> {
+        self.vendor_id
+            .and_then(usb_ids::Vendor::from_id)
+            .map(|v| v.name())
+    }
+
+    /// Product name of `vendor_id` and `product_id` from the Linux USB IDs repository
+    pub fn usb_ids_product_name(&self) -> Option<&str> {
+        match (self.vendor_id, self.product_id) {
+            (Some(vid), Some(pid)) => usb_ids::Device::from_vid_pid(vid, pid).map(|d| d.name()),
+            _ => None,
+        }
+    }
+
+    /// Serialize the device as for `--json` with USB IDs names of it, its interfaces and its child devices inline
+    pub fn resolved_json(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or(Value::Null);
+        if let Value::Object(map) = &mut value {
+            insert_names(
+                map,
+                &[
+                    ("vendor_name", self.usb_ids_vendor_name()),
+                    ("product_name", self.usb_ids_product_name()),
+                    ("class_name", self.class_name()),
+                    ("sub_class_name", self.sub_class_name()),
+                    ("protocol_name", self.protocol_name()),
+                ],
+            );
+            resolve_devices(self.devices.as_ref(), map.get_mut("devices"));
+
+            let configs = map
+                .get_mut("extra")
+                .and_then(|e| e.get_mut("configurations"))
+                .and_then(|c| c.as_array_mut());
+            if let (Some(extra), Some(configs)) = (self.extra.as_ref(), configs) {
+                for (config, value) in extra.configurations.iter().zip(configs.iter_mut()) {
+                    if let Some(Value::Array(interfaces)) = value.get_mut("interfaces") {
+                        for (interface, value) in config.interfaces.iter().zip(interfaces) {
+                            resolve_interface(interface, value);
+                        }
+                    }
+                }
+            }
+        }
+
+        value
+    }
+}
+
+impl Bus {
+    /// Serialize the bus as for `--json` with each [`Device::resolved_json`]
+    pub fn resolved_json(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or(Value::Null);
+        resolve_devices(self.devices.as_ref(), value.get_mut("devices"));
+        value
+    }
+}
+
+impl SystemProfile {
+    /// Serialize as for `--json` with each [`Device::resolved_json`]
+    pub fn resolved_json(&self) -> Value {
+        Value::Object(Map::from_iter([(
+            "buses".to_string(),
+            Value::Array(self.buses.iter().map(|b| b.resolved_json()).collect()),
+        )]))
+    }
+}
@@ -0,0 +1,256 @@
+//! JSON Schema for the [`SystemProfile`](super::SystemProfile) output of `cyme --json`
+//!
+//! The schema is written by hand to follow the serde model of [`SystemProfile`](super::SystemProfile), [`Bus`](super::Bus) and [`Device`](super::Device), including the `#[serde(alias)]` property names used when reading macOS system_profiler output. Tools consuming `cyme --json` can use it to validate output in CI.
+//!
+//! ```
+//! let schema = cyme::profiler::schema::json_schema();
+//! assert_eq!(schema["$defs"]["Bus"]["properties"]["pci_vendor"]["type"][0], "integer");
+//! ```
+use serde_json::{json, Value};
+
+/// JSON Schema draft used by [`json_schema`]
+pub const SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Number fields are serialized as integers but also deserialized from strings, as system_profiler does
+fn number(description: &str) -> Value {
+    json!({
+        "type": ["integer", "string"],
+        "description": description,
+    })
+}
+
+fn string(description: &str) -> Value {
+    json!({
+        "type": "string",
+        "description": description,
+    })
+}
+
+fn devices() -> Value {
+    json!({
+        "type": "array",
+        "description": "Devices attached",
+        "items": { "$ref": "#/$defs/Device" },
+    })
+}
+
+fn system_profile() -> Value {
+    json!({
+        "type": "object",
+        "description": "Root of the USB system profile",
+        "properties": {
+            "buses": {
+                "type": "array",
+                "description": "USB buses on the system",
+                "items": { "$ref": "#/$defs/Bus" },
+            },
+            "SPUSBDataType": { "$ref": "#/$defs/SystemProfile/properties/buses" },
+        },
+        "anyOf": [
+            { "required": ["buses"] },
+            { "required": ["SPUSBDataType"] },
+        ],
+    })
+}
+
+fn bus() -> Value {
+    json!({
+        "type": "object",
+        "description": "USB bus returned from system_profiler or profiler",
+        "properties": {
+            "name": string("System internal bus name based on Root Hub device name"),
+            "_name": { "$ref": "#/$defs/Bus/properties/name" },
+            "host_controller": string("System internal bus provider name"),
+            "host_controller_vendor": string("Vendor name of PCI Host Controller from pci.ids"),
+            "host_controller_device": string("Device name of PCI Host Controller from pci.ids"),
+            "pci_vendor": number("PCI vendor ID (VID)"),
+            "pci_device": number("PCI device ID (PID)"),
+            "pci_revision": number("PCI revision ID"),
+            "pci_class": number("PCI class code of the host controller"),
+            "usb_bus_number": number("Number of bus on system"),
+            "devices": devices(),
+            "_items": { "$ref": "#/$defs/Bus/properties/devices" },
+        },
+        "allOf": [
+            { "anyOf": [{ "required": ["name"] }, { "required": ["_name"] }] },
+            { "required": ["host_controller"] },
+        ],
+    })
+}
+
+fn device() -> Value {
+    json!({
+        "type": "object",
+        "description": "USB device data based on JSON object output from system_profiler but now used for other platforms",
+        "properties": {
+            "name": string("Device name from descriptor"),
+            "_name": { "$ref": "#/$defs/Device/properties/name" },
+            "vendor_id": number("Unique vendor identifier - purchased from USB IF"),
+            "product_id": number("Vendor unique product identifier"),
+            "location_id": { "$ref": "#/$defs/DeviceLocation" },
+            "serial_num": string("Device serial number as reported by descriptor"),
+            "manufacturer": string("The device manufacturer as provided in descriptor"),
+            "bcd_device": string("Device version as 'M.mm', such as '6.00'"),
+            "bcd_usb": string("USB version compliance as 'M.mm', such as '2.00'"),
+            "bus_power": number("macOS system_profiler only - actually bus current in mA not power!"),
+            "bus_power_used": number("macOS system_profiler only - actually bus current used in mA not power!"),
+            "device_speed": string("Advertised device capable speed, such as '480.0 Mb/s' or 'high_speed'"),
+            "extra_current_used": number("macOS system_profiler only - actually bus current used in mA not power!"),
+            "devices": devices(),
+            "_items": { "$ref": "#/$defs/Device/properties/devices" },
+            "class": string("Device class, such as 'hub'"),
+            "sub_class": number("Device sub-class"),
+            "protocol": number("Device protocol"),
+            "extra": { "$ref": "#/$defs/DeviceExtra" },
+            "last_event": { "$ref": "#/$defs/DeviceEvent" },
+        },
+        "allOf": [
+            { "anyOf": [{ "required": ["name"] }, { "required": ["_name"] }] },
+            { "required": ["location_id"] },
+        ],
+    })
+}
+
+fn device_location() -> Value {
+    json!({
+        "description": "Location of device on bus; system_profiler uses a 'LocationReg / DeviceNo' string",
+        "oneOf": [
+            {
+                "type": "object",
+                "properties": {
+                    "bus": { "type": "integer", "description": "Number of bus attached too" },
+                    "tree_positions": {
+                        "type": "array",
+                        "description": "Length is depth in tree and position at each branch, empty is bus controller",
+                        "items": { "type": "integer" },
+                    },
+                    "number": { "type": "integer", "description": "Device number on bus, generally not related to tree" },
+                },
+                "required": ["bus", "tree_positions", "number"],
+            },
+            { "type": "string" },
+        ],
+    })
+}
+
+fn device_extra() -> Value {
+    json!({
+        "type": "object",
+        "description": "Extra USB device data for verbose printing",
+        "properties": {
+            "max_packet_size": { "type": "integer", "description": "Maximum packet size in bytes" },
+            "driver": string("Driver obtained from udev on Linux only"),
+            "syspath": string("syspath obtained from udev on Linux only"),
+            "vendor": string("Vendor name from usb_ids VID lookup"),
+            "product_name": string("Product name from usb_ids VIDPID lookup"),
+            "string_indexes": {
+                "type": "array",
+                "description": "Indexes to strings (iProduct, iManufacturer, iSerialNumber)",
+                "items": { "type": "integer" },
+                "minItems": 3,
+                "maxItems": 3,
+            },
+            "configurations": {
+                "type": "array",
+                "description": "Device configurations with interfaces and endpoints",
+                "items": { "type": "object" },
+            },
+            "status": { "type": "integer", "description": "Device status" },
+            "debug": { "type": "object", "description": "Debug descriptor if present" },
+            "binary_object_store": { "type": "object", "description": "Binary Object Store (BOS) descriptor if present" },
+            "qualifier": { "type": "object", "description": "Device qualifier descriptor if present" },
+            "hub": { "type": "object", "description": "Hub descriptor if present (is a hub)" },
+            "negotiated_speed": string("Speed that the device is operating at"),
+            "power_state": string("Runtime power management status from sysfs on Linux only"),
+        },
+        "required": ["max_packet_size", "configurations"],
+    })
+}
+
+fn device_event() -> Value {
+    let event = |name: &str| {
+        json!({
+            "type": "object",
+            "properties": { name: { "type": "string", "format": "date-time" } },
+            "required": [name],
+            "additionalProperties": false,
+        })
+    };
+
+    json!({
+        "description": "Last event for device, used by the watch feature",
+        "oneOf": [event("profiled"), event("connected"), event("disconnected")],
+    })
+}
+
+/// Returns the JSON Schema describing [`SystemProfile`](super::SystemProfile) and its [`Bus`](super::Bus) and [`Device`](super::Device) children as output by `cyme --json`
+///
+/// The root validates a [`SystemProfile`](super::SystemProfile); the individual types are under `$defs` so a flattened device list can be validated against `#/$defs/Device`.
+pub fn json_schema() -> Value {
+    let mut schema = system_profile();
+    let root = schema.as_object_mut().expect("schema root is an object");
+    root.insert("$schema".into(), SCHEMA_DRAFT.into());
+    root.insert("title".into(), "cyme SystemProfile".into());
+    root.insert(
+        "$defs".into(),
+        json!({
+            "SystemProfile": system_profile(),
+            "Bus": bus(),
+            "Device": device(),
+            "DeviceLocation": device_location(),
+            "DeviceExtra": device_extra(),
+            "DeviceEvent": device_event(),
+        }),
+    );
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiler::read_json_dump;
+
+    fn assert_keys_in(value: &Value, schema: &Value, def: &str) {
+        let properties = schema["$defs"][def]["properties"]
+            .as_object()
+            .unwrap_or_else(|| panic!("{} has no properties", def));
+        for key in value.as_object().unwrap().keys() {
+            assert!(
+                properties.contains_key(key),
+                "serialized {} field '{}' missing from schema",
+                def,
+                key
+            );
+        }
+    }
+
+    fn assert_device_keys(device: &Value, schema: &Value) {
+        assert_keys_in(device, schema, "Device");
+        if let Some(extra) = device.get("extra") {
+            assert_keys_in(extra, schema, "DeviceExtra");
+        }
+        for d in device["devices"].as_array().into_iter().flatten() {
+            assert_device_keys(d, schema);
+        }
+    }
+
+    #[test]
+    fn test_schema_matches_serialized_fields() {
+        let schema = json_schema();
+        for path in [
+            "./tests/data/cyme_libusb_linux_tree.json",
+            "./tests/data/cyme_libusb_macos_tree.json",
+        ] {
+            let spusb = read_json_dump(path).unwrap();
+            let value = serde_json::to_value(&spusb).unwrap();
+            assert_keys_in(&value, &schema, "SystemProfile");
+            for bus in value["buses"].as_array().unwrap() {
+                assert_keys_in(bus, &schema, "Bus");
+                for d in bus["devices"].as_array().into_iter().flatten() {
+                    assert_device_keys(d, &schema);
+                }
+            }
+        }
+    }
+}
@@ -26,6 +26,27 @@ pub struct SystemProfile {
 }
 
 impl SystemProfile {
+    /// Build a partial [`SystemProfile`] from the text output of `lsusb -v`
+    ///
+    /// Only fields that lsusb prints for the device, configuration, interface and endpoint descriptors are populated, others are `None`. The lsusb text does not include the port of devices so they are placed directly on their bus with the device number as the port.
+    pub fn from_lsusb_verbose(text: &str) -> Result<Self> {
+        crate::lsusb::parse::parse_verbose(text).map(|mut sp| {
+            sp.update_endpoint_speeds();
+            sp
+        })
+    }
+
+    /// Calls [`Device::update_endpoint_speeds`] on the devices of all buses
+    ///
+    /// The endpoint device speed is not serialized so this is done when reading a dump
+    pub fn update_endpoint_speeds(&mut self) {
+        for bus in self.buses.iter_mut() {
+            if let Some(devices) = bus.devices.as_mut() {
+                devices.iter_mut().for_each(|d| d.update_endpoint_speeds());
+            }
+        }
+    }
+
     /// Returns total number of devices across all buses
     pub fn len(&self) -> usize {
         self.buses.iter().map(|b| b.len()).sum()
@@ -329,6 +350,7 @@ pub(crate) struct PciInfo {
     pub vendor_id: u16,
     pub product_id: u16,
     pub revision: u16,
+    pub class: Option<u32>,
 }
 
 /// USB bus returned from system_profiler but now used for other platforms.
@@ -357,6 +379,9 @@ pub struct Bus {
     #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
     /// PCI Revsision ID
     pub pci_revision: Option<u16>,
+    /// PCI class code: base class, sub-class and programming interface bytes such as 0x0c0330 for an xHCI USB controller
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub pci_class: Option<u32>,
     /// Number of bus on system
     #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
     pub usb_bus_number: Option<u8>,
@@ -390,10 +415,16 @@ impl TryFrom<Device> for Bus {
         }
 
         // attempt to get PCI info from platform
-        let (pci_vendor, pci_device, pci_revision) = match platform::pci_info_from_device(&device) {
-            Some(v) => (Some(v.vendor_id), Some(v.product_id), Some(v.revision)),
-            None => (None, None, None),
-        };
+        let (pci_vendor, pci_device, pci_revision, pci_class) =
+            match platform::pci_info_from_device(&device) {
+                Some(v) => (
+                    Some(v.vendor_id),
+                    Some(v.product_id),
+                    Some(v.revision),
+                    v.class,
+                ),
+                None => (None, None, None, None),
+            };
 
         let (host_controller_vendor, host_controller_device) =
             if let (Some(v), Some(p)) = (pci_vendor, pci_device) {
@@ -417,6 +448,7 @@ impl TryFrom<Device> for Bus {
             pci_device: pci_device.filter(|v| *v != 0xffff && *v != 0),
             pci_vendor: pci_vendor.filter(|v| *v != 0xffff && *v != 0),
             pci_revision: pci_revision.filter(|v| *v != 0xffff && *v != 0),
+            pci_class,
             usb_bus_number: Some(device.location_id.bus),
             devices: device.devices,
             ..Default::default()
@@ -447,6 +479,25 @@ impl From<u8> for Bus {
 
 /// Returns of Vec of devices in the Bus as a reference
 impl Bus {
+    /// PCI sub-class and programming interface names of the host controller from pci.ids, e.g. "USB controller / XHCI"
+    ///
+    /// ```
+    /// use cyme::profiler::Bus;
+    ///
+    /// let mut bus = Bus::default();
+    /// bus.pci_class = Some(0x0c0330);
+    /// assert_eq!(bus.host_controller_type().unwrap(), "USB controller / XHCI");
+    /// ```
+    pub fn host_controller_type(&self) -> Option<String> {
+        let class = self.pci_class?;
+        let [_, base, sub, prog_if] = class.to_be_bytes();
+        let subclass = pci_ids::Subclass::from_cid_sid(base, sub)?;
+        match subclass.prog_ifs().find(|p| p.id() == prog_if) {
+            Some(p) => Some(format!("{} / {}", subclass.name(), p.name())),
+            None => Some(subclass.name().to_string()),
+        }
+    }
+
     /// Returns total number of devices in the bus
     pub fn len(&self) -> usize {
         self.devices
@@ -474,11 +525,7 @@ impl Bus {
     ///
     /// Note that whilst `Vec` of references is flat, the `Device`s still contain a `devices` `Vec` where the references point; recursive functions on the returned `Vec` will produce weird results
     pub fn flattened_devices(&self) -> Vec<&Device> {
-        if let Some(devices) = &self.devices {
-            devices.iter().flat_map(|d| d.flatten()).collect()
-        } else {
-            Vec::new()
-        }
+        self.iter_devices().map(|(_, d)| d).collect()
     }
 
     /// Whether the bus has no [`Device`]s
@@ -1105,10 +1152,9 @@ impl fmt::Display for DeviceSpeed {
         match self {
             DeviceSpeed::SpeedValue(v) => {
                 let dv = NumericalUnit::<f32>::from(v);
-                if f.alternate() && dv.description.is_some() {
-                    write!(f, "{}", dv.description.unwrap())
-                } else {
-                    write!(f, "{dv:.1}")
+                match dv.description {
+                    Some(ref d) if f.alternate() => write!(f, "{d}"),
+                    _ => write!(f, "{dv:.1}"),
                 }
             }
             DeviceSpeed::Description(v) => {
@@ -1135,6 +1181,41 @@ impl FromStr for DeviceSpeed {
     }
 }
 
+/// Current budget against draw of a [`Device`] from [`Device::power_summary`]; all currents are mA
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PowerSummary {
+    /// Current available from the upstream port including extra operating current; macOS only
+    pub available: Option<u32>,
+    /// Current drawn by the device itself
+    pub drawn: Option<u32>,
+    /// Current drawn by the downstream devices
+    pub downstream: u32,
+    /// Whether the device is self powered if known
+    pub self_powered: Option<bool>,
+    /// Number of downstream devices drawing more than available from their port
+    pub over_budget_ports: usize,
+}
+
+impl PowerSummary {
+    /// Current taken from the upstream port: the device draw plus downstream draw if it is known to be bus powered
+    pub fn total(&self) -> Option<u32> {
+        match self.self_powered {
+            Some(false) => Some(self.drawn.unwrap_or(0) + self.downstream),
+            _ => self.drawn,
+        }
+    }
+
+    /// Draws more than available from the upstream port
+    pub fn is_over_budget(&self) -> bool {
+        matches!((self.total(), self.available), (Some(t), Some(a)) if t > a)
+    }
+
+    /// Is over budget or has downstream devices that are
+    pub fn has_over_budget(&self) -> bool {
+        self.is_over_budget() || self.over_budget_ports > 0
+    }
+}
+
 /// Events used by the watch feature
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -1179,6 +1260,39 @@ impl DeviceEvent {
     pub fn format(&self, fmt: &str) -> String {
         self.time().format(fmt).to_string()
     }
+
+    /// Format the event time relative to `now`, such as "C: 3s ago"
+    ///
+    /// Less than a second is "now" and events after `now` due to clock skew are "0s ago". Only the largest whole unit of days, hours, minutes or seconds is shown.
+    ///
+    /// ```
+    /// use cyme::profiler::DeviceEvent;
+    ///
+    /// let now = chrono::Local::now();
+    /// let event = DeviceEvent::Connected(now - chrono::Duration::seconds(3));
+    /// assert_eq!(event.format_relative(now), "C: 3s ago");
+    /// let event = DeviceEvent::Disconnected(now - chrono::Duration::minutes(90));
+    /// assert_eq!(event.format_relative(now), "D: 1h ago");
+    /// ```
+    pub fn format_relative(&self, now: chrono::DateTime<chrono::Local>) -> String {
+        let prefix = match self {
+            DeviceEvent::Profiled(_) => "P",
+            DeviceEvent::Connected(_) => "C",
+            DeviceEvent::Disconnected(_) => "D",
+        };
+        let elapsed = now.signed_duration_since(self.time());
+        if elapsed < chrono::Duration::zero() {
+            return format!("{prefix}: 0s ago");
+        }
+        let s = elapsed.num_seconds();
+        match s {
+            0 => format!("{prefix}: now"),
+            1..=59 => format!("{prefix}: {s}s ago"),
+            60..=3599 => format!("{prefix}: {}m ago", s / 60),
+            3600..=86399 => format!("{prefix}: {}h ago", s / 3600),
+            _ => format!("{prefix}: {}d ago", s / 86400),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -1186,6 +1300,16 @@ impl DeviceEvent {
 pub struct InternalData {
     pub(crate) expanded: bool,
     pub(crate) hidden: bool,
+    pub(crate) duplicates: usize,
+    /// Devices of a [`Bus`] have been reversed in parse order, so that [`crate::display::prepare`] reverses [`crate::display::Sort::NoSort`] only once in watch mode
+    pub(crate) reversed: bool,
+    /// Fields have been masked by [`crate::display::mask_device_fields`], which only masks once
+    pub(crate) masked: bool,
+    /// Port path masked once by [`crate::display::mask_device_fields`] so that every render shows the same value
+    pub(crate) masked_port_path: Option<String>,
+    /// Summary from before the device was flattened, when the downstream devices were known
+    #[serde(skip)]
+    pub(crate) power_summary: Option<PowerSummary>,
 }
 
 /// USB device data based on JSON object output from system_profiler but now used for other platforms
@@ -1275,6 +1399,141 @@ impl PartialEq for Device {
 }
 
 impl Device {
+    /// Whether the advertised `device_speed` and `extra.negotiated_speed` are both known and differ, such as a SuperSpeed device running at high speed on a USB 2 hub or cable
+    pub fn has_speed_mismatch(&self) -> bool {
+        let negotiated = self
+            .extra
+            .as_ref()
+            .and_then(|e| e.negotiated_speed.as_ref());
+        match (&self.device_speed, negotiated) {
+            (Some(DeviceSpeed::SpeedValue(d)), Some(n)) => {
+                *d != Speed::Unknown && *n != Speed::Unknown && d != n
+            }
+            _ => false,
+        }
+    }
+
+    /// USB specification generation such as "USB 3.2 Gen 2" from [`Version::usb_generation`], or the bcdUSB version if not a known generation
+    ///
+    /// Uses the negotiated speed if known, otherwise the device speed
+    pub fn usb_generation(&self) -> Option<String> {
+        let speed = self
+            .extra
+            .as_ref()
+            .and_then(|e| e.negotiated_speed.as_ref())
+            .or(match &self.device_speed {
+                Some(DeviceSpeed::SpeedValue(s)) => Some(s),
+                _ => None,
+            });
+        self.bcd_usb.map(|v| {
+            v.usb_generation(speed)
+                .map_or_else(|| v.to_string(), String::from)
+        })
+    }
+
+    /// Sets the device speed on all [`Endpoint`]s so polling intervals can be computed, including child devices
+    ///
+    /// Uses the negotiated speed if known, otherwise the device speed
+    pub fn update_endpoint_speeds(&mut self) {
+        let speed = self
+            .extra
+            .as_ref()
+            .and_then(|e| e.negotiated_speed.to_owned())
+            .or_else(|| match &self.device_speed {
+                Some(DeviceSpeed::SpeedValue(s)) => Some(s.to_owned()),
+                _ => None,
+            })
+            .filter(|s| *s != Speed::Unknown);
+
+        if let Some(extra) = self.extra.as_mut() {
+            for config in extra.configurations.iter_mut() {
+                for interface in config.interfaces.iter_mut() {
+                    for endpoint in interface.endpoints.iter_mut() {
+                        endpoint.device_speed = speed.to_owned();
+                    }
+                }
+            }
+        }
+
+        if let Some(devices) = self.devices.as_mut() {
+            devices.iter_mut().for_each(|d| d.update_endpoint_speeds());
+        }
+    }
+
+    /// The active [`Configuration`] if it has [`DeviceExtra`], falling back to the first if the active configuration is not known
+    pub fn active_configuration(&self) -> Option<&Configuration> {
+        self.extra.as_ref().and_then(|e| {
+            e.configurations
+                .iter()
+                .find(|c| c.active == Some(true))
+                .or(e.configurations.first())
+        })
+    }
+
+    /// Maximum power in mA of the [`Device::active_configuration`]
+    pub fn max_power(&self) -> Option<u32> {
+        self.active_configuration().map(|c| c.max_power.value)
+    }
+
+    /// USB-C alternate modes from the Billboard capabilities in the BOS descriptor if it has [`DeviceExtra`]
+    pub fn alternate_modes(&self) -> Vec<&bos::AlternateMode> {
+        self.extra
+            .iter()
+            .flat_map(|e| e.binary_object_store.iter())
+            .flat_map(|b| b.capabilities.iter())
+            .flat_map(|c| match c {
+                bos::BosCapability::Billboard(bc) => bc.alternate_modes.iter(),
+                _ => [].iter(),
+            })
+            .collect()
+    }
+
+    /// Distinct [`BaseClass`] of the [`Interface`]s in all [`Configuration`]s if it has [`DeviceExtra`], ordered by class code
+    pub fn interface_classes(&self) -> Vec<BaseClass> {
+        let mut classes: Vec<BaseClass> = self
+            .extra
+            .iter()
+            .flat_map(|e| e.configurations.iter())
+            .flat_map(|c| c.interfaces.iter())
+            .map(|i| i.class)
+            .collect();
+        classes.sort_by_key(|c| u8::from(*c));
+        classes.dedup();
+        classes
+    }
+
+    /// Self powered from the attributes of the [`Device::active_configuration`]
+    pub fn is_self_powered(&self) -> Option<bool> {
+        self.active_configuration()
+            .map(|c| c.attributes.contains(&ConfigAttributes::SelfPowered))
+    }
+
+    /// Current budget against draw for the device and the devices down its hub chain
+    ///
+    /// The available current is only known from macOS system_profiler, other profilers only have the draw. Flattening with [`Device::into_flattened`] keeps the summary from before the downstream devices were removed.
+    pub fn power_summary(&self) -> PowerSummary {
+        if let Some(summary) = self.internal.power_summary {
+            return summary;
+        }
+
+        let mut summary = PowerSummary {
+            available: self
+                .bus_power
+                .map(|p| p as u32 + self.extra_current_used.unwrap_or(0) as u32),
+            drawn: self.bus_power_used.map(u32::from).or(self.max_power()),
+            self_powered: self.is_self_powered(),
+            ..Default::default()
+        };
+
+        for d in self.devices.iter().flatten() {
+            let ds = d.power_summary();
+            summary.downstream += ds.total().unwrap_or(0);
+            summary.over_budget_ports += ds.over_budget_ports + ds.is_over_budget() as usize;
+        }
+
+        summary
+    }
+
     /// Does the device have child devices; `devices` is Some and > 0
     pub fn has_devices(&self) -> bool {
         match &self.devices {
@@ -1509,6 +1768,18 @@ impl Device {
             || self.class.as_ref().is_some_and(|c| *c == BaseClass::Hub)
     }
 
+    /// Number of downstream ports from the hub descriptor if the device is [`BaseClass::Hub`]
+    ///
+    /// None if not a hub class device or the hub descriptor was not read; requires extra data
+    pub fn hub_ports(&self) -> Option<u8> {
+        if self.class != Some(BaseClass::Hub) {
+            return None;
+        }
+        self.extra
+            .as_ref()
+            .and_then(|e| e.hub.as_ref().map(|h| h.num_ports))
+    }
+
     /// [`PortPath`] of the [`Device`]
     pub fn port_path(&self) -> PortPath {
         self.location_id.clone().into()
@@ -1800,6 +2071,9 @@ impl Device {
     /// Similar to `flatten` but flattens in place rather than returning references so is destructive
     pub fn into_flattened(mut self) -> Vec<Device> {
         let mut ret: Vec<Device> = Vec::with_capacity(self.len());
+        if self.devices.as_ref().is_some_and(|d| !d.is_empty()) {
+            self.internal.power_summary = Some(self.power_summary());
+        }
         if let Some(mut d) = self.devices.take() {
             while let Some(child) = d.pop() {
                 ret.extend(child.into_flattened());
@@ -1867,6 +2141,11 @@ impl Device {
         self.internal.expanded
     }
 
+    /// Number of identical devices following this one collapsed into it by [`crate::display::collapse_duplicate_devices`]
+    pub fn duplicates(&self) -> usize {
+        self.internal.duplicates
+    }
+
     /// Toggle the expanded state of the device
     pub fn toggle_expanded(&mut self) {
         self.internal.expanded = !self.internal.expanded;
@@ -1984,12 +2263,18 @@ pub struct Filter {
     pub bus: Option<u8>,
     /// Retain only devices with this device number
     pub number: Option<u8>,
+    /// Retain only devices at or below this port path in the tree
+    pub port_path: Option<PortPath>,
     /// Retain only devices with name.contains(name)
     pub name: Option<String>,
     /// retain only devices with serial.contains(serial)
     pub serial: Option<String>,
     /// retain only device of BaseClass class
     pub class: Option<BaseClass>,
+    /// Retain only devices with a device or interface driver containing driver - Linux only
+    pub driver: Option<String>,
+    /// Retain only devices with a known device speed that differs from the known negotiated speed
+    pub speed_mismatch: bool,
     /// Exclude empty buses in the tree
     pub exclude_empty_bus: bool,
     /// Exclude empty hubs in the tree
@@ -2060,6 +2345,45 @@ pub type USBFilter = Filter;
 /// assert_eq!(flattened.first().unwrap().name, "Black Magic Probe  v1.8.2");
 /// ```
 ///
+/// Filter devices by port path, retaining the subtree below it
+///
+/// ```
+/// use cyme::profiler::*;
+///
+/// # let mut spusb = read_json_dump(&"./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let filter = Filter {
+///     port_path: Some("2-2".parse().unwrap()),
+///     ..Default::default()
+/// };
+/// filter.retain_buses(&mut spusb.buses);
+/// let flattened = spusb.flattened_devices();
+/// // the hub and the devices on it
+/// assert!(flattened.iter().all(|d| d.port_path().to_string().starts_with("2-2")));
+/// assert!(flattened.iter().any(|d| d.name == "J-Link"));
+/// assert_eq!(spusb.buses.len(), 1);
+/// ```
+///
+/// Filter devices with a speed mismatch
+///
+/// ```
+/// use cyme::profiler::*;
+///
+/// # let mut spusb = read_json_dump(&"./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// // full speed J-Link reported as operating at high speed
+/// let path = cyme::usb::PortPath::try_from("2-2.1").unwrap();
+/// let device = spusb.get_node_mut(&path).unwrap();
+/// device.extra.as_mut().unwrap().negotiated_speed = Some(cyme::usb::Speed::HighSpeed);
+/// let filter = Filter {
+///     speed_mismatch: true,
+///     ..Default::default()
+/// };
+/// filter.retain_buses(&mut spusb.buses);
+/// let flattened = spusb.flattened_devices();
+/// // parent hub is retained with it
+/// assert_eq!(flattened.len(), 2);
+/// assert!(flattened.iter().any(|d| d.name == "J-Link"));
+/// ```
+///
 /// Filter devices with class
 ///
 /// ```
@@ -2077,6 +2401,29 @@ pub type USBFilter = Filter;
 /// assert_eq!(device.unwrap().name, "Black Magic Probe  v1.8.2");
 /// ```
 ///
+/// Filter devices with an interface bound to a driver
+///
+/// ```
+/// use cyme::profiler::*;
+///
+/// # let mut spusb = read_json_dump(&"./tests/data/cyme_libusb_linux_tree.json").unwrap();
+/// let filter = Filter {
+///     driver: Some(String::from("usbhid")),
+///     ..Default::default()
+/// };
+/// let mut flattened = spusb.flattened_devices();
+/// filter.retain_flattened_devices_ref(&mut flattened);
+/// assert!(!flattened.is_empty());
+/// assert!(flattened.iter().all(|d| d
+///     .extra
+///     .as_ref()
+///     .unwrap()
+///     .configurations
+///     .iter()
+///     .flat_map(|c| c.interfaces.iter())
+///     .any(|i| i.driver.as_deref() == Some("usbhid"))));
+/// ```
+///
 impl Filter {
     /// Creates a new filter with defaults
     pub fn new() -> Self {
@@ -2101,10 +2448,34 @@ impl Filter {
         }
     }
 
+    /// Does `device` or any of its interfaces have a driver matching `driver`
+    fn driver_match(&self, device: &Device) -> bool {
+        if self.driver.is_none() {
+            return true;
+        }
+
+        device.extra.as_ref().is_some_and(|extra| {
+            extra
+                .driver
+                .iter()
+                .chain(
+                    extra
+                        .configurations
+                        .iter()
+                        .flat_map(|c| c.interfaces.iter().flat_map(|i| i.driver.as_ref())),
+                )
+                .any(|d| self.string_match(&self.driver, Some(d)))
+        })
+    }
+
     /// Checks whether `device` passes through filter
     pub fn is_match(&self, device: &Device) -> bool {
         (Some(device.location_id.bus) == self.bus || self.bus.is_none())
             && (Some(device.location_id.number) == self.number || self.number.is_none())
+            && self
+                .port_path
+                .as_ref()
+                .is_none_or(|p| device.port_path().is_within(p))
             && (device.vendor_id == self.vid || self.vid.is_none())
             && (device.product_id == self.pid || self.pid.is_none())
             && (self.string_match(&self.name, Some(&device.name)))
@@ -2112,6 +2483,8 @@ impl Filter {
             && self.class.as_ref().is_none_or(|fc| {
                 device.class.as_ref() == Some(fc) || device.has_interface_class(fc)
             })
+            && self.driver_match(device)
+            && (!self.speed_mismatch || device.has_speed_mismatch())
             && !(self.exclude_empty_hub && device.is_hub() && !device.has_devices())
             && (!device.is_root_hub() || self.no_exclude_root_hub)
     }
@@ -2119,6 +2492,10 @@ impl Filter {
     /// Checks whether `bus` passes through filter
     pub fn is_bus_match(&self, bus: &Bus) -> bool {
         (bus.usb_bus_number == self.bus || self.bus.is_none() || bus.usb_bus_number.is_none())
+            && self
+                .port_path
+                .as_ref()
+                .is_none_or(|p| bus.usb_bus_number.is_none_or(|n| n == p.bus()))
             && !(self.exclude_empty_bus && bus.is_empty())
     }
 
@@ -2204,7 +2581,7 @@ pub fn read_json_dump<P: AsRef<Path>>(file_path: P) -> Result<SystemProfile> {
     let mut data = String::new();
     file.read_to_string(&mut data)?;
 
-    let json_dump: SystemProfile = serde_json::from_str(&data).map_err(|e| {
+    let mut json_dump: SystemProfile = serde_json::from_str(&data).map_err(|e| {
         Error::new(
             ErrorKind::Parsing,
             &format!(
@@ -2214,6 +2591,7 @@ pub fn read_json_dump<P: AsRef<Path>>(file_path: P) -> Result<SystemProfile> {
             ),
         )
     })?;
+    json_dump.update_endpoint_speeds();
 
     Ok(json_dump)
 }
@@ -2225,7 +2603,7 @@ pub fn read_flat_json_dump<P: AsRef<Path>>(file_path: P) -> Result<Vec<Device>>
     let mut data = String::new();
     file.read_to_string(&mut data)?;
 
-    let json_dump: Vec<Device> = serde_json::from_str(&data).map_err(|e| {
+    let mut json_dump: Vec<Device> = serde_json::from_str(&data).map_err(|e| {
         Error::new(
             ErrorKind::Parsing,
             &format!(
@@ -2235,6 +2613,9 @@ pub fn read_flat_json_dump<P: AsRef<Path>>(file_path: P) -> Result<Vec<Device>>
             ),
         )
     })?;
+    json_dump
+        .iter_mut()
+        .for_each(|d| d.update_endpoint_speeds());
 
     Ok(json_dump)
 }
@@ -2376,6 +2757,22 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_read_json_dump_endpoint_speeds() {
+        let spusb = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        let endpoints: Vec<&crate::usb::Endpoint> = spusb
+            .flattened_devices()
+            .into_iter()
+            .filter(|d| matches!(d.device_speed, Some(DeviceSpeed::SpeedValue(_))))
+            .filter_map(|d| d.extra.as_ref())
+            .flat_map(|e| e.configurations.iter())
+            .flat_map(|c| c.interfaces.iter())
+            .flat_map(|i| i.endpoints.iter())
+            .collect();
+        assert!(!endpoints.is_empty());
+        assert!(endpoints.iter().all(|e| e.device_speed().is_some()));
+    }
+
     #[test]
     fn test_deserialize_device() {
         let device_json = "{
@@ -2437,6 +2834,115 @@ mod tests {
         assert_eq!(device.usb_bus_number, Some(0x00));
     }
 
+    #[test]
+    fn test_usb_generation() {
+        let mut device = Device {
+            bcd_usb: Some(Version(3, 2, 0)),
+            device_speed: Some(DeviceSpeed::SpeedValue(Speed::SuperSpeedPlus)),
+            ..Default::default()
+        };
+        assert_eq!(device.usb_generation().as_deref(), Some("USB 3.2 Gen 2"));
+        // unknown version falls back to bcdUSB
+        device.bcd_usb = Some(Version(5, 1, 0));
+        assert_eq!(device.usb_generation().as_deref(), Some("5.10"));
+        device.bcd_usb = None;
+        assert_eq!(device.usb_generation(), None);
+    }
+
+    #[test]
+    fn test_hub_ports() {
+        let hub = crate::usb::HubDescriptor::try_from(
+            [0x09, 0x29, 0x04, 0xe0, 0x00, 0x32, 0x64, 0x00, 0xff].as_slice(),
+        )
+        .unwrap();
+        let mut extra: DeviceExtra =
+            serde_json::from_str(r#"{"max_packet_size": 64, "configurations": []}"#).unwrap();
+        extra.hub = Some(hub);
+        let mut device = Device {
+            class: Some(BaseClass::Hub),
+            extra: Some(extra),
+            ..Default::default()
+        };
+        assert_eq!(device.hub_ports(), Some(4));
+        // only hub class devices
+        device.class = Some(BaseClass::Hid);
+        assert_eq!(device.hub_ports(), None);
+        // no hub descriptor
+        device.class = Some(BaseClass::Hub);
+        device.extra = None;
+        assert_eq!(device.hub_ports(), None);
+    }
+
+    #[test]
+    fn test_active_configuration_power() {
+        // bus powered 100 mA then self powered 20 mA configurations
+        let configurations = Configuration::parse_all(&[
+            0x09, 0x02, 0x09, 0x00, 0x00, 0x01, 0x00, 0x80, 0x32, 0x09, 0x02, 0x09, 0x00, 0x00,
+            0x02, 0x00, 0xc0, 0x0a,
+        ])
+        .unwrap();
+        let mut extra: DeviceExtra =
+            serde_json::from_str(r#"{"max_packet_size": 64, "configurations": []}"#).unwrap();
+        extra.configurations = configurations;
+        let mut device = Device {
+            extra: Some(extra),
+            ..Default::default()
+        };
+        // active not known so first
+        assert_eq!(device.max_power(), Some(100));
+        assert_eq!(device.is_self_powered(), Some(false));
+
+        device.extra.as_mut().unwrap().configurations[1].active = Some(true);
+        assert_eq!(device.active_configuration().map(|c| c.number), Some(2));
+        assert_eq!(device.max_power(), Some(20));
+        assert_eq!(device.is_self_powered(), Some(true));
+    }
+
+    #[test]
+    fn test_power_summary() {
+        let mut port = Device {
+            bus_power: Some(100),
+            bus_power_used: Some(500),
+            ..Default::default()
+        };
+        assert!(port.power_summary().is_over_budget());
+
+        let mut hub = Device {
+            bus_power: Some(500),
+            bus_power_used: Some(100),
+            devices: Some(vec![port.clone()]),
+            ..Default::default()
+        };
+        let summary = hub.power_summary();
+        assert_eq!(summary.downstream, 500);
+        // unknown if self powered so downstream not taken from hub port
+        assert_eq!(summary.total(), Some(100));
+        assert!(!summary.is_over_budget());
+        assert!(summary.has_over_budget());
+
+        // list mode flattens the hub but keeps the downstream draw
+        let flat = hub.clone().into_flattened();
+        assert_eq!(flat[0].power_summary(), summary);
+
+        port.bus_power = Some(500);
+        hub.devices = Some(vec![port]);
+        assert!(!hub.power_summary().has_over_budget());
+    }
+
+    #[test]
+    fn test_device_event_format_relative() {
+        let now = chrono::Local::now();
+        let ago = |ms: i64| DeviceEvent::Connected(now - chrono::Duration::milliseconds(ms));
+        assert_eq!(ago(400).format_relative(now), "C: now");
+        assert_eq!(ago(1_000).format_relative(now), "C: 1s ago");
+        assert_eq!(ago(59_999).format_relative(now), "C: 59s ago");
+        assert_eq!(ago(60_000).format_relative(now), "C: 1m ago");
+        assert_eq!(ago(2 * 86_400_000).format_relative(now), "C: 2d ago");
+        // clock skew
+        assert_eq!(ago(-5_000).format_relative(now), "C: 0s ago");
+        assert_eq!(DeviceEvent::Profiled(now).format_relative(now), "P: now");
+    }
+
     #[test]
     fn test_json_dump_read_not_panic() {
         read_json_dump("./tests/data/system_profiler_dump.json").unwrap();
@@ -77,6 +77,38 @@ impl Version {
         let Version(_, _, sub_minor) = self;
         sub_minor
     }
+
+    /// Specification generation name for a bcdUSB version, such as "USB 3.2 Gen 2x2"
+    ///
+    /// USB 3.x devices are named by the `speed` if it is a SuperSpeed speed since the Gen depends on the signalling rate, otherwise by the specification version. None if the version is not a known USB specification.
+    ///
+    /// ```
+    /// use cyme::usb::{Speed, Version};
+    ///
+    /// assert_eq!(Version(2, 1, 0).usb_generation(None), Some("USB 2.0"));
+    /// assert_eq!(Version(3, 2, 0).usb_generation(None), Some("USB 3.2"));
+    /// assert_eq!(Version(3, 2, 0).usb_generation(Some(&Speed::SuperSpeedPlusX2)), Some("USB 3.2 Gen 2x2"));
+    /// assert_eq!(Version(3, 1, 0).usb_generation(Some(&Speed::SuperSpeed)), Some("USB 3.2 Gen 1"));
+    /// // USB 3 device connected at high speed
+    /// assert_eq!(Version(3, 0, 0).usb_generation(Some(&Speed::HighSpeed)), Some("USB 3.0"));
+    /// assert_eq!(Version(4, 0, 0).usb_generation(None), Some("USB4"));
+    /// assert_eq!(Version(9, 0, 0).usb_generation(None), None);
+    /// ```
+    pub fn usb_generation(self, speed: Option<&Speed>) -> Option<&'static str> {
+        match (self.major(), self.minor(), speed) {
+            (1, 0, _) => Some("USB 1.0"),
+            (1, 1, _) => Some("USB 1.1"),
+            (2, 0 | 1, _) => Some("USB 2.0"),
+            (3, 0..=2, Some(Speed::SuperSpeed)) => Some("USB 3.2 Gen 1"),
+            (3, 1 | 2, Some(Speed::SuperSpeedPlus)) => Some("USB 3.2 Gen 2"),
+            (3, 2, Some(Speed::SuperSpeedPlusX2)) => Some("USB 3.2 Gen 2x2"),
+            (3, 0, _) => Some("USB 3.0"),
+            (3, 1, _) => Some("USB 3.1"),
+            (3, 2, _) => Some("USB 3.2"),
+            (4, 0, _) => Some("USB4"),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for Version {
@@ -178,6 +210,29 @@ impl ConfigAttributes {
         let vec: Vec<String> = attributes.iter().map(|a| a.to_string()).collect();
         vec.join(";")
     }
+
+    /// Converts the Configuration Descriptor bmAttributes `value` into [`ConfigAttributes`]; the inverse of [`Configuration::attributes_value`]
+    ///
+    /// ```
+    /// use cyme::usb::ConfigAttributes;
+    ///
+    /// assert_eq!(ConfigAttributes::attributes_from_value(0xa0), vec![ConfigAttributes::RemoteWakeup, ConfigAttributes::BusPowered]);
+    /// ```
+    pub fn attributes_from_value(value: u8) -> Vec<ConfigAttributes> {
+        let mut ret = Vec::new();
+        if value & 0x10 != 0 {
+            ret.push(ConfigAttributes::BatteryPowered);
+        }
+        if value & 0x20 != 0 {
+            ret.push(ConfigAttributes::RemoteWakeup);
+        }
+        if value & 0x40 != 0 {
+            ret.push(ConfigAttributes::SelfPowered);
+        } else {
+            ret.push(ConfigAttributes::BusPowered);
+        }
+        ret
+    }
 }
 
 /// Explains how the `BaseClass` is used
@@ -390,6 +445,44 @@ impl BaseClass {
         }
     }
 
+    /// Short name of the class such as "HID" or "MSC" for compact summaries
+    ///
+    /// ```
+    /// # use cyme::usb::BaseClass;
+    ///
+    /// assert_eq!(BaseClass::Hid.short_name(), "HID");
+    /// assert_eq!(BaseClass::MassStorage.short_name(), "MSC");
+    /// ```
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            BaseClass::UseInterfaceDescriptor => "Unspecified",
+            BaseClass::Audio => "Audio",
+            BaseClass::CdcCommunications => "CDC",
+            BaseClass::Hid => "HID",
+            BaseClass::Physical => "Physical",
+            BaseClass::Image => "Image",
+            BaseClass::Printer => "Printer",
+            BaseClass::MassStorage => "MSC",
+            BaseClass::Hub => "Hub",
+            BaseClass::CdcData => "CDC-Data",
+            BaseClass::SmartCard => "CCID",
+            BaseClass::ContentSecurity => "Security",
+            BaseClass::Video => "Video",
+            BaseClass::PersonalHealthcare => "PHDC",
+            BaseClass::AudioVideo => "AV",
+            BaseClass::Billboard => "Billboard",
+            BaseClass::UsbTypeCBridge => "TypeC",
+            BaseClass::Bdp => "BDP",
+            BaseClass::Mctp => "MCTP",
+            BaseClass::I3cDevice => "I3C",
+            BaseClass::Diagnostic => "Diagnostic",
+            BaseClass::WirelessController => "Wireless",
+            BaseClass::Miscellaneous => "Misc",
+            BaseClass::ApplicationSpecificInterface => "App",
+            BaseClass::VendorSpecificClass => "Vendor",
+        }
+    }
+
     /// Converts Pascal case enum to space separated on capitals
     /// ```
     /// # use cyme::usb::BaseClass;
@@ -763,10 +856,89 @@ impl Speed {
             _ => format!("{:.0}{}", dv.value, prefix),
         }
     }
+
+    /// Speed in Mb/s, the unit of the USB specification values
+    pub fn to_mbps(&self) -> f32 {
+        let dv = NumericalUnit::<f32>::from(self);
+        match dv.unit.chars().next() {
+            Some('G') => dv.value * 1000.0,
+            _ => dv.value,
+        }
+    }
+
+    /// The speed as a [`NumericalUnit`] in [`SpeedUnit`] `unit`
+    pub fn to_numerical_unit(&self, unit: SpeedUnit) -> NumericalUnit<f32> {
+        let (value, unit) = match unit {
+            SpeedUnit::Auto => return NumericalUnit::<f32>::from(self),
+            SpeedUnit::Mbps => (self.to_mbps(), "Mb/s"),
+            SpeedUnit::Binary => {
+                let bits = self.to_mbps() as f64 * 1e6;
+                if bits >= (1u64 << 30) as f64 {
+                    ((bits / (1u64 << 30) as f64) as f32, "Gib/s")
+                } else {
+                    ((bits / (1u64 << 20) as f64) as f32, "Mib/s")
+                }
+            }
+        };
+        NumericalUnit {
+            value,
+            unit: unit.to_string(),
+            description: Some(self.to_string()),
+        }
+    }
+
+    /// Format the speed in [`SpeedUnit`] `unit` to fit the 10 char speed blocks
+    ///
+    /// [`SpeedUnit::Auto`] has one decimal place like [`DeviceSpeed`](crate::profiler::DeviceSpeed); the others only show a decimal place where needed
+    ///
+    /// ```
+    /// # use cyme::usb::{Speed, SpeedUnit};
+    ///
+    /// assert_eq!(Speed::FullSpeed.to_string_in(SpeedUnit::Auto), "12.0 Mb/s");
+    /// assert_eq!(Speed::LowSpeed.to_string_in(SpeedUnit::Auto), "1.5 Mb/s");
+    /// assert_eq!(Speed::SuperSpeed.to_string_in(SpeedUnit::Auto), "5.0 Gb/s");
+    ///
+    /// assert_eq!(Speed::FullSpeed.to_string_in(SpeedUnit::Mbps), "12 Mb/s");
+    /// assert_eq!(Speed::LowSpeed.to_string_in(SpeedUnit::Mbps), "1.5 Mb/s");
+    /// assert_eq!(Speed::SuperSpeedPlusX2.to_string_in(SpeedUnit::Mbps), "20000 Mb/s");
+    ///
+    /// assert_eq!(Speed::FullSpeed.to_string_in(SpeedUnit::Binary), "11.4 Mib/s");
+    /// assert_eq!(Speed::LowSpeed.to_string_in(SpeedUnit::Binary), "1.4 Mib/s");
+    /// assert_eq!(Speed::HighSpeed.to_string_in(SpeedUnit::Binary), "458 Mib/s");
+    /// assert_eq!(Speed::SuperSpeed.to_string_in(SpeedUnit::Binary), "4.7 Gib/s");
+    /// ```
+    pub fn to_string_in(&self, unit: SpeedUnit) -> String {
+        let nu = self.to_numerical_unit(unit);
+        let precision = match unit {
+            SpeedUnit::Auto => 1,
+            _ if nu.value.fract() == 0.0 || nu.value >= 100.0 => 0,
+            _ => 1,
+        };
+        format!("{nu:.precision$}")
+    }
+}
+
+/// Unit to show a [`Speed`] in
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpeedUnit {
+    /// Decimal Mb/s or Gb/s depending on the speed, as in the USB specification
+    #[default]
+    Auto,
+    /// Always decimal Mb/s so speeds can be compared
+    Mbps,
+    /// Binary Mib/s or Gib/s depending on the speed
+    Binary,
+}
+
+impl fmt::Display for SpeedUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
 }
 
 /// Transfer and [`Endpoint`] direction
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
 pub enum Direction {
     /// Direction for write (host to device) transfers.
     Out,
@@ -788,7 +960,7 @@ impl fmt::Display for Direction {
 }
 
 /// Transfer type  for [`Endpoint`]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum TransferType {
     /// Control endpoint.
@@ -977,12 +1149,40 @@ pub struct Endpoint {
     ///
     /// Allows lookup back to parent
     pub(crate) endpoint_path: Option<EndpointPath>,
+    /// Speed of parent device for computing polling interval, not serialized so set by the profiler or when reading a dump with [`crate::profiler::Device::update_endpoint_speeds`]
+    #[serde(skip)]
+    pub(crate) device_speed: Option<Speed>,
 }
 
 /// Deprecated alias for [`Endpoint`]
 #[deprecated(since = "2.0.0", note = "Use Endpoint instead")]
 pub type USBEndpoint = Endpoint;
 
+/// Parses a standard Endpoint Descriptor; `extra` is empty as the descriptors following it are not included
+impl TryFrom<&[u8]> for Endpoint {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.len() < 7 {
+            return Err(Error::new_descriptor_len("Endpoint", 7, value.len()));
+        }
+
+        Ok(Endpoint {
+            length: value[0],
+            address: EndpointAddress::from(value[2]),
+            transfer_type: TransferType::from(value[3]),
+            sync_type: SyncType::from(value[3]),
+            usage_type: UsageType::from(value[3]),
+            max_packet_size: u16::from_le_bytes([value[4], value[5]]),
+            interval: value[6],
+            extra: Some(Vec::new()),
+            internal: InternalData::default(),
+            endpoint_path: None,
+            device_speed: None,
+        })
+    }
+}
+
 impl Endpoint {
     /// Decodes the max packet value into a multiplier and number of bytes like lsusb
     pub fn max_packet_string(&self) -> String {
@@ -1004,6 +1204,14 @@ impl Endpoint {
         ((self.max_packet_size >> 11) & 0b11) as u8 + 1
     }
 
+    /// Is a high-bandwidth Isochronous or Interrupt endpoint; more than one transaction per microframe encoded in bits 11-12 of 'wMaxPacketSize'
+    pub fn is_high_bandwidth(&self) -> bool {
+        matches!(
+            self.transfer_type,
+            TransferType::Isochronous | TransferType::Interrupt
+        ) && self.packets_per_microframe() > 1
+    }
+
     /// Returns the attributes byte for the endpoint
     pub fn attributes(&self) -> u8 {
         self.transfer_type.to_owned() as u8
@@ -1030,6 +1238,73 @@ impl Endpoint {
     pub fn endpoint_path(&self) -> Option<EndpointPath> {
         self.endpoint_path.to_owned()
     }
+
+    /// SuperSpeed Endpoint Companion descriptor from `extra` if present
+    ///
+    /// Only SuperSpeed and above endpoints have a companion
+    pub fn ss_companion(&self) -> Option<&SsEndpointCompanionDescriptor> {
+        self.extra.as_ref()?.iter().find_map(|d| match d {
+            Descriptor::SsEndpointCompanion(ss) => Some(ss),
+            _ => None,
+        })
+    }
+
+    /// Speed of the parent device if known, used for [`Endpoint::interval_ms`]
+    pub fn device_speed(&self) -> Option<&Speed> {
+        self.device_speed.as_ref()
+    }
+
+    /// Converts bInterval to polling interval in milliseconds for the device `speed`
+    ///
+    /// Returns `None` for Control and Bulk endpoints, where bInterval is not a polling interval, or if `speed` is unknown. Low/Full speed Interrupt endpoints are bInterval frames (1 ms); Isochronous and all High/SuperSpeed endpoints are 2^(bInterval-1) (micro)frames.
+    pub fn interval_ms(&self, speed: &Speed) -> Option<f32> {
+        if !matches!(
+            self.transfer_type,
+            TransferType::Interrupt | TransferType::Isochronous
+        ) || self.interval == 0
+        {
+            return None;
+        }
+
+        let exponential = (1u32 << (self.interval.min(16) - 1)) as f32;
+        match speed {
+            Speed::LowSpeed | Speed::FullSpeed => match self.transfer_type {
+                TransferType::Interrupt => Some(self.interval as f32),
+                _ => Some(exponential),
+            },
+            Speed::HighSpeed
+            | Speed::HighBandwidth
+            | Speed::SuperSpeed
+            | Speed::SuperSpeedPlus
+            | Speed::SuperSpeedPlusX2 => Some(exponential * 0.125),
+            Speed::Unknown => None,
+        }
+    }
+
+    /// Packets per service interval at device `speed`
+    ///
+    /// At SuperSpeed and above this is (bMaxBurst + 1) * (Mult + 1) from the [`SsEndpointCompanionDescriptor`], where Mult is only defined for Isochronous endpoints. Otherwise it is [`Endpoint::packets_per_microframe`].
+    pub fn packets_per_interval(&self, speed: &Speed) -> usize {
+        match (speed, self.ss_companion()) {
+            (Speed::SuperSpeed | Speed::SuperSpeedPlus | Speed::SuperSpeedPlusX2, Some(ss)) => {
+                let mult = match self.transfer_type {
+                    TransferType::Isochronous => (ss.attributes & 0x03) as usize,
+                    _ => 0,
+                };
+                (ss.max_burst as usize + 1) * (mult + 1)
+            }
+            _ => self.packets_per_microframe() as usize,
+        }
+    }
+
+    /// Maximum periodic data rate in bytes per second at device `speed`: max packet size * [`Endpoint::packets_per_interval`] / interval
+    ///
+    /// Returns `None` where [`Endpoint::interval_ms`] does; not a periodic endpoint or `speed` is unknown
+    pub fn periodic_bytes_per_second(&self, speed: &Speed) -> Option<f32> {
+        let interval_ms = self.interval_ms(speed)?;
+        let bytes = self.max_packet_size() * self.packets_per_interval(speed);
+        Some(bytes as f32 * 1000.0 / interval_ms)
+    }
 }
 
 /// Interface within a [`Configuration`]
@@ -1052,6 +1327,9 @@ pub struct Interface {
     pub protocol: u8,
     /// Interfaces can have the same number/path but an alternate setting defined here
     pub alt_setting: u8,
+    /// Whether this is the selected alternate setting of the interface number - None if not known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
     /// Driver obtained from udev on Linux only
     pub driver: Option<String>,
     /// syspath obtained from udev on Linux only
@@ -1076,6 +1354,36 @@ pub struct Interface {
 #[deprecated(since = "2.0.0", note = "Use Interface instead")]
 pub type USBInterface = Interface;
 
+/// Parses a standard Interface Descriptor; `endpoints` and `extra` are empty as the descriptors following it are not included and the path is not known
+impl TryFrom<&[u8]> for Interface {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.len() < 9 {
+            return Err(Error::new_descriptor_len("Interface", 9, value.len()));
+        }
+
+        Ok(Interface {
+            name: None,
+            string_index: value[8],
+            number: value[2],
+            path: String::new(),
+            class: BaseClass::from(value[5]),
+            sub_class: value[6],
+            protocol: value[7],
+            alt_setting: value[3],
+            active: None,
+            driver: None,
+            syspath: None,
+            endpoints: Vec::with_capacity(value[4] as usize),
+            length: value[0],
+            extra: Some(Vec::new()),
+            internal: InternalData::default(),
+            device_path: None,
+        })
+    }
+}
+
 impl Interface {
     /// Linux sysfs name of [`Interface`]
     ///
@@ -1130,6 +1438,29 @@ impl Interface {
         (self.class, self.sub_class, self.protocol).into()
     }
 
+    /// Estimated periodic bandwidth of the Interrupt and Isochronous endpoints as a percentage of the bus periodic budget at the device speed
+    ///
+    /// The budget is 90% of the bit rate for Low/Full speed and 80% for High speed and above; protocol overhead is not included. Returns `None` if there are no endpoints or the speed is unknown
+    pub fn periodic_bandwidth(&self) -> Option<f32> {
+        let speed = self.endpoints.first()?.device_speed()?;
+        let (bits_per_second, budget) = match speed {
+            Speed::LowSpeed => (1.5e6, 0.9),
+            Speed::FullSpeed => (12e6, 0.9),
+            Speed::HighSpeed | Speed::HighBandwidth => (480e6, 0.8),
+            Speed::SuperSpeed => (5e9, 0.8),
+            Speed::SuperSpeedPlus => (10e9, 0.8),
+            Speed::SuperSpeedPlusX2 => (20e9, 0.8),
+            Speed::Unknown => return None,
+        };
+        let bytes_per_second: f32 = self
+            .endpoints
+            .iter()
+            .filter_map(|e| e.periodic_bytes_per_second(speed))
+            // fold rather than sum since empty f32 sum is -0.0
+            .fold(0.0, |acc, b| acc + b);
+        Some(bytes_per_second * 8.0 / (bits_per_second * budget) * 100.0)
+    }
+
     /// Should the interface be displayed expanded in a tree
     pub fn is_expanded(&self) -> bool {
         self.internal.expanded
@@ -1174,6 +1505,9 @@ pub struct Configuration {
     /// Extra descriptors for configuration based on type
     #[serde(default)] // default for legacy json
     pub extra: Option<Vec<Descriptor>>,
+    /// Whether this is the active configuration of the device - None if the active configuration is not known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
     #[serde(skip)]
     pub(crate) internal: InternalData,
 }
@@ -1183,6 +1517,39 @@ pub struct Configuration {
 pub type USBConfiguration = Configuration;
 
 impl Configuration {
+    /// Interface Association Descriptors grouping interfaces of the configuration into functions, ordered by first interface
+    ///
+    /// Looks in the configuration, interface and endpoint extra descriptors since an IAD is parsed as part of the descriptor it follows
+    pub fn interface_associations(&self) -> Vec<&InterfaceAssociationDescriptor> {
+        let mut ret: Vec<&InterfaceAssociationDescriptor> = self
+            .extra
+            .iter()
+            .flatten()
+            .chain(self.interfaces.iter().flat_map(|i| {
+                i.extra
+                    .iter()
+                    .flatten()
+                    .chain(i.endpoints.iter().flat_map(|e| e.extra.iter().flatten()))
+            }))
+            .filter_map(|d| match d {
+                Descriptor::InterfaceAssociation(iad) => Some(iad),
+                _ => None,
+            })
+            .collect();
+        ret.sort_by_key(|iad| iad.first_interface);
+        // alternate settings repeat the extra descriptors
+        ret.dedup();
+        ret
+    }
+
+    /// [`Interface`]s, including alternate settings, grouped into the function by `iad`
+    pub fn function_interfaces(&self, iad: &InterfaceAssociationDescriptor) -> Vec<&Interface> {
+        self.interfaces
+            .iter()
+            .filter(|i| iad.interface_numbers().contains(&i.number))
+            .collect()
+    }
+
     /// Converts attributes into a ';' separated String
     pub fn attributes_string(&self) -> String {
         ConfigAttributes::attributes_to_string(&self.attributes)
@@ -1203,6 +1570,155 @@ impl Configuration {
         ret
     }
 
+    /// Parses the [`Configuration`]s in `bytes` of concatenated Configuration Descriptors, each followed by its Interface, Endpoint and class-specific descriptors up to wTotalLength as returned by GET_DESCRIPTOR
+    ///
+    /// Descriptors following the Configuration, an Interface or an Endpoint Descriptor are parsed into its `extra` like the profilers do, with class-specific descriptors such as the audio ones decoded using the class of the Interface they follow. No strings are fetched and paths are not known, so names and paths are empty. MaxPower is converted using the 2 mA units of USB 2, SuperSpeed devices use 8 mA units.
+    ///
+    /// Errors if a descriptor is shorter than its type requires, a bLength runs past the wTotalLength of the Configuration, a wTotalLength runs past `bytes` or an Endpoint does not follow an Interface.
+    ///
+    /// ```
+    /// use cyme::usb::{BaseClass, Configuration, TransferType};
+    ///
+    /// let bytes = [
+    ///     // Configuration 1 with wTotalLength 25, self powered, 100 mA
+    ///     0x09, 0x02, 0x19, 0x00, 0x01, 0x01, 0x00, 0xc0, 0x32,
+    ///     // Interface 0 Vendor Specific with one endpoint
+    ///     0x09, 0x04, 0x00, 0x00, 0x01, 0xff, 0x00, 0x00, 0x00,
+    ///     // Endpoint 0x81 Bulk IN 512 bytes
+    ///     0x07, 0x05, 0x81, 0x02, 0x00, 0x02, 0x00,
+    /// ];
+    /// let configs = Configuration::parse_all(&bytes).unwrap();
+    /// assert_eq!(configs.len(), 1);
+    /// assert_eq!(configs[0].max_power.value, 100);
+    /// assert_eq!(configs[0].interfaces[0].class, BaseClass::VendorSpecificClass);
+    /// let endpoint = &configs[0].interfaces[0].endpoints[0];
+    /// assert_eq!(endpoint.transfer_type, TransferType::Bulk);
+    /// assert_eq!(endpoint.max_packet_size(), 512);
+    ///
+    /// // wTotalLength longer than the data
+    /// assert!(Configuration::parse_all(&bytes[..20]).is_err());
+    /// ```
+    pub fn parse_all(bytes: &[u8]) -> error::Result<Vec<Configuration>> {
+        let mut ret = Vec::new();
+        let mut rest = bytes;
+
+        while !rest.is_empty() {
+            if rest.len() < 9 {
+                return Err(Error::new_descriptor_len("Configuration", 9, rest.len()));
+            }
+            if rest[1] != u8::from(DescriptorType::Config) {
+                return Err(Error::new(
+                    ErrorKind::InvalidDescriptor,
+                    &format!(
+                        "Expected a Configuration Descriptor but got descriptor type 0x{:02x}",
+                        rest[1]
+                    ),
+                ));
+            }
+            let total_length = u16::from_le_bytes([rest[2], rest[3]]) as usize;
+            if total_length < 9 || total_length > rest.len() {
+                return Err(Error::new(
+                    ErrorKind::InvalidDescriptor,
+                    &format!(
+                        "Configuration wTotalLength {} is invalid for {} bytes remaining",
+                        total_length,
+                        rest.len()
+                    ),
+                ));
+            }
+            let (config, tail) = rest.split_at(total_length);
+            ret.push(Configuration::parse(config)?);
+            rest = tail;
+        }
+
+        Ok(ret)
+    }
+
+    /// Parses a single Configuration Descriptor and the descriptors following it for [`Self::parse_all`]; `data` is wTotalLength long
+    fn parse(data: &[u8]) -> error::Result<Configuration> {
+        let length = data[0];
+        let mut config = Configuration {
+            name: String::new(),
+            string_index: data[6],
+            number: data[5],
+            interfaces: Vec::new(),
+            attributes: ConfigAttributes::attributes_from_value(data[7]),
+            max_power: NumericalUnit {
+                value: data[8] as u32 * 2,
+                unit: String::from("mA"),
+                description: None,
+            },
+            length,
+            total_length: data.len() as u16,
+            extra: Some(Vec::new()),
+            active: None,
+            internal: InternalData::default(),
+        };
+
+        let mut offset = (length as usize).max(9);
+        while offset < data.len() {
+            let len = data[offset] as usize;
+            if len < 2 || offset + len > data.len() {
+                return Err(Error::new(
+                    ErrorKind::InvalidDescriptor,
+                    &format!(
+                        "Descriptor at offset {} with bLength {} runs past the Configuration wTotalLength {}",
+                        offset,
+                        len,
+                        data.len()
+                    ),
+                ));
+            }
+            let descriptor = &data[offset..offset + len];
+
+            match descriptor[1] {
+                0x04 => config.interfaces.push(Interface::try_from(descriptor)?),
+                0x05 => {
+                    let interface = config.interfaces.last_mut().ok_or(Error::new(
+                        ErrorKind::InvalidDescriptor,
+                        &format!(
+                            "Endpoint Descriptor at offset {offset} is not within an Interface"
+                        ),
+                    ))?;
+                    interface.endpoints.push(Endpoint::try_from(descriptor)?);
+                }
+                _ => {
+                    let mut dt = Descriptor::try_from(descriptor)?;
+                    // extra descriptors belong to the last Endpoint, Interface or the Configuration itself
+                    let extra = match config.interfaces.last_mut() {
+                        Some(interface) => {
+                            if let Err(e) = dt.update_with_class_context((
+                                interface.class,
+                                interface.sub_class,
+                                interface.protocol,
+                            )) {
+                                log::debug!(
+                                    "Failed to update extra descriptor with class context: {e}"
+                                );
+                            }
+                            match interface.endpoints.last_mut() {
+                                Some(endpoint) => &mut endpoint.extra,
+                                None => &mut interface.extra,
+                            }
+                        }
+                        None => &mut config.extra,
+                    };
+                    extra.get_or_insert_with(Vec::new).push(dt);
+                }
+            }
+            offset += len;
+        }
+
+        // UAC3 terminals reference the channel cluster by id so can only be resolved once all are parsed
+        for interface in config.interfaces.iter_mut() {
+            if let Some(extra) = interface.extra.as_mut() {
+                resolve_audio_channel_names(extra);
+            }
+        }
+
+        Ok(config)
+    }
+
     /// Should the configuration be displayed expanded in a tree
     pub fn is_expanded(&self) -> bool {
         self.internal.expanded
@@ -1268,6 +1784,8 @@ pub struct DeviceExtra {
     pub hub: Option<HubDescriptor>,
     /// Speed that the device is operating at
     pub negotiated_speed: Option<Speed>,
+    /// Runtime power management status from sysfs 'power/runtime_status' on Linux only, such as "active" or "suspended"
+    pub power_state: Option<String>,
 }
 
 /// Deprecated alias for [`DeviceExtra`]
@@ -1293,4 +1811,186 @@ mod tests {
         assert_eq!(Version::try_from(2.01).unwrap(), Version(2, 0, 1));
         assert_eq!(Version::try_from(2.31).unwrap(), Version(2, 1, 15));
     }
+
+    #[test]
+    fn test_endpoint_interval_ms() {
+        let mut ep = Endpoint {
+            length: 7,
+            address: EndpointAddress::from(0x81),
+            transfer_type: TransferType::Interrupt,
+            sync_type: SyncType::None,
+            usage_type: UsageType::Data,
+            max_packet_size: 8,
+            interval: 4,
+            extra: None,
+            internal: Default::default(),
+            endpoint_path: None,
+            device_speed: None,
+        };
+        assert_eq!(ep.interval_ms(&Speed::FullSpeed), Some(4.0));
+        assert_eq!(ep.interval_ms(&Speed::HighSpeed), Some(1.0));
+        assert_eq!(ep.interval_ms(&Speed::Unknown), None);
+        ep.transfer_type = TransferType::Bulk;
+        assert_eq!(ep.interval_ms(&Speed::HighSpeed), None);
+    }
+
+    #[test]
+    fn test_endpoint_ss_companion() {
+        let mut ep = Endpoint {
+            length: 7,
+            address: EndpointAddress::from(0x81),
+            transfer_type: TransferType::Bulk,
+            sync_type: SyncType::None,
+            usage_type: UsageType::Data,
+            max_packet_size: 1024,
+            interval: 0,
+            extra: None,
+            internal: Default::default(),
+            endpoint_path: None,
+            device_speed: None,
+        };
+        assert!(ep.ss_companion().is_none());
+        let companion = SsEndpointCompanionDescriptor::try_from(
+            [0x06, 0x30, 0x0f, 0x00, 0x00, 0x00].as_slice(),
+        )
+        .unwrap();
+        ep.extra = Some(vec![Descriptor::SsEndpointCompanion(companion.clone())]);
+        assert_eq!(ep.ss_companion(), Some(&companion));
+        assert_eq!(ep.ss_companion().unwrap().max_burst, 15);
+    }
+
+    #[test]
+    fn test_endpoint_periodic_bytes_per_second() {
+        let ep = Endpoint {
+            length: 7,
+            address: EndpointAddress::from(0x81),
+            transfer_type: TransferType::Isochronous,
+            sync_type: SyncType::Asynchronous,
+            usage_type: UsageType::Data,
+            // 3 transactions of 1024 bytes
+            max_packet_size: 0x1400,
+            interval: 1,
+            extra: None,
+            internal: Default::default(),
+            endpoint_path: None,
+            device_speed: Some(Speed::HighSpeed),
+        };
+        assert!(ep.is_high_bandwidth());
+        assert_eq!(ep.max_packet_string(), "3x 1024");
+        assert_eq!(
+            ep.periodic_bytes_per_second(&Speed::HighSpeed),
+            Some(3.0 * 1024.0 * 8000.0)
+        );
+        assert_eq!(ep.periodic_bytes_per_second(&Speed::Unknown), None);
+
+        // SuperSpeed burst of 4 with Mult 2 so 12 packets of 1024 each service interval
+        let companion = SsEndpointCompanionDescriptor::try_from(
+            [0x06, 0x30, 0x03, 0x02, 0x00, 0x30].as_slice(),
+        )
+        .unwrap();
+        let ep = Endpoint {
+            max_packet_size: 1024,
+            extra: Some(vec![Descriptor::SsEndpointCompanion(companion)]),
+            device_speed: Some(Speed::SuperSpeed),
+            ..ep
+        };
+        assert_eq!(ep.packets_per_interval(&Speed::SuperSpeed), 12);
+        assert_eq!(
+            ep.periodic_bytes_per_second(&Speed::SuperSpeed),
+            Some(12.0 * 1024.0 * 8000.0)
+        );
+        // companion ignored below SuperSpeed
+        assert_eq!(ep.packets_per_interval(&Speed::HighSpeed), 1);
+    }
+
+    #[test]
+    fn test_configuration_interface_associations() {
+        let spusb =
+            crate::profiler::read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        let device = spusb.get_node_by_str("2-2.1").unwrap();
+        let mut config = device.extra.as_ref().unwrap().configurations[0].clone();
+        let iad = |first_interface, function_class| InterfaceAssociationDescriptor {
+            length: 8,
+            descriptor_type: 0x0b,
+            first_interface,
+            interface_count: 2,
+            function_class,
+            function_sub_class: 0x02,
+            function_protocol: 0x01,
+            function_string_index: 0,
+            function_string: None,
+        };
+        assert!(config.interface_associations().is_empty());
+        // second function IAD follows the endpoints of the first function
+        config.interfaces[1].endpoints[0].extra =
+            Some(vec![Descriptor::InterfaceAssociation(iad(2, 0x02))]);
+        config.extra = Some(vec![Descriptor::InterfaceAssociation(iad(0, 0x02))]);
+        let iads = config.interface_associations();
+        assert_eq!(iads.len(), 2);
+        assert_eq!(iads[1].first_interface, 2);
+        assert_eq!(iads[1].interface_numbers(), 2..4);
+        assert_eq!(iads[0].function_base_class(), BaseClass::CdcCommunications);
+        assert_eq!(
+            config
+                .function_interfaces(iads[1])
+                .iter()
+                .map(|i| i.number)
+                .collect::<Vec<u8>>(),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn test_configuration_parse_all_audio() {
+        let bytes = [
+            // Configuration 1 with wTotalLength 59, bus powered
+            0x09, 0x02, 0x3b, 0x00, 0x02, 0x01, 0x00, 0x80, 0xfa,
+            // IAD for interfaces 0-1 Audio
+            0x08, 0x0b, 0x00, 0x02, 0x01, 0x00, 0x20, 0x00,
+            // Interface 0 AudioControl UAC2
+            0x09, 0x04, 0x00, 0x00, 0x00, 0x01, 0x01, 0x20, 0x00,
+            // AC Header2 with wTotalLength 9
+            0x09, 0x24, 0x01, 0x00, 0x02, 0x08, 0x09, 0x00, 0x00,
+            // Interface 1 alt 1 AudioStreaming UAC2 with one endpoint
+            0x09, 0x04, 0x01, 0x01, 0x01, 0x01, 0x02, 0x20, 0x00,
+            // Endpoint 0x01 Isochronous OUT async
+            0x07, 0x05, 0x01, 0x05, 0xc0, 0x00, 0x01, // CS Endpoint general
+            0x08, 0x25, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let configs = Configuration::parse_all(&bytes).unwrap();
+        assert_eq!(configs.len(), 1);
+        let config = &configs[0];
+        assert_eq!(config.attributes, vec![ConfigAttributes::BusPowered]);
+        assert_eq!(config.max_power.value, 500);
+        assert_eq!(config.interface_associations().len(), 1);
+        assert_eq!(config.interfaces.len(), 2);
+
+        let ac = &config.interfaces[0];
+        assert_eq!(ac.class, BaseClass::Audio);
+        assert!(ac.endpoints.is_empty());
+        assert!(matches!(
+            ac.extra.as_deref(),
+            Some([Descriptor::Interface(ClassDescriptor::Audio(ad, _))])
+                if matches!(ad.interface, audio::UacInterfaceDescriptor::Header2(_))
+        ));
+
+        let streaming = &config.interfaces[1];
+        assert_eq!(streaming.alt_setting, 1);
+        assert_eq!(
+            streaming.endpoints[0].transfer_type,
+            TransferType::Isochronous
+        );
+        assert!(matches!(
+            streaming.endpoints[0].extra.as_deref(),
+            Some([Descriptor::Endpoint(ClassDescriptor::Audio(..))])
+        ));
+
+        // Configurations are concatenated
+        let two = [bytes.as_slice(), bytes.as_slice()].concat();
+        assert_eq!(Configuration::parse_all(&two).unwrap().len(), 2);
+        // Endpoint bLength runs past wTotalLength
+        let mut short = bytes;
+        short[2] = 0x38;
+        assert!(Configuration::parse_all(&short[..0x38]).is_err());
+    }
 }
@@ -344,6 +344,27 @@ impl Descriptor {
     }
 }
 
+/// Resolves the channel names of the UAC3 terminals in the interface `descriptors` from the channel clusters they reference by id
+///
+/// Can only be done once all the descriptors of the interface are parsed; see [`audio::resolve_channel_clusters`]
+pub(crate) fn resolve_audio_channel_names(descriptors: &mut [Descriptor]) {
+    let audio = descriptors
+        .iter()
+        .filter_map(|d| match d {
+            Descriptor::Interface(ClassDescriptor::Audio(ad, _)) => Some(ad.interface.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let clusters = audio::resolve_channel_clusters(&audio);
+    if !clusters.is_empty() {
+        for d in descriptors.iter_mut() {
+            if let Descriptor::Interface(ClassDescriptor::Audio(ad, _)) = d {
+                ad.interface.resolve_channel_names(&clusters);
+            }
+        }
+    }
+}
+
 /// Device Capability Type Codes (Wireless USB spec and USB 3.0 bus spec)
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -408,6 +429,27 @@ impl TryFrom<&[u8]> for InterfaceAssociationDescriptor {
     }
 }
 
+impl InterfaceAssociationDescriptor {
+    /// Numbers of the interfaces grouped by the association
+    pub fn interface_numbers(&self) -> std::ops::Range<u8> {
+        self.first_interface..self.first_interface.saturating_add(self.interface_count)
+    }
+
+    /// [`BaseClass`] of the function
+    pub fn function_base_class(&self) -> BaseClass {
+        BaseClass::from(self.function_class)
+    }
+
+    /// [`ClassCode`] of the function from class, sub-class and protocol
+    pub fn function_class_code(&self) -> ClassCode {
+        ClassCode::from((
+            self.function_base_class(),
+            self.function_sub_class,
+            self.function_protocol,
+        ))
+    }
+}
+
 impl From<InterfaceAssociationDescriptor> for Vec<u8> {
     fn from(iad: InterfaceAssociationDescriptor) -> Self {
         vec![
@@ -805,6 +847,27 @@ impl TryFrom<&[u8]> for GenericDescriptor {
     }
 }
 
+impl GenericDescriptor {
+    /// Set `length` in place from the serialized size of the descriptor
+    ///
+    /// The `Vec<u8>` conversion writes `length` as is, so call this after mutating `data`. Returns an error if the descriptor is longer than bLength can represent.
+    pub fn recompute_length(&mut self) -> error::Result<()> {
+        let len = 3 + self.data.as_ref().map_or(0, |d| d.len());
+        self.length = descriptor_length(len)?;
+        Ok(())
+    }
+}
+
+/// Checked bLength for a serialized descriptor of `len` bytes
+pub(crate) fn descriptor_length(len: usize) -> error::Result<u8> {
+    u8::try_from(len).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidDescriptor,
+            &format!("Descriptor length {len} exceeds bLength maximum of 255"),
+        )
+    })
+}
+
 impl From<GenericDescriptor> for Vec<u8> {
     fn from(gd: GenericDescriptor) -> Self {
         let mut ret = Vec::new();
@@ -1,5 +1,6 @@
 //! Defines for the USB Audio Class (UAC) interface descriptors and MIDI
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
 use std::fmt;
 use strum::VariantArray;
@@ -129,6 +130,44 @@ impl From<MidiDescriptor> for Vec<u8> {
     }
 }
 
+impl MidiDescriptor {
+    /// Set `length` in place from the serialized size of the descriptor
+    ///
+    /// The `Vec<u8>` conversion writes `length` as is, so call this after mutating the descriptor. Returns an error if the descriptor is longer than bLength can represent.
+    pub fn recompute_length(&mut self) -> error::Result<()> {
+        let data: Vec<u8> = self.interface.to_owned().into();
+        self.length = descriptor_length(data.len() + 3)?;
+        Ok(())
+    }
+
+    /// Set `length` of each descriptor and the `total_length` of any Header in place from the serialized size of the collection
+    ///
+    /// `descriptors` should be the class-specific MIDIStreaming descriptors that the Header covers, including the Header itself
+    pub fn recompute_total_length(descriptors: &mut [MidiDescriptor]) -> error::Result<()> {
+        for d in descriptors.iter_mut() {
+            d.recompute_length()?;
+        }
+        let total: usize = descriptors.iter().map(|d| d.length as usize).sum();
+        let total_length = u16::try_from(total).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidDescriptor,
+                &format!("Descriptor total length {total} exceeds wTotalLength maximum"),
+            )
+        })?;
+        for d in descriptors.iter_mut() {
+            match &mut d.interface {
+                MidiInterfaceDescriptor::Header(h) => h.total_length = total_length,
+                MidiInterfaceDescriptor::GroupTerminalBlockHeader(h) => {
+                    h.total_length = total_length
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl TryFrom<GenericDescriptor> for MidiDescriptor {
     type Error = Error;
 
@@ -322,7 +361,8 @@ impl TryFrom<&[u8]> for Element {
         }
 
         let mut element_caps: u16 = 0;
-        for i in 0..capsize {
+        // caps beyond u16 are not defined and would overflow the shift
+        for i in 0..capsize.min(2) {
             element_caps |= (value[j + 4 + i] as u16) << (i * 8);
         }
 
@@ -369,6 +409,125 @@ impl From<Element> for Vec<u8> {
     }
 }
 
+/// bDescriptorType for MIDI 2.0 class-specific Group Terminal Block descriptors (CS_GR_TRM_BLOCK)
+pub const MIDI_CS_GR_TRM_BLOCK: u8 = 0x26;
+
+/// MIDI 2.0: 5.4.1 Group Terminal Block Header Descriptor; Table 5-5
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct GroupTerminalBlockHeader {
+    pub total_length: u16,
+}
+
+impl TryFrom<&[u8]> for GroupTerminalBlockHeader {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.len() < 2 {
+            return Err(Error::new_descriptor_len(
+                "GroupTerminalBlockHeader",
+                2,
+                value.len(),
+            ));
+        }
+
+        Ok(GroupTerminalBlockHeader {
+            total_length: u16::from_le_bytes([value[0], value[1]]),
+        })
+    }
+}
+
+impl From<GroupTerminalBlockHeader> for Vec<u8> {
+    fn from(gh: GroupTerminalBlockHeader) -> Self {
+        gh.total_length.to_le_bytes().to_vec()
+    }
+}
+
+/// MIDI 2.0: 5.4.2.1 Group Terminal Block Descriptor; Table 5-6
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct GroupTerminalBlock {
+    pub block_id: u8,
+    /// 0x00: bi-directional, 0x01: input only, 0x02: output only
+    pub block_type: u8,
+    pub first_group: u8,
+    pub num_groups: u8,
+    pub block_string_index: u8,
+    pub block_string: Option<String>,
+    pub midi_protocol: u8,
+    pub max_input_bandwidth: u16,
+    pub max_output_bandwidth: u16,
+}
+
+impl TryFrom<&[u8]> for GroupTerminalBlock {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.len() < 10 {
+            return Err(Error::new_descriptor_len(
+                "GroupTerminalBlock",
+                10,
+                value.len(),
+            ));
+        }
+
+        Ok(GroupTerminalBlock {
+            block_id: value[0],
+            block_type: value[1],
+            first_group: value[2],
+            num_groups: value[3],
+            block_string_index: value[4],
+            block_string: None,
+            midi_protocol: value[5],
+            max_input_bandwidth: u16::from_le_bytes([value[6], value[7]]),
+            max_output_bandwidth: u16::from_le_bytes([value[8], value[9]]),
+        })
+    }
+}
+
+impl From<GroupTerminalBlock> for Vec<u8> {
+    fn from(gb: GroupTerminalBlock) -> Self {
+        let mut ret = vec![
+            gb.block_id,
+            gb.block_type,
+            gb.first_group,
+            gb.num_groups,
+            gb.block_string_index,
+            gb.midi_protocol,
+        ];
+        ret.extend_from_slice(&gb.max_input_bandwidth.to_le_bytes());
+        ret.extend_from_slice(&gb.max_output_bandwidth.to_le_bytes());
+
+        ret
+    }
+}
+
+impl GroupTerminalBlock {
+    /// Get the block type as a string
+    pub fn block_type_name(&self) -> &'static str {
+        match self.block_type {
+            0x00 => "Bi-directional",
+            0x01 => "Input only",
+            0x02 => "Output only",
+            _ => "Invalid",
+        }
+    }
+
+    /// Get the MIDI protocol as a string
+    pub fn midi_protocol_name(&self) -> &'static str {
+        match self.midi_protocol {
+            0x00 => "Unknown",
+            0x01 => "MIDI 1.0, 64 bits",
+            0x02 => "MIDI 1.0, 64 bits, JR Timestamps",
+            0x03 => "MIDI 1.0, 128 bits",
+            0x04 => "MIDI 1.0, 128 bits, JR Timestamps",
+            0x11 => "MIDI 2.0",
+            0x12 => "MIDI 2.0, JR Timestamps",
+            _ => "Reserved",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct MidiEndpointDescriptor {
@@ -419,6 +578,8 @@ pub enum MidiInterfaceDescriptor {
     OutputJack(OutputJack),
     Element(Element),
     Endpoint(MidiEndpointDescriptor),
+    GroupTerminalBlockHeader(GroupTerminalBlockHeader),
+    GroupTerminalBlock(GroupTerminalBlock),
     Invalid(Vec<u8>),
     Undefined(Vec<u8>),
 }
@@ -432,10 +593,27 @@ impl MidiInterfaceDescriptor {
     ) -> Result<Self, Error> {
         match descriptor_type {
             DescriptorType::Endpoint => Self::from_midi_endpoint(data),
+            DescriptorType::Unknown(MIDI_CS_GR_TRM_BLOCK) => {
+                Self::from_midi_group_terminal_block(u8::from(subtype.to_owned()), data)
+            }
             _ => subtype.get_interface_descriptor(data),
         }
     }
 
+    /// Try to parse the MIDI 2.0 Group Terminal Block descriptor from the provided data
+    ///
+    /// The bDescriptorSubtype overlaps [`MidiSubtype`] values so is passed as the raw u8: 0x01 GR_TRM_BLOCK_HEADER, 0x02 GR_TRM_BLOCK
+    pub fn from_midi_group_terminal_block(subtype: u8, data: &[u8]) -> Result<Self, Error> {
+        match subtype {
+            0x01 => GroupTerminalBlockHeader::try_from(data)
+                .map(MidiInterfaceDescriptor::GroupTerminalBlockHeader),
+            0x02 => {
+                GroupTerminalBlock::try_from(data).map(MidiInterfaceDescriptor::GroupTerminalBlock)
+            }
+            _ => Ok(MidiInterfaceDescriptor::Undefined(data.to_vec())),
+        }
+    }
+
     /// Try to parse the MIDI interface descriptor for an Endpoint from the provided data
     pub fn from_midi_endpoint(data: &[u8]) -> Result<Self, Error> {
         MidiEndpointDescriptor::try_from(data).map(MidiInterfaceDescriptor::Endpoint)
@@ -465,12 +643,248 @@ impl From<MidiInterfaceDescriptor> for Vec<u8> {
             MidiInterfaceDescriptor::OutputJack(a) => a.into(),
             MidiInterfaceDescriptor::Element(a) => a.into(),
             MidiInterfaceDescriptor::Endpoint(a) => a.into(),
+            MidiInterfaceDescriptor::GroupTerminalBlockHeader(a) => a.into(),
+            MidiInterfaceDescriptor::GroupTerminalBlock(a) => a.into(),
             MidiInterfaceDescriptor::Invalid(a) => a,
             MidiInterfaceDescriptor::Undefined(a) => a,
         }
     }
 }
 
+/// Name of the MIDI Jack bJackType: Embedded or External
+pub fn midi_jack_type_name(jack_type: u8) -> Option<&'static str> {
+    match jack_type {
+        0x01 => Some("Embedded"),
+        0x02 => Some("External"),
+        _ => None,
+    }
+}
+
+impl fmt::Display for MidiInterfaceDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, fields) = self.display_fields();
+        // lsusb style
+        if f.alternate() {
+            write!(f, "{}", heck::AsShoutySnakeCase(name))?;
+            for (lsusb_name, _, value) in fields {
+                write!(f, " {lsusb_name} {value}")?;
+            }
+            Ok(())
+        } else {
+            write_human_fields(f, name, &fields)
+        }
+    }
+}
+
+impl MidiInterfaceDescriptor {
+    /// Descriptor name and key fields as (lsusb name, human name, value) used for [`fmt::Display`]
+    fn display_fields(&self) -> (&'static str, Vec<(&'static str, &'static str, String)>) {
+        let jack_type = |jt: u8| -> String {
+            match midi_jack_type_name(jt) {
+                Some(n) => format!("0x{jt:02x} ({n})"),
+                None => format!("0x{jt:02x}"),
+            }
+        };
+        // baSourceID and baSourcePin pairs
+        let sources = |sources: &[(u8, u8)]| -> String {
+            format!(
+                "[{}]",
+                sources
+                    .iter()
+                    .map(|(id, pin)| format!("{id}:{pin}"))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+        let string = |index: u8, s: &Option<String>| -> String {
+            match s {
+                Some(s) => format!("{index} {s}"),
+                None => index.to_string(),
+            }
+        };
+
+        match self {
+            MidiInterfaceDescriptor::Header(d) => (
+                "Header",
+                vec![
+                    ("bcdMSC", "Version", d.version.to_string()),
+                    ("wTotalLength", "Total Length", d.total_length.to_string()),
+                ],
+            ),
+            MidiInterfaceDescriptor::InputJack(d) => (
+                "MIDI IN Jack",
+                vec![
+                    ("bJackType", "Type", jack_type(d.jack_type)),
+                    ("bJackID", "ID", d.jack_id.to_string()),
+                    ("iJack", "Name", string(d.jack_string_index, &d.jack_string)),
+                ],
+            ),
+            MidiInterfaceDescriptor::OutputJack(d) => (
+                "MIDI OUT Jack",
+                vec![
+                    ("bJackType", "Type", jack_type(d.jack_type)),
+                    ("bJackID", "ID", d.jack_id.to_string()),
+                    ("baSourceID", "Sources", sources(&d.source_ids)),
+                    ("iJack", "Name", string(d.jack_string_index, &d.jack_string)),
+                ],
+            ),
+            MidiInterfaceDescriptor::Element(d) => (
+                "Element",
+                vec![
+                    ("bElementID", "ID", d.element_id.to_string()),
+                    ("baSourceID", "Sources", sources(&d.source_ids)),
+                    (
+                        "bNrOutputPins",
+                        "Output Pins",
+                        d.num_output_pins.to_string(),
+                    ),
+                    (
+                        "bInTerminalLink",
+                        "In Terminal",
+                        d.in_terminal_link.to_string(),
+                    ),
+                    (
+                        "bOutTerminalLink",
+                        "Out Terminal",
+                        d.out_terminal_link.to_string(),
+                    ),
+                    (
+                        "bmElementCaps",
+                        "Capabilities",
+                        format!("0x{:04x}", d.element_caps),
+                    ),
+                    (
+                        "iElement",
+                        "Name",
+                        string(d.element_string_index, &d.element_string),
+                    ),
+                ],
+            ),
+            MidiInterfaceDescriptor::Endpoint(d) => (
+                "Endpoint",
+                vec![(
+                    "baAssocJackID",
+                    "Jacks",
+                    format!(
+                        "[{}]",
+                        d.jacks
+                            .iter()
+                            .map(|j| j.to_string())
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                )],
+            ),
+            MidiInterfaceDescriptor::GroupTerminalBlockHeader(d) => (
+                "Group Terminal Block Header",
+                vec![("wTotalLength", "Total Length", d.total_length.to_string())],
+            ),
+            MidiInterfaceDescriptor::GroupTerminalBlock(d) => (
+                "Group Terminal Block",
+                vec![
+                    ("bGrpTrmBlkID", "ID", d.block_id.to_string()),
+                    ("bGrpTrmBlkType", "Type", d.block_type_name().to_string()),
+                    ("nGroupTrm", "First Group", d.first_group.to_string()),
+                    ("nNumGroupTrm", "Groups", d.num_groups.to_string()),
+                    (
+                        "iBlockItem",
+                        "Name",
+                        string(d.block_string_index, &d.block_string),
+                    ),
+                    (
+                        "bMIDIProtocol",
+                        "Protocol",
+                        d.midi_protocol_name().to_string(),
+                    ),
+                ],
+            ),
+            MidiInterfaceDescriptor::Invalid(d) => {
+                ("Invalid", vec![("Data", "Data", format!("{d:02x?}"))])
+            }
+            MidiInterfaceDescriptor::Undefined(d) => {
+                ("Undefined", vec![("Data", "Data", format!("{d:02x?}"))])
+            }
+        }
+    }
+
+    /// The bJackID or bElementID of a MIDI IN Jack, MIDI OUT Jack or Element
+    pub fn entity_id(&self) -> Option<u8> {
+        match self {
+            MidiInterfaceDescriptor::InputJack(d) => Some(d.jack_id),
+            MidiInterfaceDescriptor::OutputJack(d) => Some(d.jack_id),
+            MidiInterfaceDescriptor::Element(d) => Some(d.element_id),
+            _ => None,
+        }
+    }
+
+    /// The (baSourceID, baSourcePin) pairs of the Jacks or Elements a MIDI OUT Jack or Element takes input from
+    pub fn source_ids(&self) -> Vec<(u8, u8)> {
+        match self {
+            MidiInterfaceDescriptor::OutputJack(d) => d.source_ids.to_owned(),
+            MidiInterfaceDescriptor::Element(d) => d.source_ids.to_owned(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Description of the Jack or Element with `id` for [`resolve_midi_jacks`]
+    fn entity_name(&self, id: u8) -> String {
+        let (name, _) = self.display_fields();
+        let (jack_type, jack) = match self {
+            MidiInterfaceDescriptor::InputJack(d) => (d.jack_type, d.jack_string.as_ref()),
+            MidiInterfaceDescriptor::OutputJack(d) => (d.jack_type, d.jack_string.as_ref()),
+            _ => return format!("{name} {id}"),
+        };
+
+        match jack
+            .filter(|s| !s.is_empty())
+            .map(|s| s.as_str())
+            .or(midi_jack_type_name(jack_type))
+        {
+            Some(t) => format!("{name} '{t}'"),
+            None => format!("{name} {id}"),
+        }
+    }
+
+    /// Formats like [`fmt::Display`] but with the source ids followed by the name of the Jack or Element they reference in `jacks`, such as `Sources [1:1] <- MIDI IN Jack 'External'`
+    ///
+    /// `jacks` is usually from [`resolve_midi_jacks`] of the MIDIStreaming interface this descriptor is part of; ids not found are shown as "ID n". Source pins other than 1 are appended as "pin n"
+    pub fn to_string_with_sources(&self, jacks: &HashMap<u8, String>) -> String {
+        let (name, mut fields) = self.display_fields();
+        let sources = self.source_ids();
+        if !sources.is_empty() {
+            if let Some((_, _, value)) = fields.iter_mut().find(|f| f.0 == "baSourceID") {
+                let names = sources
+                    .iter()
+                    .map(|(id, pin)| {
+                        let name = jacks.get(id).cloned().unwrap_or(format!("ID {id}"));
+                        match pin {
+                            1 => name,
+                            _ => format!("{name} pin {pin}"),
+                        }
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                *value = format!("{value} <- {names}");
+            }
+        }
+
+        let mut ret = String::new();
+        // writing to a String cannot fail
+        let _ = write_human_fields(&mut ret, name, &fields);
+        ret
+    }
+}
+
+/// Maps the id of each MIDI IN Jack, MIDI OUT Jack and Element in the MIDIStreaming interface `descriptors` to a description for [`MidiInterfaceDescriptor::to_string_with_sources`]
+///
+/// Jacks are named by direction with the iJack string or jack type, such as "MIDI IN Jack 'External'"; Elements by id like "Element 3"
+pub fn resolve_midi_jacks(descriptors: &[MidiInterfaceDescriptor]) -> HashMap<u8, String> {
+    descriptors
+        .iter()
+        .filter_map(|d| d.entity_id().map(|id| (id, d.entity_name(id))))
+        .collect()
+}
+
 impl TryFrom<GenericDescriptor> for MidiEndpointDescriptor {
     type Error = Error;
 
@@ -514,7 +928,11 @@ impl From<UacDescriptor> for Vec<u8> {
         let mut ret: Vec<u8> = Vec::new();
         ret.push(acd.length);
         ret.push(acd.descriptor_type);
-        ret.push(u8::from(acd.descriptor_subtype));
+        let subtype = match &acd.descriptor_subtype {
+            UacType::Control(aci) => aci.to_uac_subtype(u8::from(acd.get_protocol())),
+            st => u8::from(st.to_owned()),
+        };
+        ret.push(subtype);
         let data: Vec<u8> = acd.interface.into();
         ret.extend(&data);
 
@@ -536,6 +954,41 @@ impl UacDescriptor {
     pub fn get_protocol(&self) -> UacProtocol {
         self.interface.get_protocol()
     }
+
+    /// Set `length` in place from the serialized size of the descriptor
+    ///
+    /// The `Vec<u8>` conversion writes `length` as is, so call this after mutating the descriptor. Returns an error if the descriptor is longer than bLength can represent.
+    pub fn recompute_length(&mut self) -> error::Result<()> {
+        let data: Vec<u8> = self.interface.to_owned().into();
+        self.length = descriptor_length(data.len() + 3)?;
+        Ok(())
+    }
+
+    /// Set `length` of each descriptor and the `total_length` of any Header in place from the serialized size of the collection
+    ///
+    /// `descriptors` should be the class-specific AC interface descriptors that the Header covers, including the Header itself
+    pub fn recompute_total_length(descriptors: &mut [UacDescriptor]) -> error::Result<()> {
+        for d in descriptors.iter_mut() {
+            d.recompute_length()?;
+        }
+        let total: usize = descriptors.iter().map(|d| d.length as usize).sum();
+        let total_length = u16::try_from(total).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidDescriptor,
+                &format!("Descriptor total length {total} exceeds wTotalLength maximum"),
+            )
+        })?;
+        for d in descriptors.iter_mut() {
+            match &mut d.interface {
+                UacInterfaceDescriptor::Header1(h) => h.total_length = total_length,
+                UacInterfaceDescriptor::Header2(h) => h.total_length = total_length,
+                UacInterfaceDescriptor::Header3(h) => h.total_length = total_length,
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// USB Audio Class (UAC) interface descriptors
@@ -559,6 +1012,7 @@ pub enum UacInterfaceDescriptor {
     OutputTerminal2(OutputTerminal2),
     OutputTerminal3(OutputTerminal3),
     ExtendedTerminalHeader(ExtendedTerminalHeader),
+    Connectors3(Connectors3),
     PowerDomain(PowerDomain),
     MixerUnit1(MixerUnit1),
     MixerUnit2(MixerUnit2),
@@ -616,6 +1070,7 @@ impl From<UacInterfaceDescriptor> for Vec<u8> {
             UacInterfaceDescriptor::OutputTerminal2(a) => a.into(),
             UacInterfaceDescriptor::OutputTerminal3(a) => a.into(),
             UacInterfaceDescriptor::ExtendedTerminalHeader(a) => a.into(),
+            UacInterfaceDescriptor::Connectors3(a) => a.into(),
             UacInterfaceDescriptor::PowerDomain(a) => a.into(),
             UacInterfaceDescriptor::MixerUnit1(a) => a.into(),
             UacInterfaceDescriptor::MixerUnit2(a) => a.into(),
@@ -657,6 +1112,780 @@ impl From<UacInterfaceDescriptor> for Vec<u8> {
     }
 }
 
+impl fmt::Display for UacInterfaceDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, fields) = self.display_fields();
+        // lsusb style
+        if f.alternate() {
+            write!(f, "{}", heck::AsShoutySnakeCase(name))?;
+            for (lsusb_name, _, value) in fields {
+                write!(f, " {lsusb_name} {value}")?;
+            }
+            Ok(())
+        } else {
+            write_human_fields(f, name, &fields)
+        }
+    }
+}
+
+/// Writes `fields` from [`UacInterfaceDescriptor::display_fields`] as "Name: Field value, Field value"
+pub(crate) fn write_human_fields<W: fmt::Write>(
+    w: &mut W,
+    name: &str,
+    fields: &[(&'static str, &'static str, String)],
+) -> fmt::Result {
+    write!(w, "{name}")?;
+    for (i, (_, human_name, value)) in fields.iter().enumerate() {
+        let sep = if i == 0 { ":" } else { "," };
+        write!(w, "{sep} {human_name} {value}")?;
+    }
+    Ok(())
+}
+
+impl UacInterfaceDescriptor {
+    /// Descriptor name and key fields as (lsusb name, human name, value) used for [`fmt::Display`]
+    fn display_fields(&self) -> (&'static str, Vec<(&'static str, &'static str, String)>) {
+        let channels = |config: u32| -> String {
+            let names = self.get_channel_names(config);
+            if names.is_empty() {
+                format!("0x{config:08x}")
+            } else {
+                format!(
+                    "0x{:08x} ({})",
+                    config,
+                    names
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            }
+        };
+        let ids = |ids: &[u8]| -> String {
+            format!(
+                "[{}]",
+                ids.iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+        let terminal_type = |tt: u16| -> String {
+            match terminal_type_name(tt) {
+                Some(n) => format!("0x{tt:04x} ({n})"),
+                None => format!("0x{tt:04x}"),
+            }
+        };
+        let formats = |bitmap: u64, width: usize, names: Vec<&str>| -> String {
+            if names.is_empty() {
+                format!("0x{bitmap:0width$x}")
+            } else {
+                format!("0x{:0width$x} ({})", bitmap, names.join(", "))
+            }
+        };
+        let effect_type = |et: u16| -> String {
+            match effect_type_name(et) {
+                Some(n) => format!("{et} ({n})"),
+                None => et.to_string(),
+            }
+        };
+        // master channel bmaControls(0) with decoded control names
+        let effect_controls =
+            |controls: &[u32], decoded: Vec<(u8, Vec<(&str, ControlSetting)>)>| -> String {
+                let master = controls.first().copied().unwrap_or(0);
+                match decoded.into_iter().next() {
+                    Some((_, names)) if !names.is_empty() => format!(
+                        "0x{:08x} {}",
+                        master,
+                        names
+                            .iter()
+                            .map(|(n, s)| format!("{n} ({s})"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    _ => format!("0x{master:08x}"),
+                }
+            };
+        let string = |index: u8, s: &Option<String>| -> String {
+            match s {
+                Some(s) => format!("{index} {s}"),
+                None => index.to_string(),
+            }
+        };
+
+        match self {
+            UacInterfaceDescriptor::Header1(d) => (
+                "Header",
+                vec![
+                    ("bcdADC", "Version", d.version.to_string()),
+                    ("wTotalLength", "Total Length", d.total_length.to_string()),
+                    ("bInCollection", "Interfaces", ids(&d.interfaces)),
+                ],
+            ),
+            UacInterfaceDescriptor::Header2(d) => (
+                "Header",
+                vec![
+                    ("bcdADC", "Version", d.version.to_string()),
+                    (
+                        "bCategory",
+                        "Category",
+                        format!("{} ({})", d.category, d.category_name()),
+                    ),
+                    ("wTotalLength", "Total Length", d.total_length.to_string()),
+                    (
+                        "bmControls",
+                        "Controls",
+                        match d.latency_control() {
+                            Some(s) => format!("0x{:02x} Latency Control ({s})", d.controls),
+                            None => format!("0x{:02x}", d.controls),
+                        },
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::Header3(d) => (
+                "Header",
+                vec![
+                    ("bCategory", "Category", d.category.to_string()),
+                    ("wTotalLength", "Total Length", d.total_length.to_string()),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                ],
+            ),
+            UacInterfaceDescriptor::InputTerminal1(d) => (
+                "Input Terminal",
+                vec![
+                    ("bTerminalID", "ID", d.terminal_id.to_string()),
+                    ("wTerminalType", "Type", terminal_type(d.terminal_type)),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                    (
+                        "wChannelConfig",
+                        "Channel Config",
+                        channels(d.channel_config as u32),
+                    ),
+                    ("iTerminal", "Name", string(d.terminal_index, &d.terminal)),
+                ],
+            ),
+            UacInterfaceDescriptor::InputTerminal2(d) => (
+                "Input Terminal",
+                vec![
+                    ("bTerminalID", "ID", d.terminal_id.to_string()),
+                    ("wTerminalType", "Type", terminal_type(d.terminal_type)),
+                    ("bCSourceID", "Clock Source", d.csource_id.to_string()),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                    (
+                        "bmChannelConfig",
+                        "Channel Config",
+                        channels(d.channel_config),
+                    ),
+                    ("bmControls", "Controls", format!("0x{:04x}", d.controls)),
+                    ("iTerminal", "Name", string(d.terminal_index, &d.terminal)),
+                ],
+            ),
+            UacInterfaceDescriptor::InputTerminal3(d) => (
+                "Input Terminal",
+                vec![
+                    ("bTerminalID", "ID", d.terminal_id.to_string()),
+                    ("wTerminalType", "Type", terminal_type(d.terminal_type)),
+                    ("bCSourceID", "Clock Source", d.csource_id.to_string()),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                    ("wClusterDescrID", "Cluster", d.cluster_descr_id.to_string()),
+                    (
+                        "wExTDescrID",
+                        "Extended Terminal",
+                        match &d.channel_names {
+                            Some(names) => format!("{} ({})", d.ex_terminal_descr_id, names),
+                            None => d.ex_terminal_descr_id.to_string(),
+                        },
+                    ),
+                    (
+                        "wTerminalDescrStr",
+                        "Name",
+                        d.terminal_descr_str.to_string(),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::OutputTerminal1(d) => (
+                "Output Terminal",
+                vec![
+                    ("bTerminalID", "ID", d.terminal_id.to_string()),
+                    ("wTerminalType", "Type", terminal_type(d.terminal_type)),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    ("iTerminal", "Name", string(d.terminal_index, &d.terminal)),
+                ],
+            ),
+            UacInterfaceDescriptor::OutputTerminal2(d) => (
+                "Output Terminal",
+                vec![
+                    ("bTerminalID", "ID", d.terminal_id.to_string()),
+                    ("wTerminalType", "Type", terminal_type(d.terminal_type)),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    ("bCSourceID", "Clock Source", d.c_source_id.to_string()),
+                    ("bmControls", "Controls", format!("0x{:04x}", d.controls)),
+                    ("iTerminal", "Name", string(d.terminal_index, &d.terminal)),
+                ],
+            ),
+            UacInterfaceDescriptor::OutputTerminal3(d) => (
+                "Output Terminal",
+                vec![
+                    ("bTerminalID", "ID", d.terminal_id.to_string()),
+                    ("wTerminalType", "Type", terminal_type(d.terminal_type)),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    ("bCSourceID", "Clock Source", d.c_source_id.to_string()),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                    (
+                        "wTerminalDescrStr",
+                        "Name",
+                        d.terminal_descr_str.to_string(),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ExtendedTerminalHeader(d) => (
+                "Extended Terminal",
+                vec![
+                    ("wDescriptorID", "ID", d.descriptor_id.to_string()),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                ],
+            ),
+            UacInterfaceDescriptor::Connectors3(d) => (
+                "Connectors",
+                vec![
+                    ("wDescriptorID", "ID", d.descriptor_id.to_string()),
+                    ("bNrConnectors", "Connectors", d.nr_connectors.to_string()),
+                ],
+            ),
+            UacInterfaceDescriptor::PowerDomain(d) => (
+                "Power Domain",
+                vec![
+                    ("bPowerDomainID", "ID", d.power_domain_id.to_string()),
+                    (
+                        "waRecoveryTime(1)",
+                        "D1->D0:",
+                        format!("{} ms", d.recovery_time_1_ms()),
+                    ),
+                    (
+                        "waRecoveryTime(2)",
+                        "D2->D0:",
+                        format!("{} ms", d.recovery_time_2_ms()),
+                    ),
+                    ("baEntityID", "Entities", ids(&d.entity_ids)),
+                ],
+            ),
+            UacInterfaceDescriptor::MixerUnit1(d) => (
+                "Mixer Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                    (
+                        "wChannelConfig",
+                        "Channel Config",
+                        channels(d.channel_config as u32),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::MixerUnit2(d) => (
+                "Mixer Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                    (
+                        "bmChannelConfig",
+                        "Channel Config",
+                        channels(d.channel_config),
+                    ),
+                    ("bmControls", "Controls", format!("0x{:02x}", d.controls)),
+                ],
+            ),
+            UacInterfaceDescriptor::MixerUnit3(d) => (
+                "Mixer Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("wClusterDescrID", "Cluster", d.cluster_descr_id.to_string()),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                ],
+            ),
+            UacInterfaceDescriptor::SelectorUnit1(d) => (
+                "Selector Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("iSelector", "Name", string(d.selector_index, &d.selector)),
+                ],
+            ),
+            UacInterfaceDescriptor::SelectorUnit2(d) => (
+                "Selector Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("bmControls", "Controls", format!("0x{:02x}", d.controls)),
+                    ("iSelector", "Name", string(d.selector_index, &d.selector)),
+                ],
+            ),
+            UacInterfaceDescriptor::SelectorUnit3(d) => (
+                "Selector Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                    (
+                        "wSelectorDescrStr",
+                        "Name",
+                        d.selector_descr_str.to_string(),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ProcessingUnit1(d) => (
+                "Processing Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("wProcessType", "Type", d.processing_type().to_string()),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                    (
+                        "wChannelConfig",
+                        "Channel Config",
+                        channels(d.channel_config as u32),
+                    ),
+                    (
+                        "iProcessing",
+                        "Name",
+                        string(d.processing_index, &d.processing),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ProcessingUnit2(d) => (
+                "Processing Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("wProcessType", "Type", d.processing_type().to_string()),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                    (
+                        "bmChannelConfig",
+                        "Channel Config",
+                        channels(d.channel_config),
+                    ),
+                    ("bmControls", "Controls", format!("0x{:04x}", d.controls)),
+                    (
+                        "iProcessing",
+                        "Name",
+                        string(d.processing_index, &d.processing),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ProcessingUnit3(d) => (
+                "Processing Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("wProcessType", "Type", d.processing_type().to_string()),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    (
+                        "wProcessingDescrStr",
+                        "Name",
+                        d.processing_descr_str.to_string(),
+                    ),
+                ]
+                .into_iter()
+                .chain(match &d.specific {
+                    Some(AudioProcessingUnit3Specific::UpDownMix(udm)) => {
+                        Some(("waClusterDescrID", "Modes", udm.modes_string()))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            ),
+            UacInterfaceDescriptor::EffectUnit2(d) => (
+                "Effect Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("wEffectType", "Type", effect_type(d.effect_type)),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    (
+                        "bmaControls(0)",
+                        "Controls",
+                        effect_controls(&d.controls, d.decoded_controls()),
+                    ),
+                    ("iEffects", "Name", string(d.effect_index, &d.effect)),
+                ],
+            ),
+            UacInterfaceDescriptor::EffectUnit3(d) => (
+                "Effect Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("wEffectType", "Type", effect_type(d.effect_type)),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    (
+                        "bmaControls(0)",
+                        "Controls",
+                        effect_controls(&d.controls, d.decoded_controls()),
+                    ),
+                    ("wEffectsDescrStr", "Name", d.effect_descr_str.to_string()),
+                ],
+            ),
+            UacInterfaceDescriptor::FeatureUnit1(d) => (
+                "Feature Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    ("bControlSize", "Control Size", d.control_size.to_string()),
+                    ("iFeature", "Name", string(d.feature_index, &d.feature)),
+                ],
+            ),
+            UacInterfaceDescriptor::FeatureUnit2(d) => (
+                "Feature Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    (
+                        "bmaControls(0)",
+                        "Controls",
+                        format!(
+                            "0x{:08x}",
+                            d.channel_controls().first().copied().unwrap_or(0)
+                        ),
+                    ),
+                    ("iFeature", "Name", string(d.feature_index, &d.feature)),
+                ],
+            ),
+            UacInterfaceDescriptor::FeatureUnit3(d) => (
+                "Feature Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    (
+                        "bmaControls(0)",
+                        "Controls",
+                        format!(
+                            "0x{:08x}",
+                            d.channel_controls().first().copied().unwrap_or(0)
+                        ),
+                    ),
+                    ("wFeatureDescrStr", "Name", d.feature_descr_str.to_string()),
+                ],
+            ),
+            UacInterfaceDescriptor::ExtensionUnit1(d) => (
+                "Extension Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    (
+                        "wExtensionCode",
+                        "Code",
+                        format!("0x{:04x}", d.extension_code),
+                    ),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                    (
+                        "wChannelConfig",
+                        "Channel Config",
+                        channels(d.channel_config as u32),
+                    ),
+                    (
+                        "iExtension",
+                        "Name",
+                        string(d.extension_index, &d.extension),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ExtensionUnit2(d) => (
+                "Extension Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    (
+                        "wExtensionCode",
+                        "Code",
+                        format!("0x{:04x}", d.extension_code),
+                    ),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                    (
+                        "bmChannelConfig",
+                        "Channel Config",
+                        channels(d.channel_config),
+                    ),
+                    ("bmControls", "Controls", format!("0x{:02x}", d.controls)),
+                    (
+                        "iExtension",
+                        "Name",
+                        string(d.extension_index, &d.extension),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ExtensionUnit3(d) => (
+                "Extension Unit",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    (
+                        "wExtensionCode",
+                        "Code",
+                        format!("0x{:04x}", d.extension_code),
+                    ),
+                    ("baSourceID", "Sources", ids(&d.source_ids)),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                    (
+                        "wExtensionDescrStr",
+                        "Name",
+                        d.extension_descr_str.to_string(),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ClockSource2(d) => (
+                "Clock Source",
+                vec![
+                    ("bClockID", "ID", d.clock_id.to_string()),
+                    (
+                        "bmAttributes",
+                        "Attributes",
+                        format!(
+                            "0x{:02x} ({})",
+                            d.attributes,
+                            clock_attributes_string(d.clock_type(), d.synced_to_sof())
+                        ),
+                    ),
+                    ("bmControls", "Controls", format!("0x{:02x}", d.controls)),
+                    ("bAssocTerminal", "Terminal", d.assoc_terminal.to_string()),
+                    (
+                        "iClockSource",
+                        "Name",
+                        string(d.clock_source_index, &d.clock_source),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ClockSource3(d) => (
+                "Clock Source",
+                vec![
+                    ("bClockID", "ID", d.clock_id.to_string()),
+                    (
+                        "bmAttributes",
+                        "Attributes",
+                        format!(
+                            "0x{:02x} ({})",
+                            d.attributes,
+                            clock_attributes_string(d.clock_type(), d.synced_to_sof())
+                        ),
+                    ),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                    (
+                        "bReferenceTerminal",
+                        "Terminal",
+                        d.reference_terminal.to_string(),
+                    ),
+                    ("wCSourceDescrStr", "Name", d.clock_source_str.to_string()),
+                ],
+            ),
+            UacInterfaceDescriptor::ClockSelector2(d) => (
+                "Clock Selector",
+                vec![
+                    ("bClockID", "ID", d.clock_id.to_string()),
+                    ("baCSourceID", "Sources", ids(&d.csource_ids)),
+                    ("bmControls", "Controls", format!("0x{:02x}", d.controls)),
+                    (
+                        "iClockSelector",
+                        "Name",
+                        string(d.clock_selector_index, &d.clock_selector),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ClockSelector3(d) => (
+                "Clock Selector",
+                vec![
+                    ("bClockID", "ID", d.clock_id.to_string()),
+                    ("baCSourceID", "Sources", ids(&d.csource_ids)),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                    (
+                        "wCSelectorDescrStr",
+                        "Name",
+                        d.cselector_descr_str.to_string(),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ClockMultiplier2(d) => (
+                "Clock Multiplier",
+                vec![
+                    ("bClockID", "ID", d.clock_id.to_string()),
+                    ("bCSourceID", "Source", d.csource_id.to_string()),
+                    ("bmControls", "Controls", format!("0x{:02x}", d.controls)),
+                    (
+                        "iClockMultiplier",
+                        "Name",
+                        string(d.clock_multiplier_index, &d.clock_multiplier),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::ClockMultiplier3(d) => (
+                "Clock Multiplier",
+                vec![
+                    ("bClockID", "ID", d.clock_id.to_string()),
+                    ("bCSourceID", "Source", d.csource_id.to_string()),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                    (
+                        "wCMultiplierDescrStr",
+                        "Name",
+                        d.cmultiplier_descr_str.to_string(),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::SampleRateConverter2(d) => (
+                "Sample Rate Converter",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    ("bCSourceInID", "Clock In", d.csource_in_id.to_string()),
+                    ("bCSourceOutID", "Clock Out", d.csource_out_id.to_string()),
+                    ("iSRC", "Name", string(d.src_index, &d.src)),
+                ],
+            ),
+            UacInterfaceDescriptor::SampleRateConverter3(d) => (
+                "Sample Rate Converter",
+                vec![
+                    ("bUnitID", "ID", d.unit_id.to_string()),
+                    ("bSourceID", "Source", d.source_id.to_string()),
+                    ("bCSourceInID", "Clock In", d.csource_in_id.to_string()),
+                    ("bCSourceOutID", "Clock Out", d.csource_out_id.to_string()),
+                    ("wSRCDescrStr", "Name", d.src_descr_str.to_string()),
+                ],
+            ),
+            UacInterfaceDescriptor::StreamingInterface1(d) => (
+                "Streaming Interface",
+                vec![
+                    ("bTerminalLink", "Terminal", d.terminal_link.to_string()),
+                    ("bDelay", "Delay", d.delay.to_string()),
+                    ("wFormatTag", "Format", format!("0x{:04x}", d.format_tag)),
+                ],
+            ),
+            UacInterfaceDescriptor::StreamingInterface2(d) => (
+                "Streaming Interface",
+                vec![
+                    ("bTerminalLink", "Terminal", d.terminal_link.to_string()),
+                    ("bmControls", "Controls", {
+                        let controls = d.decoded_controls();
+                        if controls.is_empty() {
+                            format!("0x{:02x}", d.controls)
+                        } else {
+                            format!(
+                                "0x{:02x} {}",
+                                d.controls,
+                                controls
+                                    .iter()
+                                    .map(|(n, s)| format!("{n} ({s})"))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            )
+                        }
+                    }),
+                    ("bFormatType", "Format Type", d.format_type.to_string()),
+                    (
+                        "bmFormats",
+                        "Formats",
+                        formats(d.formats as u64, 8, d.format_names()),
+                    ),
+                    ("bNrChannels", "Channels", d.nr_channels.to_string()),
+                    (
+                        "bmChannelConfig",
+                        "Channel Config",
+                        channels(d.channel_config),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::StreamingInterface3(d) => (
+                "Streaming Interface",
+                vec![
+                    ("bTerminalLink", "Terminal", d.terminal_link.to_string()),
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                    ("wClusterDescrID", "Cluster", d.cluster_descr_id.to_string()),
+                    (
+                        "bmFormats",
+                        "Formats",
+                        formats(d.formats, 16, d.format_names()),
+                    ),
+                    ("bSubslotSize", "Subslot Size", d.sub_slot_size.to_string()),
+                    (
+                        "bBitResolution",
+                        "Bit Resolution",
+                        d.bit_resolution.to_string(),
+                    ),
+                ],
+            ),
+            UacInterfaceDescriptor::StreamingFormat(d) => (
+                "Format Type",
+                match &d.interface {
+                    StreamingFormatInterface::FormatTypeI1(ft) => vec![
+                        ("bFormatType", "Type", format!("{}", d.format_type)),
+                        ("tSamFreq", "Sample Frequencies", ft.frequencies_string()),
+                    ],
+                    StreamingFormatInterface::FormatTypeII1(ft) => vec![
+                        ("bFormatType", "Type", format!("{}", d.format_type)),
+                        ("tSamFreq", "Sample Frequencies", ft.frequencies_string()),
+                    ],
+                    StreamingFormatInterface::FormatTypeIII1(ft) => vec![
+                        ("bFormatType", "Type", format!("{}", d.format_type)),
+                        ("tSamFreq", "Sample Frequencies", ft.frequencies_string()),
+                    ],
+                    _ => vec![("bFormatType", "Type", format!("{}", d.format_type))],
+                },
+            ),
+            UacInterfaceDescriptor::StreamingFormatSpecific(d) => (
+                "Format Specific",
+                vec![("wFormatTag", "Format", format!("0x{:04x}", d.format_tag))],
+            ),
+            UacInterfaceDescriptor::DataStreamingEndpoint1(d) => (
+                "Endpoint",
+                vec![
+                    (
+                        "bmAttributes",
+                        "Attributes",
+                        format!("0x{:02x}", d.attributes),
+                    ),
+                    (
+                        "bLockDelayUnits",
+                        "Lock Delay Units",
+                        d.lock_delay_units().to_string(),
+                    ),
+                    ("wLockDelay", "Lock Delay", d.lock_delay.to_string()),
+                ],
+            ),
+            UacInterfaceDescriptor::DatastreamingEndpoint2(d) => (
+                "Endpoint",
+                vec![
+                    (
+                        "bmAttributes",
+                        "Attributes",
+                        format!("0x{:02x}", d.attributes),
+                    ),
+                    ("bmControls", "Controls", format!("0x{:02x}", d.controls)),
+                    (
+                        "bLockDelayUnits",
+                        "Lock Delay Units",
+                        d.lock_delay_units().to_string(),
+                    ),
+                    ("wLockDelay", "Lock Delay", d.lock_delay.to_string()),
+                ],
+            ),
+            UacInterfaceDescriptor::DataStreamingEndpoint3(d) => (
+                "Endpoint",
+                vec![
+                    ("bmControls", "Controls", format!("0x{:08x}", d.controls)),
+                    (
+                        "bLockDelayUnits",
+                        "Lock Delay Units",
+                        d.lock_delay_units().to_string(),
+                    ),
+                    ("wLockDelay", "Lock Delay", d.lock_delay.to_string()),
+                ],
+            ),
+            UacInterfaceDescriptor::Invalid(d) => {
+                ("Invalid", vec![("Data", "Data", format!("{d:02x?}"))])
+            }
+            UacInterfaceDescriptor::Generic(d) => {
+                ("Generic", vec![("Data", "Data", format!("{d:02x?}"))])
+            }
+            UacInterfaceDescriptor::Undefined(d) => {
+                ("Undefined", vec![("Data", "Data", format!("{d:02x?}"))])
+            }
+        }
+    }
+}
+
 /// USB Audio Class (UAC) protocol 1 channel names based on the "wChannelConfig" field
 ///
 /// Decoded as bitstring; each bit corresponds to a channel name
@@ -801,6 +2030,66 @@ impl Uac2ChannelNames {
     }
 }
 
+/// USB Audio Class (UAC) protocol 3 channel names based on the common 32-bit spatial location "bmChannelConfig" bitmap
+///
+/// UAC3 describes channels with cluster descriptors but the spatial location bits match UAC2 with bit 31 as raw data
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, VariantArray)]
+#[allow(missing_docs)]
+#[serde(rename_all = "kebab-case")]
+#[repr(u8)]
+pub enum Uac3ChannelNames {
+    FrontLeft = 0,
+    FrontRight = 1,
+    FrontCenter = 2,
+    LowFrequencyEffects = 3,
+    BackLeft = 4,
+    BackRight = 5,
+    FrontLeftOfCenter = 6,
+    FrontRightOfCenter = 7,
+    BackCenter = 8,
+    SideLeft = 9,
+    SideRight = 10,
+    TopCenter = 11,
+    TopFrontLeft = 12,
+    TopFrontCenter = 13,
+    TopFrontRight = 14,
+    TopBackLeft = 15,
+    TopBackCenter = 16,
+    TopBackRight = 17,
+    TopFrontLeftOfCenter = 18,
+    TopFrontRightOfCenter = 19,
+    LeftLowFrequencyEffects = 20,
+    RightLowFrequencyEffects = 21,
+    TopSideLeft = 22,
+    TopSideRight = 23,
+    BottomCenter = 24,
+    BackLeftOfCenter = 25,
+    BackRightOfCenter = 26,
+    RawData = 31,
+}
+
+impl fmt::Display for Uac3ChannelNames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Uac3ChannelNames::RawData => write!(f, "Raw Data (RD)"),
+            // same spatial locations as UAC2
+            c => write!(f, "{}", Uac2ChannelNames::VARIANTS[*c as usize]),
+        }
+    }
+}
+
+impl Uac3ChannelNames {
+    /// Get the supported [`Uac3ChannelNames`] from the bitmap value
+    pub fn from_bitmap<T: Into<u32>>(bitmap: T) -> Vec<Uac3ChannelNames> {
+        let bitmap = bitmap.into();
+        Uac3ChannelNames::VARIANTS
+            .iter()
+            .filter(|c| bitmap & (1 << (**c as u8)) != 0)
+            .copied()
+            .collect()
+    }
+}
+
 /// USB Audio Class (UAC) channel names based on the "wChannelConfig" field
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -810,6 +2099,8 @@ pub enum ChannelNames {
     Uac1(Uac1ChannelNames),
     /// UAC2 channel names
     Uac2(Uac2ChannelNames),
+    /// UAC3 channel names
+    Uac3(Uac3ChannelNames),
 }
 
 impl fmt::Display for ChannelNames {
@@ -817,6 +2108,7 @@ impl fmt::Display for ChannelNames {
         match self {
             ChannelNames::Uac1(c) => write!(f, "{c}"),
             ChannelNames::Uac2(c) => write!(f, "{c}"),
+            ChannelNames::Uac3(c) => write!(f, "{c}"),
         }
     }
 }
@@ -833,6 +2125,10 @@ impl ChannelNames {
                 .iter()
                 .map(|c| ChannelNames::Uac2(*c))
                 .collect(),
+            UacProtocol::Uac3 => Uac3ChannelNames::from_bitmap(bitmap)
+                .iter()
+                .map(|c| ChannelNames::Uac3(*c))
+                .collect(),
             _ => Vec::new(),
         }
     }
@@ -881,6 +2177,12 @@ impl UacInterfaceDescriptor {
                     .map(UacInterfaceDescriptor::ExtendedTerminalHeader),
                 _ => Ok(UacInterfaceDescriptor::Invalid(data.to_vec())),
             },
+            ControlSubtype::Connectors => match protocol {
+                UacProtocol::Uac3 => {
+                    Connectors3::try_from(data).map(UacInterfaceDescriptor::Connectors3)
+                }
+                _ => Ok(UacInterfaceDescriptor::Invalid(data.to_vec())),
+            },
             ControlSubtype::PowerDomain => match protocol {
                 UacProtocol::Uac3 => {
                     PowerDomain::try_from(data).map(UacInterfaceDescriptor::PowerDomain)
@@ -991,7 +2293,7 @@ impl UacInterfaceDescriptor {
                 _ => Ok(UacInterfaceDescriptor::Invalid(data.to_vec())),
             },
             ControlSubtype::Undefined => Ok(UacInterfaceDescriptor::Undefined(data.to_vec())),
-            _ => Ok(UacInterfaceDescriptor::Generic(data.to_vec())),
+            //_ => Ok(UacInterfaceDescriptor::Generic(data.to_vec())),
         }
     }
 
@@ -1084,6 +2386,10 @@ impl UacInterfaceDescriptor {
                 .iter()
                 .map(|c| ChannelNames::Uac2(*c))
                 .collect(),
+            UacProtocol::Uac3 => Uac3ChannelNames::from_bitmap(channel_config)
+                .iter()
+                .map(|c| ChannelNames::Uac3(*c))
+                .collect(),
             _ => Vec::new(),
         }
     }
@@ -1102,6 +2408,10 @@ impl UacInterfaceDescriptor {
                 .iter()
                 .map(|c| c.to_string())
                 .collect(),
+            UacProtocol::Uac3 => Uac3ChannelNames::from_bitmap(channel_config)
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
             _ => Vec::new(),
         }
     }
@@ -1164,10 +2474,456 @@ impl UacInterfaceDescriptor {
             | UacInterfaceDescriptor::StreamingInterface3(_)
             | UacInterfaceDescriptor::DataStreamingEndpoint3(_)
             | UacInterfaceDescriptor::ExtendedTerminalHeader(_)
+            | UacInterfaceDescriptor::Connectors3(_)
             | UacInterfaceDescriptor::PowerDomain(_) => UacProtocol::Uac3,
             _ => UacProtocol::Unknown(0xff),
         }
     }
+
+    /// Resolve the string descriptor fields from their string indexes using the `lookup` function
+    ///
+    /// `lookup` is typically backed by a GET_DESCRIPTOR string request to the device. An index of 0 means no string so the field is left `None` without calling `lookup`. UAC3 descriptors use class-specific string IDs (`*_descr_str`) rather than string indexes so are not resolved.
+    pub fn resolve_strings(&mut self, lookup: &dyn Fn(u8) -> Option<String>) {
+        let get = |index: u8| match index {
+            0 => None,
+            i => lookup(i),
+        };
+
+        match self {
+            UacInterfaceDescriptor::InputTerminal1(ah) => {
+                ah.channel_names = get(ah.channel_names_index);
+                ah.terminal = get(ah.terminal_index);
+            }
+            UacInterfaceDescriptor::InputTerminal2(ah) => {
+                ah.channel_names = get(ah.channel_names_index);
+                ah.terminal = get(ah.terminal_index);
+            }
+            UacInterfaceDescriptor::OutputTerminal1(ah) => {
+                ah.terminal = get(ah.terminal_index);
+            }
+            UacInterfaceDescriptor::OutputTerminal2(ah) => {
+                ah.terminal = get(ah.terminal_index);
+            }
+            UacInterfaceDescriptor::StreamingInterface2(ah) => {
+                ah.channel_names = get(ah.channel_names_index);
+            }
+            UacInterfaceDescriptor::SelectorUnit1(ah) => {
+                ah.selector = get(ah.selector_index);
+            }
+            UacInterfaceDescriptor::SelectorUnit2(ah) => {
+                ah.selector = get(ah.selector_index);
+            }
+            // the type-specific extensions in `specific` have no string indexes
+            UacInterfaceDescriptor::ProcessingUnit1(ah) => {
+                ah.channel_names = get(ah.channel_names_index);
+                ah.processing = get(ah.processing_index);
+            }
+            UacInterfaceDescriptor::ProcessingUnit2(ah) => {
+                ah.channel_names = get(ah.channel_names_index);
+                ah.processing = get(ah.processing_index);
+            }
+            UacInterfaceDescriptor::EffectUnit2(ah) => {
+                ah.effect = get(ah.effect_index);
+            }
+            UacInterfaceDescriptor::FeatureUnit1(ah) => {
+                ah.feature = get(ah.feature_index);
+            }
+            UacInterfaceDescriptor::FeatureUnit2(ah) => {
+                ah.feature = get(ah.feature_index);
+            }
+            UacInterfaceDescriptor::ExtensionUnit1(ah) => {
+                ah.channel_names = get(ah.channel_names_index);
+                ah.extension = get(ah.extension_index);
+            }
+            UacInterfaceDescriptor::ExtensionUnit2(ah) => {
+                ah.channel_names = get(ah.channel_names_index);
+                ah.extension = get(ah.extension_index);
+            }
+            UacInterfaceDescriptor::ClockSource2(ah) => {
+                ah.clock_source = get(ah.clock_source_index);
+            }
+            UacInterfaceDescriptor::ClockSelector2(ah) => {
+                ah.clock_selector = get(ah.clock_selector_index);
+            }
+            UacInterfaceDescriptor::ClockMultiplier2(ah) => {
+                ah.clock_multiplier = get(ah.clock_multiplier_index);
+            }
+            UacInterfaceDescriptor::SampleRateConverter2(ah) => {
+                ah.src = get(ah.src_index);
+            }
+            _ => (),
+        }
+    }
+
+    /// Resolve the UAC3 Input Terminal channel names by cross-referencing its wExTDescrID in `clusters`
+    ///
+    /// `clusters` is usually from [`resolve_channel_clusters`] of the AudioControl interface this descriptor is part of. UAC1/2 channel names are string indexes so are resolved by [`Self::resolve_strings`] instead
+    pub fn resolve_channel_names(&mut self, clusters: &HashMap<u16, String>) {
+        if let UacInterfaceDescriptor::InputTerminal3(ah) = self {
+            ah.channel_names = clusters.get(&ah.ex_terminal_descr_id).cloned();
+        }
+    }
+
+    /// The resolved channel names of the descriptor regardless of UAC protocol, if any
+    ///
+    /// UAC1/2 names are the iChannelNames string from [`Self::resolve_strings`] and UAC3 those from [`Self::resolve_channel_names`]
+    pub fn channel_names(&self) -> Option<&str> {
+        match self {
+            UacInterfaceDescriptor::InputTerminal1(d) => d.channel_names.as_deref(),
+            UacInterfaceDescriptor::InputTerminal2(d) => d.channel_names.as_deref(),
+            UacInterfaceDescriptor::InputTerminal3(d) => d.channel_names.as_deref(),
+            UacInterfaceDescriptor::StreamingInterface2(d) => d.channel_names.as_deref(),
+            UacInterfaceDescriptor::ProcessingUnit1(d) => d.channel_names.as_deref(),
+            UacInterfaceDescriptor::ProcessingUnit2(d) => d.channel_names.as_deref(),
+            UacInterfaceDescriptor::ExtensionUnit1(d) => d.channel_names.as_deref(),
+            UacInterfaceDescriptor::ExtensionUnit2(d) => d.channel_names.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The bUnitID, bTerminalID or bClockID of a Unit, Terminal or Clock Entity descriptor
+    ///
+    /// Returns `None` for descriptors that are not Entities such as the header
+    pub fn entity_id(&self) -> Option<u8> {
+        match self {
+            UacInterfaceDescriptor::InputTerminal1(d) => Some(d.terminal_id),
+            UacInterfaceDescriptor::InputTerminal2(d) => Some(d.terminal_id),
+            UacInterfaceDescriptor::InputTerminal3(d) => Some(d.terminal_id),
+            UacInterfaceDescriptor::OutputTerminal1(d) => Some(d.terminal_id),
+            UacInterfaceDescriptor::OutputTerminal2(d) => Some(d.terminal_id),
+            UacInterfaceDescriptor::OutputTerminal3(d) => Some(d.terminal_id),
+            UacInterfaceDescriptor::MixerUnit1(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::MixerUnit2(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::MixerUnit3(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::SelectorUnit1(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::SelectorUnit2(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::SelectorUnit3(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::ProcessingUnit1(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::ProcessingUnit2(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::ProcessingUnit3(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::EffectUnit2(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::EffectUnit3(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::FeatureUnit1(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::FeatureUnit2(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::FeatureUnit3(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::ExtensionUnit1(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::ExtensionUnit2(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::ExtensionUnit3(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::ClockSource2(d) => Some(d.clock_id),
+            UacInterfaceDescriptor::ClockSource3(d) => Some(d.clock_id),
+            UacInterfaceDescriptor::ClockSelector2(d) => Some(d.clock_id),
+            UacInterfaceDescriptor::ClockSelector3(d) => Some(d.clock_id),
+            UacInterfaceDescriptor::ClockMultiplier2(d) => Some(d.clock_id),
+            UacInterfaceDescriptor::ClockMultiplier3(d) => Some(d.clock_id),
+            UacInterfaceDescriptor::SampleRateConverter2(d) => Some(d.unit_id),
+            UacInterfaceDescriptor::SampleRateConverter3(d) => Some(d.unit_id),
+            _ => None,
+        }
+    }
+
+    /// The ids of the Entities the audio signal of this Entity is taken from; baSourceID or bSourceID
+    pub fn source_ids(&self) -> Vec<u8> {
+        match self {
+            UacInterfaceDescriptor::OutputTerminal1(d) => vec![d.source_id],
+            UacInterfaceDescriptor::OutputTerminal2(d) => vec![d.source_id],
+            UacInterfaceDescriptor::OutputTerminal3(d) => vec![d.source_id],
+            UacInterfaceDescriptor::MixerUnit1(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::MixerUnit2(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::MixerUnit3(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::SelectorUnit1(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::SelectorUnit2(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::SelectorUnit3(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::ProcessingUnit1(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::ProcessingUnit2(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::ProcessingUnit3(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::EffectUnit2(d) => vec![d.source_id],
+            UacInterfaceDescriptor::EffectUnit3(d) => vec![d.source_id],
+            UacInterfaceDescriptor::FeatureUnit1(d) => vec![d.source_id],
+            UacInterfaceDescriptor::FeatureUnit2(d) => vec![d.source_id],
+            UacInterfaceDescriptor::FeatureUnit3(d) => vec![d.source_id],
+            UacInterfaceDescriptor::ExtensionUnit1(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::ExtensionUnit2(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::ExtensionUnit3(d) => d.source_ids.to_owned(),
+            UacInterfaceDescriptor::SampleRateConverter2(d) => vec![d.source_id],
+            UacInterfaceDescriptor::SampleRateConverter3(d) => vec![d.source_id],
+            _ => Vec::new(),
+        }
+    }
+
+    /// The ids of the Clock Entities this Entity is clocked by or selects between; bCSourceID or baCSourceID
+    pub fn clock_source_ids(&self) -> Vec<u8> {
+        match self {
+            UacInterfaceDescriptor::InputTerminal2(d) => vec![d.csource_id],
+            UacInterfaceDescriptor::InputTerminal3(d) => vec![d.csource_id],
+            UacInterfaceDescriptor::OutputTerminal2(d) => vec![d.c_source_id],
+            UacInterfaceDescriptor::OutputTerminal3(d) => vec![d.c_source_id],
+            UacInterfaceDescriptor::ClockSelector2(d) => d.csource_ids.to_owned(),
+            UacInterfaceDescriptor::ClockSelector3(d) => d.csource_ids.to_owned(),
+            UacInterfaceDescriptor::ClockMultiplier2(d) => vec![d.csource_id],
+            UacInterfaceDescriptor::ClockMultiplier3(d) => vec![d.csource_id],
+            UacInterfaceDescriptor::SampleRateConverter2(d) => {
+                vec![d.csource_in_id, d.csource_out_id]
+            }
+            UacInterfaceDescriptor::SampleRateConverter3(d) => {
+                vec![d.csource_in_id, d.csource_out_id]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Name of the Entity with `id` used by [`resolve_entities`]; Terminals include the iTerminal string or terminal type name
+    fn entity_name(&self, id: u8) -> String {
+        let (name, _) = self.display_fields();
+        let (terminal_type, terminal) = match self {
+            UacInterfaceDescriptor::InputTerminal1(d) => (d.terminal_type, d.terminal.as_ref()),
+            UacInterfaceDescriptor::InputTerminal2(d) => (d.terminal_type, d.terminal.as_ref()),
+            UacInterfaceDescriptor::InputTerminal3(d) => (d.terminal_type, None),
+            UacInterfaceDescriptor::OutputTerminal1(d) => (d.terminal_type, d.terminal.as_ref()),
+            UacInterfaceDescriptor::OutputTerminal2(d) => (d.terminal_type, d.terminal.as_ref()),
+            UacInterfaceDescriptor::OutputTerminal3(d) => (d.terminal_type, None),
+            _ => return format!("{name} {id}"),
+        };
+
+        match terminal
+            .filter(|s| !s.is_empty())
+            .map(|s| s.as_str())
+            .or(terminal_type_name(terminal_type))
+        {
+            Some(t) => format!("{name} ({t})"),
+            None => format!("{name} {id}"),
+        }
+    }
+
+    /// Formats like [`fmt::Display`] but with the source and clock source ids followed by the name of the Entity they reference in `entities`, such as "Source 1 <- Input Terminal (Microphone)"; Power Domain entity ids are resolved the same way
+    ///
+    /// `entities` is usually from [`resolve_entities`] of the AudioControl interface this descriptor is part of; ids not found are shown as "ID n"
+    pub fn to_string_with_sources(&self, entities: &HashMap<u8, String>) -> String {
+        let (name, mut fields) = self.display_fields();
+        for (lsusb_name, _, value) in fields.iter_mut() {
+            let ids = match *lsusb_name {
+                "bSourceID" | "baSourceID" => self.source_ids(),
+                "bCSourceID" | "baCSourceID" => self.clock_source_ids(),
+                "baEntityID" => match self {
+                    UacInterfaceDescriptor::PowerDomain(d) => d.entity_ids.clone(),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            if !ids.is_empty() {
+                let names = ids
+                    .iter()
+                    .map(|id| entities.get(id).cloned().unwrap_or(format!("ID {id}")))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                *value = format!("{value} <- {names}");
+            }
+        }
+
+        let mut ret = String::new();
+        // writing to a String cannot fail
+        let _ = write_human_fields(&mut ret, name, &fields);
+        ret
+    }
+}
+
+/// Topology of the Entities in an AudioControl interface built with [`audio_topology`]
+///
+/// Each Unit, Terminal or Clock Entity id maps to the ids it takes input from, so following the sources from an Output Terminal walks back along the signal path
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioGraph {
+    /// Entity id to the ids of the Entities the audio signal is taken from
+    pub sources: BTreeMap<u8, Vec<u8>>,
+    /// Entity id to the ids of the Clock Entities it is clocked by; only Entities with clock sources are present
+    pub clock_sources: BTreeMap<u8, Vec<u8>>,
+}
+
+impl AudioGraph {
+    /// Audio signal edges as (source id, sink id) pairs
+    pub fn edges(&self) -> Vec<(u8, u8)> {
+        self.sources
+            .iter()
+            .flat_map(|(sink, sources)| sources.iter().map(move |source| (*source, *sink)))
+            .collect()
+    }
+
+    /// Ids of the Entities taking their audio signal from `id`
+    pub fn sinks(&self, id: u8) -> Vec<u8> {
+        self.sources
+            .iter()
+            .filter(|(_, sources)| sources.contains(&id))
+            .map(|(sink, _)| *sink)
+            .collect()
+    }
+}
+
+/// Builds the [`AudioGraph`] of the Entities in `descriptors` parsed from one AudioControl interface
+///
+/// Descriptors that are not Entities are skipped. If two Entities share an id, the last one wins
+pub fn audio_topology(descriptors: &[UacInterfaceDescriptor]) -> AudioGraph {
+    let mut graph = AudioGraph::default();
+    for (id, d) in descriptors
+        .iter()
+        .filter_map(|d| d.entity_id().map(|id| (id, d)))
+    {
+        graph.sources.insert(id, d.source_ids());
+        let clock_sources = d.clock_source_ids();
+        if !clock_sources.is_empty() {
+            graph.clock_sources.insert(id, clock_sources);
+        }
+    }
+    graph
+}
+
+/// Maps the id of each Clock Entity in the AudioControl interface `descriptors` to a description, so the [`UacInterfaceDescriptor::clock_source_ids`] of a Clock Selector can be shown by name
+///
+/// Clock Sources use the iClockSource string if read, else the clock type like "Internal programmable Clock"; Clock Selectors and Multipliers are named by their type and id
+pub fn resolve_clock_sources(descriptors: &[UacInterfaceDescriptor]) -> HashMap<u8, String> {
+    descriptors
+        .iter()
+        .filter_map(|d| match d {
+            UacInterfaceDescriptor::ClockSource2(cs) => Some((
+                cs.clock_id,
+                cs.clock_source
+                    .clone()
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or_else(|| {
+                        clock_attributes_string(cs.clock_type(), cs.synced_to_sof())
+                    }),
+            )),
+            UacInterfaceDescriptor::ClockSource3(cs) => Some((
+                cs.clock_id,
+                clock_attributes_string(cs.clock_type(), cs.synced_to_sof()),
+            )),
+            UacInterfaceDescriptor::ClockSelector2(cs) => {
+                Some((cs.clock_id, format!("Clock Selector {}", cs.clock_id)))
+            }
+            UacInterfaceDescriptor::ClockSelector3(cs) => {
+                Some((cs.clock_id, format!("Clock Selector {}", cs.clock_id)))
+            }
+            UacInterfaceDescriptor::ClockMultiplier2(cm) => {
+                Some((cm.clock_id, format!("Clock Multiplier {}", cm.clock_id)))
+            }
+            UacInterfaceDescriptor::ClockMultiplier3(cm) => {
+                Some((cm.clock_id, format!("Clock Multiplier {}", cm.clock_id)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Maps the wDescriptorID of each Extended Terminal in the AudioControl interface `descriptors` to its channel names, so a UAC3 Input Terminal wExTDescrID can be resolved with [`UacInterfaceDescriptor::resolve_channel_names`]
+///
+/// Channel names are from [`ExtendedTerminalHeader::channel_names_string`]; Extended Terminals without channels are not included
+pub fn resolve_channel_clusters(descriptors: &[UacInterfaceDescriptor]) -> HashMap<u16, String> {
+    descriptors
+        .iter()
+        .filter_map(|d| match d {
+            UacInterfaceDescriptor::ExtendedTerminalHeader(et) if !et.channels.is_empty() => {
+                Some((et.descriptor_id, et.channel_names_string()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Maps the id of each Unit, Terminal and Clock Entity in the AudioControl interface `descriptors` to a description for [`UacInterfaceDescriptor::to_string_with_sources`]
+///
+/// Terminals are named by type with the iTerminal string or terminal type name, such as "Input Terminal (Microphone)"; Units by type and id like "Feature Unit 2". Clock Entities use [`resolve_clock_sources`]
+pub fn resolve_entities(descriptors: &[UacInterfaceDescriptor]) -> HashMap<u8, String> {
+    let mut entities: HashMap<u8, String> = descriptors
+        .iter()
+        .filter_map(|d| d.entity_id().map(|id| (id, d.entity_name(id))))
+        .collect();
+    entities.extend(resolve_clock_sources(descriptors));
+    entities
+}
+
+/// Parses the class-specific audio descriptor `data` for interface `sub_class` and `protocol` then serializes it back to bytes
+///
+/// `data` is the full descriptor including bLength, bDescriptorType and bDescriptorSubtype. A descriptor that parses should serialize to the same bytes so this is useful to check the parsers against captured descriptors. Errors if the descriptor cannot be parsed rather than returning the [`UacInterfaceDescriptor::Invalid`] bytes
+pub fn reserialize(data: &[u8], sub_class: u8, protocol: u8) -> error::Result<Vec<u8>> {
+    let gd = GenericDescriptor::try_from(data)?;
+    let length = gd.length as usize;
+    let gd = GenericDescriptor {
+        data: data.get(3..length).map(|d| d.to_vec()),
+        ..gd
+    };
+    let ud = UacDescriptor::try_from((gd, sub_class, protocol))?;
+    if let UacInterfaceDescriptor::Invalid(_) = ud.interface {
+        return Err(Error::new(
+            ErrorKind::InvalidDescriptor,
+            &format!("Failed to parse {} descriptor", ud.descriptor_subtype),
+        ));
+    }
+    Ok(ud.into())
+}
+
+/// Parses the next AudioControl descriptor at the start of `data`, which must fit within `data`
+fn next_ac_descriptor(data: &[u8], protocol: u8) -> error::Result<UacDescriptor> {
+    let length = data.first().copied().unwrap_or(0) as usize;
+    if length < 3 || length > data.len() {
+        return Err(Error::new_descriptor_len(
+            "UAC AudioControl descriptor within wTotalLength",
+            length,
+            data.len(),
+        ));
+    }
+    let gd = GenericDescriptor::try_from(&data[..length])?;
+    UacDescriptor::try_from((gd, 1, protocol))
+}
+
+/// Iterates the class-specific AudioControl interface descriptors in `header_and_body` for `protocol`, bounded by the wTotalLength of the Header that starts them
+///
+/// `header_and_body` should start with the Header descriptor, which is yielded first, followed by each descriptor within `total_length`; anything after, such as the endpoint descriptors, is not read. The protocol is required as the Header layout differs between UAC versions. A first descriptor that is not a Header, a `total_length` longer than `header_and_body` or a descriptor that runs past `total_length` yields an error and ends the iteration
+pub fn iter_ac_descriptors(
+    header_and_body: &[u8],
+    protocol: u8,
+) -> impl Iterator<Item = error::Result<UacDescriptor>> + '_ {
+    let mut offset = 0;
+    // set from the Header
+    let mut end: Option<usize> = None;
+    let mut done = false;
+
+    std::iter::from_fn(move || {
+        let bound = end.unwrap_or(header_and_body.len());
+        if done || offset >= bound {
+            return None;
+        }
+
+        let ret = next_ac_descriptor(&header_and_body[offset..bound], protocol).and_then(|ud| {
+            if end.is_none() {
+                let total_length = match &ud.interface {
+                    UacInterfaceDescriptor::Header1(h) => h.total_length,
+                    UacInterfaceDescriptor::Header2(h) => h.total_length,
+                    UacInterfaceDescriptor::Header3(h) => h.total_length,
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidDescriptor,
+                            &format!(
+                                "Expected AudioControl Header, got {}",
+                                ud.descriptor_subtype
+                            ),
+                        ))
+                    }
+                } as usize;
+                if total_length < ud.length as usize || total_length > header_and_body.len() {
+                    return Err(Error::new_descriptor_len(
+                        "UAC AudioControl Header wTotalLength",
+                        total_length,
+                        header_and_body.len(),
+                    ));
+                }
+                end = Some(total_length);
+            }
+            Ok(ud)
+        });
+
+        match &ret {
+            Ok(ud) => offset += ud.length as usize,
+            Err(_) => done = true,
+        }
+        Some(ret)
+    })
 }
 
 /// USB Audio Class (UAC) protocol byte defines the version of the UAC
@@ -1405,6 +3161,36 @@ impl ControlSubtype {
         }
     }
 
+    /// Inverse of [`ControlSubtype::get_uac_subtype`]: the bDescriptorSubtype byte for `protocol`
+    pub fn to_uac_subtype(&self, protocol: u8) -> u8 {
+        match protocol {
+            // UAC1
+            0x00 => match self {
+                ControlSubtype::MixerUnit => 0x04,
+                ControlSubtype::SelectorUnit => 0x05,
+                ControlSubtype::FeatureUnit => 0x06,
+                ControlSubtype::ProcessingUnit => 0x07,
+                ControlSubtype::ExtensionUnit => 0x08,
+                cs => cs.to_owned() as u8,
+            },
+            // UAC2
+            0x20 => match self {
+                ControlSubtype::MixerUnit => 0x04,
+                ControlSubtype::SelectorUnit => 0x05,
+                ControlSubtype::FeatureUnit => 0x06,
+                ControlSubtype::EffectUnit => 0x07,
+                ControlSubtype::ProcessingUnit => 0x08,
+                ControlSubtype::ExtensionUnit => 0x09,
+                ControlSubtype::ClockSource => 0x0a,
+                ControlSubtype::ClockSelector => 0x0b,
+                ControlSubtype::ClockMultiplier => 0x0c,
+                ControlSubtype::SampleRateConverter => 0x0d,
+                cs => cs.to_owned() as u8,
+            },
+            _ => self.to_owned() as u8,
+        }
+    }
+
     /// Get the UAC interface descriptor from the UAC interface
     pub fn get_descriptor(
         &self,
@@ -1547,6 +3333,41 @@ impl fmt::Display for SampleFrequencyType {
     }
 }
 
+/// Formats UAC1 tSamFreq `frequencies` as "lo–hi Hz" if [`SampleFrequencyType::Continuous`] or a comma list in kHz if [`SampleFrequencyType::Discrete`]
+///
+/// ```
+/// use cyme::usb::descriptors::audio::{sample_frequencies_string, SampleFrequencyType};
+///
+/// assert_eq!(
+///     sample_frequencies_string(&SampleFrequencyType::Continuous, &[8000, 48000]),
+///     "8000–48000 Hz"
+/// );
+/// assert_eq!(
+///     sample_frequencies_string(&SampleFrequencyType::Discrete(2), &[44100, 48000]),
+///     "44.1, 48 kHz"
+/// );
+/// ```
+pub fn sample_frequencies_string(
+    sample_frequency_type: &SampleFrequencyType,
+    frequencies: &[u32],
+) -> String {
+    match sample_frequency_type {
+        SampleFrequencyType::Continuous => format!(
+            "{}–{} Hz",
+            frequencies.first().unwrap_or(&0),
+            frequencies.get(1).unwrap_or(&0)
+        ),
+        SampleFrequencyType::Discrete(_) => format!(
+            "{} kHz",
+            frequencies
+                .iter()
+                .map(|f| (*f as f64 / 1000.0).to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub enum StreamingFormatInterface {
@@ -1556,6 +3377,7 @@ pub enum StreamingFormatInterface {
     FormatTypeI2(FormatTypeI2),
     FormatTypeII2(FormatTypeII2),
     FormatTypeIII2(FormatTypeIII2),
+    FormatTypeIV2(FormatTypeIV2),
     FormatSpecificMpeg(FormatSpecificMpeg),
     FormatSpecificAc3(FormatSpecificAc3),
     Invalid(Vec<u8>),
@@ -1571,6 +3393,7 @@ impl From<StreamingFormatInterface> for Vec<u8> {
             StreamingFormatInterface::FormatTypeI2(ft) => ft.into(),
             StreamingFormatInterface::FormatTypeII2(ft) => ft.into(),
             StreamingFormatInterface::FormatTypeIII2(ft) => ft.into(),
+            StreamingFormatInterface::FormatTypeIV2(ft) => ft.into(),
             StreamingFormatInterface::FormatSpecificMpeg(ft) => ft.into(),
             StreamingFormatInterface::FormatSpecificAc3(ft) => ft.into(),
             StreamingFormatInterface::Invalid(data) => data,
@@ -1638,6 +3461,12 @@ impl StreamingFormat {
                         interface: StreamingFormatInterface::FormatTypeIII2(ft),
                     })
                 }
+                StreamingFormatType::TypeIV => {
+                    FormatTypeIV2::try_from(&data[1..]).map(|ft| StreamingFormat {
+                        format_type,
+                        interface: StreamingFormatInterface::FormatTypeIV2(ft),
+                    })
+                }
                 _ => Ok(StreamingFormat {
                     format_type,
                     interface: StreamingFormatInterface::Undefined(data[1..].to_vec()),
@@ -1776,12 +3605,20 @@ impl TryFrom<&[u8]> for FormatTypeI1 {
     }
 }
 
+impl FormatTypeI1 {
+    /// The tSamFreq sample frequencies as a range or list; see [`sample_frequencies_string`]
+    pub fn frequencies_string(&self) -> String {
+        sample_frequencies_string(&self.sample_frequency_type, &self.sample_frequencies)
+    }
+}
+
 impl From<FormatTypeI1> for Vec<u8> {
     fn from(ft: FormatTypeI1) -> Vec<u8> {
         let mut data = vec![ft.num_channels, ft.subframe_size, ft.bit_resolution];
         data.push(ft.sample_frequency_type.into());
+        // tSamFreq is 3 bytes
         for sf in ft.sample_frequencies {
-            data.extend_from_slice(&sf.to_le_bytes());
+            data.extend_from_slice(&sf.to_le_bytes()[..3]);
         }
         data
     }
@@ -1848,14 +3685,22 @@ impl TryFrom<&[u8]> for FormatTypeII1 {
     }
 }
 
+impl FormatTypeII1 {
+    /// The tSamFreq sample frequencies as a range or list; see [`sample_frequencies_string`]
+    pub fn frequencies_string(&self) -> String {
+        sample_frequencies_string(&self.sample_frequency_type, &self.sample_frequencies)
+    }
+}
+
 impl From<FormatTypeII1> for Vec<u8> {
     fn from(ft: FormatTypeII1) -> Vec<u8> {
         let mut data = Vec::new();
         data.extend_from_slice(&ft.max_bit_rate.to_le_bytes());
         data.extend_from_slice(&ft.samples_per_frame.to_le_bytes());
         data.push(ft.sample_frequency_type.into());
+        // tSamFreq is 3 bytes
         for sf in ft.sample_frequencies {
-            data.extend_from_slice(&sf.to_le_bytes());
+            data.extend_from_slice(&sf.to_le_bytes()[..3]);
         }
         data
     }
@@ -1925,12 +3770,20 @@ impl TryFrom<&[u8]> for FormatTypeIII1 {
     }
 }
 
+impl FormatTypeIII1 {
+    /// The tSamFreq sample frequencies as a range or list; see [`sample_frequencies_string`]
+    pub fn frequencies_string(&self) -> String {
+        sample_frequencies_string(&self.sample_frequency_type, &self.sample_frequencies)
+    }
+}
+
 impl From<FormatTypeIII1> for Vec<u8> {
     fn from(ft: FormatTypeIII1) -> Vec<u8> {
         let mut data = vec![ft.num_channels, ft.subframe_size, ft.bit_resolution];
         data.push(ft.sample_frequency_type.into());
+        // tSamFreq is 3 bytes
         for sf in ft.sample_frequencies {
-            data.extend_from_slice(&sf.to_le_bytes());
+            data.extend_from_slice(&sf.to_le_bytes()[..3]);
         }
         data
     }
@@ -1998,6 +3851,27 @@ impl From<FormatTypeII2> for Vec<u8> {
 #[allow(missing_docs)]
 pub type FormatTypeIII2 = FormatTypeI2;
 
+/// UAC2 Format Type IV; Frmts20 2.3.4.1
+///
+/// Type IV is only defined for UAC2 and has no fields after bFormatType. Format Type IV is not defined for UAC1.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct FormatTypeIV2 {}
+
+impl TryFrom<&[u8]> for FormatTypeIV2 {
+    type Error = Error;
+
+    fn try_from(_value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(FormatTypeIV2 {})
+    }
+}
+
+impl From<FormatTypeIV2> for Vec<u8> {
+    fn from(_ft: FormatTypeIV2) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct FormatSpecificMpeg {
@@ -2027,12 +3901,58 @@ impl TryFrom<&[u8]> for FormatSpecificMpeg {
 impl From<FormatSpecificMpeg> for Vec<u8> {
     fn from(ft: FormatSpecificMpeg) -> Vec<u8> {
         let mut data = Vec::new();
-        data.push(ft.mpeg_features);
         data.extend_from_slice(&ft.mpeg_capabilities.to_le_bytes());
+        data.push(ft.mpeg_features);
         data
     }
 }
 
+/// Internal Dynamic Range Control support from bits D5..4 of MPEG and AC-3 features
+fn dynamic_range_control_string(features: u8) -> &'static str {
+    match (features >> 4) & 3 {
+        0 => "not supported",
+        1 => "supported but not scalable",
+        2 => "scalable, common boost and cut scaling value",
+        _ => "scalable, separate boost and cut scaling value",
+    }
+}
+
+impl FormatSpecificMpeg {
+    /// Names of the supported capabilities in bmMPEGCapabilities D7..0 per UAC Formats 2.4.1.1
+    pub fn capabilities(&self) -> Vec<&'static str> {
+        [
+            "Layer I",
+            "Layer II",
+            "Layer III",
+            "MPEG-1 only",
+            "MPEG-1 dual-channel",
+            "MPEG-2 second stereo",
+            "MPEG-2 7.1 channel augmentation",
+            "Adaptive multi-channel prediction",
+        ]
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| self.mpeg_capabilities & (1 << i) != 0)
+        .map(|(_, s)| s)
+        .collect()
+    }
+
+    /// MPEG-2 multilingual support from bmMPEGCapabilities D9..8
+    pub fn multilingual_support(&self) -> &'static str {
+        match (self.mpeg_capabilities >> 8) & 3 {
+            0 => "Not supported",
+            1 => "Supported at Fs",
+            2 => "Reserved",
+            _ => "Supported at Fs and 1/2Fs",
+        }
+    }
+
+    /// Internal Dynamic Range Control support from bmMPEGFeatures D5..4
+    pub fn dynamic_range_control(&self) -> &'static str {
+        dynamic_range_control_string(self.mpeg_features)
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct FormatSpecificAc3 {
@@ -2068,6 +3988,28 @@ impl From<FormatSpecificAc3> for Vec<u8> {
     }
 }
 
+impl FormatSpecificAc3 {
+    /// Bit Stream ID values supported, bit n of bmBSID set means BSID n is supported
+    pub fn bsid_values(&self) -> Vec<u8> {
+        (0..32).filter(|i| self.bsid & (1 << i) != 0).collect()
+    }
+
+    /// Names of the supported modes in bmAC3Features D3..0 per UAC Formats 2.4.2.1
+    pub fn features(&self) -> Vec<&'static str> {
+        ["RF mode", "Line mode", "Custom0 mode", "Custom1 mode"]
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| self.ac3_features & (1 << i) != 0)
+            .map(|(_, s)| s)
+            .collect()
+    }
+
+    /// Internal Dynamic Range Control support from bmAC3Features D5..4
+    pub fn dynamic_range_control(&self) -> &'static str {
+        dynamic_range_control_string(self.ac3_features)
+    }
+}
+
 /// The control setting for a UAC bmControls byte
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
@@ -2109,6 +4051,81 @@ pub enum ControlType {
     BmControl2,
 }
 
+/// Audio Feature Unit controls in bmaControls bit order
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, VariantArray)]
+#[allow(missing_docs)]
+#[serde(rename_all = "kebab-case")]
+pub enum AudioFeatureControl {
+    Mute,
+    Volume,
+    Bass,
+    Mid,
+    Treble,
+    GraphicEqualizer,
+    AutomaticGain,
+    Delay,
+    BassBoost,
+    Loudness,
+    InputGain,
+    InputGainPad,
+    PhaseInverter,
+    Underflow,
+    Overflow,
+}
+
+impl fmt::Display for AudioFeatureControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioFeatureControl::Mute => write!(f, "Mute"),
+            AudioFeatureControl::Volume => write!(f, "Volume"),
+            AudioFeatureControl::Bass => write!(f, "Bass"),
+            AudioFeatureControl::Mid => write!(f, "Mid"),
+            AudioFeatureControl::Treble => write!(f, "Treble"),
+            AudioFeatureControl::GraphicEqualizer => write!(f, "Graphic Equalizer"),
+            AudioFeatureControl::AutomaticGain => write!(f, "Automatic Gain"),
+            AudioFeatureControl::Delay => write!(f, "Delay"),
+            AudioFeatureControl::BassBoost => write!(f, "Bass Boost"),
+            AudioFeatureControl::Loudness => write!(f, "Loudness"),
+            AudioFeatureControl::InputGain => write!(f, "Input Gain"),
+            AudioFeatureControl::InputGainPad => write!(f, "Input Gain Pad"),
+            AudioFeatureControl::PhaseInverter => write!(f, "Phase Inverter"),
+            AudioFeatureControl::Underflow => write!(f, "Underflow"),
+            AudioFeatureControl::Overflow => write!(f, "Overflow"),
+        }
+    }
+}
+
+impl AudioFeatureControl {
+    /// Get the supported [`AudioFeatureControl`]s and their [`ControlSetting`] from a bmaControls bitmap
+    ///
+    /// [`ControlType::BmControl1`] (UAC1) only flags presence so present controls are reported as [`ControlSetting::ReadWrite`]. UAC1 defines D0..D9; D10 and above are reserved and ignored.
+    pub fn from_bitmap<T: Into<u32>>(
+        bitmap: T,
+        control_type: &ControlType,
+    ) -> Vec<(AudioFeatureControl, ControlSetting)> {
+        let bitmap = bitmap.into();
+        let mut ret = Vec::new();
+        for (i, c) in AudioFeatureControl::VARIANTS.iter().enumerate() {
+            match control_type {
+                ControlType::BmControl1 => {
+                    // UAC1 stops at Loudness (D9)
+                    if i < 10 && bitmap & (1 << i) != 0 {
+                        ret.push((*c, ControlSetting::ReadWrite));
+                    }
+                }
+                ControlType::BmControl2 => {
+                    // 15 controls * 2 bits fits in u32
+                    let setting = ((bitmap >> (i * 2)) & 0x3) as u8;
+                    if setting != 0 {
+                        ret.push((*c, ControlSetting::from(setting)));
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
 /// UAC1: 4.3.2 Class-Specific AC Interface Descriptor; Table 4-2.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -2181,6 +4198,42 @@ impl TryFrom<&[u8]> for Header2 {
     }
 }
 
+impl Header2 {
+    /// Name of the audio function bCategory; Audio20 Appendix A.7
+    ///
+    /// ```
+    /// use cyme::usb::descriptors::audio::Header2;
+    ///
+    /// let header = Header2::try_from([0x00, 0x02, 0x04, 0x40, 0x00, 0x00].as_slice()).unwrap();
+    /// assert_eq!(header.category_name(), "Headset");
+    /// ```
+    pub fn category_name(&self) -> &'static str {
+        match self.category {
+            0x01 => "Desktop Speaker",
+            0x02 => "Home Theater",
+            0x03 => "Microphone",
+            0x04 => "Headset",
+            0x05 => "Telephone",
+            0x06 => "Converter",
+            0x07 => "Voice/Sound Recorder",
+            0x08 => "I/O Box",
+            0x09 => "Musical Instrument",
+            0x0a => "Pro-Audio",
+            0x0b => "Audio/Video",
+            0x0c => "Control Panel",
+            _ => "Other/Undefined",
+        }
+    }
+
+    /// The Latency Control [`ControlSetting`] from bmControls bits 0-1; None if the control is not present
+    pub fn latency_control(&self) -> Option<ControlSetting> {
+        match self.controls & 0b11 {
+            0 => None,
+            c => Some(ControlSetting::from(c)),
+        }
+    }
+}
+
 impl From<Header2> for Vec<u8> {
     fn from(val: Header2) -> Self {
         let mut data = Vec::new();
@@ -2230,6 +4283,196 @@ impl From<Header3> for Vec<u8> {
     }
 }
 
+const UAC2_TYPE_I_FORMATS: [&str; 5] = ["PCM", "PCM8", "IEEE_FLOAT", "ALAW", "MULAW"];
+const UAC2_TYPE_II_FORMATS: [&str; 4] = ["MPEG", "AC-3", "WMA", "DTS"];
+const UAC2_TYPE_III_FORMATS: [&str; 13] = [
+    "IEC61937_AC-3",
+    "IEC61937_MPEG-1_Layer1",
+    "IEC61937_MPEG-1_Layer2/3 or IEC61937_MPEG-2_NOEXT",
+    "IEC61937_MPEG-2_EXT",
+    "IEC61937_MPEG-2_AAC_ADTS",
+    "IEC61937_MPEG-2_Layer1_LS",
+    "IEC61937_MPEG-2_Layer2/3_LS",
+    "IEC61937_DTS-I",
+    "IEC61937_DTS-II",
+    "IEC61937_DTS-III",
+    "IEC61937_ATRAC",
+    "IEC61937_ATRAC2/3",
+    "TYPE_III_WMA",
+];
+const UAC2_TYPE_IV_FORMATS: [&str; 22] = [
+    "PCM",
+    "PCM8",
+    "IEEE_FLOAT",
+    "ALAW",
+    "MULAW",
+    "MPEG",
+    "AC-3",
+    "WMA",
+    "IEC61937_AC-3",
+    "IEC61937_MPEG-1_Layer1",
+    "IEC61937_MPEG-1_Layer2/3 or IEC61937_MPEG-2_NOEXT",
+    "IEC61937_MPEG-2_EXT",
+    "IEC61937_MPEG-2_AAC_ADTS",
+    "IEC61937_MPEG-2_Layer1_LS",
+    "IEC61937_MPEG-2_Layer2/3_LS",
+    "IEC61937_DTS-I",
+    "IEC61937_DTS-II",
+    "IEC61937_DTS-III",
+    "IEC61937_ATRAC",
+    "IEC61937_ATRAC2/3",
+    "TYPE_III_WMA",
+    "IEC60958_PCM",
+];
+const UAC3_FORMATS: [&str; 33] = [
+    "PCM",
+    "PCM8",
+    "IEEE_FLOAT",
+    "ALAW",
+    "MULAW",
+    "DSD",
+    "RAW_DATA",
+    "PCM_IEC60958",
+    "AC-3",
+    "MPEG-1_Layer1",
+    "MPEG-1_Layer2/3 or MPEG-2_NOEXT",
+    "MPEG-2_EXT",
+    "MPEG-2_AAC_ADTS",
+    "MPEG-2_Layer1_LS",
+    "MPEG-2_Layer2/3_LS",
+    "DTS-I",
+    "DTS-II",
+    "DTS-III",
+    "ATRAC",
+    "ATRAC2/3",
+    "WMA",
+    "E-AC-3",
+    "MAT",
+    "DTS-IV",
+    "MPEG-4_HE_AAC",
+    "MPEG-4_HE_AAC_V2",
+    "MPEG-4_AAC_LC",
+    "DRA",
+    "MPEG-4_HE_AAC_SURROUND",
+    "MPEG-4_AAC_LC_SURROUND",
+    "MPEG-H_3D_AUDIO",
+    "AC-4",
+    "MPEG-4_AAC_ELD",
+];
+
+/// Names of the bits set in `bitmap` from `names` indexed by bit
+fn bitmap_names(bitmap: u64, names: &[&'static str]) -> Vec<&'static str> {
+    names
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| bitmap & (1 << i) != 0)
+        .map(|(_, n)| *n)
+        .collect()
+}
+
+/// Names of the audio data formats set in a UAC2 `formats` (bmFormats) bitmap, whose meaning depends on `format_type` (bFormatType)
+///
+/// Format types I-IV are from the USB Audio Data Formats 2.0 specification with bit 31 the type raw data; other format types return an empty `Vec`
+///
+/// ```
+/// use cyme::usb::audio::format_names;
+///
+/// assert_eq!(format_names(1, 0x0000_0005), vec!["PCM", "IEEE_FLOAT"]);
+/// assert_eq!(format_names(2, 0x8000_0002), vec!["AC-3", "TYPE_II_RAW_DATA"]);
+/// ```
+pub fn format_names(format_type: u8, formats: u32) -> Vec<&'static str> {
+    let (names, raw): (&[&'static str], Option<&'static str>) = match format_type {
+        1 => (&UAC2_TYPE_I_FORMATS, Some("TYPE_I_RAW_DATA")),
+        2 => (&UAC2_TYPE_II_FORMATS, Some("TYPE_II_RAW_DATA")),
+        3 => (&UAC2_TYPE_III_FORMATS, None),
+        4 => (&UAC2_TYPE_IV_FORMATS, None),
+        _ => return Vec::new(),
+    };
+
+    let mut ret = bitmap_names(formats as u64, names);
+    if let Some(raw) = raw.filter(|_| formats & (1 << 31) != 0) {
+        ret.push(raw);
+    }
+    ret
+}
+
+/// Names of the audio data formats set in a UAC3 `formats` (bmFormats) bitmap from the USB Audio Data Formats 3.0 specification
+pub fn uac3_format_names(formats: u64) -> Vec<&'static str> {
+    bitmap_names(formats, &UAC3_FORMATS)
+}
+
+/// Get the name of a USB Audio Terminal Type (wTerminalType) as defined in the USB Audio Terminal Types specification
+///
+/// Covers the USB, Input, Output, Bi-directional, Telephony, External and Embedded terminal types. Returns `None` for unknown or vendor defined values.
+pub fn terminal_type_name(terminal_type: u16) -> Option<&'static str> {
+    match terminal_type {
+        0x0100 => Some("USB Undefined"),
+        0x0101 => Some("USB Streaming"),
+        0x01ff => Some("USB Vendor Specific"),
+        0x0200 => Some("Input Undefined"),
+        0x0201 => Some("Microphone"),
+        0x0202 => Some("Desktop Microphone"),
+        0x0203 => Some("Personal Microphone"),
+        0x0204 => Some("Omni-directional Microphone"),
+        0x0205 => Some("Microphone Array"),
+        0x0206 => Some("Processing Microphone Array"),
+        0x0300 => Some("Output Undefined"),
+        0x0301 => Some("Speaker"),
+        0x0302 => Some("Headphones"),
+        0x0303 => Some("Head Mounted Display Audio"),
+        0x0304 => Some("Desktop Speaker"),
+        0x0305 => Some("Room Speaker"),
+        0x0306 => Some("Communication Speaker"),
+        0x0307 => Some("Low Frequency Effects Speaker"),
+        0x0400 => Some("Bi-directional Undefined"),
+        0x0401 => Some("Handset"),
+        0x0402 => Some("Headset"),
+        0x0403 => Some("Speakerphone, no echo reduction"),
+        0x0404 => Some("Echo-suppressing speakerphone"),
+        0x0405 => Some("Echo-canceling speakerphone"),
+        0x0500 => Some("Telephony Undefined"),
+        0x0501 => Some("Phone line"),
+        0x0502 => Some("Telephone"),
+        0x0503 => Some("Down Line Phone"),
+        0x0600 => Some("External Undefined"),
+        0x0601 => Some("Analog Connector"),
+        0x0602 => Some("Digital Audio Interface"),
+        0x0603 => Some("Line Connector"),
+        0x0604 => Some("Legacy Audio Connector"),
+        0x0605 => Some("S/PDIF Interface"),
+        0x0606 => Some("1394 DA Stream"),
+        0x0607 => Some("1394 DV Stream Soundtrack"),
+        0x0608 => Some("ADAT Lightpipe"),
+        0x0609 => Some("TDIF"),
+        0x060a => Some("MADI"),
+        0x0700 => Some("Embedded Undefined"),
+        0x0701 => Some("Level Calibration Noise Source"),
+        0x0702 => Some("Equalization Noise"),
+        0x0703 => Some("CD Player"),
+        0x0704 => Some("DAT"),
+        0x0705 => Some("DCC"),
+        0x0706 => Some("MiniDisk"),
+        0x0707 => Some("Analog Tape"),
+        0x0708 => Some("Phonograph"),
+        0x0709 => Some("VCR Audio"),
+        0x070a => Some("Video Disc Audio"),
+        0x070b => Some("DVD Audio"),
+        0x070c => Some("TV Tuner Audio"),
+        0x070d => Some("Satellite Receiver Audio"),
+        0x070e => Some("Cable Tuner Audio"),
+        0x070f => Some("DSS Audio"),
+        0x0710 => Some("Radio Receiver"),
+        0x0711 => Some("Radio Transmitter"),
+        0x0712 => Some("Multi-track Recorder"),
+        0x0713 => Some("Synthesizer"),
+        0x0714 => Some("Piano"),
+        0x0715 => Some("Guitar"),
+        0x0716 => Some("Drums/Rhythm"),
+        0x0717 => Some("Other Musical Instrument"),
+        _ => None,
+    }
+}
+
 /// UAC1: 4.3.2.1 Input Terminal Descriptor; Table 4-3.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -2281,6 +4524,13 @@ impl From<InputTerminal1> for Vec<u8> {
     }
 }
 
+impl InputTerminal1 {
+    /// See [`terminal_type_name`]
+    pub fn terminal_type_name(&self) -> Option<&'static str> {
+        terminal_type_name(self.terminal_type)
+    }
+}
+
 /// UAC2: 4.7.2.4 Input Terminal Descriptor; Table 4-9.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -2338,6 +4588,13 @@ impl From<InputTerminal2> for Vec<u8> {
     }
 }
 
+impl InputTerminal2 {
+    /// See [`terminal_type_name`]
+    pub fn terminal_type_name(&self) -> Option<&'static str> {
+        terminal_type_name(self.terminal_type)
+    }
+}
+
 /// UAC3: 4.5.2.1 Input Terminal Descriptor; Table 4-16.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -2348,6 +4605,9 @@ pub struct InputTerminal3 {
     pub csource_id: u8,
     pub controls: u32,
     pub cluster_descr_id: u16,
+    /// Channel names of the Extended Terminal wExTDescrID resolved with [`UacInterfaceDescriptor::resolve_channel_names`]
+    #[serde(default)]
+    pub channel_names: Option<String>,
     pub ex_terminal_descr_id: u16,
     pub connectors_descr_id: u16,
     pub terminal_descr_str: u16,
@@ -2368,6 +4628,7 @@ impl TryFrom<&[u8]> for InputTerminal3 {
             csource_id: value[4],
             controls: u32::from_le_bytes([value[5], value[6], value[7], value[8]]),
             cluster_descr_id: u16::from_le_bytes([value[9], value[10]]),
+            channel_names: None,
             ex_terminal_descr_id: u16::from_le_bytes([value[11], value[12]]),
             connectors_descr_id: u16::from_le_bytes([value[13], value[14]]),
             terminal_descr_str: u16::from_le_bytes([value[15], value[16]]),
@@ -2391,6 +4652,13 @@ impl From<InputTerminal3> for Vec<u8> {
     }
 }
 
+impl InputTerminal3 {
+    /// See [`terminal_type_name`]
+    pub fn terminal_type_name(&self) -> Option<&'static str> {
+        terminal_type_name(self.terminal_type)
+    }
+}
+
 /// UAC1: 4.3.2.2 Output Terminal Descriptor; Table 4-4.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -2434,6 +4702,13 @@ impl From<OutputTerminal1> for Vec<u8> {
     }
 }
 
+impl OutputTerminal1 {
+    /// See [`terminal_type_name`]
+    pub fn terminal_type_name(&self) -> Option<&'static str> {
+        terminal_type_name(self.terminal_type)
+    }
+}
+
 /// UAC2: 4.7.2.5 Output Terminal Descriptor; Table 4-10.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -2483,6 +4758,13 @@ impl From<OutputTerminal2> for Vec<u8> {
     }
 }
 
+impl OutputTerminal2 {
+    /// See [`terminal_type_name`]
+    pub fn terminal_type_name(&self) -> Option<&'static str> {
+        terminal_type_name(self.terminal_type)
+    }
+}
+
 /// UAC3: 4.5.2.2 Output Terminal Descriptor; Table 4-17.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -2540,36 +4822,251 @@ impl From<OutputTerminal3> for Vec<u8> {
     }
 }
 
+impl OutputTerminal3 {
+    /// See [`terminal_type_name`]
+    pub fn terminal_type_name(&self) -> Option<&'static str> {
+        terminal_type_name(self.terminal_type)
+    }
+}
+
 /// UAC3: 4.5.2.3.1 Extended Terminal Header Descriptor; Table 4-18.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct ExtendedTerminalHeader {
-    pub descriptor_id: u8,
+    pub descriptor_id: u16,
     pub nr_channels: u8,
+    /// Channel entries following the header; only complete entries up to `nr_channels` that are present
+    pub channels: Vec<ExtendedTerminalChannel>,
 }
 
 impl TryFrom<&[u8]> for ExtendedTerminalHeader {
     type Error = Error;
 
     fn try_from(value: &[u8]) -> error::Result<Self> {
-        if value.len() < 2 {
+        if value.len() < 3 {
             return Err(Error::new_descriptor_len(
                 "ExtendedTerminalHeader",
-                2,
+                3,
                 value.len(),
             ));
         }
 
+        let nr_channels = value[2];
+        let channels = value[3..]
+            .chunks_exact(ExtendedTerminalChannel::size())
+            .take(nr_channels as usize)
+            .map(ExtendedTerminalChannel::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(ExtendedTerminalHeader {
-            descriptor_id: value[0],
-            nr_channels: value[1],
+            descriptor_id: u16::from_le_bytes([value[0], value[1]]),
+            nr_channels,
+            channels,
         })
     }
 }
 
 impl From<ExtendedTerminalHeader> for Vec<u8> {
     fn from(val: ExtendedTerminalHeader) -> Self {
-        vec![val.descriptor_id, val.nr_channels]
+        let mut data = Vec::new();
+        data.extend_from_slice(&val.descriptor_id.to_le_bytes());
+        data.push(val.nr_channels);
+        for channel in val.channels {
+            data.extend(Vec::<u8>::from(channel));
+        }
+        data
+    }
+}
+
+/// UAC3: 4.5.2.3.1 Extended Terminal Header Descriptor; channel entry
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtendedTerminalChannel {
+    /// Purpose of the channel, bChPurpose
+    pub purpose: u8,
+    /// Spatial relationship of the channel, bChRelationship
+    pub relationship: u8,
+    /// String descriptor ID of the channel label, wChLabelStr
+    pub label_str: u16,
+}
+
+impl ExtendedTerminalChannel {
+    const EXPECTED_LENGTH: usize = 4;
+
+    /// Get the expected length of the channel entry
+    pub fn size() -> usize {
+        Self::EXPECTED_LENGTH
+    }
+
+    /// Returns the name of the bChRelationship spatial relationship if known
+    pub fn relationship_name(&self) -> Option<&'static str> {
+        match self.relationship {
+            0x01 => Some("Mono"),
+            0x02 => Some("Left"),
+            0x03 => Some("Right"),
+            0x04 => Some("Array"),
+            0x20 => Some("Pattern X"),
+            0x21 => Some("Pattern Y"),
+            0x22 => Some("Pattern A"),
+            0x23 => Some("Pattern B"),
+            0x24 => Some("Pattern M"),
+            0x25 => Some("Pattern S"),
+            0x80 => Some("Front Left"),
+            0x81 => Some("Front Right"),
+            0x82 => Some("Front Center"),
+            0x83 => Some("Front Left of Center"),
+            0x84 => Some("Front Right of Center"),
+            _ => None,
+        }
+    }
+}
+
+impl ExtendedTerminalHeader {
+    /// Comma separated name of each channel by [`ExtendedTerminalChannel::relationship_name`], or the relationship value if not known
+    pub fn channel_names_string(&self) -> String {
+        self.channels
+            .iter()
+            .map(|c| match c.relationship_name() {
+                Some(n) => n.to_string(),
+                None => format!("0x{:02x}", c.relationship),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+impl TryFrom<&[u8]> for ExtendedTerminalChannel {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.len() < Self::size() {
+            return Err(Error::new_descriptor_len(
+                "ExtendedTerminalChannel",
+                Self::size(),
+                value.len(),
+            ));
+        }
+
+        Ok(ExtendedTerminalChannel {
+            purpose: value[0],
+            relationship: value[1],
+            label_str: u16::from_le_bytes([value[2], value[3]]),
+        })
+    }
+}
+
+impl From<ExtendedTerminalChannel> for Vec<u8> {
+    fn from(val: ExtendedTerminalChannel) -> Self {
+        let mut data = vec![val.purpose, val.relationship];
+        data.extend_from_slice(&val.label_str.to_le_bytes());
+        data
+    }
+}
+
+/// UAC3: 4.5.2.4 Connectors Descriptor; connector entry
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct Connector3 {
+    pub con_id: u8,
+    pub cluster_descr_id: u16,
+    pub con_type: u8,
+    pub con_attributes: u8,
+    pub con_descr_str: u16,
+    pub con_color: u32,
+}
+
+impl Connector3 {
+    const EXPECTED_LENGTH: usize = 11;
+
+    /// Get the expected length of the connector entry
+    pub fn size() -> usize {
+        Self::EXPECTED_LENGTH
+    }
+}
+
+impl TryFrom<&[u8]> for Connector3 {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.len() < Self::size() {
+            return Err(Error::new_descriptor_len(
+                "Connector3",
+                Self::size(),
+                value.len(),
+            ));
+        }
+
+        Ok(Connector3 {
+            con_id: value[0],
+            cluster_descr_id: u16::from_le_bytes([value[1], value[2]]),
+            con_type: value[3],
+            con_attributes: value[4],
+            con_descr_str: u16::from_le_bytes([value[5], value[6]]),
+            con_color: u32::from_le_bytes([value[7], value[8], value[9], value[10]]),
+        })
+    }
+}
+
+impl From<Connector3> for Vec<u8> {
+    fn from(val: Connector3) -> Self {
+        let mut data = Vec::new();
+        data.push(val.con_id);
+        data.extend_from_slice(&val.cluster_descr_id.to_le_bytes());
+        data.push(val.con_type);
+        data.push(val.con_attributes);
+        data.extend_from_slice(&val.con_descr_str.to_le_bytes());
+        data.extend_from_slice(&val.con_color.to_le_bytes());
+        data
+    }
+}
+
+/// UAC3: 4.5.2.4 Connectors Descriptor
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct Connectors3 {
+    pub descriptor_id: u16,
+    pub nr_connectors: u8,
+    pub connectors: Vec<Connector3>,
+}
+
+impl TryFrom<&[u8]> for Connectors3 {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.len() < 3 {
+            return Err(Error::new_descriptor_len("Connectors3", 3, value.len()));
+        }
+
+        let nr_connectors = value[2] as usize;
+        let expected_len = 3 + nr_connectors * Connector3::size();
+        if value.len() < expected_len {
+            return Err(Error::new(
+                ErrorKind::InvalidDescriptor,
+                "Connectors3 descriptor too short for the number of connectors",
+            ));
+        }
+
+        let connectors = value[3..expected_len]
+            .chunks_exact(Connector3::size())
+            .map(Connector3::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Connectors3 {
+            descriptor_id: u16::from_le_bytes([value[0], value[1]]),
+            nr_connectors: value[2],
+            connectors,
+        })
+    }
+}
+
+impl From<Connectors3> for Vec<u8> {
+    fn from(val: Connectors3) -> Self {
+        let mut data = Vec::new();
+        data.extend_from_slice(&val.descriptor_id.to_le_bytes());
+        data.push(val.nr_connectors);
+        for connector in val.connectors {
+            data.extend(Vec::<u8>::from(connector));
+        }
+        data
     }
 }
 
@@ -2613,6 +5110,38 @@ impl TryFrom<&[u8]> for PowerDomain {
     }
 }
 
+impl PowerDomain {
+    /// waRecoveryTime(1) from D1 to D0 in milliseconds; the field is in 50 µs units
+    pub fn recovery_time_1_ms(&self) -> f32 {
+        self.recovery_time_1 as f32 / 20.0
+    }
+
+    /// waRecoveryTime(2) from D2 to D0 in milliseconds; the field is in 50 µs units
+    pub fn recovery_time_2_ms(&self) -> f32 {
+        self.recovery_time_2 as f32 / 20.0
+    }
+
+    /// The recovery times to D0 as "D1->D0: 0.5 ms, D2->D0: 15 ms"
+    ///
+    /// ```
+    /// use cyme::usb::descriptors::audio::PowerDomain;
+    ///
+    /// let pd = PowerDomain::try_from(
+    ///     [0x01, 0x0a, 0x00, 0x2c, 0x01, 0x02, 0x03, 0x05, 0x00, 0x00].as_slice(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(pd.recovery_time_1_ms(), 0.5);
+    /// assert_eq!(pd.recovery_times_string(), "D1->D0: 0.5 ms, D2->D0: 15 ms");
+    /// ```
+    pub fn recovery_times_string(&self) -> String {
+        format!(
+            "D1->D0: {} ms, D2->D0: {} ms",
+            self.recovery_time_1_ms(),
+            self.recovery_time_2_ms()
+        )
+    }
+}
+
 impl From<PowerDomain> for Vec<u8> {
     fn from(val: PowerDomain) -> Self {
         let mut data = Vec::new();
@@ -2649,18 +5178,15 @@ impl TryFrom<&[u8]> for MixerUnit1 {
         }
 
         let nr_in_pins = value[1] as usize;
-        // The number of channels is after the source IDs so depends on the number of input pins
-        let nr_channels = *value
-            .get(2 + nr_in_pins)
-            .ok_or_else(|| Error::new_descriptor_len("MixerUnit1", 2 + nr_in_pins, value.len()))?
-            as usize;
-        let expected_len = 7 + nr_in_pins + nr_channels;
+        let expected_len = 7 + nr_in_pins;
         if value.len() < expected_len {
             return Err(Error::new(
                 ErrorKind::InvalidDescriptor,
-                "MixerUnit1 descriptor too short for the number of pins and channels",
+                "MixerUnit1 descriptor too short for the number of pins",
             ));
         }
+        // bmControls size depends on the input and output channel counts so is the remaining length
+        let nr_controls = value.len() - expected_len;
 
         Ok(MixerUnit1 {
             unit_id: value[0],
@@ -2669,8 +5195,8 @@ impl TryFrom<&[u8]> for MixerUnit1 {
             nr_channels: value[2 + nr_in_pins],
             channel_config: u16::from_le_bytes([value[3 + nr_in_pins], value[4 + nr_in_pins]]),
             channel_names: value[5 + nr_in_pins],
-            controls: value[6 + nr_in_pins..6 + nr_in_pins + nr_channels].to_vec(),
-            mixer: value[6 + nr_in_pins + nr_channels],
+            controls: value[6 + nr_in_pins..6 + nr_in_pins + nr_controls].to_vec(),
+            mixer: value[6 + nr_in_pins + nr_controls],
         })
     }
 }
@@ -2714,18 +5240,15 @@ impl TryFrom<&[u8]> for MixerUnit2 {
         }
 
         let nr_in_pins = value[1] as usize;
-        // The number of channels is after the source IDs so depends on the number of input pins
-        let nr_channels = *value
-            .get(2 + nr_in_pins)
-            .ok_or_else(|| Error::new_descriptor_len("MixerUnit1", 2 + nr_in_pins, value.len()))?
-            as usize;
-        let expected_len = 10 + nr_in_pins + nr_channels;
+        let expected_len = 10 + nr_in_pins;
         if value.len() < expected_len {
             return Err(Error::new(
                 ErrorKind::InvalidDescriptor,
-                "MixerUnit2 descriptor too short for the number of pins and channels",
+                "MixerUnit2 descriptor too short for the number of pins",
             ));
         }
+        // bmMixerControls size depends on the input and output channel counts so is the remaining length
+        let nr_controls = value.len() - expected_len;
 
         Ok(MixerUnit2 {
             unit_id: value[0],
@@ -2739,9 +5262,9 @@ impl TryFrom<&[u8]> for MixerUnit2 {
                 value[6 + nr_in_pins],
             ]),
             channel_names: value[7 + nr_in_pins],
-            mixer_controls: value[8 + nr_in_pins..8 + nr_in_pins + nr_channels].to_vec(),
-            controls: value[8 + nr_in_pins + nr_channels],
-            mixer: value[9 + nr_in_pins + nr_channels],
+            mixer_controls: value[8 + nr_in_pins..8 + nr_in_pins + nr_controls].to_vec(),
+            controls: value[8 + nr_in_pins + nr_controls],
+            mixer: value[9 + nr_in_pins + nr_controls],
         })
     }
 }
@@ -2791,20 +5314,17 @@ impl TryFrom<&[u8]> for MixerUnit3 {
                 "MixerUnit3 descriptor too short for the number of pins",
             ));
         }
+        // bmMixerControls size depends on the cluster channel counts so is the remaining length; at least one byte
+        let j = value.len() - 6;
 
         Ok(MixerUnit3 {
             unit_id: value[0],
             nr_in_pins: value[1],
             source_ids: value[2..2 + nr_in_pins].to_vec(),
             cluster_descr_id: u16::from_le_bytes([value[2 + nr_in_pins], value[3 + nr_in_pins]]),
-            mixer_controls: value[4 + nr_in_pins..4 + nr_in_pins + 1].to_vec(),
-            controls: u32::from_le_bytes([
-                value[5 + nr_in_pins],
-                value[6 + nr_in_pins],
-                value[7 + nr_in_pins],
-                value[8 + nr_in_pins],
-            ]),
-            mixer_descr_str: u16::from_le_bytes([value[9 + nr_in_pins], value[10 + nr_in_pins]]),
+            mixer_controls: value[4 + nr_in_pins..j].to_vec(),
+            controls: u32::from_le_bytes([value[j], value[j + 1], value[j + 2], value[j + 3]]),
+            mixer_descr_str: u16::from_le_bytes([value[j + 4], value[j + 5]]),
         })
     }
 }
@@ -2899,6 +5419,46 @@ impl TryFrom<&[u8]> for StreamingInterface2 {
     }
 }
 
+impl StreamingInterface2 {
+    /// Names of the audio data formats set in bmFormats for the bFormatType; see [`format_names`]
+    pub fn format_names(&self) -> Vec<&'static str> {
+        format_names(self.format_type, self.formats)
+    }
+
+    /// The Active Alternate Setting Control [`ControlSetting`] from bmControls bits 0-1; None if the control is not present
+    pub fn active_alt_setting_control(&self) -> Option<ControlSetting> {
+        match self.controls & 0b11 {
+            0 => None,
+            c => Some(ControlSetting::from(c)),
+        }
+    }
+
+    /// The Valid Alternate Settings Control [`ControlSetting`] from bmControls bits 2-3; None if the control is not present
+    pub fn valid_alt_settings_control(&self) -> Option<ControlSetting> {
+        match (self.controls >> 2) & 0b11 {
+            0 => None,
+            c => Some(ControlSetting::from(c)),
+        }
+    }
+
+    /// Names of the present bmControls controls with their [`ControlSetting`]
+    pub fn decoded_controls(&self) -> Vec<(&'static str, ControlSetting)> {
+        [
+            (
+                "Active Alternate Setting",
+                self.active_alt_setting_control(),
+            ),
+            (
+                "Valid Alternate Settings",
+                self.valid_alt_settings_control(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, c)| c.map(|c| (name, c)))
+        .collect()
+    }
+}
+
 impl From<StreamingInterface2> for Vec<u8> {
     fn from(val: StreamingInterface2) -> Self {
         let mut data = Vec::new();
@@ -2953,6 +5513,13 @@ impl TryFrom<&[u8]> for StreamingInterface3 {
     }
 }
 
+impl StreamingInterface3 {
+    /// Names of the audio data formats set in bmFormats; see [`uac3_format_names`]
+    pub fn format_names(&self) -> Vec<&'static str> {
+        uac3_format_names(self.formats)
+    }
+}
+
 impl From<StreamingInterface3> for Vec<u8> {
     fn from(val: StreamingInterface3) -> Self {
         let mut data = Vec::new();
@@ -3370,65 +5937,228 @@ impl From<(UacProtocol, u16)> for AudioProcessingUnitType {
     }
 }
 
-impl fmt::Display for AudioProcessingUnitType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            AudioProcessingUnitType::Undefined => write!(f, "Undefined"),
-            AudioProcessingUnitType::UpDownMix => write!(f, "Up/Down-mix"),
-            AudioProcessingUnitType::DolbyPrologic => write!(f, "Dolby Prologic"),
-            AudioProcessingUnitType::StereoExtender3d => write!(f, "3D Stereo Extender"),
-            AudioProcessingUnitType::StereoExtender => write!(f, "Stereo Extender"),
-            AudioProcessingUnitType::Reverberation => write!(f, "Reverberation"),
-            AudioProcessingUnitType::Chorus => write!(f, "Chorus"),
-            AudioProcessingUnitType::DynRangeComp => write!(f, "Dyn Range Comp"),
-            AudioProcessingUnitType::MultiFunction => write!(f, "Multi-Function"),
+impl fmt::Display for AudioProcessingUnitType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AudioProcessingUnitType::Undefined => write!(f, "Undefined"),
+            AudioProcessingUnitType::UpDownMix => write!(f, "Up/Down-mix"),
+            AudioProcessingUnitType::DolbyPrologic => write!(f, "Dolby Prologic"),
+            AudioProcessingUnitType::StereoExtender3d => write!(f, "3D Stereo Extender"),
+            AudioProcessingUnitType::StereoExtender => write!(f, "Stereo Extender"),
+            AudioProcessingUnitType::Reverberation => write!(f, "Reverberation"),
+            AudioProcessingUnitType::Chorus => write!(f, "Chorus"),
+            AudioProcessingUnitType::DynRangeComp => write!(f, "Dyn Range Comp"),
+            AudioProcessingUnitType::MultiFunction => write!(f, "Multi-Function"),
+        }
+    }
+}
+
+/// UAC1: Up/Down-mix and Dolby Prologic proc unit descriptor extensions Table 4-9, Table 4-10.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct AudioProcessingUnitExtended1 {
+    pub nr_modes: u8,
+    pub modes: Vec<u16>,
+}
+
+impl TryFrom<&[u8]> for AudioProcessingUnitExtended1 {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.len() < 3 {
+            return Err(Error::new_descriptor_len(
+                "AudioProcessingUnitExtended1",
+                3,
+                value.len(),
+            ));
+        }
+
+        let nr_modes = value[0];
+        let modes = value[1..]
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+
+        Ok(AudioProcessingUnitExtended1 { nr_modes, modes })
+    }
+}
+
+impl From<AudioProcessingUnitExtended1> for Vec<u8> {
+    fn from(val: AudioProcessingUnitExtended1) -> Self {
+        let mut data = Vec::new();
+        data.push(val.nr_modes);
+        for mode in val.modes {
+            data.extend_from_slice(&mode.to_le_bytes());
+        }
+        data
+    }
+}
+
+/// UAC1: 3D Stereo Extender Processing Unit bmControls; Table 4-11.
+///
+/// UAC1 defines no process-specific fields after iProcessing for this type so it is decoded from the unit bmControls
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct AudioProcessingUnit1StereoExtender3d {
+    pub enable: bool,
+    pub spaciousness: bool,
+}
+
+impl TryFrom<&[u8]> for AudioProcessingUnit1StereoExtender3d {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.is_empty() {
+            return Err(Error::new_descriptor_len(
+                "AudioProcessingUnit1StereoExtender3d",
+                1,
+                value.len(),
+            ));
+        }
+
+        Ok(AudioProcessingUnit1StereoExtender3d {
+            enable: value[0] & 0x01 != 0,
+            spaciousness: value[0] & 0x02 != 0,
+        })
+    }
+}
+
+/// UAC1: Reverberation Processing Unit bmControls; Table 4-12.
+///
+/// UAC1 defines no process-specific fields after iProcessing for this type so it is decoded from the unit bmControls
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct AudioProcessingUnit1Reverberation {
+    pub enable: bool,
+    pub reverb_type: bool,
+    pub reverb_level: bool,
+    pub reverb_time: bool,
+    pub reverb_delay_feedback: bool,
+}
+
+impl TryFrom<&[u8]> for AudioProcessingUnit1Reverberation {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.is_empty() {
+            return Err(Error::new_descriptor_len(
+                "AudioProcessingUnit1Reverberation",
+                1,
+                value.len(),
+            ));
+        }
+
+        Ok(AudioProcessingUnit1Reverberation {
+            enable: value[0] & 0x01 != 0,
+            reverb_type: value[0] & 0x02 != 0,
+            reverb_level: value[0] & 0x04 != 0,
+            reverb_time: value[0] & 0x08 != 0,
+            reverb_delay_feedback: value[0] & 0x10 != 0,
+        })
+    }
+}
+
+/// UAC1: Chorus Processing Unit bmControls; Table 4-13.
+///
+/// UAC1 defines no process-specific fields after iProcessing for this type so it is decoded from the unit bmControls
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct AudioProcessingUnit1Chorus {
+    pub enable: bool,
+    pub chorus_level: bool,
+    pub chorus_modulation_rate: bool,
+    pub chorus_modulation_depth: bool,
+}
+
+impl TryFrom<&[u8]> for AudioProcessingUnit1Chorus {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> error::Result<Self> {
+        if value.is_empty() {
+            return Err(Error::new_descriptor_len(
+                "AudioProcessingUnit1Chorus",
+                1,
+                value.len(),
+            ));
         }
+
+        Ok(AudioProcessingUnit1Chorus {
+            enable: value[0] & 0x01 != 0,
+            chorus_level: value[0] & 0x02 != 0,
+            chorus_modulation_rate: value[0] & 0x04 != 0,
+            chorus_modulation_depth: value[0] & 0x08 != 0,
+        })
     }
 }
 
-/// UAC1: Up/Down-mix and Dolby Prologic proc unit descriptor extensions Table 4-9, Table 4-10.
+/// UAC1: Dynamic Range Compressor Processing Unit bmControls; Table 4-14.
+///
+/// UAC1 defines no process-specific fields after iProcessing for this type so it is decoded from the unit bmControls
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
-pub struct AudioProcessingUnitExtended1 {
-    pub nr_modes: u8,
-    pub modes: Vec<u16>,
+pub struct AudioProcessingUnit1DynRangeComp {
+    pub enable: bool,
+    pub compression_ratio: bool,
+    pub max_amplitude: bool,
+    pub threshold: bool,
+    pub attack_time: bool,
+    pub release_time: bool,
 }
 
-impl TryFrom<&[u8]> for AudioProcessingUnitExtended1 {
+impl TryFrom<&[u8]> for AudioProcessingUnit1DynRangeComp {
     type Error = Error;
 
     fn try_from(value: &[u8]) -> error::Result<Self> {
-        if value.len() < 3 {
+        if value.is_empty() {
             return Err(Error::new_descriptor_len(
-                "AudioProcessingUnitExtended1",
-                3,
+                "AudioProcessingUnit1DynRangeComp",
+                1,
                 value.len(),
             ));
         }
 
-        let nr_modes = value[0];
-        let modes = (1..value.len())
-            .step_by(2)
-            .map(|i| u16::from_le_bytes([value[i], value[i + 1]]))
-            .collect();
-
-        Ok(AudioProcessingUnitExtended1 { nr_modes, modes })
+        Ok(AudioProcessingUnit1DynRangeComp {
+            enable: value[0] & 0x01 != 0,
+            compression_ratio: value[0] & 0x02 != 0,
+            max_amplitude: value[0] & 0x04 != 0,
+            threshold: value[0] & 0x08 != 0,
+            attack_time: value[0] & 0x10 != 0,
+            release_time: value[0] & 0x20 != 0,
+        })
     }
 }
 
-impl From<AudioProcessingUnitExtended1> for Vec<u8> {
-    fn from(val: AudioProcessingUnitExtended1) -> Self {
-        let mut data = Vec::new();
-        data.push(val.nr_modes);
-        for mode in val.modes {
-            data.extend_from_slice(&mode.to_le_bytes());
+/// UAC1: Process type specific data of a [`ProcessingUnit1`]
+///
+/// Only Up/Down-mix and Dolby Prologic have fields following iProcessing; the others are decoded from bmControls so add no bytes
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+#[serde(rename_all = "kebab-case")]
+pub enum AudioProcessingUnit1Specific {
+    UpDownMix(AudioProcessingUnitExtended1),
+    DolbyPrologic(AudioProcessingUnitExtended1),
+    StereoExtender3d(AudioProcessingUnit1StereoExtender3d),
+    Reverberation(AudioProcessingUnit1Reverberation),
+    Chorus(AudioProcessingUnit1Chorus),
+    DynRangeComp(AudioProcessingUnit1DynRangeComp),
+}
+
+impl From<AudioProcessingUnit1Specific> for Vec<u8> {
+    fn from(val: AudioProcessingUnit1Specific) -> Self {
+        match val {
+            AudioProcessingUnit1Specific::UpDownMix(up_down_mix) => up_down_mix.into(),
+            AudioProcessingUnit1Specific::DolbyPrologic(dolby_prologic) => dolby_prologic.into(),
+            AudioProcessingUnit1Specific::StereoExtender3d(_)
+            | AudioProcessingUnit1Specific::Reverberation(_)
+            | AudioProcessingUnit1Specific::Chorus(_)
+            | AudioProcessingUnit1Specific::DynRangeComp(_) => Vec::new(),
         }
-        data
     }
 }
 
 /// UAC1: 4.3.2.6 Processing Unit Descriptor; Table 4-8.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "ProcessingUnit1Json")]
 #[allow(missing_docs)]
 pub struct ProcessingUnit1 {
     pub unit_id: u8,
@@ -3443,7 +6173,69 @@ pub struct ProcessingUnit1 {
     pub controls: Vec<u8>,
     pub processing_index: u8,
     pub processing: Option<String>,
-    pub specific: Option<AudioProcessingUnitExtended1>,
+    pub specific: Option<AudioProcessingUnit1Specific>,
+}
+
+/// [`ProcessingUnit1`] json before `specific` was an [`AudioProcessingUnit1Specific`], when it was the Up/Down-mix and Dolby Prologic [`AudioProcessingUnitExtended1`] only
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ProcessingUnit1SpecificJson {
+    Specific(AudioProcessingUnit1Specific),
+    Extended(AudioProcessingUnitExtended1),
+}
+
+/// Deserializes [`ProcessingUnit1`] with either form of `specific` in [`ProcessingUnit1SpecificJson`]
+#[derive(Deserialize)]
+struct ProcessingUnit1Json {
+    unit_id: u8,
+    process_type: u16,
+    nr_in_pins: u8,
+    source_ids: Vec<u8>,
+    nr_channels: u8,
+    channel_config: u16,
+    channel_names_index: u8,
+    channel_names: Option<String>,
+    control_size: u8,
+    controls: Vec<u8>,
+    processing_index: u8,
+    processing: Option<String>,
+    specific: Option<ProcessingUnit1SpecificJson>,
+}
+
+impl From<ProcessingUnit1Json> for ProcessingUnit1 {
+    fn from(val: ProcessingUnit1Json) -> Self {
+        let specific = val.specific.and_then(|s| match s {
+            ProcessingUnit1SpecificJson::Specific(s) => Some(s),
+            // the process type decides which it was
+            ProcessingUnit1SpecificJson::Extended(e) => {
+                match AudioProcessingUnitType::from((UacProtocol::Uac1, val.process_type)) {
+                    AudioProcessingUnitType::UpDownMix => {
+                        Some(AudioProcessingUnit1Specific::UpDownMix(e))
+                    }
+                    AudioProcessingUnitType::DolbyPrologic => {
+                        Some(AudioProcessingUnit1Specific::DolbyPrologic(e))
+                    }
+                    _ => None,
+                }
+            }
+        });
+
+        ProcessingUnit1 {
+            unit_id: val.unit_id,
+            process_type: val.process_type,
+            nr_in_pins: val.nr_in_pins,
+            source_ids: val.source_ids,
+            nr_channels: val.nr_channels,
+            channel_config: val.channel_config,
+            channel_names_index: val.channel_names_index,
+            channel_names: val.channel_names,
+            control_size: val.control_size,
+            controls: val.controls,
+            processing_index: val.processing_index,
+            processing: val.processing,
+            specific,
+        }
+    }
 }
 
 impl TryFrom<&[u8]> for ProcessingUnit1 {
@@ -3470,16 +6262,42 @@ impl TryFrom<&[u8]> for ProcessingUnit1 {
             ));
         }
 
-        let specific = match value[1] {
-            1 | 2 => Some(AudioProcessingUnitExtended1::try_from(
-                &value[expected_length..],
-            )?),
+        let process_type = u16::from_le_bytes([value[1], value[2]]);
+        let controls = &value[9 + nr_in_pins as usize..expected_length - 1];
+        let specific = match AudioProcessingUnitType::from((UacProtocol::Uac1, process_type)) {
+            AudioProcessingUnitType::UpDownMix => Some(AudioProcessingUnit1Specific::UpDownMix(
+                AudioProcessingUnitExtended1::try_from(&value[expected_length..])?,
+            )),
+            AudioProcessingUnitType::DolbyPrologic => {
+                Some(AudioProcessingUnit1Specific::DolbyPrologic(
+                    AudioProcessingUnitExtended1::try_from(&value[expected_length..])?,
+                ))
+            }
+            // controls can be empty if bControlSize is 0
+            AudioProcessingUnitType::StereoExtender3d => {
+                AudioProcessingUnit1StereoExtender3d::try_from(controls)
+                    .ok()
+                    .map(AudioProcessingUnit1Specific::StereoExtender3d)
+            }
+            AudioProcessingUnitType::Reverberation => {
+                AudioProcessingUnit1Reverberation::try_from(controls)
+                    .ok()
+                    .map(AudioProcessingUnit1Specific::Reverberation)
+            }
+            AudioProcessingUnitType::Chorus => AudioProcessingUnit1Chorus::try_from(controls)
+                .ok()
+                .map(AudioProcessingUnit1Specific::Chorus),
+            AudioProcessingUnitType::DynRangeComp => {
+                AudioProcessingUnit1DynRangeComp::try_from(controls)
+                    .ok()
+                    .map(AudioProcessingUnit1Specific::DynRangeComp)
+            }
             _ => None,
         };
 
         Ok(ProcessingUnit1 {
             unit_id: value[0],
-            process_type: u16::from_le_bytes([value[1], value[2]]),
+            process_type,
             nr_in_pins,
             source_ids: value[4..4 + nr_in_pins as usize].to_vec(),
             nr_channels: value[4 + nr_in_pins as usize],
@@ -3490,9 +6308,7 @@ impl TryFrom<&[u8]> for ProcessingUnit1 {
             channel_names_index: value[7 + nr_in_pins as usize],
             channel_names: None,
             control_size,
-            controls: value
-                [9 + nr_in_pins as usize..9 + nr_in_pins as usize + control_size as usize]
-                .to_vec(),
+            controls: controls.to_vec(),
             processing_index: value[expected_length - 1],
             processing: None,
             specific,
@@ -3512,11 +6328,11 @@ impl From<ProcessingUnit1> for Vec<u8> {
         data.push(val.channel_names_index);
         data.push(val.control_size);
         data.extend_from_slice(&val.controls);
+        data.push(val.processing_index);
         if let Some(specific) = val.specific {
             let specific_data: Vec<u8> = specific.into();
             data.extend_from_slice(&specific_data);
         }
-        data.push(val.processing_index);
         data
     }
 }
@@ -3549,9 +6365,9 @@ impl TryFrom<&[u8]> for AudioProcessingUnit2UpDownMix {
         }
 
         let nr_modes = value[0];
-        let modes = (1..value.len())
-            .step_by(4)
-            .map(|i| u32::from_le_bytes([value[i], value[i + 1], value[i + 2], value[i + 3]]))
+        let modes = value[1..]
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
             .collect();
 
         Ok(AudioProcessingUnit2UpDownMix { nr_modes, modes })
@@ -3590,9 +6406,9 @@ impl TryFrom<&[u8]> for AudioProcessingUnit2DolbyPrologic {
         }
 
         let nr_modes = value[0];
-        let modes = (1..value.len())
-            .step_by(4)
-            .map(|i| u32::from_le_bytes([value[i], value[i + 1], value[i + 2], value[i + 3]]))
+        let modes = value[1..]
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
             .collect();
 
         Ok(AudioProcessingUnit2DolbyPrologic { nr_modes, modes })
@@ -3649,9 +6465,9 @@ impl TryFrom<&[u8]> for AudioProcessingUnit3UpDownMix {
         }
 
         let nr_modes = value[4];
-        let cluster_descr_ids = (5..value.len())
-            .step_by(2)
-            .map(|i| u16::from_le_bytes([value[i], value[i + 1]]))
+        let cluster_descr_ids = value[5..]
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
             .collect();
 
         Ok(AudioProcessingUnit3UpDownMix {
@@ -3662,6 +6478,26 @@ impl TryFrom<&[u8]> for AudioProcessingUnit3UpDownMix {
     }
 }
 
+impl AudioProcessingUnit3UpDownMix {
+    /// Each mode paired with the waClusterDescrID of the channel cluster it outputs
+    ///
+    /// Modes are numbered from 1 like the Mode Select Control; only the `nr_modes` cluster ids present are returned
+    pub fn modes(&self) -> Vec<(u8, u16)> {
+        (1..=self.nr_modes)
+            .zip(self.cluster_descr_ids.iter().copied())
+            .collect()
+    }
+
+    /// The [`Self::modes`] as "1: Cluster 3, 2: Cluster 4"
+    pub fn modes_string(&self) -> String {
+        self.modes()
+            .iter()
+            .map(|(mode, id)| format!("{mode}: Cluster {id}"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
 impl From<AudioProcessingUnit3UpDownMix> for Vec<u8> {
     fn from(val: AudioProcessingUnit3UpDownMix) -> Self {
         let mut data = Vec::new();
@@ -4034,6 +6870,99 @@ impl ProcessingUnit3 {
     }
 }
 
+/// Get the name of a UAC2/UAC3 Effect Unit wEffectType; `None` for undefined or unknown values
+pub fn effect_type_name(effect_type: u16) -> Option<&'static str> {
+    match effect_type {
+        0x01 => Some("Parametric Equalizer Section"),
+        0x02 => Some("Reverberation"),
+        0x03 => Some("Modulation Delay"),
+        0x04 => Some("Dynamic Range Compressor"),
+        _ => None,
+    }
+}
+
+/// Parametric Equalizer Section Effect Unit bmaControls in bit pair order; UAC2 Table 4-16 and UAC3 Table 4-34
+const PARAM_EQ_SECTION_CONTROLS: [&str; 6] = [
+    "Enable",
+    "Center Frequency",
+    "Q Factor",
+    "Gain",
+    "Underflow",
+    "Overflow",
+];
+
+/// Reverberation Effect Unit bmaControls in bit pair order; UAC2 Table 4-17 and UAC3 Table 4-35
+const REVERBERATION_CONTROLS: [&str; 10] = [
+    "Enable",
+    "Type",
+    "Level",
+    "Time",
+    "Delay Feedback",
+    "Pre-Delay",
+    "Density",
+    "Hi-Freq Roll-Off",
+    "Underflow",
+    "Overflow",
+];
+
+/// Modulation Delay Effect Unit bmaControls in bit pair order; UAC2 Table 4-18 and UAC3 Table 4-36
+const MOD_DELAY_CONTROLS: [&str; 8] = [
+    "Enable",
+    "Balance",
+    "Rate",
+    "Depth",
+    "Time",
+    "Feedback Level",
+    "Underflow",
+    "Overflow",
+];
+
+/// Dynamic Range Compressor Effect Unit bmaControls in bit pair order; UAC2 Table 4-19 and UAC3 Table 4-37
+const DYN_RANGE_COMP_CONTROLS: [&str; 8] = [
+    "Enable",
+    "Compression Ratio",
+    "MaxAmpl",
+    "Threshold",
+    "Attack Time",
+    "Release Time",
+    "Underflow",
+    "Overflow",
+];
+
+/// Decode Effect Unit bmaControls for each channel (0 is master) into control names for the `effect_type` using the 2-bit [`ControlSetting`] layout
+///
+/// The meaning of each bit pair depends on the effect type so nothing is decoded for unknown types
+pub fn decode_effect_controls(
+    effect_type: u16,
+    controls: &[u32],
+) -> Vec<(u8, Vec<(&'static str, ControlSetting)>)> {
+    let names: &[&'static str] = match effect_type {
+        0x01 => &PARAM_EQ_SECTION_CONTROLS,
+        0x02 => &REVERBERATION_CONTROLS,
+        0x03 => &MOD_DELAY_CONTROLS,
+        0x04 => &DYN_RANGE_COMP_CONTROLS,
+        _ => &[],
+    };
+
+    controls
+        .iter()
+        .enumerate()
+        .map(|(ch, bitmap)| {
+            (
+                ch as u8,
+                names
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, n)| match ((bitmap >> (i * 2)) & 0x3) as u8 {
+                        0 => None,
+                        c => Some((*n, ControlSetting::from(c))),
+                    })
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
 /// UAC2: 4.7.2.10 Effect Unit Descriptor; Table 4-15.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -4054,9 +6983,9 @@ impl TryFrom<&[u8]> for EffectUnit2 {
             return Err(Error::new_descriptor_len("EffectUnit2", 9, value.len()));
         }
 
-        let controls = (4..value.len() - 1)
-            .step_by(4)
-            .map(|i| u32::from_le_bytes([value[i], value[i + 1], value[i + 2], value[i + 3]]))
+        let controls = value[4..value.len() - 1]
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
             .collect();
 
         Ok(EffectUnit2 {
@@ -4070,6 +6999,18 @@ impl TryFrom<&[u8]> for EffectUnit2 {
     }
 }
 
+impl EffectUnit2 {
+    /// Returns the name of the effect type if known; see [`effect_type_name`]
+    pub fn effect_type_name(&self) -> Option<&'static str> {
+        effect_type_name(self.effect_type)
+    }
+
+    /// Decode bmaControls for each channel (0 is master) by the effect type; see [`decode_effect_controls`]
+    pub fn decoded_controls(&self) -> Vec<(u8, Vec<(&'static str, ControlSetting)>)> {
+        decode_effect_controls(self.effect_type, &self.controls)
+    }
+}
+
 impl From<EffectUnit2> for Vec<u8> {
     fn from(val: EffectUnit2) -> Self {
         let mut data = Vec::new();
@@ -4103,9 +7044,9 @@ impl TryFrom<&[u8]> for EffectUnit3 {
             return Err(Error::new_descriptor_len("EffectUnit3", 10, value.len()));
         }
 
-        let controls = (4..value.len() - 2)
-            .step_by(4)
-            .map(|i| u32::from_le_bytes([value[i], value[i + 1], value[i + 2], value[i + 3]]))
+        let controls = value[4..value.len() - 2]
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
             .collect();
 
         Ok(EffectUnit3 {
@@ -4118,6 +7059,18 @@ impl TryFrom<&[u8]> for EffectUnit3 {
     }
 }
 
+impl EffectUnit3 {
+    /// Returns the name of the effect type if known; see [`effect_type_name`]
+    pub fn effect_type_name(&self) -> Option<&'static str> {
+        effect_type_name(self.effect_type)
+    }
+
+    /// Decode bmaControls for each channel (0 is master) by the effect type; see [`decode_effect_controls`]
+    pub fn decoded_controls(&self) -> Vec<(u8, Vec<(&'static str, ControlSetting)>)> {
+        decode_effect_controls(self.effect_type, &self.controls)
+    }
+}
+
 impl From<EffectUnit3> for Vec<u8> {
     fn from(val: EffectUnit3) -> Self {
         let mut data = Vec::new();
@@ -4161,14 +7114,15 @@ impl TryFrom<&[u8]> for FeatureUnit1 {
             ));
         }
 
-        let controls = value[3..(3 + control_size as usize)].to_vec();
+        // bmaControls for master and each logical channel, iFeature is always last
+        let controls = value[3..value.len() - 1].to_vec();
 
         Ok(FeatureUnit1 {
             unit_id: value[0],
             source_id: value[1],
             control_size,
             controls,
-            feature_index: value[3 + control_size as usize],
+            feature_index: value[value.len() - 1],
             feature: None,
         })
     }
@@ -4186,13 +7140,40 @@ impl From<FeatureUnit1> for Vec<u8> {
     }
 }
 
+impl FeatureUnit1 {
+    /// Decode bmaControls for each channel (0 is master) into [`AudioFeatureControl`]s
+    ///
+    /// Controls are `control_size` bytes per channel using the UAC1 1-bit layout.
+    pub fn decoded_controls(&self) -> Vec<(u8, Vec<(AudioFeatureControl, ControlSetting)>)> {
+        if self.control_size == 0 {
+            return Vec::new();
+        }
+
+        self.controls
+            .chunks(self.control_size as usize)
+            .enumerate()
+            .map(|(ch, bytes)| {
+                let bitmap = bytes
+                    .iter()
+                    .take(4)
+                    .enumerate()
+                    .fold(0u32, |acc, (i, b)| acc | (*b as u32) << (i * 8));
+                (
+                    ch as u8,
+                    AudioFeatureControl::from_bitmap(bitmap, &ControlType::BmControl1),
+                )
+            })
+            .collect()
+    }
+}
+
 /// UAC2: 4.7.2.8 Feature Unit Descriptor; Table 4-13.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct FeatureUnit2 {
     pub unit_id: u8,
     pub source_id: u8,
-    pub controls: [u8; 4],
+    pub controls: Vec<u8>,
     pub feature_index: u8,
     pub feature: Option<String>,
 }
@@ -4201,15 +7182,16 @@ impl TryFrom<&[u8]> for FeatureUnit2 {
     type Error = Error;
 
     fn try_from(value: &[u8]) -> error::Result<Self> {
-        if value.len() < 8 {
-            return Err(Error::new_descriptor_len("FeatureUnit2", 8, value.len()));
+        if value.len() < 7 {
+            return Err(Error::new_descriptor_len("FeatureUnit2", 7, value.len()));
         }
 
+        // 4 byte bmaControls for master and each logical channel, iFeature is always last
         Ok(FeatureUnit2 {
             unit_id: value[0],
             source_id: value[1],
-            controls: value[2..6].try_into().unwrap(),
-            feature_index: value[7],
+            controls: value[2..value.len() - 1].to_vec(),
+            feature_index: value[value.len() - 1],
             feature: None,
         })
     }
@@ -4226,13 +7208,25 @@ impl From<FeatureUnit2> for Vec<u8> {
     }
 }
 
+impl FeatureUnit2 {
+    /// bmaControls for each channel (0 is master) as 32-bit bitmaps
+    pub fn channel_controls(&self) -> Vec<u32> {
+        channel_controls_u32(&self.controls)
+    }
+
+    /// Decode bmaControls for each channel (0 is master) into [`AudioFeatureControl`]s using the 2-bit [`ControlSetting`] layout
+    pub fn decoded_controls(&self) -> Vec<(u8, Vec<(AudioFeatureControl, ControlSetting)>)> {
+        decode_channel_controls(&self.channel_controls())
+    }
+}
+
 /// UAC3: 4.5.2.7 Feature Unit Descriptor; Table 4-31.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct FeatureUnit3 {
     pub unit_id: u8,
     pub source_id: u8,
-    pub controls: [u8; 4],
+    pub controls: Vec<u8>,
     pub feature_descr_str: u16,
 }
 
@@ -4244,11 +7238,13 @@ impl TryFrom<&[u8]> for FeatureUnit3 {
             return Err(Error::new_descriptor_len("FeatureUnit3", 8, value.len()));
         }
 
+        // 4 byte bmaControls for master and each logical channel, wFeatureDescrStr is always last
+        let len = value.len();
         Ok(FeatureUnit3 {
             unit_id: value[0],
             source_id: value[1],
-            controls: value[2..6].try_into().unwrap(),
-            feature_descr_str: u16::from_le_bytes([value[6], value[7]]),
+            controls: value[2..len - 2].to_vec(),
+            feature_descr_str: u16::from_le_bytes([value[len - 2], value[len - 1]]),
         })
     }
 }
@@ -4264,6 +7260,41 @@ impl From<FeatureUnit3> for Vec<u8> {
     }
 }
 
+impl FeatureUnit3 {
+    /// bmaControls for each channel (0 is master) as 32-bit bitmaps
+    pub fn channel_controls(&self) -> Vec<u32> {
+        channel_controls_u32(&self.controls)
+    }
+
+    /// Decode bmaControls for each channel (0 is master) into [`AudioFeatureControl`]s using the 2-bit [`ControlSetting`] layout
+    pub fn decoded_controls(&self) -> Vec<(u8, Vec<(AudioFeatureControl, ControlSetting)>)> {
+        decode_channel_controls(&self.channel_controls())
+    }
+}
+
+/// Split UAC2/UAC3 bmaControls into the 4 byte bitmap of each channel, ignoring any trailing partial entry
+fn channel_controls_u32(controls: &[u8]) -> Vec<u32> {
+    controls
+        .chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+fn decode_channel_controls(
+    controls: &[u32],
+) -> Vec<(u8, Vec<(AudioFeatureControl, ControlSetting)>)> {
+    controls
+        .iter()
+        .enumerate()
+        .map(|(ch, bitmap)| {
+            (
+                ch as u8,
+                AudioFeatureControl::from_bitmap(*bitmap, &ControlType::BmControl2),
+            )
+        })
+        .collect()
+}
+
 /// UAC1: 4.3.2.7 Extension Unit Descriptor; Table 4-15.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -4291,7 +7322,9 @@ impl TryFrom<&[u8]> for ExtensionUnit1 {
         }
 
         let nr_in_pins = value[3] as usize;
-        let control_size = value[8 + nr_in_pins];
+        let control_size = *value.get(8 + nr_in_pins).ok_or_else(|| {
+            Error::new_descriptor_len("ExtensionUnit1", 10 + nr_in_pins, value.len())
+        })?;
         let expected_length = 10 + nr_in_pins + control_size as usize;
         if value.len() < expected_length {
             return Err(Error::new(
@@ -4473,6 +7506,48 @@ impl From<ExtensionUnit3> for Vec<u8> {
     }
 }
 
+/// Clock type from bits 0-1 of the Clock Source bmAttributes
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClockSourceType {
+    External = 0b00,
+    InternalFixed = 0b01,
+    InternalVariable = 0b10,
+    InternalProgrammable = 0b11,
+}
+
+impl From<u8> for ClockSourceType {
+    fn from(b: u8) -> Self {
+        match b & 0b11 {
+            0b00 => ClockSourceType::External,
+            0b01 => ClockSourceType::InternalFixed,
+            0b10 => ClockSourceType::InternalVariable,
+            _ => ClockSourceType::InternalProgrammable,
+        }
+    }
+}
+
+impl fmt::Display for ClockSourceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClockSourceType::External => write!(f, "External"),
+            ClockSourceType::InternalFixed => write!(f, "Internal fixed"),
+            ClockSourceType::InternalVariable => write!(f, "Internal variable"),
+            ClockSourceType::InternalProgrammable => write!(f, "Internal programmable"),
+        }
+    }
+}
+
+/// Describe Clock Source bmAttributes, e.g. "Internal programmable Clock, synchronized to SOF"
+fn clock_attributes_string(clock_type: ClockSourceType, synced_to_sof: bool) -> String {
+    if synced_to_sof {
+        format!("{clock_type} Clock, synchronized to SOF")
+    } else {
+        format!("{clock_type} Clock")
+    }
+}
+
 /// UAC2: 4.7.2.1 Clock Source Descriptor; Table 4-6.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -4516,6 +7591,18 @@ impl From<ClockSource2> for Vec<u8> {
     }
 }
 
+impl ClockSource2 {
+    /// Get the [`ClockSourceType`] from bmAttributes
+    pub fn clock_type(&self) -> ClockSourceType {
+        ClockSourceType::from(self.attributes)
+    }
+
+    /// Whether the clock is synchronized to SOF (bmAttributes bit 2)
+    pub fn synced_to_sof(&self) -> bool {
+        self.attributes & 0b100 != 0
+    }
+}
+
 /// UAC3: 4.5.2.12 Clock Source Descriptor; Table 4-43.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -4557,6 +7644,18 @@ impl From<ClockSource3> for Vec<u8> {
     }
 }
 
+impl ClockSource3 {
+    /// Get the [`ClockSourceType`] from bmAttributes
+    pub fn clock_type(&self) -> ClockSourceType {
+        ClockSourceType::from(self.attributes)
+    }
+
+    /// Whether the clock is synchronized to SOF (bmAttributes bit 2)
+    pub fn synced_to_sof(&self) -> bool {
+        self.attributes & 0b100 != 0
+    }
+}
+
 /// UAC2: 4.7.2.2 Clock Selector Descriptor; Table 4-7.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -4847,3 +7946,1053 @@ impl From<SampleRateConverter3> for Vec<u8> {
         data
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uac_interface_descriptor_display() {
+        let uac1 = UacInterfaceDescriptor::InputTerminal1(
+            InputTerminal1::try_from(&[0x01, 0x01, 0x02, 0x00, 0x02, 0x03, 0x00, 0x00, 0x00][..])
+                .unwrap(),
+        );
+        assert_eq!(
+            uac1.to_string(),
+            "Input Terminal: ID 1, Type 0x0201 (Microphone), Channels 2, Channel Config 0x00000003 (Left Front (L), Right Front (R)), Name 0"
+        );
+        assert_eq!(
+            format!("{uac1:#}"),
+            "INPUT_TERMINAL bTerminalID 1 wTerminalType 0x0201 (Microphone) bNrChannels 2 wChannelConfig 0x00000003 (Left Front (L), Right Front (R)) iTerminal 0"
+        );
+
+        let uac2 = UacInterfaceDescriptor::InputTerminal2(
+            InputTerminal2::try_from(
+                &[
+                    0x01, 0x01, 0x02, 0x00, 0x04, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00,
+                ][..],
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            uac2.to_string(),
+            "Input Terminal: ID 1, Type 0x0201 (Microphone), Clock Source 4, Channels 2, Channel Config 0x00000003 (Front Left (FL), Front Right (FR)), Controls 0x0000, Name 0"
+        );
+        assert_eq!(
+            format!("{uac2:#}"),
+            "INPUT_TERMINAL bTerminalID 1 wTerminalType 0x0201 (Microphone) bCSourceID 4 bNrChannels 2 bmChannelConfig 0x00000003 (Front Left (FL), Front Right (FR)) bmControls 0x0000 iTerminal 0"
+        );
+
+        let uac3 = UacInterfaceDescriptor::InputTerminal3(
+            InputTerminal3::try_from(
+                &[
+                    0x01, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x00, 0x06, 0x00,
+                    0x00, 0x00, 0x00, 0x00,
+                ][..],
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            uac3.to_string(),
+            "Input Terminal: ID 1, Type 0x0201 (Microphone), Clock Source 0, Controls 0x00000000, Cluster 5, Extended Terminal 6, Name 0"
+        );
+        assert_eq!(
+            format!("{uac3:#}"),
+            "INPUT_TERMINAL bTerminalID 1 wTerminalType 0x0201 (Microphone) bCSourceID 0 bmControls 0x00000000 wClusterDescrID 5 wExTDescrID 6 wTerminalDescrStr 0"
+        );
+    }
+
+    #[test]
+    fn test_terminal_type_name() {
+        assert_eq!(terminal_type_name(0x0101), Some("USB Streaming"));
+        assert_eq!(terminal_type_name(0x0301), Some("Speaker"));
+        assert_eq!(terminal_type_name(0x0402), Some("Headset"));
+        assert_eq!(terminal_type_name(0x0000), None);
+        assert_eq!(terminal_type_name(0xffff), None);
+
+        let it =
+            InputTerminal1::try_from(&[0x01, 0x01, 0x02, 0x00, 0x02, 0x03, 0x00, 0x00, 0x00][..])
+                .unwrap();
+        assert_eq!(it.terminal_type_name(), Some("Microphone"));
+    }
+
+    #[test]
+    fn test_resolve_strings() {
+        let lookup = |i: u8| match i {
+            1 => Some("Left Right".to_string()),
+            2 => Some("Microphone".to_string()),
+            _ => None,
+        };
+        // iChannelNames 1, iTerminal 2
+        let mut d = UacInterfaceDescriptor::from_uac_ac_interface(
+            &ControlSubtype::InputTerminal,
+            &UacProtocol::Uac1,
+            &[0x01, 0x01, 0x02, 0x00, 0x02, 0x03, 0x00, 0x01, 0x02],
+        )
+        .unwrap();
+        d.resolve_strings(&lookup);
+        let UacInterfaceDescriptor::InputTerminal1(ref it) = d else {
+            panic!("Expected InputTerminal1, got {d:?}");
+        };
+        assert_eq!(it.channel_names.as_deref(), Some("Left Right"));
+        assert_eq!(it.terminal.as_deref(), Some("Microphone"));
+
+        // index 0 is no string and must not call lookup
+        let mut d = UacInterfaceDescriptor::from_uac_ac_interface(
+            &ControlSubtype::InputTerminal,
+            &UacProtocol::Uac1,
+            &[0x01, 0x01, 0x02, 0x00, 0x02, 0x03, 0x00, 0x00, 0x00],
+        )
+        .unwrap();
+        d.resolve_strings(&|_| panic!("lookup called for index 0"));
+        let UacInterfaceDescriptor::InputTerminal1(ref it) = d else {
+            panic!("Expected InputTerminal1, got {d:?}");
+        };
+        assert_eq!(it.channel_names, None);
+        assert_eq!(it.terminal, None);
+    }
+
+    #[test]
+    fn test_connectors3_round_trip() {
+        let data: Vec<u8> = vec![
+            0x02, 0x00, 0x02, // wDescriptorID, bNrConnectors
+            0x01, 0x03, 0x00, 0x02, 0x01, 0x00, 0x00, 0x00, 0x00, 0xff, 0x00, // connector 1
+            0x02, 0x03, 0x00, 0x03, 0x02, 0x05, 0x00, 0xff, 0x00, 0x00, 0x00, // connector 2
+        ];
+        let d = UacInterfaceDescriptor::from_uac_ac_interface(
+            &ControlSubtype::Connectors,
+            &UacProtocol::Uac3,
+            &data,
+        )
+        .unwrap();
+        let UacInterfaceDescriptor::Connectors3(ref c) = d else {
+            panic!("Expected Connectors3, got {d:?}");
+        };
+        assert_eq!(c.descriptor_id, 2);
+        assert_eq!(c.connectors.len(), 2);
+        assert_eq!(c.connectors[0].cluster_descr_id, 3);
+        assert_eq!(c.connectors[0].con_color, 0x00ff0000);
+        assert_eq!(c.connectors[1].con_descr_str, 5);
+        assert_eq!(Vec::<u8>::from(d), data);
+    }
+
+    #[test]
+    fn test_processing_unit1_specific() {
+        // up/down-mix with 2 modes then reverberation with enable and reverb time controls
+        let up_down = vec![
+            0x05, 0x01, 0x00, 0x01, 0x02, 0x02, 0x03, 0x00, 0x00, 0x01, 0x03, 0x00, 0x02, 0x03,
+            0x00, 0x0c, 0x00,
+        ];
+        let reverb = vec![
+            0x06, 0x04, 0x00, 0x01, 0x05, 0x02, 0x03, 0x00, 0x00, 0x01, 0x09, 0x00,
+        ];
+        let pu = ProcessingUnit1::try_from(up_down.as_slice()).unwrap();
+        assert_eq!(
+            pu.specific,
+            Some(AudioProcessingUnit1Specific::UpDownMix(
+                AudioProcessingUnitExtended1 {
+                    nr_modes: 2,
+                    modes: vec![0x0003, 0x000c],
+                }
+            ))
+        );
+        assert_eq!(Vec::<u8>::from(pu), up_down);
+        let pu = ProcessingUnit1::try_from(reverb.as_slice()).unwrap();
+        assert_eq!(
+            pu.specific,
+            Some(AudioProcessingUnit1Specific::Reverberation(
+                AudioProcessingUnit1Reverberation {
+                    enable: true,
+                    reverb_type: false,
+                    reverb_level: false,
+                    reverb_time: true,
+                    reverb_delay_feedback: false,
+                }
+            ))
+        );
+        assert_eq!(Vec::<u8>::from(pu), reverb);
+    }
+
+    #[test]
+    fn test_processing_unit1_legacy_json() {
+        let json = |process_type: u16, specific: &str| {
+            format!(
+                r#"{{"unit_id": 5, "process_type": {process_type}, "nr_in_pins": 1, "source_ids": [2], "nr_channels": 2, "channel_config": 3, "channel_names_index": 0, "channel_names": null, "control_size": 1, "controls": [3], "processing_index": 0, "processing": null, "specific": {specific}}}"#
+            )
+        };
+        let extended = AudioProcessingUnitExtended1 {
+            nr_modes: 2,
+            modes: vec![0x0003, 0x000c],
+        };
+
+        // before specific was tagged by process type
+        let legacy = r#"{"nr_modes": 2, "modes": [3, 12]}"#;
+        let pu: ProcessingUnit1 = serde_json::from_str(&json(2, legacy)).unwrap();
+        assert_eq!(
+            pu.specific,
+            Some(AudioProcessingUnit1Specific::DolbyPrologic(
+                extended.clone()
+            ))
+        );
+        let pu: ProcessingUnit1 = serde_json::from_str(&json(1, legacy)).unwrap();
+        assert_eq!(
+            pu.specific,
+            Some(AudioProcessingUnit1Specific::UpDownMix(extended))
+        );
+
+        // current form round trips
+        let ser = serde_json::to_string(&pu).unwrap();
+        assert_eq!(serde_json::from_str::<ProcessingUnit1>(&ser).unwrap(), pu);
+        let pu: ProcessingUnit1 = serde_json::from_str(&json(1, "null")).unwrap();
+        assert_eq!(pu.specific, None);
+    }
+
+    #[test]
+    fn test_feature_unit_decoded_controls() {
+        // master mute + volume
+        let fu1 = FeatureUnit1::try_from(&[0x02, 0x01, 0x01, 0x03, 0x00][..]).unwrap();
+        assert_eq!(
+            fu1.decoded_controls(),
+            vec![(
+                0,
+                vec![
+                    (AudioFeatureControl::Mute, ControlSetting::ReadWrite),
+                    (AudioFeatureControl::Volume, ControlSetting::ReadWrite)
+                ]
+            )]
+        );
+
+        // mute read/write, volume read-only
+        let fu2 =
+            FeatureUnit2::try_from(&[0x02, 0x01, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00][..]).unwrap();
+        assert_eq!(
+            fu2.decoded_controls(),
+            vec![(
+                0,
+                vec![
+                    (AudioFeatureControl::Mute, ControlSetting::ReadWrite),
+                    (AudioFeatureControl::Volume, ControlSetting::ReadOnly)
+                ]
+            )]
+        );
+
+        // UAC1 Loudness with reserved D10..D12 set
+        let fu1 = FeatureUnit1::try_from(&[0x02, 0x01, 0x02, 0x00, 0x1e, 0x00][..]).unwrap();
+        assert_eq!(
+            fu1.decoded_controls(),
+            vec![(
+                0,
+                vec![(AudioFeatureControl::Loudness, ControlSetting::ReadWrite)]
+            )]
+        );
+
+        // master underflow read-only, channel 1 volume read/write
+        let fu2 = FeatureUnit2::try_from(
+            &[
+                0x02, 0x01, 0x00, 0x00, 0x00, 0x04, 0x0c, 0x00, 0x00, 0x00, 0x00,
+            ][..],
+        )
+        .unwrap();
+        assert_eq!(
+            fu2.decoded_controls(),
+            vec![
+                (
+                    0,
+                    vec![(AudioFeatureControl::Underflow, ControlSetting::ReadOnly)]
+                ),
+                (
+                    1,
+                    vec![(AudioFeatureControl::Volume, ControlSetting::ReadWrite)]
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_midi_group_terminal_block_round_trip() {
+        let header = [0x05, 0x26, 0x01, 0x12, 0x00];
+        let md = MidiDescriptor::try_from(&header[..]).unwrap();
+        assert_eq!(
+            md.interface,
+            MidiInterfaceDescriptor::GroupTerminalBlockHeader(GroupTerminalBlockHeader {
+                total_length: 0x12
+            })
+        );
+        assert_eq!(Vec::<u8>::from(md), header.to_vec());
+
+        let block = [
+            0x0d, 0x26, 0x02, 0x01, 0x00, 0x00, 0x01, 0x04, 0x11, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let md = MidiDescriptor::try_from(&block[..]).unwrap();
+        match &md.interface {
+            MidiInterfaceDescriptor::GroupTerminalBlock(gb) => {
+                assert_eq!(gb.block_id, 1);
+                assert_eq!(gb.num_groups, 1);
+                assert_eq!(gb.block_string_index, 4);
+                assert_eq!(gb.midi_protocol_name(), "MIDI 2.0");
+            }
+            i => panic!("Expected GroupTerminalBlock, got {i:?}"),
+        }
+        assert_eq!(Vec::<u8>::from(md), block.to_vec());
+    }
+
+    #[test]
+    fn test_format_type_i1_frequencies_string() {
+        let continuous = FormatTypeI1::try_from(
+            [0x02, 0x02, 0x10, 0x00, 0x40, 0x1f, 0x00, 0x80, 0xbb, 0x00].as_slice(),
+        )
+        .unwrap();
+        assert_eq!(continuous.frequencies_string(), "8000–48000 Hz");
+        let discrete = FormatTypeI1::try_from(
+            [0x02, 0x02, 0x10, 0x02, 0x44, 0xac, 0x00, 0x80, 0xbb, 0x00].as_slice(),
+        )
+        .unwrap();
+        assert_eq!(discrete.frequencies_string(), "44.1, 48 kHz");
+        assert!(UacInterfaceDescriptor::StreamingFormat(StreamingFormat {
+            format_type: StreamingFormatType::TypeI,
+            interface: StreamingFormatInterface::FormatTypeI1(discrete),
+        })
+        .to_string()
+        .contains("Sample Frequencies 44.1, 48 kHz"));
+    }
+
+    #[test]
+    fn test_format_type_iv_round_trip() {
+        let data = [0x04, 0x24, 0x02, 0x04];
+        let gd = GenericDescriptor::try_from(&data[..]).unwrap();
+        let ud = UacDescriptor::try_from((gd, 2, 0x20)).unwrap();
+        assert_eq!(
+            ud.interface,
+            UacInterfaceDescriptor::StreamingFormat(StreamingFormat {
+                format_type: StreamingFormatType::TypeIV,
+                interface: StreamingFormatInterface::FormatTypeIV2(FormatTypeIV2 {}),
+            })
+        );
+        assert_eq!(Vec::<u8>::from(ud), data.to_vec());
+    }
+
+    #[test]
+    fn test_recompute_total_length() {
+        let header = GenericDescriptor::try_from(
+            &[0x09, 0x24, 0x01, 0x00, 0x02, 0x08, 0x00, 0x00, 0x00][..],
+        )
+        .unwrap();
+        let clock =
+            GenericDescriptor::try_from(&[0x08, 0x24, 0x0a, 0x10, 0x01, 0x07, 0x00, 0x00][..])
+                .unwrap();
+        let mut descriptors = vec![
+            UacDescriptor::try_from((header, 1, 0x20)).unwrap(),
+            UacDescriptor::try_from((clock, 1, 0x20)).unwrap(),
+        ];
+        descriptors[1].length = 0;
+
+        UacDescriptor::recompute_total_length(&mut descriptors).unwrap();
+        assert_eq!(descriptors[1].length, 8);
+        match &descriptors[0].interface {
+            UacInterfaceDescriptor::Header2(h) => assert_eq!(h.total_length, 17),
+            i => panic!("Expected Header2, got {i:?}"),
+        }
+
+        // bLength can't represent more than 255 bytes
+        let mut gd = GenericDescriptor::try_from(&[0x03, 0x24, 0x01][..]).unwrap();
+        gd.data = Some(vec![0; 253]);
+        assert!(gd.recompute_length().is_err());
+    }
+
+    #[test]
+    fn test_iter_ac_descriptors() {
+        // Header2 with wTotalLength 17, Clock Source then a following endpoint descriptor
+        let data = [
+            0x09, 0x24, 0x01, 0x00, 0x02, 0x08, 0x11, 0x00, 0x00, 0x08, 0x24, 0x0a, 0x10, 0x01,
+            0x07, 0x00, 0x00, 0x07, 0x05, 0x81, 0x03, 0x08, 0x00, 0x04,
+        ];
+        let descriptors: Vec<UacDescriptor> = iter_ac_descriptors(&data, 0x20)
+            .collect::<error::Result<_>>()
+            .unwrap();
+        assert_eq!(descriptors.len(), 2);
+        assert!(matches!(
+            descriptors[0].interface,
+            UacInterfaceDescriptor::Header2(_)
+        ));
+        assert!(matches!(
+            descriptors[1].interface,
+            UacInterfaceDescriptor::ClockSource2(_)
+        ));
+
+        // Clock Source bLength runs past wTotalLength of 16
+        let mut short = data;
+        short[6] = 0x10;
+        let results: Vec<_> = iter_ac_descriptors(&short, 0x20).collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+
+        // wTotalLength longer than the data
+        let results: Vec<_> = iter_ac_descriptors(&data[..12], 0x20).collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+
+        // not starting with a Header
+        assert!(iter_ac_descriptors(&data[9..], 0x20)
+            .next()
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn test_clock_source_attributes() {
+        let cs = ClockSource2::try_from(&[0x10, 0x07, 0x07, 0x00, 0x00][..]).unwrap();
+        assert_eq!(cs.clock_type(), ClockSourceType::InternalProgrammable);
+        assert!(cs.synced_to_sof());
+        assert_eq!(
+            clock_attributes_string(cs.clock_type(), cs.synced_to_sof()),
+            "Internal programmable Clock, synchronized to SOF"
+        );
+    }
+
+    /// Inputs for parser fuzzing: every length up to 64 of all 0x00, all 0xff, counting and seeded random bytes
+    fn fuzz_inputs() -> Vec<Vec<u8>> {
+        let mut rng = fastrand::Rng::with_seed(0xc1e);
+        let mut inputs = Vec::new();
+        for len in 0..64 {
+            inputs.push(vec![0x00; len]);
+            inputs.push(vec![0xff; len]);
+            inputs.push((0..len as u8).collect());
+            for _ in 0..16 {
+                inputs.push((0..len).map(|_| rng.u8(..)).collect());
+            }
+        }
+        inputs
+    }
+
+    macro_rules! assert_try_from_total {
+        ($($t:ty),* $(,)?) => {
+            $(
+                if let Some(input) = fuzz_inputs().into_iter().find(|input| {
+                    std::panic::catch_unwind(|| <$t>::try_from(input.as_slice()).is_ok()).is_err()
+                }) {
+                    panic!("{} panicked for input {:02x?}", stringify!($t), input);
+                }
+                // all have a minimum length except FormatTypeIV2
+                if stringify!($t) != "FormatTypeIV2" {
+                    assert!(<$t>::try_from([].as_slice()).is_err(), "{} accepted empty input", stringify!($t));
+                }
+            )*
+        };
+    }
+
+    #[test]
+    fn test_uac3_channel_names() {
+        assert_eq!(
+            Uac3ChannelNames::from_bitmap(0x8000_0003u32),
+            vec![
+                Uac3ChannelNames::FrontLeft,
+                Uac3ChannelNames::FrontRight,
+                Uac3ChannelNames::RawData
+            ]
+        );
+        assert_eq!(
+            UacInterfaceDescriptor::get_channel_name_strings(&UacProtocol::Uac3, 0x8000_0009u32),
+            vec![
+                "Front Left (FL)",
+                "Low Frequency Effects (LFE)",
+                "Raw Data (RD)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_specific_mpeg_round_trip() {
+        // bmMPEGCapabilities low byte first, then bmMPEGFeatures
+        let bytes = [0x4f, 0x02, 0x11];
+        let mpeg = FormatSpecificMpeg::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(mpeg.mpeg_capabilities, 0x024f);
+        assert_eq!(mpeg.mpeg_features, 0x11);
+        assert_eq!(Vec::<u8>::from(mpeg), bytes);
+    }
+
+    #[test]
+    fn test_format_specific_features() {
+        let mpeg = FormatSpecificMpeg::try_from([0x03, 0x01, 0x20].as_slice()).unwrap();
+        assert_eq!(mpeg.capabilities(), vec!["Layer I", "Layer II"]);
+        assert_eq!(mpeg.multilingual_support(), "Supported at Fs");
+        assert_eq!(
+            mpeg.dynamic_range_control(),
+            "scalable, common boost and cut scaling value"
+        );
+
+        let ac3 = FormatSpecificAc3::try_from([0x00, 0x03, 0x00, 0x00, 0x13].as_slice()).unwrap();
+        assert_eq!(ac3.bsid_values(), vec![8, 9]);
+        assert_eq!(ac3.features(), vec!["RF mode", "Line mode"]);
+        assert_eq!(ac3.dynamic_range_control(), "supported but not scalable");
+    }
+
+    #[test]
+    fn test_reserialize_captured_descriptors() {
+        // (sub_class, protocol, descriptor) captured from UAC1 and UAC2 headsets and interfaces
+        let corpus: &[(u8, u8, &[u8])] = &[
+            // UAC1 AC header, input, output and feature unit
+            (
+                1,
+                0x00,
+                &[0x0a, 0x24, 0x01, 0x00, 0x01, 0x64, 0x00, 0x02, 0x01, 0x02],
+            ),
+            (
+                1,
+                0x00,
+                &[
+                    0x0c, 0x24, 0x02, 0x01, 0x01, 0x01, 0x00, 0x02, 0x03, 0x00, 0x00, 0x00,
+                ],
+            ),
+            (
+                1,
+                0x00,
+                &[0x09, 0x24, 0x03, 0x06, 0x01, 0x03, 0x00, 0x09, 0x00],
+            ),
+            (
+                1,
+                0x00,
+                &[0x0a, 0x24, 0x06, 0x09, 0x01, 0x01, 0x01, 0x02, 0x02, 0x00],
+            ),
+            (1, 0x00, &[0x07, 0x24, 0x05, 0x0f, 0x01, 0x0d, 0x00]),
+            // UAC1 AS general, format type I and iso endpoint
+            (2, 0x00, &[0x07, 0x24, 0x01, 0x01, 0x01, 0x01, 0x00]),
+            (
+                2,
+                0x00,
+                &[
+                    0x0b, 0x24, 0x02, 0x01, 0x02, 0x02, 0x10, 0x01, 0x80, 0xbb, 0x00,
+                ],
+            ),
+            (2, 0x00, &[0x07, 0x25, 0x01, 0x01, 0x00, 0x00, 0x00]),
+            // UAC1 AS MPEG format specific
+            (2, 0x00, &[0x08, 0x24, 0x03, 0x01, 0x10, 0x4f, 0x00, 0x01]),
+            // UAC2 AC header, clock source, input, output and feature unit
+            (
+                1,
+                0x20,
+                &[0x09, 0x24, 0x01, 0x00, 0x02, 0x08, 0x40, 0x00, 0x00],
+            ),
+            (1, 0x20, &[0x08, 0x24, 0x0a, 0x29, 0x03, 0x07, 0x00, 0x00]),
+            (
+                1,
+                0x20,
+                &[
+                    0x11, 0x24, 0x02, 0x02, 0x01, 0x01, 0x00, 0x29, 0x02, 0x03, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00,
+                ],
+            ),
+            (
+                1,
+                0x20,
+                &[
+                    0x0c, 0x24, 0x03, 0x06, 0x01, 0x03, 0x00, 0x09, 0x29, 0x00, 0x00, 0x00,
+                ],
+            ),
+            (
+                1,
+                0x20,
+                &[
+                    0x12, 0x24, 0x06, 0x09, 0x02, 0x0f, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x00, 0x00,
+                    0x0c, 0x00, 0x00, 0x00, 0x00,
+                ],
+            ),
+            // UAC2 AS general, format type I and iso endpoint
+            (
+                2,
+                0x20,
+                &[
+                    0x10, 0x24, 0x01, 0x02, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x02, 0x03, 0x00,
+                    0x00, 0x00, 0x00,
+                ],
+            ),
+            (2, 0x20, &[0x06, 0x24, 0x02, 0x01, 0x02, 0x10]),
+            (2, 0x20, &[0x08, 0x25, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00]),
+        ];
+        for (sub_class, protocol, data) in corpus {
+            assert_eq!(
+                reserialize(data, *sub_class, *protocol).as_deref(),
+                Ok(*data),
+                "descriptor {data:02x?}"
+            );
+        }
+        // unparsable is an error rather than the raw bytes
+        assert!(reserialize(&[0x04, 0x24, 0x02, 0x01], 1, 0x00).is_err());
+    }
+
+    #[test]
+    fn test_extended_terminal_channels() {
+        let data = [
+            0x05, 0x00, 0x02, // wDescriptorID, bNrChannels
+            0x01, 0x02, 0x10, 0x00, // channel 0
+            0x01, 0x03, 0x11, 0x00, // channel 1
+        ];
+        let et = ExtendedTerminalHeader::try_from(data.as_slice()).unwrap();
+        assert_eq!(et.descriptor_id, 5);
+        assert_eq!(et.channels.len(), 2);
+        assert_eq!(
+            et.channels[1],
+            ExtendedTerminalChannel {
+                purpose: 0x01,
+                relationship: 0x03,
+                label_str: 0x11,
+            }
+        );
+        assert_eq!(Vec::<u8>::from(et), data.to_vec());
+        // channels not present are not parsed
+        let et = ExtendedTerminalHeader::try_from(&data[..9]).unwrap();
+        assert_eq!(et.nr_channels, 2);
+        assert_eq!(et.channels.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_channel_names() {
+        // wClusterDescrID 9, wExTDescrID 5
+        let input = InputTerminal3::try_from(
+            [
+                0x01, 0x01, 0x02, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x09, 0x00, 0x05, 0x00, 0x00,
+                0x00, 0x00, 0x00,
+            ]
+            .as_slice(),
+        )
+        .unwrap();
+        let et = ExtendedTerminalHeader::try_from(
+            [
+                0x05, 0x00, 0x02, 0x01, 0x02, 0x10, 0x00, 0x01, 0x7f, 0x11, 0x00,
+            ]
+            .as_slice(),
+        )
+        .unwrap();
+        let mut descriptors = vec![
+            UacInterfaceDescriptor::InputTerminal3(input),
+            UacInterfaceDescriptor::ExtendedTerminalHeader(et),
+        ];
+
+        let clusters = resolve_channel_clusters(&descriptors);
+        assert_eq!(clusters.get(&5).unwrap(), "Left, 0x7f");
+        for d in descriptors.iter_mut() {
+            d.resolve_channel_names(&clusters);
+        }
+        assert_eq!(descriptors[0].channel_names(), Some("Left, 0x7f"));
+        assert!(descriptors[0]
+            .to_string()
+            .contains("Cluster 9, Extended Terminal 5 (Left, 0x7f)"));
+
+        // cluster not present
+        descriptors[0].resolve_channel_names(&HashMap::new());
+        assert_eq!(descriptors[0].channel_names(), None);
+    }
+
+    #[test]
+    fn test_up_down_mix3_modes() {
+        let data = [
+            0x02, 0x01, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, // unit 2, up/down-mix, source 1
+            0x00, 0x00, 0x00, 0x00, 0x02, 0x05, 0x00, 0x06, 0x00, // 2 modes, clusters 5 and 6
+        ];
+        let mut pu = UacInterfaceDescriptor::ProcessingUnit3(
+            ProcessingUnit3::try_from(data.as_slice()).unwrap(),
+        );
+        let udm = match &pu {
+            UacInterfaceDescriptor::ProcessingUnit3(ProcessingUnit3 {
+                specific: Some(AudioProcessingUnit3Specific::UpDownMix(udm)),
+                ..
+            }) => udm.to_owned(),
+            _ => panic!("expected up/down-mix"),
+        };
+        assert_eq!(udm.modes(), vec![(1, 5), (2, 6)]);
+        assert!(pu.to_string().contains("Modes 1: Cluster 5, 2: Cluster 6"));
+
+        // waClusterDescrID are not Extended Terminal IDs so no names are resolved
+        let clusters = HashMap::from([(5, "Left, Right".to_string())]);
+        pu.resolve_channel_names(&clusters);
+        assert!(pu.to_string().contains("Modes 1: Cluster 5, 2: Cluster 6"));
+
+        // only bNrModes cluster ids are modes
+        let udm = AudioProcessingUnit3UpDownMix { nr_modes: 1, ..udm };
+        assert_eq!(udm.modes(), vec![(1, 5)]);
+    }
+
+    #[test]
+    fn test_effect_unit_controls() {
+        // reverberation, master Enable read/write, Level read-only and Overflow read/write
+        let data = [
+            0x05, 0x02, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let mut eu = EffectUnit2::try_from(data.as_slice()).unwrap();
+        eu.controls[0] = 0x03 | (0x01 << 4) | (0x03 << 18);
+        assert_eq!(eu.effect_type_name(), Some("Reverberation"));
+        assert_eq!(
+            eu.decoded_controls(),
+            vec![
+                (
+                    0,
+                    vec![
+                        ("Enable", ControlSetting::ReadWrite),
+                        ("Level", ControlSetting::ReadOnly),
+                        ("Overflow", ControlSetting::ReadWrite)
+                    ]
+                ),
+                (1, vec![])
+            ]
+        );
+        let s = UacInterfaceDescriptor::EffectUnit2(eu).to_string();
+        assert!(s.contains("Type 2 (Reverberation)"));
+        assert!(s.contains("Enable (read/write), Level (read-only), Overflow (read/write)"));
+
+        // unknown effect types are not decoded
+        assert_eq!(
+            decode_effect_controls(0x10, &[0x03]),
+            vec![(0, Vec::<(&str, ControlSetting)>::new())]
+        );
+    }
+
+    #[test]
+    fn test_audio_topology() {
+        // input terminal 1 -> feature unit 2 -> output terminal 3, clocked by clock source 4
+        let input = InputTerminal2::try_from(
+            [
+                0x01, 0x01, 0x01, 0x00, 0x04, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ]
+            .as_slice(),
+        )
+        .unwrap();
+        let feature =
+            FeatureUnit2::try_from([0x02, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00].as_slice())
+                .unwrap();
+        let output = OutputTerminal2::try_from(
+            [0x03, 0x01, 0x03, 0x00, 0x02, 0x04, 0x00, 0x00, 0x00].as_slice(),
+        )
+        .unwrap();
+        let descriptors = vec![
+            UacInterfaceDescriptor::InputTerminal2(input),
+            UacInterfaceDescriptor::FeatureUnit2(feature),
+            UacInterfaceDescriptor::OutputTerminal2(output),
+        ];
+
+        let graph = audio_topology(&descriptors);
+        assert_eq!(graph.edges(), vec![(1, 2), (2, 3)]);
+        assert_eq!(graph.sinks(2), vec![3]);
+        assert_eq!(graph.clock_sources.get(&1), Some(&vec![4]));
+        assert_eq!(graph.clock_sources.get(&3), Some(&vec![4]));
+        assert_eq!(graph.clock_sources.get(&2), None);
+    }
+
+    #[test]
+    fn test_header2_latency_control() {
+        let mut header =
+            Header2::try_from([0x00, 0x02, 0x08, 0x40, 0x00, 0x00].as_slice()).unwrap();
+        assert_eq!(header.latency_control(), None);
+        header.controls = 0b01;
+        assert_eq!(header.latency_control(), Some(ControlSetting::ReadOnly));
+        header.controls = 0b111;
+        assert_eq!(header.latency_control(), Some(ControlSetting::ReadWrite));
+    }
+
+    #[test]
+    fn test_streaming_interface2_controls() {
+        let mut si = StreamingInterface2::try_from(
+            [
+                0x01, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00,
+            ]
+            .as_slice(),
+        )
+        .unwrap();
+        assert!(si.decoded_controls().is_empty());
+        si.controls = 0b1101;
+        assert_eq!(
+            si.active_alt_setting_control(),
+            Some(ControlSetting::ReadOnly)
+        );
+        assert_eq!(
+            si.valid_alt_settings_control(),
+            Some(ControlSetting::ReadWrite)
+        );
+        assert!(UacInterfaceDescriptor::StreamingInterface2(si)
+            .to_string()
+            .contains(
+                "0x0d Active Alternate Setting (read-only), Valid Alternate Settings (read/write)"
+            ));
+    }
+
+    #[test]
+    fn test_resolve_clock_sources() {
+        let mut internal =
+            ClockSource2::try_from([0x01, 0x03, 0x07, 0x00, 0x00].as_slice()).unwrap();
+        internal.clock_source = Some(String::from("Internal PLL"));
+        let external = ClockSource2::try_from([0x02, 0x00, 0x07, 0x00, 0x00].as_slice()).unwrap();
+        let selector =
+            ClockSelector2::try_from([0x03, 0x02, 0x01, 0x02, 0x03, 0x00].as_slice()).unwrap();
+        let descriptors = vec![
+            UacInterfaceDescriptor::ClockSource2(internal),
+            UacInterfaceDescriptor::ClockSource2(external),
+            UacInterfaceDescriptor::ClockSelector2(selector.clone()),
+        ];
+
+        let names = resolve_clock_sources(&descriptors);
+        let inputs: Vec<&str> = UacInterfaceDescriptor::ClockSelector2(selector)
+            .clock_source_ids()
+            .iter()
+            .filter_map(|id| names.get(id).map(|s| s.as_str()))
+            .collect();
+        assert_eq!(inputs, vec!["Internal PLL", "External Clock"]);
+        assert_eq!(names.get(&3).unwrap(), "Clock Selector 3");
+    }
+
+    #[test]
+    fn test_to_string_with_sources() {
+        let mut input = InputTerminal2::try_from(
+            [
+                0x01, 0x01, 0x01, 0x00, 0x04, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ]
+            .as_slice(),
+        )
+        .unwrap();
+        input.terminal_type = 0x0201;
+        let feature =
+            FeatureUnit2::try_from([0x02, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00].as_slice())
+                .unwrap();
+        let output = OutputTerminal2::try_from(
+            [0x03, 0x01, 0x03, 0x00, 0x02, 0x04, 0x00, 0x00, 0x00].as_slice(),
+        )
+        .unwrap();
+        let descriptors = vec![
+            UacInterfaceDescriptor::InputTerminal2(input),
+            UacInterfaceDescriptor::FeatureUnit2(feature),
+            UacInterfaceDescriptor::OutputTerminal2(output),
+        ];
+
+        let entities = resolve_entities(&descriptors);
+        assert_eq!(entities.get(&1).unwrap(), "Input Terminal (Microphone)");
+        assert_eq!(entities.get(&2).unwrap(), "Feature Unit 2");
+
+        let feature = descriptors[1].to_string_with_sources(&entities);
+        assert!(feature.contains("Source 1 <- Input Terminal (Microphone)"));
+        // clock source 4 is not present
+        let output = descriptors[2].to_string_with_sources(&entities);
+        assert!(output.contains("Source 2 <- Feature Unit 2"));
+        assert!(output.contains("Clock Source 4 <- ID 4"));
+    }
+
+    #[test]
+    fn test_power_domain_to_string() {
+        let input = InputTerminal2::try_from(
+            [
+                0x01, 0x01, 0x02, 0x00, 0x04, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ]
+            .as_slice(),
+        )
+        .unwrap();
+        // D1 3 * 50 µs, D2 1000 * 50 µs governing entities 1 and 7
+        let pd = PowerDomain::try_from(
+            [0x01, 0x03, 0x00, 0xe8, 0x03, 0x02, 0x01, 0x07, 0x00, 0x00].as_slice(),
+        )
+        .unwrap();
+        assert_eq!(pd.recovery_time_1_ms(), 0.15);
+        assert_eq!(pd.recovery_time_2_ms(), 50.0);
+
+        let descriptors = vec![
+            UacInterfaceDescriptor::InputTerminal2(input),
+            UacInterfaceDescriptor::PowerDomain(pd),
+        ];
+        assert_eq!(
+            descriptors[1].to_string(),
+            "Power Domain: ID 1, D1->D0: 0.15 ms, D2->D0: 50 ms, Entities [1, 7]"
+        );
+        let entities = resolve_entities(&descriptors);
+        assert!(descriptors[1]
+            .to_string_with_sources(&entities)
+            .ends_with("Entities [1, 7] <- Input Terminal (Microphone), ID 7"));
+    }
+
+    #[test]
+    fn test_midi_to_string_with_sources() {
+        let input = InputJack::try_from([0x02, 0x01, 0x00].as_slice()).unwrap();
+        let mut embedded = InputJack::try_from([0x01, 0x02, 0x00].as_slice()).unwrap();
+        embedded.jack_string = Some("Synth".into());
+        // embedded OUT jack 3 from external IN jack 1, pin 1 and jack 2, pin 2
+        let output =
+            OutputJack::try_from([0x01, 0x03, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00].as_slice())
+                .unwrap();
+        let descriptors = vec![
+            MidiInterfaceDescriptor::InputJack(input),
+            MidiInterfaceDescriptor::InputJack(embedded),
+            MidiInterfaceDescriptor::OutputJack(output),
+        ];
+
+        let jacks = resolve_midi_jacks(&descriptors);
+        assert_eq!(jacks.get(&1).unwrap(), "MIDI IN Jack 'External'");
+        assert_eq!(jacks.get(&2).unwrap(), "MIDI IN Jack 'Synth'");
+        assert_eq!(jacks.get(&3).unwrap(), "MIDI OUT Jack 'Embedded'");
+
+        assert_eq!(
+            descriptors[0].to_string(),
+            "MIDI IN Jack: Type 0x02 (External), ID 1, Name 0"
+        );
+        assert_eq!(
+            format!("{:#}", descriptors[2]),
+            "MIDI_OUT_JACK bJackType 0x01 (Embedded) bJackID 3 baSourceID [1:1, 2:2] iJack 0"
+        );
+        assert!(descriptors[2]
+            .to_string_with_sources(&jacks)
+            .contains("Sources [1:1, 2:2] <- MIDI IN Jack 'External', MIDI IN Jack 'Synth' pin 2"));
+    }
+
+    #[test]
+    fn test_streaming_interface_format_names() {
+        let si = StreamingInterface2::try_from(
+            [
+                0x01, 0x00, 0x01, 0x01, 0x00, 0x00, 0x80, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00,
+            ]
+            .as_slice(),
+        )
+        .unwrap();
+        assert_eq!(si.format_names(), vec!["PCM", "TYPE_I_RAW_DATA"]);
+        assert!(UacInterfaceDescriptor::StreamingInterface2(si)
+            .to_string()
+            .contains("Formats 0x80000001 (PCM, TYPE_I_RAW_DATA)"));
+
+        assert_eq!(format_names(0x81, 0x01), Vec::<&str>::new());
+        assert_eq!(
+            uac3_format_names(1 << 32 | 1 << 5),
+            vec!["DSD", "MPEG-4_AAC_ELD"]
+        );
+    }
+
+    #[test]
+    fn test_mixer_unit3_controls_length() {
+        // 2 input pins with 3 bytes of bmMixerControls
+        let data: Vec<u8> = vec![
+            0x04, 0x02, 0x01, 0x02, 0x10, 0x00, 0xaa, 0xbb, 0xcc, 0x01, 0x00, 0x00, 0x00, 0x20,
+            0x00,
+        ];
+        let mu = MixerUnit3::try_from(data.as_slice()).unwrap();
+        assert_eq!(mu.mixer_controls, vec![0xaa, 0xbb, 0xcc]);
+        assert_eq!(mu.controls, 1);
+        assert_eq!(mu.mixer_descr_str, 0x20);
+        assert_eq!(Vec::<u8>::from(mu), data);
+    }
+
+    #[test]
+    fn test_try_from_never_panics() {
+        assert_try_from_total!(
+            Header,
+            InputJack,
+            OutputJack,
+            Element,
+            GroupTerminalBlockHeader,
+            GroupTerminalBlock,
+            MidiEndpointDescriptor,
+            StreamingFormatSpecific,
+            FormatTypeI1,
+            FormatTypeII1,
+            FormatTypeIII1,
+            FormatTypeI2,
+            FormatTypeII2,
+            FormatTypeIV2,
+            FormatSpecificMpeg,
+            FormatSpecificAc3,
+            Header1,
+            Header2,
+            Header3,
+            InputTerminal1,
+            InputTerminal2,
+            InputTerminal3,
+            OutputTerminal1,
+            OutputTerminal2,
+            OutputTerminal3,
+            ExtendedTerminalHeader,
+            Connector3,
+            Connectors3,
+            PowerDomain,
+            MixerUnit1,
+            MixerUnit2,
+            MixerUnit3,
+            StreamingInterface1,
+            StreamingInterface2,
+            StreamingInterface3,
+            DataStreamingEndpoint1,
+            DataStreamingEndpoint2,
+            DataStreamingEndpoint3,
+            SelectorUnit1,
+            SelectorUnit2,
+            SelectorUnit3,
+            AudioProcessingUnitExtended1,
+            AudioProcessingUnit1StereoExtender3d,
+            AudioProcessingUnit1Reverberation,
+            AudioProcessingUnit1Chorus,
+            AudioProcessingUnit1DynRangeComp,
+            ProcessingUnit1,
+            AudioProcessingUnit2UpDownMix,
+            AudioProcessingUnit2DolbyPrologic,
+            AudioProcessingUnit3UpDownMix,
+            AudioProcessingUnit3StereoExtender,
+            AudioProcessingUnit3MultiFunction,
+            ProcessingUnit2,
+            ProcessingUnit3,
+            EffectUnit2,
+            EffectUnit3,
+            FeatureUnit1,
+            FeatureUnit2,
+            FeatureUnit3,
+            ExtensionUnit1,
+            ExtensionUnit2,
+            ExtensionUnit3,
+            ClockSource2,
+            ClockSource3,
+            ClockSelector2,
+            ClockSelector3,
+            ClockMultiplier2,
+            ClockMultiplier3,
+            SampleRateConverter2,
+            SampleRateConverter3,
+        );
+    }
+
+    #[test]
+    fn test_descriptor_dispatch_never_panics() {
+        for input in fuzz_inputs() {
+            let ret =
+                std::panic::catch_unwind(|| MidiDescriptor::try_from(input.as_slice()).is_ok());
+            assert!(
+                ret.is_ok(),
+                "MidiDescriptor panicked for input {input:02x?}"
+            );
+            for subtype in 0..=0x10 {
+                for (subclass, protocol) in [
+                    (1, 0x00),
+                    (2, 0x00),
+                    (1, 0x20),
+                    (2, 0x20),
+                    (1, 0x30),
+                    (2, 0x30),
+                ] {
+                    let gd = GenericDescriptor {
+                        length: input.len() as u8 + 3,
+                        descriptor_type: 0x24,
+                        descriptor_subtype: subtype,
+                        data: Some(input.clone()),
+                    };
+                    let ret = std::panic::catch_unwind(|| {
+                        UacDescriptor::try_from((gd.clone(), subclass, protocol)).is_ok()
+                    });
+                    assert!(
+                        ret.is_ok(),
+                        "UacDescriptor ({subclass}, {protocol:#04x}) subtype {subtype:#04x} panicked for input {input:02x?}"
+                    );
+                    let gd = GenericDescriptor {
+                        descriptor_type: 0x25,
+                        ..gd
+                    };
+                    let ret = std::panic::catch_unwind(|| {
+                        UacDescriptor::try_from((gd, subclass, protocol)).is_ok()
+                    });
+                    assert!(
+                        ret.is_ok(),
+                        "UacDescriptor endpoint panicked for input {input:02x?}"
+                    );
+                }
+            }
+        }
+    }
+}
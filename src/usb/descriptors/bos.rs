@@ -581,6 +581,39 @@ pub struct AlternateMode {
     pub alternate_mode_string: Option<String>,
 }
 
+impl AlternateMode {
+    /// Name of the alternate mode Standard or Vendor ID (SVID) if it is a well known one
+    ///
+    /// ```
+    /// # use cyme::usb::bos::AlternateMode;
+    /// let am = AlternateMode {
+    ///     svid: 0xff01,
+    ///     alternate_mode: 0,
+    ///     alternate_mode_string_index: 0,
+    ///     alternate_mode_string: None,
+    /// };
+    /// assert_eq!(am.svid_name(), Some("DisplayPort Alt Mode"));
+    /// assert_eq!(am.description(), "DisplayPort Alt Mode");
+    /// ```
+    pub fn svid_name(&self) -> Option<&'static str> {
+        match self.svid {
+            0xff01 => Some("DisplayPort Alt Mode"),
+            0x8087 => Some("Thunderbolt Alt Mode"),
+            _ => None,
+        }
+    }
+
+    /// Description of the alternate mode: the iAlternateModeString if read, else [`Self::svid_name`] or the SVID
+    pub fn description(&self) -> String {
+        self.alternate_mode_string
+            .as_ref()
+            .filter(|s| !s.is_empty())
+            .cloned()
+            .or(self.svid_name().map(String::from))
+            .unwrap_or(format!("SVID 0x{:04x}", self.svid))
+    }
+}
+
 impl TryFrom<&[u8]> for BillboardCapability {
     type Error = Error;
 
@@ -1,6 +1,10 @@
 //! Defines for the USB Video Class (UVC) interface descriptors
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
+use std::fmt;
+use strum::VariantArray;
+use strum_macros::VariantArray;
+use usb_ids::{self, FromId};
 use uuid::Uuid;
 
 use super::audio;
@@ -164,7 +168,7 @@ impl UvcType {
     /// Get the UVC descriptor from protocol and descriptor interface data
     pub fn get_uvc_descriptor(
         &self,
-        _protocol: u8,
+        protocol: u8,
         data: &[u8],
     ) -> error::Result<UvcInterfaceDescriptor> {
         match self {
@@ -172,9 +176,13 @@ impl UvcType {
                 ControlSubtype::Header => {
                     Ok(UvcInterfaceDescriptor::Header(Header::try_from(data)?))
                 }
-                ControlSubtype::InputTerminal => Ok(UvcInterfaceDescriptor::InputTerminal(
-                    InputTerminal::try_from(data)?,
-                )),
+                ControlSubtype::InputTerminal => {
+                    let mut it = InputTerminal::try_from(data)?;
+                    if let Some(extra) = it.extra.as_mut() {
+                        extra.protocol = protocol;
+                    }
+                    Ok(UvcInterfaceDescriptor::InputTerminal(it))
+                }
                 ControlSubtype::OutputTerminal => Ok(UvcInterfaceDescriptor::OutputTerminal(
                     OutputTerminal::try_from(data)?,
                 )),
@@ -237,10 +245,10 @@ impl UvcType {
     pub fn uvc_descriptor_from_generic(
         &self,
         gd: GenericDescriptor,
-        _protocol: u8,
+        protocol: u8,
     ) -> error::Result<UvcInterfaceDescriptor> {
         match gd.data {
-            Some(data) => match self.get_uvc_descriptor(_protocol, &data) {
+            Some(data) => match self.get_uvc_descriptor(protocol, &data) {
                 Ok(v) => Ok(v),
                 Err(e) => {
                     log::warn!("Error parsing UVC descriptor: {e}");
@@ -296,6 +304,35 @@ impl From<UvcDescriptor> for Vec<u8> {
     }
 }
 
+/// USB Video Class (UVC) interface descriptors
+///
+/// [`fmt::Display`] decodes the VideoControl Header and Input/Camera Terminal, with the Camera Terminal controls defined by the interface protocol:
+///
+/// ```
+/// use cyme::usb::descriptors::video::UvcDescriptor;
+/// use cyme::usb::descriptors::GenericDescriptor;
+///
+/// let camera = [
+///     0x12, 0x24, 0x02, 0x01, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
+///     0x0a, 0x00, 0x08,
+/// ];
+/// let gd = GenericDescriptor::try_from(&camera[..]).unwrap();
+/// let vd = UvcDescriptor::try_from((gd, 1, 0)).unwrap();
+/// assert_eq!(
+///     vd.interface.to_string(),
+///     "Camera Terminal: ID 1, Type 0x0201 (Camera Sensor), Associated Terminal 0, \
+///      Objective Focal Length Min 0, Objective Focal Length Max 0, Ocular Focal Length 0, \
+///      Controls 0x08000a (Auto-Exposure Mode, Exposure Time (Absolute))"
+/// );
+///
+/// // UVC 1.5 defines Focus, Simple
+/// let gd = GenericDescriptor::try_from(&camera[..]).unwrap();
+/// let vd = UvcDescriptor::try_from((gd, 1, 1)).unwrap();
+/// assert!(vd
+///     .interface
+///     .to_string()
+///     .ends_with("Controls 0x08000a (Auto-Exposure Mode, Exposure Time (Absolute), Focus, Simple)"));
+/// ```
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub enum UvcInterfaceDescriptor {
@@ -357,6 +394,142 @@ impl From<UvcInterfaceDescriptor> for Vec<u8> {
     }
 }
 
+impl fmt::Display for UvcInterfaceDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, fields) = self.display_fields();
+        // lsusb style
+        if f.alternate() {
+            write!(f, "{}", heck::AsShoutySnakeCase(name))?;
+            for (lsusb_name, _, value) in fields {
+                write!(f, " {lsusb_name} {value}")?;
+            }
+            Ok(())
+        } else {
+            audio::write_human_fields(f, name, &fields)
+        }
+    }
+}
+
+impl UvcInterfaceDescriptor {
+    /// Descriptor name and key fields as (lsusb name, human name, value) used for [`fmt::Display`]
+    ///
+    /// The VideoControl Header and Input/Camera Terminal are decoded; other descriptors show their data bytes
+    fn display_fields(&self) -> (&'static str, Vec<(&'static str, &'static str, String)>) {
+        match self {
+            UvcInterfaceDescriptor::Header(d) => (
+                "Header",
+                vec![
+                    ("bcdUVC", "Version", d.version.to_string()),
+                    ("wTotalLength", "Total Length", d.total_length.to_string()),
+                    ("dwClockFrequency", "Clock", d.clock_frequency_string()),
+                    (
+                        "baInterfaceNr",
+                        "Interfaces",
+                        format!(
+                            "[{}]",
+                            d.interfaces
+                                .iter()
+                                .map(|i| i.to_string())
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ),
+                    ),
+                ],
+            ),
+            UvcInterfaceDescriptor::InputTerminal(d) => {
+                let mut fields = vec![
+                    ("bTerminalID", "ID", d.terminal_id.to_string()),
+                    (
+                        "wTerminalType",
+                        "Type",
+                        match d.terminal_type_name() {
+                            Some(n) => format!("0x{:04x} ({n})", d.terminal_type),
+                            None => format!("0x{:04x}", d.terminal_type),
+                        },
+                    ),
+                    (
+                        "bAssocTerminal",
+                        "Associated Terminal",
+                        d.associated_terminal.to_string(),
+                    ),
+                ];
+                if let Some(name) = d.terminal.as_ref() {
+                    fields.push(("iTerminal", "Name", name.to_owned()));
+                }
+                if let Some(extra) = d.extra.as_ref() {
+                    fields.extend([
+                        (
+                            "wObjectiveFocalLengthMin",
+                            "Objective Focal Length Min",
+                            extra.objective_focal_length_min.to_string(),
+                        ),
+                        (
+                            "wObjectiveFocalLengthMax",
+                            "Objective Focal Length Max",
+                            extra.objective_focal_length_max.to_string(),
+                        ),
+                        (
+                            "wOcularFocalLength",
+                            "Ocular Focal Length",
+                            extra.ocular_focal_length.to_string(),
+                        ),
+                    ]);
+                    let controls = extra.camera_controls(extra.protocol);
+                    fields.push((
+                        "bmControls",
+                        "Controls",
+                        if controls.is_empty() {
+                            format!("0x{:06x}", extra.controls)
+                        } else {
+                            format!(
+                                "0x{:06x} ({})",
+                                extra.controls,
+                                controls
+                                    .iter()
+                                    .map(|c| c.to_string())
+                                    .collect::<Vec<String>>()
+                                    .join(", ")
+                            )
+                        },
+                    ));
+                }
+                let name = if d.is_camera() {
+                    "Camera Terminal"
+                } else {
+                    "Input Terminal"
+                };
+                (name, fields)
+            }
+            d => {
+                let name = match d {
+                    UvcInterfaceDescriptor::OutputTerminal(_) => "Output Terminal",
+                    UvcInterfaceDescriptor::SelectorUnit(_) => "Selector Unit",
+                    UvcInterfaceDescriptor::ProcessingUnit(_) => "Processing Unit",
+                    UvcInterfaceDescriptor::ExtensionUnit(_) => "Extension Unit",
+                    UvcInterfaceDescriptor::EncodingUnit(_) => "Encoding Unit",
+                    UvcInterfaceDescriptor::InputHeader(_) => "Input Header",
+                    UvcInterfaceDescriptor::OutputHeader(_) => "Output Header",
+                    UvcInterfaceDescriptor::StillImageFrame(_) => "Still Image Frame",
+                    UvcInterfaceDescriptor::FrameUncompressed(_) => "Frame Uncompressed",
+                    UvcInterfaceDescriptor::FrameMJPEG(_) => "Frame MJPEG",
+                    UvcInterfaceDescriptor::FrameFrameBased(_) => "Frame Frame Based",
+                    UvcInterfaceDescriptor::FormatUncompressed(_) => "Format Uncompressed",
+                    UvcInterfaceDescriptor::FormatFrameBased(_) => "Format Frame Based",
+                    UvcInterfaceDescriptor::FormatStreamBased(_) => "Format Stream Based",
+                    UvcInterfaceDescriptor::FormatMJPEG(_) => "Format MJPEG",
+                    UvcInterfaceDescriptor::FormatMPEG2TS(_) => "Format MPEG2TS",
+                    UvcInterfaceDescriptor::ColorFormat(_) => "Color Format",
+                    UvcInterfaceDescriptor::Invalid(_) => "Invalid",
+                    UvcInterfaceDescriptor::Generic(_) => "Generic",
+                    _ => "Undefined",
+                };
+                let data: Vec<u8> = d.to_owned().into();
+                (name, vec![("Data", "Data", format!("{data:02x?}"))])
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct Header {
@@ -391,6 +564,17 @@ impl TryFrom<&[u8]> for Header {
     }
 }
 
+impl Header {
+    /// The dwClockFrequency formatted in MHz like lsusb, such as "48.000000 MHz"
+    pub fn clock_frequency_string(&self) -> String {
+        format!(
+            "{}.{:06} MHz",
+            self.clock_frequency / 1000000,
+            self.clock_frequency % 1000000
+        )
+    }
+}
+
 impl From<Header> for Vec<u8> {
     fn from(h: Header) -> Self {
         let mut ret = Vec::new();
@@ -412,6 +596,9 @@ pub struct TerminalExtra {
     pub ocular_focal_length: u16,
     pub control_size: u8,
     pub controls: u32,
+    /// UVC interface protocol the descriptor was parsed with, which defines the [`CameraControl`]s of `controls`; set by [`UvcType::get_uvc_descriptor`]
+    #[serde(default)]
+    pub protocol: u8,
 }
 
 impl TryFrom<&[u8]> for TerminalExtra {
@@ -449,10 +636,22 @@ impl TryFrom<&[u8]> for TerminalExtra {
             ocular_focal_length,
             control_size,
             controls,
+            protocol: 0,
         })
     }
 }
 
+impl TerminalExtra {
+    /// The supported [`CameraControl`]s in `controls` for UVC `protocol`; only UVC 1.5 (protocol 0x01) defines the controls after [`CameraControl::Privacy`]
+    pub fn camera_controls(&self, protocol: u8) -> Vec<CameraControl> {
+        if protocol == 0x01 {
+            CameraControl::from_bitmap(self.controls)
+        } else {
+            CameraControl::from_bitmap(self.controls & 0x07ffff)
+        }
+    }
+}
+
 impl From<TerminalExtra> for Vec<u8> {
     fn from(te: TerminalExtra) -> Self {
         let mut ret = Vec::new();
@@ -511,6 +710,96 @@ impl TryFrom<&[u8]> for InputTerminal {
     }
 }
 
+impl InputTerminal {
+    /// Whether the terminal is a Camera Terminal (ITT_CAMERA), which has [`TerminalExtra`]
+    pub fn is_camera(&self) -> bool {
+        self.terminal_type == 0x0201
+    }
+
+    /// Returns the name of the terminal type if known; see [`terminal_type_name`]
+    pub fn terminal_type_name(&self) -> Option<&'static str> {
+        terminal_type_name(self.terminal_type)
+    }
+}
+
+/// Get the name of a USB Video Terminal Type (wTerminalType) from the Linux USB IDs repository, such as "Camera Sensor"
+pub fn terminal_type_name(terminal_type: u16) -> Option<&'static str> {
+    usb_ids::VideoTerminal::from_id(terminal_type).map(|v| v.name())
+}
+
+/// UVC Camera Terminal controls based on the "bmControls" field of the [`TerminalExtra`]
+///
+/// Decoded as bitstring; each bit corresponds to a control
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, VariantArray)]
+#[allow(missing_docs)]
+#[serde(rename_all = "kebab-case")]
+pub enum CameraControl {
+    ScanningMode,
+    AutoExposureMode,
+    AutoExposurePriority,
+    ExposureTimeAbsolute,
+    ExposureTimeRelative,
+    FocusAbsolute,
+    FocusRelative,
+    IrisAbsolute,
+    IrisRelative,
+    ZoomAbsolute,
+    ZoomRelative,
+    PanTiltAbsolute,
+    PanTiltRelative,
+    RollAbsolute,
+    RollRelative,
+    Reserved15,
+    Reserved16,
+    FocusAuto,
+    Privacy,
+    FocusSimple,
+    Window,
+    RegionOfInterest,
+}
+
+impl fmt::Display for CameraControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CameraControl::ScanningMode => write!(f, "Scanning Mode"),
+            CameraControl::AutoExposureMode => write!(f, "Auto-Exposure Mode"),
+            CameraControl::AutoExposurePriority => write!(f, "Auto-Exposure Priority"),
+            CameraControl::ExposureTimeAbsolute => write!(f, "Exposure Time (Absolute)"),
+            CameraControl::ExposureTimeRelative => write!(f, "Exposure Time (Relative)"),
+            CameraControl::FocusAbsolute => write!(f, "Focus (Absolute)"),
+            CameraControl::FocusRelative => write!(f, "Focus (Relative)"),
+            CameraControl::IrisAbsolute => write!(f, "Iris (Absolute)"),
+            CameraControl::IrisRelative => write!(f, "Iris (Relative)"),
+            CameraControl::ZoomAbsolute => write!(f, "Zoom (Absolute)"),
+            CameraControl::ZoomRelative => write!(f, "Zoom (Relative)"),
+            CameraControl::PanTiltAbsolute => write!(f, "PanTilt (Absolute)"),
+            CameraControl::PanTiltRelative => write!(f, "PanTilt (Relative)"),
+            CameraControl::RollAbsolute => write!(f, "Roll (Absolute)"),
+            CameraControl::RollRelative => write!(f, "Roll (Relative)"),
+            CameraControl::Reserved15 | CameraControl::Reserved16 => write!(f, "Reserved"),
+            CameraControl::FocusAuto => write!(f, "Focus, Auto"),
+            CameraControl::Privacy => write!(f, "Privacy"),
+            CameraControl::FocusSimple => write!(f, "Focus, Simple"),
+            CameraControl::Window => write!(f, "Window"),
+            CameraControl::RegionOfInterest => write!(f, "Region of Interest"),
+        }
+    }
+}
+
+impl CameraControl {
+    /// Get the supported [`CameraControl`]s from the bitmap value
+    pub fn from_bitmap<T: Into<u32>>(bitmap: T) -> Vec<CameraControl> {
+        let mut ret = Vec::new();
+        let bitmap = bitmap.into();
+        for (i, s) in CameraControl::VARIANTS.iter().enumerate() {
+            if bitmap & (1 << i) != 0 {
+                ret.push(*s);
+            }
+        }
+        ret
+    }
+}
+
 impl From<InputTerminal> for Vec<u8> {
     fn from(it: InputTerminal) -> Self {
         let mut ret = Vec::new();
@@ -350,6 +350,12 @@ impl FromStr for PortPath {
                 .map_err(|_| {
                     Error::new(ErrorKind::Parsing, &format!("Invalid port number: {s}"))
                 })?;
+            if parts.next().is_some() {
+                return Err(Error::new(
+                    ErrorKind::Parsing,
+                    &format!("Expected 'bus-port.port' port path: {s}"),
+                ));
+            }
             Ok(Self { bus, ports })
         }
     }
@@ -472,6 +478,21 @@ impl PortPath {
     pub fn is_root_hub(&self) -> bool {
         self.ports.is_empty() || self.ports == [0]
     }
+
+    /// Is the port path `other` or one of its descendants in the tree; everything on the bus is within a root hub
+    ///
+    /// ```
+    /// use cyme::usb::PortPath;
+    /// let path = PortPath::new(1, vec![4, 2]);
+    /// assert!(path.is_within(&PortPath::new(1, vec![4])));
+    /// assert!(path.is_within(&PortPath::new(1, vec![4, 2])));
+    /// assert!(path.is_within(&PortPath::new(1, vec![])));
+    /// assert!(!path.is_within(&PortPath::new(1, vec![4, 2, 1])));
+    /// assert!(!path.is_within(&PortPath::new(2, vec![4])));
+    /// ```
+    pub fn is_within(&self, other: &PortPath) -> bool {
+        self.bus == other.bus && (other.is_root_hub() || self.ports.starts_with(&other.ports))
+    }
 }
 
 /// Helper type for defining the location of a [`Configuration`]
@@ -939,6 +960,11 @@ mod test {
                 ports: vec![]
             })
         );
+
+        assert!("1-4-2".parse::<PortPath>().is_err());
+        assert!("1-4.x".parse::<PortPath>().is_err());
+        assert!("1".parse::<PortPath>().is_err());
+        assert!("1-".parse::<PortPath>().is_err());
     }
 
     #[test]
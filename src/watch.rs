@@ -12,6 +12,7 @@ use crossterm::{
     terminal,
 };
 use futures_lite::stream::StreamExt;
+use std::collections::HashMap;
 use std::env;
 use std::io::stdout;
 use std::io::Write;
@@ -22,7 +23,7 @@ use super::parse_vidpid;
 use cyme::config::Config;
 use cyme::display::*;
 use cyme::error::{Error, ErrorKind, Result};
-use cyme::profiler::{watch::SystemProfileStreamBuilder, Filter, SystemProfile};
+use cyme::profiler::{watch::SystemProfileStreamBuilder, DeviceEvent, Filter, SystemProfile};
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -48,6 +49,7 @@ enum WatchEvent {
     Error(String),
     Resize,
     DrawDevices,
+    ProfileChanged,
     DrawEditBlocks,
     WriteEditBlocks,
     ConfirmSave(String),
@@ -154,6 +156,10 @@ struct Display {
     line_context: Vec<LineItem>,
     /// Selected line context
     selected_item: Option<LineItem>,
+    /// Device block values shown before the last profile change to highlight changes
+    previous_values: ValueCache,
+    /// Device block values from the last draw
+    value_cache: ValueCache,
 }
 
 fn set_filter(field: &FilterField, value: Option<String>, filter: &mut Filter) -> Result<()> {
@@ -247,6 +253,67 @@ pub fn watch_usb_devices_json(
     Ok(())
 }
 
+/// Prints a JSON line to `dw` for each visible device with a `last_event` that differs from the last one printed in `events`
+fn print_ndjson_events<W: Write>(
+    dw: &mut DisplayWriter<W>,
+    spusb: &mut SystemProfile,
+    filter: Option<&Filter>,
+    print_settings: &PrintSettings,
+    events: &mut HashMap<String, Option<DeviceEvent>>,
+) -> Result<()> {
+    cyme::display::prepare(spusb, filter, print_settings);
+    for device in spusb.flattened_devices().iter().filter(|d| !d.is_hidden()) {
+        if events.insert(device.port_path().to_string(), device.last_event)
+            != Some(device.last_event)
+        {
+            dw.print_ndjson_event(device)?;
+        }
+    }
+    Ok(())
+}
+
+/// Watch for USB device events, printing JSON Lines of [`ndjson_event`] objects with the `port_path`, `event` and `device` without its child devices
+///
+/// A line is printed for every device with its profiled event first, then for each device whose last event changes, such as when it is connected or disconnected. Devices hidden by the `filter` are not printed.
+pub fn watch_usb_devices_ndjson(
+    spusb: SystemProfile,
+    filter: Option<Filter>,
+    mut print_settings: PrintSettings,
+) -> Result<()> {
+    // dynamic so filtering hides rather than removes devices from the profile being updated
+    print_settings.print_mode = PrintMode::Dynamic;
+    let mut profile_stream = SystemProfileStreamBuilder::new()
+        .with_spusb(spusb)
+        .is_verbose(print_settings.verbosity > 0)
+        .build()
+        .map_err(|e| Error::new(ErrorKind::Nusb, &e.to_string()))?;
+
+    let spusb = profile_stream.get_profile();
+    let mut events = HashMap::new();
+    let mut dw = DisplayWriter::default();
+    // all devices first with profiled event
+    print_ndjson_events(
+        &mut dw,
+        &mut spusb.lock().unwrap(),
+        filter.as_ref(),
+        &print_settings,
+        &mut events,
+    )?;
+
+    futures_lite::future::block_on(async {
+        while let Some(spusb) = profile_stream.next().await {
+            print_ndjson_events(
+                &mut dw,
+                &mut spusb.lock().unwrap(),
+                filter.as_ref(),
+                &print_settings,
+                &mut events,
+            )?;
+        }
+        Ok(())
+    })
+}
+
 pub fn watch_usb_devices(
     spusb: SystemProfile,
     filter: Option<Filter>,
@@ -325,6 +392,8 @@ pub fn watch_usb_devices(
         scroll_offset: 0,
         selected_line: None,
         selected_item: None,
+        previous_values: ValueCache::new(),
+        value_cache: ValueCache::new(),
         line_context: Vec::new(),
     };
 
@@ -340,7 +409,7 @@ pub fn watch_usb_devices(
                 if *stop_clone.lock().unwrap() {
                     break;
                 }
-                tx_clone.send(WatchEvent::DrawDevices).unwrap();
+                tx_clone.send(WatchEvent::ProfileChanged).unwrap();
             }
         });
     });
@@ -357,22 +426,10 @@ pub fn watch_usb_devices(
         };
 
         match event::read().unwrap() {
-            Event::Resize(_, _) => {
-                if send_wrapper(WatchEvent::Resize) {
-                    break;
-                }
-            }
+            Event::Resize(_, _) if send_wrapper(WatchEvent::Resize) => break,
             Event::Mouse(MouseEvent { kind, .. }) => match kind {
-                MouseEventKind::ScrollUp => {
-                    if send_wrapper(WatchEvent::ScrollUp(1)) {
-                        break;
-                    }
-                }
-                MouseEventKind::ScrollDown => {
-                    if send_wrapper(WatchEvent::ScrollDown(1)) {
-                        break;
-                    }
-                }
+                MouseEventKind::ScrollUp if send_wrapper(WatchEvent::ScrollUp(1)) => break,
+                MouseEventKind::ScrollDown if send_wrapper(WatchEvent::ScrollDown(1)) => break,
                 _ => (),
             },
             Event::Key(key_event) => {
@@ -555,6 +612,13 @@ pub fn watch_usb_devices(
                 display.draw_devices()?;
             }
 
+            Ok(WatchEvent::ProfileChanged) => {
+                // highlight values changed from those shown before this profile change until the next one
+                display.previous_values = std::mem::take(&mut display.value_cache);
+                display.prepare_devices();
+                display.draw_devices()?;
+            }
+
             Ok(WatchEvent::EditBlock(block_type)) => {
                 display.prepare_edit_blocks(block_type);
             }
@@ -711,6 +775,11 @@ impl State {
                 print_settings.decimal = !print_settings.decimal;
                 tx.send(WatchEvent::DrawDevices).unwrap();
             }
+            (KeyCode::Char('r'), _) => {
+                let mut print_settings = print_settings.lock().unwrap();
+                print_settings.relative_time = !print_settings.relative_time;
+                tx.send(WatchEvent::DrawDevices).unwrap();
+            }
             (KeyCode::Char('p'), _) => {
                 let mut print_settings = print_settings.lock().unwrap();
                 match print_settings.sort_devices {
@@ -1067,6 +1136,7 @@ impl Display {
         self.buffer.clear();
         let mut dw = DisplayWriter::new(&mut self.buffer);
         dw.set_raw_mode(true);
+        dw.set_previous_values(self.previous_values.clone());
 
         {
             let spusb = self.spusb.lock().unwrap();
@@ -1074,6 +1144,7 @@ impl Display {
             dw.print_sp_usb(&spusb, &print_settings);
         }
         self.line_context = dw.line_context().to_owned();
+        self.value_cache = dw.take_value_cache();
 
         // find selected device
         if let Some(selected_device) = self.selected_item.as_ref() {
@@ -1105,6 +1176,7 @@ impl Display {
  [t]: Toggle tree
  [h]: Toggle headings
  [o]: Toggle decimal/hex
+ [r]: Toggle relative event time
  [p]: Cycle sort mode
  [b]: Enter block editor
  [/]: Edit name filter
@@ -1520,3 +1592,95 @@ impl Display {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cyme::profiler::read_json_dump;
+
+    /// Runs [`print_ndjson_events`] returning the JSON Lines printed
+    fn ndjson_events(
+        spusb: &mut SystemProfile,
+        filter: Option<&Filter>,
+        events: &mut HashMap<String, Option<DeviceEvent>>,
+    ) -> Vec<serde_json::Value> {
+        let print_settings = PrintSettings {
+            print_mode: PrintMode::Dynamic,
+            ..Default::default()
+        };
+        let mut buffer = Vec::new();
+        print_ndjson_events(
+            &mut DisplayWriter::new(&mut buffer),
+            spusb,
+            filter,
+            &print_settings,
+            events,
+        )
+        .unwrap();
+        String::from_utf8(buffer)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_ndjson_event_shape() {
+        let mut spusb = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        let mut events = HashMap::new();
+        let lines = ndjson_events(&mut spusb, None, &mut events);
+
+        let port_paths: Vec<String> = spusb
+            .flattened_devices()
+            .iter()
+            .map(|d| d.port_path().to_string())
+            .collect();
+        assert_eq!(lines.len(), port_paths.len());
+        for (line, port_path) in lines.iter().zip(port_paths.iter()) {
+            let object = line.as_object().unwrap();
+            let mut keys: Vec<&str> = object.keys().map(|k| k.as_str()).collect();
+            keys.sort();
+            assert_eq!(keys, vec!["device", "event", "port_path"]);
+            assert_eq!(object["port_path"], *port_path);
+            // child devices have their own line
+            assert!(object["device"].get("devices").is_none());
+            assert!(object["device"].get("name").is_some());
+        }
+    }
+
+    #[test]
+    fn test_ndjson_only_changed_events() {
+        let mut spusb = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        let mut events = HashMap::new();
+        ndjson_events(&mut spusb, None, &mut events);
+        assert!(ndjson_events(&mut spusb, None, &mut events).is_empty());
+
+        spusb.get_node_str_mut("2-2.1").unwrap().last_event =
+            Some(DeviceEvent::Disconnected(chrono::Local::now()));
+        let lines = ndjson_events(&mut spusb, None, &mut events);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["port_path"], "2-2.1");
+        assert!(lines[0]["event"].get("disconnected").is_some());
+        assert!(ndjson_events(&mut spusb, None, &mut events).is_empty());
+    }
+
+    #[test]
+    fn test_ndjson_events_filtered() {
+        let mut spusb = read_json_dump("./tests/data/cyme_libusb_linux_tree.json").unwrap();
+        let filter = Filter {
+            port_path: Some("2-2.1".parse().unwrap()),
+            ..Default::default()
+        };
+        let lines = ndjson_events(&mut spusb, Some(&filter), &mut HashMap::new());
+        let port_paths: Vec<&str> = lines
+            .iter()
+            .map(|l| l["port_path"].as_str().unwrap())
+            .collect();
+        assert!(port_paths.contains(&"2-2.1"));
+        // only the device and the parents it is attached through
+        assert!(port_paths
+            .iter()
+            .all(|p| "2-2.1".starts_with(p) || p.starts_with("2-2.1")));
+        assert!(port_paths.len() < spusb.flattened_devices().len());
+    }
+}
@@ -160,3 +160,56 @@ fn test_tree_filtering() {
         false,
     );
 }
+
+#[test]
+fn test_color_icon_matrix() {
+    let te = common::TestEnv::new();
+    // private use area glyphs are only valid for the glyphs encoding
+    let is_glyph = |c: char| !cyme::display::Encoding::Utf8.char_is_valid(c);
+
+    // stdout is not a terminal so auto colour is off but auto icons are on with glyphs
+    for color in ["auto", "always", "never"] {
+        for icon in ["auto", "always", "never"] {
+            let output = te.assert_success_and_get_output(
+                Some(common::CYME_LIBUSB_LINUX_TREE_DUMP),
+                &["--encoding", "glyphs", "--color", color, "--icon", icon],
+            );
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            assert_eq!(
+                stdout.contains("\x1b["),
+                color == "always",
+                "--color {color} --icon {icon}"
+            );
+            assert_eq!(
+                stdout.chars().any(is_glyph),
+                icon != "never",
+                "--color {color} --icon {icon}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_collapse_duplicates_flat_outputs() {
+    let te = common::TestEnv::new();
+    let dump = Some(common::CYME_LIBUSB_MACOS_TREE_DUMP);
+    let collapse = ["--collapse-duplicates", "--collapse-ignore-serial"];
+
+    // list output collapses into a multiplier
+    let all = te.assert_success_and_get_output(dump, &[]);
+    let collapsed = te.assert_success_and_get_output(dump, &collapse);
+    assert!(String::from_utf8_lossy(&collapsed.stdout).contains(" x2"));
+    assert!(collapsed.stdout.len() < all.stdout.len());
+
+    // outputs without a multiplier list every device
+    for format in ["--csv", "--markdown", "--ndjson", "--dot", "--lsusb"] {
+        let all = te.assert_success_and_get_output(dump, &[format]);
+        let collapsed =
+            te.assert_success_and_get_output(dump, &[&[format], &collapse[..]].concat());
+        assert_eq!(
+            String::from_utf8_lossy(&collapsed.stdout),
+            String::from_utf8_lossy(&all.stdout),
+            "{format}"
+        );
+    }
+}